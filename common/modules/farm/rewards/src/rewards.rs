@@ -1,6 +1,32 @@
 #![no_std]
 
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_structs::Nonce;
+
+pub const MAX_EMISSION_SCHEDULE_ENTRIES: usize = 50;
+pub const MAX_DECAY_PERCENTAGE: u64 = 10_000;
+
+/// Starting with `start_block`, the farm produces `per_block_amount` rewards per block.
+/// An entry with a zero amount ends the emission.
+#[derive(
+    ManagedVecItem,
+    TypeAbi,
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    Clone,
+    PartialEq,
+    Debug,
+)]
+pub struct EmissionScheduleEntry<M: ManagedTypeApi> {
+    pub start_block: Nonce,
+    pub per_block_amount: BigUint<M>,
+}
+
+pub type EmissionScheduleEntries<M> = ManagedVec<M, EmissionScheduleEntry<M>>;
 
 #[multiversx_sc::module]
 pub trait RewardsModule:
@@ -8,7 +34,7 @@ pub trait RewardsModule:
 {
     fn start_produce_rewards(&self) {
         require!(
-            self.per_block_reward_amount().get() != 0u64,
+            self.per_block_reward_amount().get() != 0u64 || !self.emission_schedule().is_empty(),
             "Cannot produce zero reward amount"
         );
         require!(
@@ -25,6 +51,176 @@ pub trait RewardsModule:
         self.produce_rewards_enabled().get()
     }
 
+    /// Integrates the per block rewards between the two blocks, across the schedule breakpoints.
+    /// The entries that were reached are removed from the schedule,
+    /// and the last one of them becomes the current per block reward amount.
+    fn take_scheduled_per_block_rewards(
+        &self,
+        last_reward_block_nonce: Nonce,
+        current_block_nonce: Nonce,
+    ) -> BigUint {
+        let mut schedule = self.emission_schedule();
+        let per_block_reward_mapper = self.per_block_reward_amount();
+        let mut per_block_reward = per_block_reward_mapper.get();
+        if schedule.is_empty() {
+            return per_block_reward * (current_block_nonce - last_reward_block_nonce);
+        }
+
+        let mut total_rewards = BigUint::zero();
+        let mut last_block_nonce = last_reward_block_nonce;
+        while let Some(entry) = schedule.front() {
+            if entry.start_block > current_block_nonce {
+                break;
+            }
+
+            if entry.start_block > last_block_nonce {
+                total_rewards += &per_block_reward * (entry.start_block - last_block_nonce);
+                last_block_nonce = entry.start_block;
+            }
+
+            per_block_reward = entry.per_block_amount;
+            let _ = schedule.pop_front();
+        }
+
+        total_rewards += &per_block_reward * (current_block_nonce - last_block_nonce);
+        per_block_reward_mapper.set(&per_block_reward);
+
+        total_rewards
+    }
+
+    /// Replaces the upcoming schedule. The start blocks must be increasing and not in the past.
+    fn replace_emission_schedule(&self, entries: EmissionScheduleEntries<Self::Api>) {
+        require!(
+            entries.len() <= MAX_EMISSION_SCHEDULE_ENTRIES,
+            "Too many emission schedule entries"
+        );
+
+        let mut min_start_block = self.blockchain().get_block_nonce();
+        for entry in &entries {
+            require!(
+                entry.start_block >= min_start_block,
+                "Invalid emission schedule start block"
+            );
+
+            min_start_block = entry.start_block + 1;
+        }
+
+        let mut schedule = self.emission_schedule();
+        schedule.clear();
+        for entry in &entries {
+            schedule.push_back(entry);
+        }
+    }
+
+    fn build_emission_schedule(
+        &self,
+        entries: MultiValueEncoded<MultiValue2<Nonce, BigUint>>,
+    ) -> EmissionScheduleEntries<Self::Api> {
+        let mut schedule = ManagedVec::new();
+        for entry in entries {
+            let (start_block, per_block_amount) = entry.into_tuple();
+            schedule.push(EmissionScheduleEntry {
+                start_block,
+                per_block_amount,
+            });
+        }
+
+        schedule
+    }
+
+    /// The per block amount decreases by `decay_percentage` (out of 10_000) every period,
+    /// e.g. 5_000 for halvings. The emission ends after `nr_periods` periods.
+    fn build_decaying_emission_schedule(
+        &self,
+        start_block: Nonce,
+        initial_per_block_amount: BigUint,
+        period_in_blocks: Nonce,
+        decay_percentage: u64,
+        nr_periods: usize,
+    ) -> EmissionScheduleEntries<Self::Api> {
+        require!(
+            period_in_blocks > 0 && nr_periods > 0,
+            "Invalid emission schedule period"
+        );
+        require!(
+            decay_percentage <= MAX_DECAY_PERCENTAGE,
+            "Invalid decay percentage"
+        );
+        require!(
+            nr_periods < MAX_EMISSION_SCHEDULE_ENTRIES,
+            "Too many emission schedule entries"
+        );
+
+        let mut schedule = ManagedVec::new();
+        let mut per_block_amount = initial_per_block_amount;
+        let mut period_start_block = start_block;
+        for _ in 0..nr_periods {
+            schedule.push(EmissionScheduleEntry {
+                start_block: period_start_block,
+                per_block_amount: per_block_amount.clone(),
+            });
+
+            per_block_amount =
+                per_block_amount * (MAX_DECAY_PERCENTAGE - decay_percentage) / MAX_DECAY_PERCENTAGE;
+            period_start_block += period_in_blocks;
+        }
+
+        schedule.push(EmissionScheduleEntry {
+            start_block: period_start_block,
+            per_block_amount: BigUint::zero(),
+        });
+
+        schedule
+    }
+
+    /// The rewards produced from the current block until the end of the schedule.
+    /// The schedule must end with a zero amount entry, otherwise the emission is unbounded.
+    fn get_remaining_scheduled_rewards(&self) -> BigUint {
+        let mut per_block_reward = self.per_block_reward_amount().get();
+        let mut last_block_nonce = self.blockchain().get_block_nonce();
+        let mut total_rewards = BigUint::zero();
+        for entry in self.emission_schedule().iter() {
+            if entry.start_block > last_block_nonce {
+                total_rewards += &per_block_reward * (entry.start_block - last_block_nonce);
+                last_block_nonce = entry.start_block;
+            }
+
+            per_block_reward = entry.per_block_amount;
+        }
+
+        require!(
+            per_block_reward == 0u64,
+            "Emission schedule must have an end block"
+        );
+
+        total_rewards
+    }
+
+    /// While a schedule is active, it sets the per block amount at each breakpoint,
+    /// so the amount cannot be changed by other means.
+    /// Must be called after the rewards were generated, which removes the reached entries.
+    /// The reached entries that were not removed yet are dropped,
+    /// as the new per block amount replaces them anyway.
+    fn require_no_emission_schedule(&self) {
+        require!(
+            !self.has_active_emission_schedule(),
+            "Emission schedule is active"
+        );
+
+        self.emission_schedule().clear();
+    }
+
+    /// A schedule stays active until its last entry is reached,
+    /// even if the reached entries were not removed yet.
+    #[view(hasActiveEmissionSchedule)]
+    fn has_active_emission_schedule(&self) -> bool {
+        let current_block_nonce = self.blockchain().get_block_nonce();
+        match self.emission_schedule().back() {
+            Some(last_entry) => last_entry.start_block > current_block_nonce,
+            None => false,
+        }
+    }
+
     #[view(getRewardPerShare)]
     #[storage_mapper("reward_per_share")]
     fn reward_per_share(&self) -> SingleValueMapper<BigUint>;
@@ -32,4 +228,8 @@ pub trait RewardsModule:
     #[view(getRewardReserve)]
     #[storage_mapper("reward_reserve")]
     fn reward_reserve(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getEmissionSchedule)]
+    #[storage_mapper("emissionSchedule")]
+    fn emission_schedule(&self) -> QueueMapper<EmissionScheduleEntry<Self::Api>>;
 }
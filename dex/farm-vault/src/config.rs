@@ -0,0 +1,153 @@
+multiversx_sc::imports!();
+
+use crate::errors::*;
+
+pub const MAX_PERCENTAGE: u64 = 10_000;
+pub const MAX_KEEPER_FEE_PERCENT: u64 = 1_000;
+
+static FARMING_TOKEN_ID_STORAGE_KEY: &[u8] = b"farming_token_id";
+static REWARD_TOKEN_ID_STORAGE_KEY: &[u8] = b"reward_token_id";
+static LP_TOKEN_ID_STORAGE_KEY: &[u8] = b"lpTokenIdentifier";
+static FIRST_TOKEN_ID_STORAGE_KEY: &[u8] = b"first_token_id";
+static SECOND_TOKEN_ID_STORAGE_KEY: &[u8] = b"second_token_id";
+static MINIMUM_FARMING_EPOCHS_STORAGE_KEY: &[u8] = b"minimum_farming_epochs";
+
+#[multiversx_sc::module]
+pub trait ConfigModule {
+    /// Reads the tokens of the farm and of the pair from their storage,
+    /// so the vault must be in the same shard as them.
+    fn init_farm_and_pair(&self, farm_address: ManagedAddress, pair_address: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&farm_address)
+                && self.blockchain().is_smart_contract(&pair_address),
+            ERROR_INVALID_ARGS
+        );
+
+        let farming_token_id = self.read_token_id(&farm_address, FARMING_TOKEN_ID_STORAGE_KEY);
+        let lp_token_id = self.read_token_id(&pair_address, LP_TOKEN_ID_STORAGE_KEY);
+        require!(farming_token_id == lp_token_id, ERROR_FARM_PAIR_MISMATCH);
+        self.require_farm_without_exit_penalty(&farm_address);
+
+        self.reward_token_id()
+            .set(self.read_token_id(&farm_address, REWARD_TOKEN_ID_STORAGE_KEY));
+        self.first_token_id()
+            .set(self.read_token_id(&pair_address, FIRST_TOKEN_ID_STORAGE_KEY));
+        self.second_token_id()
+            .set(self.read_token_id(&pair_address, SECOND_TOKEN_ID_STORAGE_KEY));
+        self.lp_token_id().set(lp_token_id);
+        self.farm_address().set(farm_address);
+        self.pair_address().set(pair_address);
+    }
+
+    /// Every deposit and harvest moves the entering epoch of the single position forward,
+    /// so with minimum farming epochs anyone could keep the whole vault in the penalty window.
+    fn require_farm_without_exit_penalty(&self, farm_address: &ManagedAddress) {
+        let minimum_farming_epochs: u64 = self.storage_raw().read_from_address(
+            farm_address,
+            ManagedBuffer::new_from_bytes(MINIMUM_FARMING_EPOCHS_STORAGE_KEY),
+        );
+        require!(minimum_farming_epochs == 0, ERROR_FARM_EXIT_PENALTY);
+    }
+
+    fn read_token_id(&self, address: &ManagedAddress, key: &[u8]) -> TokenIdentifier {
+        let token_id: TokenIdentifier = self
+            .storage_raw()
+            .read_from_address(address, ManagedBuffer::new_from_bytes(key));
+        require!(token_id.is_valid_esdt_identifier(), ERROR_INVALID_ARGS);
+
+        token_id
+    }
+
+    #[only_owner]
+    #[endpoint(setKeeperFeePercent)]
+    fn set_keeper_fee_percent(&self, keeper_fee_percent: u64) {
+        self.try_set_keeper_fee_percent(keeper_fee_percent);
+    }
+
+    #[only_owner]
+    #[endpoint(setMaxDeviationPercent)]
+    fn set_max_deviation_percent(&self, max_deviation_percent: u64) {
+        self.try_set_max_deviation_percent(max_deviation_percent);
+    }
+
+    #[only_owner]
+    #[endpoint(setSafePriceViewAddress)]
+    fn set_safe_price_view_address(&self, safe_price_view_address: ManagedAddress) {
+        self.safe_price_view_address().set(&safe_price_view_address);
+    }
+
+    #[only_owner]
+    #[endpoint(setSafePriceRoundOffset)]
+    fn set_safe_price_round_offset(&self, safe_price_round_offset: u64) {
+        self.try_set_safe_price_round_offset(safe_price_round_offset);
+    }
+
+    fn try_set_keeper_fee_percent(&self, keeper_fee_percent: u64) {
+        require!(
+            keeper_fee_percent <= MAX_KEEPER_FEE_PERCENT,
+            ERROR_INVALID_ARGS
+        );
+
+        self.keeper_fee_percent().set(keeper_fee_percent);
+    }
+
+    fn try_set_max_deviation_percent(&self, max_deviation_percent: u64) {
+        require!(max_deviation_percent < MAX_PERCENTAGE, ERROR_INVALID_ARGS);
+
+        self.max_deviation_percent().set(max_deviation_percent);
+    }
+
+    fn try_set_safe_price_round_offset(&self, safe_price_round_offset: u64) {
+        require!(safe_price_round_offset > 0, ERROR_BAD_ROUND_OFFSET);
+
+        self.safe_price_round_offset().set(safe_price_round_offset);
+    }
+
+    #[view(getFarmAddress)]
+    #[storage_mapper("farmAddress")]
+    fn farm_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getPairAddress)]
+    #[storage_mapper("pairAddress")]
+    fn pair_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getRouterAddress)]
+    #[storage_mapper("routerAddress")]
+    fn router_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getLpTokenId)]
+    #[storage_mapper("lpTokenId")]
+    fn lp_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getRewardTokenId)]
+    #[storage_mapper("rewardTokenId")]
+    fn reward_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getFirstTokenId)]
+    #[storage_mapper("firstTokenId")]
+    fn first_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getSecondTokenId)]
+    #[storage_mapper("secondTokenId")]
+    fn second_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    /// Out of 10_000, taken from the rewards of each harvest.
+    #[view(getKeeperFeePercent)]
+    #[storage_mapper("keeperFeePercent")]
+    fn keeper_fee_percent(&self) -> SingleValueMapper<u64>;
+
+    /// Out of 10_000, how much less than the safe price a harvest swap can give.
+    #[view(getMaxDeviationPercent)]
+    #[storage_mapper("maxDeviationPercent")]
+    fn max_deviation_percent(&self) -> SingleValueMapper<u64>;
+
+    /// Contract queried through `getSafePriceByRoundOffset`, usually the safe price view.
+    #[view(getSafePriceViewAddress)]
+    #[storage_mapper("safePriceViewAddress")]
+    fn safe_price_view_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    /// Length, in rounds, of the window the safe price is averaged over.
+    #[view(getSafePriceRoundOffset)]
+    #[storage_mapper("safePriceRoundOffset")]
+    fn safe_price_round_offset(&self) -> SingleValueMapper<u64>;
+}
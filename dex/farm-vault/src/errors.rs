@@ -0,0 +1,10 @@
+pub static ERROR_INVALID_ARGS: &[u8] = b"Invalid args";
+pub static ERROR_BAD_ROUND_OFFSET: &[u8] = b"Bad safe price round offset";
+pub static ERROR_FARM_PAIR_MISMATCH: &[u8] = b"The farm does not accept the pair's LP tokens";
+pub static ERROR_FARM_EXIT_PENALTY: &[u8] = b"The farm has minimum farming epochs";
+
+pub static ERROR_BAD_PAYMENT: &[u8] = b"Bad payment";
+pub static ERROR_DEPOSIT_TOO_SMALL: &[u8] = b"Deposit too small";
+pub static ERROR_WITHDRAW_TOO_SMALL: &[u8] = b"Withdraw too small";
+pub static ERROR_NO_POSITION: &[u8] = b"The vault has no farm position";
+pub static ERROR_NO_PAIR: &[u8] = b"No pair for the reward token";
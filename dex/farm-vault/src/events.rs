@@ -0,0 +1,119 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[derive(TypeAbi, TopEncode)]
+pub struct DepositEvent<M: ManagedTypeApi> {
+    lp_amount: BigUint<M>,
+    shares: BigUint<M>,
+    share_token_supply: BigUint<M>,
+    position_amount: BigUint<M>,
+    block: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct WithdrawEvent<M: ManagedTypeApi> {
+    shares: BigUint<M>,
+    lp_payment: EsdtTokenPayment<M>,
+    rewards: EsdtTokenPayment<M>,
+    share_token_supply: BigUint<M>,
+    position_amount: BigUint<M>,
+    block: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct HarvestEvent<M: ManagedTypeApi> {
+    rewards: BigUint<M>,
+    keeper_fee: BigUint<M>,
+    compounded_lp_amount: BigUint<M>,
+    position_amount: BigUint<M>,
+    block: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_deposit_event(
+        &self,
+        lp_amount: BigUint,
+        shares: BigUint,
+        share_token_supply: BigUint,
+        position_amount: BigUint,
+    ) {
+        self.deposit_event(
+            &self.blockchain().get_caller(),
+            self.blockchain().get_block_epoch(),
+            &DepositEvent {
+                lp_amount,
+                shares,
+                share_token_supply,
+                position_amount,
+                block: self.blockchain().get_block_nonce(),
+            },
+        );
+    }
+
+    fn emit_withdraw_event(
+        &self,
+        shares: BigUint,
+        lp_payment: EsdtTokenPayment,
+        rewards: EsdtTokenPayment,
+        share_token_supply: BigUint,
+        position_amount: BigUint,
+    ) {
+        self.withdraw_event(
+            &self.blockchain().get_caller(),
+            self.blockchain().get_block_epoch(),
+            &WithdrawEvent {
+                shares,
+                lp_payment,
+                rewards,
+                share_token_supply,
+                position_amount,
+                block: self.blockchain().get_block_nonce(),
+            },
+        );
+    }
+
+    fn emit_harvest_event(
+        &self,
+        rewards: BigUint,
+        keeper_fee: BigUint,
+        compounded_lp_amount: BigUint,
+        position_amount: BigUint,
+    ) {
+        self.harvest_event(
+            &self.blockchain().get_caller(),
+            self.blockchain().get_block_epoch(),
+            &HarvestEvent {
+                rewards,
+                keeper_fee,
+                compounded_lp_amount,
+                position_amount,
+                block: self.blockchain().get_block_nonce(),
+            },
+        );
+    }
+
+    #[event("vault_deposit")]
+    fn deposit_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        deposit_event: &DepositEvent<Self::Api>,
+    );
+
+    #[event("vault_withdraw")]
+    fn withdraw_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        withdraw_event: &WithdrawEvent<Self::Api>,
+    );
+
+    #[event("vault_harvest")]
+    fn harvest_event(
+        &self,
+        #[indexed] keeper: &ManagedAddress,
+        #[indexed] epoch: u64,
+        harvest_event: &HarvestEvent<Self::Api>,
+    );
+}
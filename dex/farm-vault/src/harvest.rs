@@ -0,0 +1,308 @@
+multiversx_sc::imports!();
+
+use crate::{
+    config::{self, MAX_PERCENTAGE},
+    errors::*,
+    events,
+};
+
+use farm::{ClaimRewardsMultiResultType, EnterFarmResultType, ProxyTrait as _};
+use pair::safe_price_view::ProxyTrait as _;
+use pair::{AddLiquidityResultType, ProxyTrait as _};
+use router::factory::ProxyTrait as _;
+
+#[multiversx_sc::module]
+pub trait HarvestModule:
+    config::ConfigModule + events::EventsModule + router::router_swap::RouterSwapModule
+{
+    /// Claims the rewards of the pooled position and compounds them back into the farm.
+    /// Anyone can call it, and the caller receives the keeper fee out of the claimed rewards.
+    /// Returns the amount of LP tokens added to the position.
+    #[endpoint]
+    fn harvest(&self) -> BigUint {
+        require!(!self.farm_position().is_empty(), ERROR_NO_POSITION);
+
+        let rewards = self.claim_farm_rewards();
+        let keeper_fee = &rewards * self.keeper_fee_percent().get() / MAX_PERCENTAGE;
+        if keeper_fee > 0 {
+            self.send().direct_esdt(
+                &self.blockchain().get_caller(),
+                &self.reward_token_id().get(),
+                0,
+                &keeper_fee,
+            );
+        }
+
+        let compounded_lp_amount = self.compound_rewards(&rewards - &keeper_fee);
+        if compounded_lp_amount > 0 {
+            self.enter_farm(compounded_lp_amount.clone());
+        }
+
+        self.emit_harvest_event(
+            rewards,
+            keeper_fee,
+            compounded_lp_amount.clone(),
+            self.farm_position().get().amount,
+        );
+
+        compounded_lp_amount
+    }
+
+    /// The additional reward tokens of the farm, if any, are kept as leftovers,
+    /// to be compounded along with the main rewards.
+    fn claim_farm_rewards(&self) -> BigUint {
+        let position_mapper = self.farm_position();
+        if position_mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        let claim_result: ClaimRewardsMultiResultType<Self::Api> = self
+            .farm_proxy(self.farm_address().get())
+            .claim_rewards_endpoint(OptionalValue::<ManagedAddress>::None)
+            .with_esdt_transfer(position_mapper.get())
+            .execute_on_dest_context();
+        let (new_position, rewards, additional_rewards) = claim_result.into_tuple();
+        position_mapper.set(new_position);
+
+        for additional_reward in additional_rewards {
+            self.add_additional_reward(additional_reward);
+        }
+
+        rewards.amount
+    }
+
+    /// Tokens other than the reward and pair tokens are remembered,
+    /// so that each harvest swaps them into the pair tokens.
+    fn add_additional_reward(&self, payment: EsdtTokenPayment) {
+        if payment.amount == 0 {
+            return;
+        }
+
+        if payment.token_identifier != self.reward_token_id().get()
+            && payment.token_identifier != self.first_token_id().get()
+            && payment.token_identifier != self.second_token_id().get()
+        {
+            let _ = self
+                .additional_reward_token_ids()
+                .insert(payment.token_identifier.clone());
+        }
+
+        self.leftover_amount(&payment.token_identifier)
+            .update(|amount| *amount += payment.amount);
+    }
+
+    /// Converts the rewards, along with whatever was left from previous harvests,
+    /// into both pair tokens and adds them as liquidity.
+    /// Amounts too small to be swapped at the safe price are kept for the next harvest,
+    /// and so are the additional reward tokens the router has no pair for.
+    /// Returns the amount of LP tokens received, which are not yet in the farm.
+    fn compound_rewards(&self, reward_amount: BigUint) -> BigUint {
+        let reward_token_id = self.reward_token_id().get();
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        self.leftover_amount(&reward_token_id)
+            .update(|amount| *amount += reward_amount);
+
+        let (token_in, token_out) = if reward_token_id == second_token_id {
+            (second_token_id.clone(), first_token_id.clone())
+        } else {
+            (first_token_id.clone(), second_token_id.clone())
+        };
+        if reward_token_id != token_in {
+            let route_pair_address = self.get_route_pair_address(&reward_token_id, &token_in);
+            require!(!route_pair_address.is_zero(), ERROR_NO_PAIR);
+
+            let _ = self.swap_leftover(route_pair_address, &reward_token_id, &token_in, None);
+        }
+        for additional_token_id in self.additional_reward_token_ids().iter() {
+            let route_pair_address = self.get_route_pair_address(&additional_token_id, &token_in);
+            if !route_pair_address.is_zero() {
+                let _ =
+                    self.swap_leftover(route_pair_address, &additional_token_id, &token_in, None);
+            }
+        }
+
+        let pair_address = self.pair_address().get();
+        let half_amount = self.leftover_amount(&token_in).get() / 2u32;
+        if !self.swap_leftover(
+            pair_address.clone(),
+            &token_in,
+            &token_out,
+            Some(half_amount),
+        ) {
+            return BigUint::zero();
+        }
+
+        let first_amount = self.leftover_amount(&first_token_id).get();
+        let second_amount = self.leftover_amount(&second_token_id).get();
+        if self.get_expected_liquidity(pair_address.clone(), &first_amount, &second_amount) == 0 {
+            return BigUint::zero();
+        }
+
+        let mut payments = ManagedVec::new();
+        payments.push(EsdtTokenPayment::new(first_token_id, 0, first_amount));
+        payments.push(EsdtTokenPayment::new(second_token_id, 0, second_amount));
+        let add_liquidity_result: AddLiquidityResultType<Self::Api> = self
+            .pair_proxy(pair_address)
+            .add_liquidity(BigUint::from(1u32), BigUint::from(1u32))
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (lp_payment, first_used, second_used) = add_liquidity_result.into_tuple();
+
+        self.leftover_amount(&first_used.token_identifier)
+            .update(|amount| *amount -= &first_used.amount);
+        self.leftover_amount(&second_used.token_identifier)
+            .update(|amount| *amount -= &second_used.amount);
+
+        lp_payment.amount
+    }
+
+    fn get_route_pair_address(
+        &self,
+        token_id: &TokenIdentifier,
+        token_in: &TokenIdentifier,
+    ) -> ManagedAddress {
+        self.router_proxy(self.router_address().get())
+            .get_pair(token_id.clone(), token_in.clone())
+            .execute_on_dest_context()
+    }
+
+    /// Swaps the given amount of leftover tokens, or all of them if none is given.
+    /// Returns false, without swapping, if the safe price output is too small,
+    /// or if the pair currently gives less than that, e.g. right after its price was moved.
+    fn swap_leftover(
+        &self,
+        pair_address: ManagedAddress,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        opt_amount_in: Option<BigUint>,
+    ) -> bool {
+        let amount_in = match opt_amount_in {
+            Some(amount_in) => amount_in,
+            None => self.leftover_amount(token_in).get(),
+        };
+        if amount_in == 0 {
+            return false;
+        }
+
+        let min_amount_out = self.get_min_amount_out(pair_address.clone(), token_in, &amount_in);
+        if min_amount_out == 0 {
+            return false;
+        }
+
+        let quoted_amount_out: BigUint = self
+            .pair_proxy(pair_address.clone())
+            .try_get_amount_out_view(token_in.clone(), amount_in.clone())
+            .execute_on_dest_context();
+        if quoted_amount_out < min_amount_out {
+            return false;
+        }
+
+        let amount_out = self.swap_through_router(
+            self.router_address().get(),
+            pair_address,
+            EsdtTokenPayment::new(token_in.clone(), 0, amount_in.clone()),
+            token_out,
+            min_amount_out,
+        );
+        self.leftover_amount(token_in)
+            .update(|amount| *amount -= &amount_in);
+        self.leftover_amount(token_out)
+            .update(|amount| *amount += amount_out);
+
+        true
+    }
+
+    /// The safe price output over the configured round offset, minus the maximum deviation,
+    /// so a keeper cannot harvest right after moving the price.
+    fn get_min_amount_out(
+        &self,
+        pair_address: ManagedAddress,
+        token_in: &TokenIdentifier,
+        amount_in: &BigUint,
+    ) -> BigUint {
+        let safe_price_output: EsdtTokenPayment = self
+            .pair_proxy(self.safe_price_view_address().get())
+            .get_safe_price_by_round_offset(
+                pair_address,
+                self.safe_price_round_offset().get(),
+                EsdtTokenPayment::new(token_in.clone(), 0, amount_in.clone()),
+            )
+            .execute_on_dest_context();
+
+        safe_price_output.amount * (MAX_PERCENTAGE - self.max_deviation_percent().get())
+            / MAX_PERCENTAGE
+    }
+
+    /// The pair refuses to mint zero LP tokens, so tiny amounts are kept for later instead.
+    fn get_expected_liquidity(
+        &self,
+        pair_address: ManagedAddress,
+        first_amount: &BigUint,
+        second_amount: &BigUint,
+    ) -> BigUint {
+        let reserves: MultiValue3<BigUint, BigUint, BigUint> = self
+            .pair_proxy(pair_address)
+            .get_reserves_and_total_supply()
+            .execute_on_dest_context();
+        let (first_reserve, second_reserve, lp_token_supply) = reserves.into_tuple();
+        if first_reserve == 0 || second_reserve == 0 {
+            return BigUint::zero();
+        }
+
+        core::cmp::min(
+            first_amount * &lp_token_supply / first_reserve,
+            second_amount * &lp_token_supply / second_reserve,
+        )
+    }
+
+    /// Adds the LP tokens to the pooled position, merging them into a single farm token.
+    /// The farm's minimum farming epochs are checked again, since its admins can change them.
+    fn enter_farm(&self, lp_amount: BigUint) {
+        let farm_address = self.farm_address().get();
+        self.require_farm_without_exit_penalty(&farm_address);
+
+        let position_mapper = self.farm_position();
+        let mut payments = ManagedVec::new();
+        payments.push(EsdtTokenPayment::new(
+            self.lp_token_id().get(),
+            0,
+            lp_amount,
+        ));
+        if !position_mapper.is_empty() {
+            payments.push(position_mapper.get());
+        }
+
+        let enter_result: EnterFarmResultType<Self::Api> = self
+            .farm_proxy(farm_address)
+            .enter_farm_endpoint(OptionalValue::<ManagedAddress>::None)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (new_position, boosted_rewards) = enter_result.into_tuple();
+        position_mapper.set(new_position);
+
+        self.leftover_amount(&boosted_rewards.token_identifier)
+            .update(|amount| *amount += boosted_rewards.amount);
+    }
+
+    #[proxy]
+    fn farm_proxy(&self, to: ManagedAddress) -> farm::Proxy<Self::Api>;
+
+    #[proxy]
+    fn pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+
+    /// The single farm token holding the LP tokens of all depositors.
+    #[view(getFarmPosition)]
+    #[storage_mapper("farmPosition")]
+    fn farm_position(&self) -> SingleValueMapper<EsdtTokenPayment>;
+
+    /// Additional reward tokens of the farm received so far, other than the pair tokens.
+    #[view(getAdditionalRewardTokenIds)]
+    #[storage_mapper("additionalRewardTokenIds")]
+    fn additional_reward_token_ids(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    /// Tokens owned by the vault that are waiting to be compounded.
+    #[view(getLeftoverAmount)]
+    #[storage_mapper("leftoverAmount")]
+    fn leftover_amount(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
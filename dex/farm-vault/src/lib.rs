@@ -0,0 +1,188 @@
+#![no_std]
+#![allow(clippy::too_many_arguments)]
+
+multiversx_sc::imports!();
+
+pub mod config;
+pub mod errors;
+mod events;
+pub mod harvest;
+pub mod share_token;
+
+use errors::*;
+use farm::{ExitFarmMultiResultType, ProxyTrait as _};
+
+pub type WithdrawResultType<M> = MultiValue2<EsdtTokenPayment<M>, EsdtTokenPayment<M>>;
+
+/// Auto-compounding vault for an LP farm.
+///
+/// Users deposit LP tokens, which the vault keeps in a single farm position,
+/// and receive share tokens for their part of it. Anyone can call `harvest`,
+/// which claims the farm rewards, swaps them into the pair tokens through the router,
+/// adds them as liquidity and re-enters the farm, for a keeper fee out of the rewards.
+/// Burning the share tokens gives back the LP tokens they stand for.
+#[multiversx_sc::contract]
+pub trait FarmVault:
+    config::ConfigModule
+    + share_token::ShareTokenModule
+    + harvest::HarvestModule
+    + events::EventsModule
+    + router::router_swap::RouterSwapModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[init]
+    fn init(
+        &self,
+        farm_address: ManagedAddress,
+        pair_address: ManagedAddress,
+        router_address: ManagedAddress,
+        safe_price_view_address: ManagedAddress,
+        safe_price_round_offset: u64,
+        max_deviation_percent: u64,
+        keeper_fee_percent: u64,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&router_address),
+            ERROR_INVALID_ARGS
+        );
+
+        self.init_farm_and_pair(farm_address, pair_address);
+        self.router_address().set(&router_address);
+        self.safe_price_view_address().set(&safe_price_view_address);
+        self.try_set_safe_price_round_offset(safe_price_round_offset);
+        self.try_set_max_deviation_percent(max_deviation_percent);
+        self.try_set_keeper_fee_percent(keeper_fee_percent);
+    }
+
+    #[endpoint]
+    fn upgrade(&self) {}
+
+    /// Pending rewards are compounded first, without a keeper fee,
+    /// so the new shares are priced against the up-to-date position.
+    /// The swaps that would fail at the current price are skipped,
+    /// so moving the price cannot block deposits.
+    /// Returns the share tokens sent to the caller.
+    #[payable("*")]
+    #[endpoint]
+    fn deposit(&self) -> EsdtTokenPayment {
+        let (token_id, nonce, amount) = self.call_value().single_esdt().into_tuple();
+        require!(
+            token_id == self.lp_token_id().get() && nonce == 0 && amount > 0,
+            ERROR_BAD_PAYMENT
+        );
+
+        let rewards = self.claim_farm_rewards();
+        let compounded_lp_amount = self.compound_rewards(rewards);
+
+        let share_token_supply = self.share_token_supply().get();
+        let shares = if share_token_supply == 0 {
+            amount.clone()
+        } else {
+            let position_amount = self.get_position_amount() + &compounded_lp_amount;
+            &amount * &share_token_supply / position_amount
+        };
+        require!(shares > 0, ERROR_DEPOSIT_TOO_SMALL);
+
+        self.enter_farm(compounded_lp_amount + &amount);
+
+        let new_share_token_supply = share_token_supply + &shares;
+        self.share_token_supply().set(&new_share_token_supply);
+        let share_payment = self
+            .share_token()
+            .mint_and_send(&self.blockchain().get_caller(), shares.clone());
+
+        self.emit_deposit_event(
+            amount,
+            shares,
+            new_share_token_supply,
+            self.get_position_amount(),
+        );
+
+        share_payment
+    }
+
+    /// Exits the farm with the part of the position the shares stand for,
+    /// without compounding first, so it cannot fail because of the price.
+    /// Returns the LP tokens, after the farm's exit penalty, if any,
+    /// and the farm rewards of that part. The additional rewards are sent as well.
+    #[payable("*")]
+    #[endpoint]
+    fn withdraw(&self) -> WithdrawResultType<Self::Api> {
+        let payment = self.call_value().single_esdt();
+        self.share_token()
+            .require_same_token(&payment.token_identifier);
+        require!(payment.amount > 0, ERROR_BAD_PAYMENT);
+
+        let lp_amount = self.get_lp_amount_for_shares(payment.amount.clone());
+        require!(lp_amount > 0, ERROR_WITHDRAW_TOO_SMALL);
+
+        let share_token_supply = self.share_token_supply().get() - &payment.amount;
+        self.share_token_supply().set(&share_token_supply);
+        self.share_token().burn(&payment.amount);
+
+        let mut position = self.farm_position().get();
+        let exit_payment = EsdtTokenPayment::new(
+            position.token_identifier.clone(),
+            position.token_nonce,
+            lp_amount.clone(),
+        );
+        position.amount -= &lp_amount;
+        if position.amount == 0 {
+            self.farm_position().clear();
+        } else {
+            self.farm_position().set(&position);
+        }
+
+        let exit_result: ExitFarmMultiResultType<Self::Api> = self
+            .farm_proxy(self.farm_address().get())
+            .exit_farm_endpoint(OptionalValue::<ManagedAddress>::None)
+            .with_esdt_transfer(exit_payment)
+            .execute_on_dest_context();
+        let (lp_payment, rewards, additional_rewards) = exit_result.into_tuple();
+
+        let mut payments = ManagedVec::new();
+        for user_payment in [lp_payment.clone(), rewards.clone()] {
+            if user_payment.amount > 0 {
+                payments.push(user_payment);
+            }
+        }
+        for user_payment in additional_rewards {
+            if user_payment.amount > 0 {
+                payments.push(user_payment);
+            }
+        }
+        self.send()
+            .direct_multi(&self.blockchain().get_caller(), &payments);
+
+        self.emit_withdraw_event(
+            payment.amount,
+            lp_payment.clone(),
+            rewards.clone(),
+            share_token_supply,
+            position.amount,
+        );
+
+        (lp_payment, rewards).into()
+    }
+
+    /// Farm tokens of the position the given amount of shares stands for,
+    /// which is also the amount of LP tokens before the farm's exit penalty.
+    #[view(getLpAmountForShares)]
+    fn get_lp_amount_for_shares(&self, shares: BigUint) -> BigUint {
+        let share_token_supply = self.share_token_supply().get();
+        if share_token_supply == 0 {
+            return BigUint::zero();
+        }
+
+        shares * self.get_position_amount() / share_token_supply
+    }
+
+    fn get_position_amount(&self) -> BigUint {
+        let position_mapper = self.farm_position();
+        if position_mapper.is_empty() {
+            return BigUint::zero();
+        }
+
+        position_mapper.get().amount
+    }
+}
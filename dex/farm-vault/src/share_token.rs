@@ -0,0 +1,35 @@
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait ShareTokenModule:
+    multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// The share token should have the same number of decimals as the LP token,
+    /// since the first deposit mints one share per LP token.
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issueShareToken)]
+    fn issue_share_token(
+        &self,
+        token_display_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+        num_decimals: usize,
+    ) {
+        let payment_amount = self.call_value().egld_value().clone_value();
+        self.share_token().issue_and_set_all_roles(
+            payment_amount,
+            token_display_name,
+            token_ticker,
+            num_decimals,
+            None,
+        );
+    }
+
+    #[view(getShareTokenId)]
+    #[storage_mapper("shareTokenId")]
+    fn share_token(&self) -> FungibleTokenMapper;
+
+    #[view(getShareTokenSupply)]
+    #[storage_mapper("shareTokenSupply")]
+    fn share_token_supply(&self) -> SingleValueMapper<BigUint>;
+}
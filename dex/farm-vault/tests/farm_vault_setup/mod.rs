@@ -0,0 +1,388 @@
+use config::ConfigModule as FarmConfigModule;
+use energy_query::EnergyQueryModule;
+use farm::exit_penalty::ExitPenaltyModule;
+use farm::Farm;
+use farm_token::FarmTokenModule;
+use farm_vault::harvest::HarvestModule;
+use farm_vault::share_token::ShareTokenModule;
+use farm_vault::FarmVault;
+use multiversx_sc::codec::multi_types::OptionalValue;
+use multiversx_sc::storage::mappers::StorageTokenWrapper;
+use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+};
+use pair::config::ConfigModule as PairConfigModule;
+use pair::Pair;
+use pausable::{PausableModule, State};
+use router::factory::{FactoryModule, PairTokens};
+use router::Router;
+
+pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
+pub const ROUTER_WASM_PATH: &str = "router/output/router.wasm";
+pub const FARM_WASM_PATH: &str = "farm/output/farm.wasm";
+pub const FARM_VAULT_WASM_PATH: &str = "farm-vault/output/farm-vault.wasm";
+pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+pub const LPMEX_TOKEN_ID: &[u8] = b"LPMEX-abcdef";
+pub const FARM_TOKEN_ID: &[u8] = b"FARM-abcdef";
+pub const SHARE_TOKEN_ID: &[u8] = b"VAULT-abcdef";
+pub const UTK_TOKEN_ID: &[u8] = b"UTK-abcdef";
+
+pub const USER_TOTAL_WEGLD_TOKENS: u64 = 5_000_000_000;
+pub const USER_TOTAL_MEX_TOKENS: u64 = 5_000_000_000;
+pub const ADD_LIQUIDITY_TOKENS: u64 = 1_001_000;
+pub const USER_LP_TOKENS: u64 = 1_000_000;
+pub const DIV_SAFETY: u64 = 1_000_000_000_000;
+pub const PER_BLOCK_REWARD_AMOUNT: u64 = 1_000;
+pub const SAFE_PRICE_ROUND_OFFSET: u64 = 5;
+pub const MAX_DEVIATION_PERCENT: u64 = 500;
+pub const KEEPER_FEE_PERCENT: u64 = 500;
+
+#[allow(dead_code)]
+pub struct FarmVaultSetup<
+    FarmVaultObjBuilder,
+    FarmObjBuilder,
+    RouterObjBuilder,
+    PairObjBuilder,
+    EnergyFactoryObjBuilder,
+> where
+    FarmVaultObjBuilder: 'static + Copy + Fn() -> farm_vault::ContractObj<DebugApi>,
+    FarmObjBuilder: 'static + Copy + Fn() -> farm::ContractObj<DebugApi>,
+    RouterObjBuilder: 'static + Copy + Fn() -> router::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+    EnergyFactoryObjBuilder: 'static + Copy + Fn() -> energy_factory_mock::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub user_address: Address,
+    pub keeper_address: Address,
+    pub farm_vault_wrapper:
+        ContractObjWrapper<farm_vault::ContractObj<DebugApi>, FarmVaultObjBuilder>,
+    pub farm_wrapper: ContractObjWrapper<farm::ContractObj<DebugApi>, FarmObjBuilder>,
+    pub router_wrapper: ContractObjWrapper<router::ContractObj<DebugApi>, RouterObjBuilder>,
+    pub pair_wrapper: ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>,
+    pub energy_factory_wrapper:
+        ContractObjWrapper<energy_factory_mock::ContractObj<DebugApi>, EnergyFactoryObjBuilder>,
+}
+
+impl<
+        FarmVaultObjBuilder,
+        FarmObjBuilder,
+        RouterObjBuilder,
+        PairObjBuilder,
+        EnergyFactoryObjBuilder,
+    >
+    FarmVaultSetup<
+        FarmVaultObjBuilder,
+        FarmObjBuilder,
+        RouterObjBuilder,
+        PairObjBuilder,
+        EnergyFactoryObjBuilder,
+    >
+where
+    FarmVaultObjBuilder: 'static + Copy + Fn() -> farm_vault::ContractObj<DebugApi>,
+    FarmObjBuilder: 'static + Copy + Fn() -> farm::ContractObj<DebugApi>,
+    RouterObjBuilder: 'static + Copy + Fn() -> router::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+    EnergyFactoryObjBuilder: 'static + Copy + Fn() -> energy_factory_mock::ContractObj<DebugApi>,
+{
+    pub fn new(
+        farm_vault_builder: FarmVaultObjBuilder,
+        farm_builder: FarmObjBuilder,
+        router_builder: RouterObjBuilder,
+        pair_builder: PairObjBuilder,
+        energy_factory_builder: EnergyFactoryObjBuilder,
+    ) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_addr = b_mock.create_user_account(&rust_zero);
+        let user_addr = b_mock.create_user_account(&rust_zero);
+        let keeper_addr = b_mock.create_user_account(&rust_zero);
+
+        let router_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            router_builder,
+            ROUTER_WASM_PATH,
+        );
+        let pair_wrapper =
+            b_mock.create_sc_account(&rust_zero, Some(&owner_addr), pair_builder, PAIR_WASM_PATH);
+        let farm_wrapper =
+            b_mock.create_sc_account(&rust_zero, Some(&owner_addr), farm_builder, FARM_WASM_PATH);
+        let energy_factory_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            energy_factory_builder,
+            "energy_factory.wasm",
+        );
+        let farm_vault_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            farm_vault_builder,
+            FARM_VAULT_WASM_PATH,
+        );
+
+        b_mock
+            .execute_tx(&owner_addr, &pair_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    300,
+                    50,
+                    ManagedAddress::<DebugApi>::zero(),
+                    MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+                );
+
+                sc.lp_token_identifier()
+                    .set(&managed_token_id!(LPMEX_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&owner_addr, &router_wrapper, &rust_zero, |sc| {
+                sc.init(OptionalValue::None);
+
+                let pair_address = managed_address!(pair_wrapper.address_ref());
+                sc.pair_map().insert(
+                    PairTokens {
+                        first_token_id: managed_token_id!(WEGLD_TOKEN_ID),
+                        second_token_id: managed_token_id!(MEX_TOKEN_ID),
+                    },
+                    pair_address.clone(),
+                );
+                sc.address_pair_map().insert(
+                    pair_address,
+                    PairTokens {
+                        first_token_id: managed_token_id!(WEGLD_TOKEN_ID),
+                        second_token_id: managed_token_id!(MEX_TOKEN_ID),
+                    },
+                );
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&owner_addr, &farm_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_token_id!(LPMEX_TOKEN_ID),
+                    managed_biguint!(DIV_SAFETY),
+                    managed_address!(&Address::zero()),
+                    managed_address!(&owner_addr),
+                    MultiValueEncoded::new(),
+                );
+
+                sc.farm_token()
+                    .set_token_id(managed_token_id!(FARM_TOKEN_ID));
+                sc.per_block_reward_amount()
+                    .set(&managed_biguint!(PER_BLOCK_REWARD_AMOUNT));
+
+                sc.state().set(State::Active);
+                sc.produce_rewards_enabled().set(true);
+                sc.minimum_farming_epochs().set(0);
+                sc.set_energy_factory_address(managed_address!(
+                    energy_factory_wrapper.address_ref()
+                ));
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&owner_addr, &farm_vault_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_address!(farm_wrapper.address_ref()),
+                    managed_address!(pair_wrapper.address_ref()),
+                    managed_address!(router_wrapper.address_ref()),
+                    managed_address!(pair_wrapper.address_ref()),
+                    SAFE_PRICE_ROUND_OFFSET,
+                    MAX_DEVIATION_PERCENT,
+                    KEEPER_FEE_PERCENT,
+                );
+
+                sc.share_token()
+                    .set_token_id(managed_token_id!(SHARE_TOKEN_ID));
+            })
+            .assert_ok();
+
+        let lp_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        b_mock.set_esdt_local_roles(
+            pair_wrapper.address_ref(),
+            LPMEX_TOKEN_ID,
+            &lp_token_roles[..],
+        );
+
+        let farm_token_roles = [
+            EsdtLocalRole::NftCreate,
+            EsdtLocalRole::NftAddQuantity,
+            EsdtLocalRole::NftBurn,
+        ];
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            FARM_TOKEN_ID,
+            &farm_token_roles[..],
+        );
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            LPMEX_TOKEN_ID,
+            &[EsdtLocalRole::Burn][..],
+        );
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            MEX_TOKEN_ID,
+            &[EsdtLocalRole::Mint][..],
+        );
+
+        let share_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        b_mock.set_esdt_local_roles(
+            farm_vault_wrapper.address_ref(),
+            SHARE_TOKEN_ID,
+            &share_token_roles[..],
+        );
+
+        b_mock.set_esdt_balance(
+            &user_addr,
+            WEGLD_TOKEN_ID,
+            &rust_biguint!(USER_TOTAL_WEGLD_TOKENS),
+        );
+        b_mock.set_esdt_balance(
+            &user_addr,
+            MEX_TOKEN_ID,
+            &rust_biguint!(USER_TOTAL_MEX_TOKENS),
+        );
+
+        let payments = vec![
+            TxTokenTransfer {
+                token_identifier: WEGLD_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(ADD_LIQUIDITY_TOKENS),
+            },
+            TxTokenTransfer {
+                token_identifier: MEX_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(ADD_LIQUIDITY_TOKENS),
+            },
+        ];
+        b_mock
+            .execute_esdt_multi_transfer(&user_addr, &pair_wrapper, &payments, |sc| {
+                sc.add_liquidity(
+                    managed_biguint!(ADD_LIQUIDITY_TOKENS),
+                    managed_biguint!(ADD_LIQUIDITY_TOKENS),
+                );
+            })
+            .assert_ok();
+
+        FarmVaultSetup {
+            b_mock,
+            owner_address: owner_addr,
+            user_address: user_addr,
+            keeper_address: keeper_addr,
+            farm_vault_wrapper,
+            farm_wrapper,
+            router_wrapper,
+            pair_wrapper,
+            energy_factory_wrapper,
+        }
+    }
+
+    pub fn deposit(&mut self, lp_amount: u64) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            &self.user_address,
+            &self.farm_vault_wrapper,
+            LPMEX_TOKEN_ID,
+            0,
+            &rust_biguint!(lp_amount),
+            |sc| {
+                let _ = sc.deposit();
+            },
+        )
+    }
+
+    pub fn withdraw(&mut self, shares: u64) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            &self.user_address,
+            &self.farm_vault_wrapper,
+            SHARE_TOKEN_ID,
+            0,
+            &rust_biguint!(shares),
+            |sc| {
+                let _ = sc.withdraw();
+            },
+        )
+    }
+
+    pub fn harvest(&mut self) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.keeper_address,
+            &self.farm_vault_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.harvest();
+            },
+        )
+    }
+
+    pub fn get_position_amount(&mut self) -> u64 {
+        let mut position_amount = 0;
+        self.b_mock
+            .execute_query(&self.farm_vault_wrapper, |sc| {
+                position_amount = sc.farm_position().get().amount.to_u64().unwrap();
+            })
+            .assert_ok();
+
+        position_amount
+    }
+
+    pub fn swap_fixed_input(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount: u64,
+        desired_token_id: &[u8],
+        expected_amount: u64,
+    ) {
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount),
+                |sc| {
+                    let ret = sc.swap_tokens_fixed_input(
+                        managed_token_id!(desired_token_id),
+                        managed_biguint!(1),
+                        OptionalValue::None,
+                    );
+                    assert_eq!(ret.amount, managed_biguint!(expected_amount));
+                },
+            )
+            .assert_ok();
+    }
+
+    /// Swaps without checking the output, e.g. to move the price of the pair.
+    pub fn move_pair_price(&mut self, payment_token_id: &[u8], payment_amount: u64) {
+        let desired_token_id = if payment_token_id == MEX_TOKEN_ID {
+            WEGLD_TOKEN_ID
+        } else {
+            MEX_TOKEN_ID
+        };
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount),
+                |sc| {
+                    let _ = sc.swap_tokens_fixed_input(
+                        managed_token_id!(desired_token_id),
+                        managed_biguint!(1),
+                        OptionalValue::None,
+                    );
+                },
+            )
+            .assert_ok();
+    }
+}
@@ -0,0 +1,262 @@
+#![allow(deprecated)]
+
+mod farm_vault_setup;
+use farm::additional_rewards::AdditionalRewardsModule;
+use farm::exit_penalty::ExitPenaltyModule;
+use farm_vault::harvest::HarvestModule;
+use farm_vault::share_token::ShareTokenModule;
+use farm_vault::FarmVault;
+use farm_vault_setup::*;
+use multiversx_sc_scenario::{managed_biguint, managed_token_id, rust_biguint};
+
+#[test]
+fn test_farm_vault_setup() {
+    let _ = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+        energy_factory_mock::contract_obj,
+    );
+}
+
+#[test]
+fn test_farm_vault_deposit_harvest_withdraw() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+        energy_factory_mock::contract_obj,
+    );
+
+    // records the first price observation, so the safe price is available afterwards
+    setup.b_mock.set_block_round(2);
+    setup.swap_fixed_input(MEX_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 996);
+
+    // the first deposit mints one share per LP token
+    setup.b_mock.set_block_nonce(10);
+    setup.b_mock.set_block_round(10);
+    setup.deposit(100_000).assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&setup.user_address, SHARE_TOKEN_ID, &rust_biguint!(100_000));
+    assert_eq!(setup.get_position_amount(), 100_000);
+
+    // the vault is the only farmer, so it gets all the rewards of the 10 blocks
+    setup.b_mock.set_block_nonce(20);
+    setup.b_mock.set_block_round(20);
+    setup.harvest().assert_ok();
+    setup.b_mock.check_esdt_balance(
+        &setup.keeper_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(PER_BLOCK_REWARD_AMOUNT * 10 * KEEPER_FEE_PERCENT / 10_000),
+    );
+
+    let position_amount = setup.get_position_amount();
+    assert!(position_amount > 100_000);
+
+    setup
+        .b_mock
+        .execute_query(&setup.farm_vault_wrapper, |sc| {
+            assert_eq!(sc.share_token_supply().get(), managed_biguint!(100_000));
+            assert_eq!(
+                sc.get_lp_amount_for_shares(managed_biguint!(100_000)),
+                managed_biguint!(position_amount)
+            );
+        })
+        .assert_ok();
+
+    // the farm has no minimum farming epochs, so no exit penalty
+    setup.b_mock.set_block_nonce(30);
+    setup.b_mock.set_block_round(30);
+    setup.withdraw(100_000).assert_ok();
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        LPMEX_TOKEN_ID,
+        &rust_biguint!(USER_LP_TOKENS - 100_000 + position_amount),
+    );
+    setup
+        .b_mock
+        .check_esdt_balance(&setup.user_address, SHARE_TOKEN_ID, &rust_biguint!(0));
+
+    setup
+        .b_mock
+        .execute_query(&setup.farm_vault_wrapper, |sc| {
+            assert_eq!(sc.share_token_supply().get(), managed_biguint!(0));
+            assert!(sc.farm_position().is_empty());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_farm_vault_additional_rewards_kept_for_compounding() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+        energy_factory_mock::contract_obj,
+    );
+
+    setup.b_mock.set_block_round(2);
+    setup.swap_fixed_input(MEX_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 996);
+
+    setup.b_mock.set_block_nonce(10);
+    setup.b_mock.set_block_round(10);
+    setup
+        .b_mock
+        .set_esdt_balance(&setup.owner_address, UTK_TOKEN_ID, &rust_biguint!(10_000));
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.owner_address,
+            &setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_additional_reward_token(managed_token_id!(UTK_TOKEN_ID));
+                sc.set_additional_reward_per_block(
+                    managed_token_id!(UTK_TOKEN_ID),
+                    managed_biguint!(100),
+                );
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.owner_address,
+            &setup.farm_wrapper,
+            UTK_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                sc.deposit_additional_rewards();
+            },
+        )
+        .assert_ok();
+
+    setup.deposit(100_000).assert_ok();
+
+    // the router has no pair for the additional reward token, so it waits in the vault
+    setup.b_mock.set_block_nonce(20);
+    setup.b_mock.set_block_round(20);
+    setup.harvest().assert_ok();
+    setup.b_mock.check_esdt_balance(
+        setup.farm_vault_wrapper.address_ref(),
+        UTK_TOKEN_ID,
+        &rust_biguint!(1_000),
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.farm_vault_wrapper, |sc| {
+            assert_eq!(
+                sc.leftover_amount(&managed_token_id!(UTK_TOKEN_ID)).get(),
+                managed_biguint!(1_000)
+            );
+            assert!(sc
+                .additional_reward_token_ids()
+                .contains(&managed_token_id!(UTK_TOKEN_ID)));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_farm_vault_deposit_skips_compounding_after_price_move() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+        energy_factory_mock::contract_obj,
+    );
+
+    setup.b_mock.set_block_round(2);
+    setup.swap_fixed_input(MEX_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 996);
+
+    setup.b_mock.set_block_nonce(10);
+    setup.b_mock.set_block_round(10);
+    setup.deposit(100_000).assert_ok();
+
+    // the safe price is recorded before the swap, so only the pair price moves
+    setup.b_mock.set_block_nonce(20);
+    setup.b_mock.set_block_round(20);
+    setup.move_pair_price(MEX_TOKEN_ID, 500_000);
+
+    // the rewards are kept for later, instead of failing the deposit
+    setup.deposit(50_000).assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&setup.user_address, SHARE_TOKEN_ID, &rust_biguint!(150_000));
+    assert_eq!(setup.get_position_amount(), 150_000);
+
+    setup
+        .b_mock
+        .execute_query(&setup.farm_vault_wrapper, |sc| {
+            assert_eq!(
+                sc.leftover_amount(&managed_token_id!(MEX_TOKEN_ID)).get(),
+                managed_biguint!(PER_BLOCK_REWARD_AMOUNT * 10)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_farm_vault_bad_calls() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+        energy_factory_mock::contract_obj,
+    );
+
+    setup
+        .harvest()
+        .assert_user_error("The vault has no farm position");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.farm_vault_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.deposit();
+            },
+        )
+        .assert_user_error("Bad payment");
+
+    setup.deposit(1_000).assert_ok();
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.farm_vault_wrapper,
+            LPMEX_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.withdraw();
+            },
+        )
+        .assert_user_error("Invalid payment token");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &setup.owner_address,
+            &setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.minimum_farming_epochs().set(1);
+            },
+        )
+        .assert_ok();
+    setup
+        .deposit(1_000)
+        .assert_user_error("The farm has minimum farming epochs");
+}
@@ -0,0 +1,54 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Endpoints:                           26
+// Async Callback:                       1
+// Total number of exported functions:  28
+
+#![no_std]
+
+// Configuration that works with rustc < 1.73.0.
+// TODO: Recommended rustc version: 1.73.0 or newer.
+#![feature(lang_items)]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    farm_vault
+    (
+        init => init
+        upgrade => upgrade
+        deposit => deposit
+        withdraw => withdraw
+        getLpAmountForShares => get_lp_amount_for_shares
+        setKeeperFeePercent => set_keeper_fee_percent
+        setMaxDeviationPercent => set_max_deviation_percent
+        setSafePriceViewAddress => set_safe_price_view_address
+        setSafePriceRoundOffset => set_safe_price_round_offset
+        getFarmAddress => farm_address
+        getPairAddress => pair_address
+        getRouterAddress => router_address
+        getLpTokenId => lp_token_id
+        getRewardTokenId => reward_token_id
+        getFirstTokenId => first_token_id
+        getSecondTokenId => second_token_id
+        getKeeperFeePercent => keeper_fee_percent
+        getMaxDeviationPercent => max_deviation_percent
+        getSafePriceViewAddress => safe_price_view_address
+        getSafePriceRoundOffset => safe_price_round_offset
+        issueShareToken => issue_share_token
+        getShareTokenId => share_token
+        getShareTokenSupply => share_token_supply
+        harvest => harvest
+        getFarmPosition => farm_position
+        getAdditionalRewardTokenIds => additional_reward_token_ids
+        getLeftoverAmount => leftover_amount
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback! { farm_vault }
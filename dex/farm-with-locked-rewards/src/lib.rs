@@ -5,16 +5,17 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use common_structs::FarmTokenAttributes;
+use common_structs::{FarmTokenAttributes, Nonce};
 use contexts::storage_cache::StorageCache;
 use core::marker::PhantomData;
 
 use farm::{
-    base_functions::{BaseFunctionsModule, ClaimRewardsResultType, DoubleMultiPayment, Wrapper},
+    base_functions::{BaseFunctionsModule, DoubleMultiPayment, Wrapper},
     exit_penalty::{
         DEFAULT_BURN_GAS_LIMIT, DEFAULT_MINUMUM_FARMING_EPOCHS, DEFAULT_PENALTY_PERCENT,
     },
-    EnterFarmResultType, ExitFarmWithPartialPosResultType,
+    CalculateRewardsResultType, ClaimRewardsMultiResultType, EnterFarmResultType,
+    ExitFarmMultiResultType,
 };
 use farm_base_impl::base_traits_impl::FarmContract;
 
@@ -33,6 +34,7 @@ pub trait Farm:
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + farm::base_functions::BaseFunctionsModule
     + farm::exit_penalty::ExitPenaltyModule
+    + farm::additional_rewards::AdditionalRewardsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -106,7 +108,12 @@ pub trait Farm:
             orig_caller.clone(),
         );
 
+        let mut additional_farm_tokens = self.get_non_empty_payments();
+        additional_farm_tokens.remove(0);
+
+        self.generate_additional_rewards();
         let new_farm_token = self.enter_farm::<NoMintWrapper<Self>>(orig_caller.clone());
+        self.set_position_additional_reward_per_share(&new_farm_token, &additional_farm_tokens);
         self.send_payment_non_zero(&caller, &new_farm_token);
 
         self.update_energy_and_progress(&orig_caller);
@@ -119,16 +126,26 @@ pub trait Farm:
     fn claim_rewards_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ClaimRewardsResultType<Self::Api> {
+    ) -> ClaimRewardsMultiResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
         self.migrate_old_farm_positions(&orig_caller);
 
         let payments = self.call_value().all_esdt_transfers().clone_value();
+        let mut additional_payments = payments.clone();
+        let first_farm_token = self.pop_first_payment(&mut additional_payments);
+
+        self.generate_additional_rewards();
+        let additional_rewards =
+            self.claim_and_send_additional_rewards(&orig_caller, &first_farm_token);
         let base_claim_rewards_result =
             self.claim_rewards_base::<NoMintWrapper<Self>>(orig_caller.clone(), payments);
         let output_farm_token_payment = base_claim_rewards_result.new_farm_token.payment.clone();
+        self.set_position_additional_reward_per_share(
+            &output_farm_token_payment,
+            &additional_payments,
+        );
         self.send_payment_non_zero(&caller, &output_farm_token_payment);
 
         let rewards_payment = base_claim_rewards_result.rewards;
@@ -148,7 +165,12 @@ pub trait Farm:
             base_claim_rewards_result.storage_cache,
         );
 
-        (output_farm_token_payment, locked_rewards_payment).into()
+        (
+            output_farm_token_payment,
+            locked_rewards_payment,
+            additional_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -156,7 +178,7 @@ pub trait Farm:
     fn exit_farm_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+    ) -> ExitFarmMultiResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
@@ -164,6 +186,8 @@ pub trait Farm:
 
         let migrated_amount = self.migrate_old_farm_positions(&orig_caller);
 
+        self.generate_additional_rewards();
+        let additional_rewards = self.claim_and_send_additional_rewards(&orig_caller, &payment);
         let exit_farm_result = self.exit_farm::<NoMintWrapper<Self>>(orig_caller.clone(), payment);
 
         self.decrease_old_farm_positions(migrated_amount, &orig_caller);
@@ -180,7 +204,12 @@ pub trait Farm:
 
         self.clear_user_energy_if_needed(&orig_caller);
 
-        (exit_farm_result.farming_tokens, locked_rewards_payment).into()
+        (
+            exit_farm_result.farming_tokens,
+            locked_rewards_payment,
+            additional_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -195,7 +224,9 @@ pub trait Farm:
         self.migrate_old_farm_positions(&orig_caller);
         let boosted_rewards = self.claim_only_boosted_payment(&orig_caller);
 
+        let payments = self.get_non_empty_payments();
         let merged_farm_token = self.merge_farm_tokens::<NoMintWrapper<Self>>();
+        self.set_position_additional_reward_per_share(&merged_farm_token, &payments);
 
         self.send_payment_non_zero(&caller, &merged_farm_token);
         let locked_rewards_payment = self.send_to_lock_contract_non_zero(
@@ -253,25 +284,72 @@ pub trait Farm:
         self.set_per_block_rewards::<NoMintWrapper<Self>>(per_block_amount);
     }
 
+    /// Each entry is a `(start_block, per_block_amount)` pair, and a zero amount ends the emission.
+    /// Replaces the upcoming schedule.
+    #[endpoint(setEmissionSchedule)]
+    fn set_emission_schedule_endpoint(
+        &self,
+        entries: MultiValueEncoded<MultiValue2<Nonce, BigUint>>,
+    ) {
+        self.require_caller_has_admin_permissions();
+        let schedule = self.build_emission_schedule(entries);
+        self.set_emission_schedule::<NoMintWrapper<Self>>(schedule);
+    }
+
+    /// Decreases the per block amount by `decay_percentage` every period, e.g. 5_000 for halvings.
+    /// The emission ends after `nr_periods` periods.
+    #[endpoint(setDecayingEmissionSchedule)]
+    fn set_decaying_emission_schedule_endpoint(
+        &self,
+        start_block: Nonce,
+        initial_per_block_amount: BigUint,
+        period_in_blocks: Nonce,
+        decay_percentage: u64,
+        nr_periods: usize,
+    ) {
+        self.require_caller_has_admin_permissions();
+        let schedule = self.build_decaying_emission_schedule(
+            start_block,
+            initial_per_block_amount,
+            period_in_blocks,
+            decay_percentage,
+            nr_periods,
+        );
+        self.set_emission_schedule::<NoMintWrapper<Self>>(schedule);
+    }
+
+    /// The additional rewards are kept by farm token nonce, not in the attributes,
+    /// so they are only returned if the nonce is given.
     #[view(calculateRewardsForGivenPosition)]
     fn calculate_rewards_for_given_position(
         &self,
         user: ManagedAddress,
         farm_token_amount: BigUint,
         attributes: FarmTokenAttributes<Self::Api>,
-    ) -> BigUint {
+        opt_farm_token_nonce: OptionalValue<Nonce>,
+    ) -> CalculateRewardsResultType<Self::Api> {
         self.require_queried();
 
         let mut storage_cache = StorageCache::new(self);
         NoMintWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
 
-        NoMintWrapper::<Self>::calculate_rewards(
+        let rewards = NoMintWrapper::<Self>::calculate_rewards(
             self,
             &user,
             &farm_token_amount,
             &attributes,
             &storage_cache,
-        )
+        );
+
+        self.generate_additional_rewards();
+        let additional_rewards = match opt_farm_token_nonce {
+            OptionalValue::Some(farm_token_nonce) => {
+                self.calculate_additional_rewards(farm_token_nonce, &farm_token_amount)
+            }
+            OptionalValue::None => ManagedVec::new(),
+        };
+
+        (rewards, additional_rewards.into()).into()
     }
 
     fn send_to_lock_contract_non_zero(
@@ -18,6 +18,7 @@ use fees_collector_mock::*;
 
 use energy_factory::{energy::EnergyModule, SimpleLockEnergy};
 use energy_query::{Energy, EnergyQueryModule};
+use farm::additional_rewards::AdditionalRewardsModule;
 use farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule;
 use farm_boosted_yields::FarmBoostedYieldsModule;
 use farm_token::FarmTokenModule;
@@ -34,6 +35,7 @@ pub static LOCKED_REWARD_TOKEN_ID: &[u8] = b"LOCKED-123456";
 pub static LEGACY_LOCKED_TOKEN_ID: &[u8] = b"LEGACY-123456";
 pub static FARMING_TOKEN_ID: &[u8] = b"LPTOK-123456";
 pub static FARM_TOKEN_ID: &[u8] = b"FARM-123456";
+pub static ADDITIONAL_REWARD_TOKEN_ID: &[u8] = b"PARTNER-123456";
 const DIV_SAFETY: u64 = 1_000_000_000_000;
 const PER_BLOCK_REWARD_AMOUNT: u64 = 1_000;
 const FARMING_TOKEN_BALANCE: u64 = 100_000_000;
@@ -275,6 +277,35 @@ where
             .assert_ok();
     }
 
+    pub fn add_additional_reward_token(&mut self, per_block_amount: u64, deposit_amount: u64) {
+        self.b_mock.set_esdt_balance(
+            &self.owner,
+            ADDITIONAL_REWARD_TOKEN_ID,
+            &rust_biguint!(deposit_amount),
+        );
+
+        self.b_mock
+            .execute_tx(&self.owner, &self.farm_wrapper, &rust_biguint!(0), |sc| {
+                let token_id = managed_token_id!(ADDITIONAL_REWARD_TOKEN_ID);
+                sc.add_additional_reward_token(token_id.clone());
+                sc.set_additional_reward_per_block(token_id, managed_biguint!(per_block_amount));
+            })
+            .assert_ok();
+
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.owner,
+                &self.farm_wrapper,
+                ADDITIONAL_REWARD_TOKEN_ID,
+                0,
+                &rust_biguint!(deposit_amount),
+                |sc| {
+                    sc.deposit_additional_rewards();
+                },
+            )
+            .assert_ok();
+    }
+
     pub fn enter_farm(&mut self, user: &Address, farming_token_amount: u64) {
         self.last_farm_token_nonce += 1;
 
@@ -355,8 +386,10 @@ where
                     managed_address!(user),
                     managed_biguint!(farm_token_amount),
                     attributes_managed,
+                    OptionalValue::None,
                 );
-                result = result_managed.to_u64().unwrap();
+                let (rewards, _) = result_managed.into_tuple();
+                result = rewards.to_u64().unwrap();
             })
             .assert_ok();
 
@@ -381,7 +414,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (out_farm_token, out_reward_token) = sc
+                    let (out_farm_token, out_reward_token, _) = sc
                         .claim_rewards_endpoint(OptionalValue::Some(managed_address!(user)))
                         .into_tuple();
                     assert_eq!(
@@ -1,11 +1,17 @@
 #![allow(deprecated)]
 
 use common_structs::FarmTokenAttributes;
-use multiversx_sc_scenario::{managed_address, managed_biguint, rust_biguint, DebugApi};
+use farm::additional_rewards::AdditionalRewardsModule;
+use farm_with_locked_rewards::Farm;
+use multiversx_sc::codec::multi_types::OptionalValue;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, DebugApi,
+};
 use simple_lock::locked_token::LockedTokenAttributes;
 
 use crate::farm_with_locked_rewards_setup::{
-    FarmSetup, BOOSTED_YIELDS_PERCENTAGE, FARM_TOKEN_ID, LOCKED_REWARD_TOKEN_ID,
+    FarmSetup, ADDITIONAL_REWARD_TOKEN_ID, BOOSTED_YIELDS_PERCENTAGE, FARM_TOKEN_ID,
+    LOCKED_REWARD_TOKEN_ID,
 };
 
 mod farm_with_locked_rewards_setup;
@@ -335,3 +341,83 @@ fn total_farm_position_claim_with_locked_rewards_test() {
             None,
         );
 }
+
+#[test]
+fn farm_with_locked_rewards_additional_rewards_test() {
+    DebugApi::dummy();
+    let mut farm_setup = FarmSetup::new(
+        farm_with_locked_rewards::contract_obj,
+        energy_factory::contract_obj,
+    );
+
+    // 300 tokens per block, out of a 6_000 deposit
+    farm_setup.add_additional_reward_token(300, 6_000);
+
+    let first_user = farm_setup.first_user.clone();
+    let second_user = farm_setup.second_user.clone();
+    farm_setup.enter_farm(&first_user, 100_000_000);
+    farm_setup.enter_farm(&second_user, 50_000_000);
+
+    // 10 blocks - 3_000 additional tokens, split 2:1
+    farm_setup.b_mock.set_block_nonce(10);
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let attributes = FarmTokenAttributes {
+                reward_per_share: managed_biguint!(0),
+                entering_epoch: 0,
+                compounded_reward: managed_biguint!(0),
+                current_farm_amount: managed_biguint!(100_000_000),
+                original_owner: managed_address!(&first_user),
+            };
+            let (_, additional_rewards) = sc
+                .calculate_rewards_for_given_position(
+                    managed_address!(&first_user),
+                    managed_biguint!(100_000_000),
+                    attributes,
+                    OptionalValue::Some(1),
+                )
+                .into_tuple();
+            let additional_rewards = additional_rewards.to_vec();
+            assert_eq!(additional_rewards.len(), 1);
+            assert_eq!(additional_rewards.get(0).amount, managed_biguint!(2_000));
+        })
+        .assert_ok();
+
+    // the additional rewards are sent directly to the users
+    let _ = farm_setup.claim_rewards(&first_user, 1, 100_000_000);
+    farm_setup.exit_farm(&second_user, 2, 50_000_000);
+    farm_setup.b_mock.check_esdt_balance(
+        &first_user,
+        ADDITIONAL_REWARD_TOKEN_ID,
+        &rust_biguint!(2_000),
+    );
+    farm_setup.b_mock.check_esdt_balance(
+        &second_user,
+        ADDITIONAL_REWARD_TOKEN_ID,
+        &rust_biguint!(1_000),
+    );
+
+    // the new position starts from the current reward per share, and the deposit runs out
+    farm_setup.b_mock.set_block_nonce(30);
+    let _ = farm_setup.claim_rewards(&first_user, 3, 100_000_000);
+    farm_setup.b_mock.check_esdt_balance(
+        &first_user,
+        ADDITIONAL_REWARD_TOKEN_ID,
+        &rust_biguint!(5_000),
+    );
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let token_id = managed_token_id!(ADDITIONAL_REWARD_TOKEN_ID);
+            assert_eq!(
+                sc.undistributed_additional_rewards(&token_id).get(),
+                managed_biguint!(0)
+            );
+            assert_eq!(
+                sc.additional_reward_reserve(&token_id).get(),
+                managed_biguint!(0)
+            );
+        })
+        .assert_ok();
+}
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           67
+// Endpoints:                           80
 // Async Callback:                       1
-// Total number of exported functions:  69
+// Total number of exported functions:  82
 
 #![no_std]
 
@@ -31,9 +31,13 @@ multiversx_sc_wasm_adapter::endpoints! {
         startProduceRewards => start_produce_rewards_endpoint
         endProduceRewards => end_produce_rewards_endpoint
         setPerBlockRewardAmount => set_per_block_rewards_endpoint
+        setEmissionSchedule => set_emission_schedule_endpoint
+        setDecayingEmissionSchedule => set_decaying_emission_schedule_endpoint
         calculateRewardsForGivenPosition => calculate_rewards_for_given_position
         getRewardPerShare => reward_per_share
         getRewardReserve => reward_reserve
+        getEmissionSchedule => emission_schedule
+        hasActiveEmissionSchedule => has_active_emission_schedule
         allowExternalClaimBoostedRewards => allow_external_claim_boosted_rewards
         getFarmingTokenId => farming_token_id
         getRewardTokenId => reward_token_id
@@ -68,6 +72,15 @@ multiversx_sc_wasm_adapter::endpoints! {
         getMinimumFarmingEpoch => minimum_farming_epochs
         getBurnGasLimit => burn_gas_limit
         getPairContractManagedAddress => pair_contract_address
+        addAdditionalRewardToken => add_additional_reward_token
+        setAdditionalRewardPerBlock => set_additional_reward_per_block
+        depositAdditionalRewards => deposit_additional_rewards
+        getAdditionalRewardTokens => additional_reward_tokens
+        getAdditionalRewardPerBlock => additional_reward_per_block
+        getAdditionalRewardPerShare => additional_reward_per_share
+        getAdditionalRewardReserve => additional_reward_reserve
+        getUndistributedAdditionalRewards => undistributed_additional_rewards
+        getPositionAdditionalRewardPerShare => position_additional_reward_per_share
         setBoostedYieldsRewardsPercentage => set_boosted_yields_rewards_percentage
         collectUndistributedBoostedRewards => collect_undistributed_boosted_rewards
         getBoostedYieldsRewardsPercentage => boosted_yields_rewards_percentage
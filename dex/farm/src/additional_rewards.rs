@@ -0,0 +1,276 @@
+multiversx_sc::imports!();
+
+use common_structs::{Nonce, PaymentsVec};
+
+pub const MAX_ADDITIONAL_REWARD_TOKENS: usize = 5;
+
+pub type AdditionalRewardsResultType<M> = MultiValueEncoded<M, EsdtTokenPayment<M>>;
+
+/// Rewards in other tokens than the farm's reward token, e.g. from partner projects.
+/// They are not minted, but paid from the deposited amounts, at a per block rate.
+///
+/// The farm token attributes only hold the reward per share of the main reward token,
+/// so the reward per share of the additional tokens is kept in storage, for each farm token nonce.
+/// Positions without an entry start from 0, which is the reward per share of a newly added token.
+#[multiversx_sc::module]
+pub trait AdditionalRewardsModule:
+    config::ConfigModule
+    + farm_token::FarmTokenModule
+    + pausable::PausableModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[endpoint(addAdditionalRewardToken)]
+    fn add_additional_reward_token(&self, token_id: TokenIdentifier) {
+        self.require_caller_has_owner_or_admin_permissions();
+        require!(token_id.is_valid_esdt_identifier(), "Invalid token ID");
+        require!(
+            token_id != self.reward_token_id().get() && token_id != self.farming_token_id().get(),
+            "Invalid additional reward token"
+        );
+
+        let mut tokens = self.additional_reward_tokens();
+        require!(
+            tokens.len() < MAX_ADDITIONAL_REWARD_TOKENS,
+            "Too many additional reward tokens"
+        );
+        require!(
+            tokens.insert(token_id.clone()),
+            "Additional reward token already added"
+        );
+
+        let current_block_nonce = self.blockchain().get_block_nonce();
+        self.additional_reward_last_block_nonce(&token_id)
+            .set(current_block_nonce);
+    }
+
+    /// Setting the amount to 0 stops the additional rewards,
+    /// but the accumulated ones can still be claimed.
+    #[endpoint(setAdditionalRewardPerBlock)]
+    fn set_additional_reward_per_block(
+        &self,
+        token_id: TokenIdentifier,
+        per_block_amount: BigUint,
+    ) {
+        self.require_caller_has_owner_or_admin_permissions();
+        self.require_additional_reward_token(&token_id);
+
+        self.generate_additional_rewards();
+        self.additional_reward_per_block(&token_id)
+            .set(per_block_amount);
+    }
+
+    /// Anyone can fund the additional rewards.
+    /// The deposited tokens are distributed at the configured per block rate, until they run out.
+    #[payable("*")]
+    #[endpoint(depositAdditionalRewards)]
+    fn deposit_additional_rewards(&self) {
+        let (token_id, amount) = self.call_value().single_fungible_esdt();
+        self.require_additional_reward_token(&token_id);
+
+        self.generate_additional_rewards();
+        self.undistributed_additional_rewards(&token_id)
+            .update(|undistributed| *undistributed += amount);
+    }
+
+    fn generate_additional_rewards(&self) {
+        let farm_token_supply = self.farm_token_supply().get();
+        let division_safety_constant = self.division_safety_constant().get();
+        let current_block_nonce = self.blockchain().get_block_nonce();
+        for token_id in self.additional_reward_tokens().iter() {
+            let last_block_nonce_mapper = self.additional_reward_last_block_nonce(&token_id);
+            let last_block_nonce = last_block_nonce_mapper.get();
+            if current_block_nonce <= last_block_nonce {
+                continue;
+            }
+
+            last_block_nonce_mapper.set(current_block_nonce);
+
+            // without farm tokens, the rewards stay undistributed
+            if farm_token_supply == 0u64 {
+                continue;
+            }
+
+            let per_block_amount = self.additional_reward_per_block(&token_id).get();
+            let undistributed_mapper = self.undistributed_additional_rewards(&token_id);
+            let undistributed = undistributed_mapper.get();
+            let block_nonce_diff = current_block_nonce - last_block_nonce;
+            let rewards = core::cmp::min(per_block_amount * block_nonce_diff, undistributed);
+            if rewards == 0u64 {
+                continue;
+            }
+
+            undistributed_mapper.update(|undistributed| *undistributed -= &rewards);
+            self.additional_reward_reserve(&token_id)
+                .update(|reserve| *reserve += &rewards);
+
+            let increase = rewards * &division_safety_constant / &farm_token_supply;
+            self.additional_reward_per_share(&token_id)
+                .update(|reward_per_share| *reward_per_share += increase);
+        }
+    }
+
+    fn calculate_additional_rewards(
+        &self,
+        farm_token_nonce: Nonce,
+        farm_token_amount: &BigUint,
+    ) -> PaymentsVec<Self::Api> {
+        let division_safety_constant = self.division_safety_constant().get();
+        let mut rewards = ManagedVec::new();
+        for token_id in self.additional_reward_tokens().iter() {
+            let reward_per_share = self.additional_reward_per_share(&token_id).get();
+            let position_reward_per_share = self
+                .position_additional_reward_per_share(farm_token_nonce, &token_id)
+                .get();
+            if reward_per_share <= position_reward_per_share {
+                continue;
+            }
+
+            let amount = farm_token_amount * &(reward_per_share - position_reward_per_share)
+                / &division_safety_constant;
+            if amount > 0 {
+                rewards.push(EsdtTokenPayment::new(token_id, 0, amount));
+            }
+        }
+
+        rewards
+    }
+
+    fn claim_additional_rewards(&self, farm_token: &EsdtTokenPayment) -> PaymentsVec<Self::Api> {
+        let rewards = self.calculate_additional_rewards(farm_token.token_nonce, &farm_token.amount);
+        for reward in &rewards {
+            self.additional_reward_reserve(&reward.token_identifier)
+                .update(|reserve| *reserve -= &reward.amount);
+        }
+
+        self.consume_position(farm_token);
+
+        rewards
+    }
+
+    /// The additional rewards always go to the original caller, not to the contract in between,
+    /// since the contracts that call the farms on behalf of users only forward the main rewards.
+    fn claim_and_send_additional_rewards(
+        &self,
+        orig_caller: &ManagedAddress,
+        farm_token: &EsdtTokenPayment,
+    ) -> PaymentsVec<Self::Api> {
+        let rewards = self.claim_additional_rewards(farm_token);
+        if !rewards.is_empty() {
+            self.send().direct_multi(orig_caller, &rewards);
+        }
+
+        rewards
+    }
+
+    /// The new position gets the weighted average of the reward per share of the merged positions,
+    /// while the rest of it starts from the current reward per share.
+    fn set_position_additional_reward_per_share(
+        &self,
+        new_farm_token: &EsdtTokenPayment,
+        merged_positions: &PaymentsVec<Self::Api>,
+    ) {
+        let mut current_amount = new_farm_token.amount.clone();
+        for position in merged_positions {
+            current_amount -= &position.amount;
+        }
+
+        for token_id in self.additional_reward_tokens().iter() {
+            let mut weighted_sum =
+                &current_amount * &self.additional_reward_per_share(&token_id).get();
+            let mut total_amount = current_amount.clone();
+            for position in merged_positions {
+                let position_reward_per_share = self
+                    .position_additional_reward_per_share(position.token_nonce, &token_id)
+                    .get();
+                weighted_sum += &position.amount * &position_reward_per_share;
+                total_amount += &position.amount;
+            }
+
+            if total_amount == 0u64 {
+                continue;
+            }
+
+            // round up, so the merged position can't claim more than its parts
+            let position_reward_per_share =
+                (weighted_sum + &total_amount - BigUint::from(1u32)) / &total_amount;
+            self.position_additional_reward_per_share(new_farm_token.token_nonce, &token_id)
+                .set(position_reward_per_share);
+            self.position_farm_token_amount(new_farm_token.token_nonce)
+                .set(&new_farm_token.amount);
+        }
+
+        for position in merged_positions {
+            self.consume_position(&position);
+        }
+    }
+
+    /// Clears the entries of a farm token nonce once all its tokens were burned.
+    fn consume_position(&self, farm_token: &EsdtTokenPayment) {
+        let amount_mapper = self.position_farm_token_amount(farm_token.token_nonce);
+        if amount_mapper.is_empty() {
+            return;
+        }
+
+        let remaining_amount = amount_mapper.get() - &farm_token.amount;
+        if remaining_amount > 0 {
+            amount_mapper.set(remaining_amount);
+            return;
+        }
+
+        amount_mapper.clear();
+        for token_id in self.additional_reward_tokens().iter() {
+            self.position_additional_reward_per_share(farm_token.token_nonce, &token_id)
+                .clear();
+        }
+    }
+
+    fn require_additional_reward_token(&self, token_id: &TokenIdentifier) {
+        require!(
+            self.additional_reward_tokens().contains(token_id),
+            "Unknown additional reward token"
+        );
+    }
+
+    #[view(getAdditionalRewardTokens)]
+    #[storage_mapper("additionalRewardTokens")]
+    fn additional_reward_tokens(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[view(getAdditionalRewardPerBlock)]
+    #[storage_mapper("additionalRewardPerBlock")]
+    fn additional_reward_per_block(&self, token_id: &TokenIdentifier)
+        -> SingleValueMapper<BigUint>;
+
+    #[view(getAdditionalRewardPerShare)]
+    #[storage_mapper("additionalRewardPerShare")]
+    fn additional_reward_per_share(&self, token_id: &TokenIdentifier)
+        -> SingleValueMapper<BigUint>;
+
+    #[view(getAdditionalRewardReserve)]
+    #[storage_mapper("additionalRewardReserve")]
+    fn additional_reward_reserve(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getUndistributedAdditionalRewards)]
+    #[storage_mapper("undistributedAdditionalRewards")]
+    fn undistributed_additional_rewards(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("additionalRewardLastBlockNonce")]
+    fn additional_reward_last_block_nonce(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<Nonce>;
+
+    #[view(getPositionAdditionalRewardPerShare)]
+    #[storage_mapper("positionAdditionalRewardPerShare")]
+    fn position_additional_reward_per_share(
+        &self,
+        farm_token_nonce: Nonce,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("positionFarmTokenAmount")]
+    fn position_farm_token_amount(&self, farm_token_nonce: Nonce) -> SingleValueMapper<BigUint>;
+}
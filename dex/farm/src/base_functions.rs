@@ -12,6 +12,7 @@ use contexts::storage_cache::StorageCache;
 
 use farm_base_impl::base_traits_impl::{DefaultFarmWrapper, FarmContract};
 use fixed_supply_token::FixedSupplyToken;
+use rewards::EmissionScheduleEntries;
 
 use crate::exit_penalty;
 
@@ -251,10 +252,21 @@ pub trait BaseFunctionsModule:
 
         let mut storage = StorageCache::new(self);
         FC::generate_aggregated_rewards(self, &mut storage);
+        self.require_no_emission_schedule();
 
         self.per_block_reward_amount().set(&per_block_amount);
     }
 
+    fn set_emission_schedule<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        entries: EmissionScheduleEntries<Self::Api>,
+    ) {
+        let mut storage = StorageCache::new(self);
+        FC::generate_aggregated_rewards(self, &mut storage);
+
+        self.replace_emission_schedule(entries);
+    }
+
     fn require_queried(&self) {
         let caller = self.blockchain().get_caller();
         let sc_address = self.blockchain().get_sc_address();
@@ -5,11 +5,13 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+pub mod additional_rewards;
 pub mod base_functions;
 pub mod exit_penalty;
 
-use base_functions::{ClaimRewardsResultType, DoubleMultiPayment, Wrapper};
-use common_structs::FarmTokenAttributes;
+use additional_rewards::AdditionalRewardsResultType;
+use base_functions::{DoubleMultiPayment, Wrapper};
+use common_structs::{FarmTokenAttributes, Nonce};
 use contexts::storage_cache::StorageCache;
 
 use exit_penalty::{
@@ -19,6 +21,11 @@ use farm_base_impl::base_traits_impl::FarmContract;
 
 pub type EnterFarmResultType<M> = DoubleMultiPayment<M>;
 pub type ExitFarmWithPartialPosResultType<M> = DoubleMultiPayment<M>;
+pub type ClaimRewardsMultiResultType<M> =
+    MultiValue3<EsdtTokenPayment<M>, EsdtTokenPayment<M>, AdditionalRewardsResultType<M>>;
+pub type ExitFarmMultiResultType<M> =
+    MultiValue3<EsdtTokenPayment<M>, EsdtTokenPayment<M>, AdditionalRewardsResultType<M>>;
+pub type CalculateRewardsResultType<M> = MultiValue2<BigUint<M>, AdditionalRewardsResultType<M>>;
 
 #[multiversx_sc::contract]
 pub trait Farm:
@@ -33,6 +40,7 @@ pub trait Farm:
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + base_functions::BaseFunctionsModule
     + exit_penalty::ExitPenaltyModule
+    + additional_rewards::AdditionalRewardsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -103,7 +111,12 @@ pub trait Farm:
         let boosted_rewards_payment =
             EsdtTokenPayment::new(self.reward_token_id().get(), 0, boosted_rewards);
 
+        let mut additional_farm_tokens = self.get_non_empty_payments();
+        additional_farm_tokens.remove(0);
+
+        self.generate_additional_rewards();
         let new_farm_token = self.enter_farm::<Wrapper<Self>>(orig_caller.clone());
+        self.set_position_additional_reward_per_share(&new_farm_token, &additional_farm_tokens);
         self.send_payment_non_zero(&caller, &new_farm_token);
         self.send_payment_non_zero(&caller, &boosted_rewards_payment);
 
@@ -117,18 +130,33 @@ pub trait Farm:
     fn claim_rewards_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ClaimRewardsResultType<Self::Api> {
+    ) -> ClaimRewardsMultiResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
         self.migrate_old_farm_positions(&orig_caller);
 
+        let mut additional_payments = self.get_non_empty_payments();
+        let first_farm_token = self.pop_first_payment(&mut additional_payments);
+
+        self.generate_additional_rewards();
+        let additional_rewards =
+            self.claim_and_send_additional_rewards(&orig_caller, &first_farm_token);
         let claim_rewards_result = self.claim_rewards::<Wrapper<Self>>(orig_caller);
+        self.set_position_additional_reward_per_share(
+            &claim_rewards_result.new_farm_token,
+            &additional_payments,
+        );
 
         self.send_payment_non_zero(&caller, &claim_rewards_result.new_farm_token);
         self.send_payment_non_zero(&caller, &claim_rewards_result.rewards);
 
-        claim_rewards_result.into()
+        (
+            claim_rewards_result.new_farm_token,
+            claim_rewards_result.rewards,
+            additional_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -142,7 +170,17 @@ pub trait Farm:
 
         self.migrate_old_farm_positions(&orig_caller);
 
+        let mut additional_payments = self.get_non_empty_payments();
+        let first_farm_token = self.pop_first_payment(&mut additional_payments);
+
+        // only the main rewards can be compounded, the additional ones are sent to the user
+        self.generate_additional_rewards();
+        let _ = self.claim_and_send_additional_rewards(&orig_caller, &first_farm_token);
         let output_farm_token_payment = self.compound_rewards::<Wrapper<Self>>(orig_caller.clone());
+        self.set_position_additional_reward_per_share(
+            &output_farm_token_payment,
+            &additional_payments,
+        );
 
         self.send_payment_non_zero(&caller, &output_farm_token_payment);
 
@@ -156,7 +194,7 @@ pub trait Farm:
     fn exit_farm_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+    ) -> ExitFarmMultiResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
@@ -164,6 +202,8 @@ pub trait Farm:
 
         let migrated_amount = self.migrate_old_farm_positions(&orig_caller);
 
+        self.generate_additional_rewards();
+        let additional_rewards = self.claim_and_send_additional_rewards(&orig_caller, &payment);
         let exit_farm_result = self.exit_farm::<Wrapper<Self>>(orig_caller.clone(), payment);
 
         self.decrease_old_farm_positions(migrated_amount, &orig_caller);
@@ -173,7 +213,12 @@ pub trait Farm:
 
         self.clear_user_energy_if_needed(&orig_caller);
 
-        (exit_farm_result.farming_tokens, exit_farm_result.rewards).into()
+        (
+            exit_farm_result.farming_tokens,
+            exit_farm_result.rewards,
+            additional_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -190,7 +235,9 @@ pub trait Farm:
         let boosted_rewards_payment =
             EsdtTokenPayment::new(self.reward_token_id().get(), 0, boosted_rewards);
 
+        let payments = self.get_non_empty_payments();
         let merged_farm_token = self.merge_farm_tokens::<Wrapper<Self>>();
+        self.set_position_additional_reward_per_share(&merged_farm_token, &payments);
         self.send_payment_non_zero(&caller, &merged_farm_token);
         self.send_payment_non_zero(&caller, &boosted_rewards_payment);
 
@@ -242,24 +289,71 @@ pub trait Farm:
         self.set_per_block_rewards::<Wrapper<Self>>(per_block_amount);
     }
 
+    /// Each entry is a `(start_block, per_block_amount)` pair, and a zero amount ends the emission.
+    /// Replaces the upcoming schedule.
+    #[endpoint(setEmissionSchedule)]
+    fn set_emission_schedule_endpoint(
+        &self,
+        entries: MultiValueEncoded<MultiValue2<Nonce, BigUint>>,
+    ) {
+        self.require_caller_has_admin_permissions();
+        let schedule = self.build_emission_schedule(entries);
+        self.set_emission_schedule::<Wrapper<Self>>(schedule);
+    }
+
+    /// Decreases the per block amount by `decay_percentage` every period, e.g. 5_000 for halvings.
+    /// The emission ends after `nr_periods` periods.
+    #[endpoint(setDecayingEmissionSchedule)]
+    fn set_decaying_emission_schedule_endpoint(
+        &self,
+        start_block: Nonce,
+        initial_per_block_amount: BigUint,
+        period_in_blocks: Nonce,
+        decay_percentage: u64,
+        nr_periods: usize,
+    ) {
+        self.require_caller_has_admin_permissions();
+        let schedule = self.build_decaying_emission_schedule(
+            start_block,
+            initial_per_block_amount,
+            period_in_blocks,
+            decay_percentage,
+            nr_periods,
+        );
+        self.set_emission_schedule::<Wrapper<Self>>(schedule);
+    }
+
+    /// The additional rewards are kept by farm token nonce, not in the attributes,
+    /// so they are only returned if the nonce is given.
     #[view(calculateRewardsForGivenPosition)]
     fn calculate_rewards_for_given_position(
         &self,
         user: ManagedAddress,
         farm_token_amount: BigUint,
         attributes: FarmTokenAttributes<Self::Api>,
-    ) -> BigUint {
+        opt_farm_token_nonce: OptionalValue<Nonce>,
+    ) -> CalculateRewardsResultType<Self::Api> {
         self.require_queried();
 
         let mut storage_cache = StorageCache::new(self);
         Wrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
 
-        Wrapper::<Self>::calculate_rewards(
+        let rewards = Wrapper::<Self>::calculate_rewards(
             self,
             &user,
             &farm_token_amount,
             &attributes,
             &storage_cache,
-        )
+        );
+
+        self.generate_additional_rewards();
+        let additional_rewards = match opt_farm_token_nonce {
+            OptionalValue::Some(farm_token_nonce) => {
+                self.calculate_additional_rewards(farm_token_nonce, &farm_token_amount)
+            }
+            OptionalValue::None => ManagedVec::new(),
+        };
+
+        (rewards, additional_rewards.into()).into()
     }
 }
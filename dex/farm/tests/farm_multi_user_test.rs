@@ -972,3 +972,95 @@ fn farm_claim_with_minimum_tokens() {
     farm_setup.check_remaining_boosted_rewards_to_distribute(2, 0);
     farm_setup.check_remaining_boosted_rewards_to_distribute(3, 0);
 }
+
+#[test]
+fn farm_additional_rewards_test() {
+    DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory_mock::contract_obj,
+        energy_update::contract_obj,
+    );
+
+    // 100 additional tokens per block, for 50 blocks
+    farm_setup.add_additional_reward_token(100, 5_000);
+
+    let first_farm_token_amount = 100_000_000;
+    let first_user = farm_setup.first_user.clone();
+    farm_setup.enter_farm(&first_user, first_farm_token_amount);
+
+    let second_farm_token_amount = 50_000_000;
+    let second_user = farm_setup.second_user.clone();
+    farm_setup.enter_farm(&second_user, second_farm_token_amount);
+
+    // advance 10 blocks - 1_000 additional rewards, split 2:1
+    farm_setup.b_mock.set_block_nonce(10);
+    let _ = farm_setup.claim_rewards(&first_user, 1, first_farm_token_amount);
+    farm_setup.b_mock.check_esdt_balance(
+        &first_user,
+        ADDITIONAL_REWARD_TOKEN_ID,
+        &rust_biguint!(666),
+    );
+
+    // advance 10 more blocks - the second user claims both periods when exiting
+    farm_setup.b_mock.set_block_nonce(20);
+    farm_setup.exit_farm(&second_user, 2, second_farm_token_amount);
+    farm_setup.b_mock.check_esdt_balance(
+        &second_user,
+        ADDITIONAL_REWARD_TOKEN_ID,
+        &rust_biguint!(666),
+    );
+    farm_setup.check_undistributed_additional_rewards(3_000);
+
+    // the entries of the burned positions are cleared
+    farm_setup.check_position_additional_rewards_cleared(1);
+    farm_setup.check_position_additional_rewards_cleared(2);
+
+    // advance 40 blocks - only the remaining 3_000 tokens are distributed
+    farm_setup.b_mock.set_block_nonce(60);
+    let _ = farm_setup.claim_rewards(&first_user, 3, first_farm_token_amount);
+    farm_setup.b_mock.check_esdt_balance(
+        &first_user,
+        ADDITIONAL_REWARD_TOKEN_ID,
+        &rust_biguint!(666 + 3_666),
+    );
+    farm_setup.check_undistributed_additional_rewards(0);
+}
+
+#[test]
+fn farm_emission_schedule_test() {
+    DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory_mock::contract_obj,
+        energy_update::contract_obj,
+    );
+
+    let farm_token_amount = 100_000_000;
+    let first_user = farm_setup.first_user.clone();
+    farm_setup.enter_farm(&first_user, farm_token_amount);
+
+    // 2_000 per block starting with block 10, halved at block 20, ends at block 30
+    farm_setup.set_decaying_emission_schedule(10, 2_000, 10, 5_000, 2);
+    farm_setup.check_emission_schedule(PER_BLOCK_REWARD_AMOUNT, 3);
+
+    // the schedule sets the per block amount, until it ends
+    farm_setup
+        .set_per_block_rewards(500)
+        .assert_user_error("Emission schedule is active");
+
+    // 10 blocks * 1_000 + 10 blocks * 2_000 + 5 blocks * 1_000
+    farm_setup.b_mock.set_block_nonce(25);
+    let first_rewards = farm_setup.claim_rewards(&first_user, 1, farm_token_amount);
+    assert_eq!(first_rewards, 35_000);
+    farm_setup.check_emission_schedule(1_000, 1);
+
+    // 5 blocks * 1_000, then nothing
+    farm_setup.b_mock.set_block_nonce(40);
+    let second_rewards = farm_setup.claim_rewards(&first_user, 2, farm_token_amount);
+    assert_eq!(second_rewards, 5_000);
+    farm_setup.check_emission_schedule(0, 0);
+
+    farm_setup.set_per_block_rewards(500).assert_ok();
+    farm_setup.check_emission_schedule(500, 0);
+}
@@ -11,18 +11,20 @@ use multiversx_sc::{
 use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, rust_biguint,
-    whitebox_legacy::{BlockchainStateWrapper, ContractObjWrapper},
+    whitebox_legacy::{BlockchainStateWrapper, ContractObjWrapper, TxResult},
     DebugApi,
 };
 
 use energy_factory_mock::EnergyFactoryMock;
 use energy_query::{Energy, EnergyQueryModule};
 use energy_update::EnergyUpdate;
+use farm::additional_rewards::AdditionalRewardsModule;
 use farm::Farm;
 use farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule;
 use farm_boosted_yields::FarmBoostedYieldsModule;
 use farm_token::FarmTokenModule;
 use pausable::{PausableModule, State};
+use rewards::RewardsModule;
 use sc_whitelist_module::SCWhitelistModule;
 use week_timekeeping::Epoch;
 use weekly_rewards_splitting::update_claim_progress_energy::UpdateClaimProgressEnergyModule;
@@ -30,6 +32,7 @@ use weekly_rewards_splitting::update_claim_progress_energy::UpdateClaimProgressE
 pub static REWARD_TOKEN_ID: &[u8] = b"REW-123456";
 pub static FARMING_TOKEN_ID: &[u8] = b"LPTOK-123456";
 pub static FARM_TOKEN_ID: &[u8] = b"FARM-123456";
+pub static ADDITIONAL_REWARD_TOKEN_ID: &[u8] = b"PARTNER-123456";
 pub const DIV_SAFETY: u64 = 1_000_000_000_000;
 pub const PER_BLOCK_REWARD_AMOUNT: u64 = 1_000;
 pub const FARMING_TOKEN_BALANCE: u64 = 200_000_000;
@@ -231,6 +234,100 @@ where
             .assert_ok();
     }
 
+    pub fn add_additional_reward_token(&mut self, per_block_amount: u64, deposit_amount: u64) {
+        self.b_mock.set_esdt_balance(
+            &self.owner,
+            ADDITIONAL_REWARD_TOKEN_ID,
+            &rust_biguint!(deposit_amount),
+        );
+
+        self.b_mock
+            .execute_tx(&self.owner, &self.farm_wrapper, &rust_biguint!(0), |sc| {
+                let token_id = managed_token_id!(ADDITIONAL_REWARD_TOKEN_ID);
+                sc.add_additional_reward_token(token_id.clone());
+                sc.set_additional_reward_per_block(token_id, managed_biguint!(per_block_amount));
+            })
+            .assert_ok();
+
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.owner,
+                &self.farm_wrapper,
+                ADDITIONAL_REWARD_TOKEN_ID,
+                0,
+                &rust_biguint!(deposit_amount),
+                |sc| {
+                    sc.deposit_additional_rewards();
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn check_undistributed_additional_rewards(&mut self, expected_amount: u64) {
+        self.b_mock
+            .execute_query(&self.farm_wrapper, |sc| {
+                let result_managed = sc
+                    .undistributed_additional_rewards(&managed_token_id!(
+                        ADDITIONAL_REWARD_TOKEN_ID
+                    ))
+                    .get();
+                assert_eq!(result_managed, managed_biguint!(expected_amount));
+            })
+            .assert_ok();
+    }
+
+    pub fn check_position_additional_rewards_cleared(&mut self, farm_token_nonce: u64) {
+        self.b_mock
+            .execute_query(&self.farm_wrapper, |sc| {
+                let token_id = managed_token_id!(ADDITIONAL_REWARD_TOKEN_ID);
+                assert!(sc
+                    .position_additional_reward_per_share(farm_token_nonce, &token_id)
+                    .is_empty());
+                assert!(sc.position_farm_token_amount(farm_token_nonce).is_empty());
+            })
+            .assert_ok();
+    }
+
+    pub fn set_decaying_emission_schedule(
+        &mut self,
+        start_block: u64,
+        initial_per_block_amount: u64,
+        period_in_blocks: u64,
+        decay_percentage: u64,
+        nr_periods: usize,
+    ) {
+        self.b_mock
+            .execute_tx(&self.owner, &self.farm_wrapper, &rust_biguint!(0), |sc| {
+                sc.set_decaying_emission_schedule_endpoint(
+                    start_block,
+                    managed_biguint!(initial_per_block_amount),
+                    period_in_blocks,
+                    decay_percentage,
+                    nr_periods,
+                );
+            })
+            .assert_ok();
+    }
+
+    pub fn set_per_block_rewards(&mut self, per_block_amount: u64) -> TxResult {
+        self.b_mock
+            .execute_tx(&self.owner, &self.farm_wrapper, &rust_biguint!(0), |sc| {
+                sc.set_per_block_rewards_endpoint(managed_biguint!(per_block_amount));
+            })
+    }
+
+    pub fn check_emission_schedule(&mut self, expected_per_block_amount: u64, expected_len: usize) {
+        self.b_mock
+            .execute_query(&self.farm_wrapper, |sc| {
+                assert_eq!(
+                    sc.per_block_reward_amount().get(),
+                    managed_biguint!(expected_per_block_amount)
+                );
+                assert_eq!(sc.emission_schedule().len(), expected_len);
+            })
+            .assert_ok();
+    }
+
     pub fn add_known_proxy(&mut self, known_proxy: &Address) {
         self.b_mock
             .execute_tx(&self.owner, &self.farm_wrapper, &rust_biguint!(0), |sc| {
@@ -402,8 +499,10 @@ where
                     managed_address!(user),
                     managed_biguint!(farm_token_amount),
                     attributes_managed,
+                    OptionalValue::None,
                 );
-                result = result_managed.to_u64().unwrap();
+                let (rewards, _) = result_managed.into_tuple();
+                result = rewards.to_u64().unwrap();
             })
             .assert_ok();
 
@@ -428,7 +527,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (out_farm_token, out_reward_token) =
+                    let (out_farm_token, out_reward_token, _) =
                         sc.claim_rewards_endpoint(OptionalValue::None).into_tuple();
                     assert_eq!(
                         out_farm_token.token_identifier,
@@ -503,7 +602,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (out_farm_token, out_reward_token) = sc
+                    let (out_farm_token, out_reward_token, _) = sc
                         .claim_rewards_endpoint(OptionalValue::Some(managed_address!(user)))
                         .into_tuple();
                     assert_eq!(
@@ -224,7 +224,7 @@ where
                 |sc| {
                     let multi_result = sc.exit_farm_endpoint(OptionalValue::None);
 
-                    let (first_result, second_result) = multi_result.into_tuple();
+                    let (first_result, second_result, _) = multi_result.into_tuple();
 
                     assert_eq!(
                         first_result.token_identifier,
@@ -276,7 +276,7 @@ where
                 |sc| {
                     let multi_result = sc.claim_rewards_endpoint(OptionalValue::None);
 
-                    let (first_result, second_result) = multi_result.into_tuple();
+                    let (first_result, second_result, _) = multi_result.into_tuple();
 
                     assert_eq!(
                         first_result.token_identifier,
@@ -0,0 +1,69 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+/// Receives a pair `flashSwap` and pays it back straight away, borrowed amount plus fee,
+/// less `shortfall`. The contract needs its own balance to cover the fee.
+#[multiversx_sc::contract]
+pub trait FlashSwapReceiverMock {
+    #[init]
+    fn init(&self) {}
+
+    #[payable("*")]
+    #[endpoint(receiveFlashSwap)]
+    fn receive_flash_swap(&self, fee_amount: BigUint, shortfall: BigUint) {
+        let payment = self.call_value().single_esdt();
+        let pair_address = self.blockchain().get_caller();
+        let repay_amount = payment.amount + fee_amount - shortfall;
+        self.send()
+            .direct_esdt(&pair_address, &payment.token_identifier, 0, &repay_amount);
+    }
+
+    /// Swaps `swap_amount` of the borrowed tokens on the pair before paying back.
+    #[payable("*")]
+    #[endpoint(receiveFlashSwapAndSwap)]
+    fn receive_flash_swap_and_swap(
+        &self,
+        fee_amount: BigUint,
+        shortfall: BigUint,
+        token_out: TokenIdentifier,
+        swap_amount: BigUint,
+    ) {
+        let payment = self.call_value().single_esdt();
+        let pair_address = self.blockchain().get_caller();
+        let mut contract_call = self
+            .send()
+            .contract_call::<()>(
+                pair_address.clone(),
+                ManagedBuffer::from(b"swapTokensFixedInput"),
+            )
+            .with_esdt_transfer(EsdtTokenPayment::new(
+                payment.token_identifier.clone(),
+                0,
+                swap_amount,
+            ));
+        contract_call.push_raw_argument(token_out.into_managed_buffer());
+        contract_call.push_raw_argument(BigUint::from(1u64).to_bytes_be_buffer());
+        let _: IgnoreValue = contract_call.execute_on_dest_context();
+
+        let repay_amount = payment.amount + fee_amount - shortfall;
+        self.send()
+            .direct_esdt(&pair_address, &payment.token_identifier, 0, &repay_amount);
+    }
+
+    /// Calls `processFees` on the pair before paying back.
+    #[payable("*")]
+    #[endpoint(receiveFlashSwapAndProcessFees)]
+    fn receive_flash_swap_and_process_fees(&self, fee_amount: BigUint, shortfall: BigUint) {
+        let payment = self.call_value().single_esdt();
+        let pair_address = self.blockchain().get_caller();
+        let _: IgnoreValue = self
+            .send()
+            .contract_call::<()>(pair_address.clone(), ManagedBuffer::from(b"processFees"))
+            .execute_on_dest_context();
+
+        let repay_amount = payment.amount + fee_amount - shortfall;
+        self.send()
+            .direct_esdt(&pair_address, &payment.token_identifier, 0, &repay_amount);
+    }
+}
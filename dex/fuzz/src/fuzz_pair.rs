@@ -288,6 +288,7 @@ pub mod fuzz_pair_test {
                     sc.swap_tokens_fixed_input(
                         managed_token_id!(desired_token_id),
                         managed_biguint!(desired_amount_min),
+                        OptionalValue::None,
                     );
                 },
             );
@@ -336,6 +337,7 @@ pub mod fuzz_pair_test {
                     sc.swap_tokens_fixed_output(
                         managed_token_id!(desired_token_id),
                         managed_biguint!(desired_amount_min),
+                        OptionalValue::None,
                     );
                 },
             );
@@ -0,0 +1,33 @@
+multiversx_sc::imports!();
+
+use crate::errors::ERROR_BAD_KEEPER_BOUNTY_PERCENT;
+
+pub const MAX_PERCENTAGE: u64 = 100_000;
+pub const MAX_KEEPER_BOUNTY_PERCENT: u64 = 5_000;
+
+#[multiversx_sc::module]
+pub trait ConfigModule {
+    #[only_owner]
+    #[endpoint(setKeeperBountyPercent)]
+    fn set_keeper_bounty_percent(&self, keeper_bounty_percent: u64) {
+        self.try_set_keeper_bounty_percent(keeper_bounty_percent);
+    }
+
+    fn try_set_keeper_bounty_percent(&self, keeper_bounty_percent: u64) {
+        require!(
+            keeper_bounty_percent <= MAX_KEEPER_BOUNTY_PERCENT,
+            ERROR_BAD_KEEPER_BOUNTY_PERCENT
+        );
+
+        self.keeper_bounty_percent().set(keeper_bounty_percent);
+    }
+
+    #[view(getRouterAddress)]
+    #[storage_mapper("routerAddress")]
+    fn router_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    /// Part of each fill output paid to the keeper, in `MAX_PERCENTAGE` units.
+    #[view(getKeeperBountyPercent)]
+    #[storage_mapper("keeperBountyPercent")]
+    fn keeper_bounty_percent(&self) -> SingleValueMapper<u64>;
+}
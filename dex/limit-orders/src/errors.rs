@@ -0,0 +1,10 @@
+pub static ERROR_BAD_PAYMENT: &[u8] = b"Bad payment";
+pub static ERROR_INVALID_ARGS: &[u8] = b"Invalid args";
+pub static ERROR_NO_PAIR: &[u8] = b"No pair for the given tokens";
+pub static ERROR_BAD_KEEPER_BOUNTY_PERCENT: &[u8] = b"Bad keeper bounty percent";
+
+pub static ERROR_ORDER_NOT_FOUND: &[u8] = b"Order not found";
+pub static ERROR_ORDER_EXPIRED: &[u8] = b"Order expired";
+pub static ERROR_ORDER_NOT_EXPIRED: &[u8] = b"Only the owner can cancel an order before it expires";
+pub static ERROR_INVALID_FILL_AMOUNT: &[u8] = b"Invalid fill amount";
+pub static ERROR_TARGET_PRICE_NOT_REACHED: &[u8] = b"Target price not reached";
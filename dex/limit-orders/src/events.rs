@@ -0,0 +1,92 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::order::Order;
+
+#[derive(TypeAbi, TopEncode)]
+pub struct FillOrderEvent<M: ManagedTypeApi> {
+    owner: ManagedAddress<M>,
+    token_in: TokenIdentifier<M>,
+    amount_in: BigUint<M>,
+    token_out: TokenIdentifier<M>,
+    owner_amount_out: BigUint<M>,
+    bounty_amount: BigUint<M>,
+    remaining_amount_in: BigUint<M>,
+    block: u64,
+    timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_create_order_event(&self, order_id: u64, order: &Order<Self::Api>) {
+        self.create_order_event(
+            &order.owner,
+            order_id,
+            self.blockchain().get_block_timestamp(),
+            order,
+        );
+    }
+
+    fn emit_fill_order_event(
+        &self,
+        order_id: u64,
+        order: &Order<Self::Api>,
+        amount_in: BigUint,
+        owner_amount_out: BigUint,
+        bounty_amount: BigUint,
+    ) {
+        let timestamp = self.blockchain().get_block_timestamp();
+        self.fill_order_event(
+            &self.blockchain().get_caller(),
+            order_id,
+            timestamp,
+            &FillOrderEvent {
+                owner: order.owner.clone(),
+                token_in: order.token_in.clone(),
+                amount_in,
+                token_out: order.token_out.clone(),
+                owner_amount_out,
+                bounty_amount,
+                remaining_amount_in: order.remaining_amount_in.clone(),
+                block: self.blockchain().get_block_nonce(),
+                timestamp,
+            },
+        );
+    }
+
+    fn emit_cancel_order_event(&self, order_id: u64, order: &Order<Self::Api>) {
+        self.cancel_order_event(
+            &self.blockchain().get_caller(),
+            order_id,
+            self.blockchain().get_block_timestamp(),
+            order,
+        );
+    }
+
+    #[event("create_order")]
+    fn create_order_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] order_id: u64,
+        #[indexed] timestamp: u64,
+        order: &Order<Self::Api>,
+    );
+
+    #[event("fill_order")]
+    fn fill_order_event(
+        &self,
+        #[indexed] keeper: &ManagedAddress,
+        #[indexed] order_id: u64,
+        #[indexed] timestamp: u64,
+        fill_order_event: &FillOrderEvent<Self::Api>,
+    );
+
+    #[event("cancel_order")]
+    fn cancel_order_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] order_id: u64,
+        #[indexed] timestamp: u64,
+        order: &Order<Self::Api>,
+    );
+}
@@ -0,0 +1,93 @@
+multiversx_sc::imports!();
+
+use crate::{
+    config::{self, MAX_PERCENTAGE},
+    errors::*,
+    events, order,
+};
+
+use pair::ProxyTrait as _;
+
+#[multiversx_sc::module]
+pub trait FillModule: config::ConfigModule + order::OrderModule + events::EventsModule {
+    /// Swaps `amount_in` out of the order, through its pair, once the pair gives at least
+    /// the target price plus the keeper bounty. Anyone can call it, and the bounty,
+    /// taken out of the swap output, goes to the caller.
+    #[endpoint(fillOrder)]
+    fn fill_order(&self, order_id: u64, amount_in: BigUint) -> EsdtTokenPayment {
+        let mut order = self.get_order(order_id);
+        require!(
+            self.blockchain().get_block_timestamp() <= order.expiry_timestamp,
+            ERROR_ORDER_EXPIRED
+        );
+        require!(
+            amount_in > 0u64 && amount_in <= order.remaining_amount_in,
+            ERROR_INVALID_FILL_AMOUNT
+        );
+
+        let keeper_bounty_percent = self.keeper_bounty_percent().get();
+        let min_owner_amount_out = order.get_min_amount_out(&amount_in);
+        let min_amount_out =
+            self.get_amount_out_before_bounty(&min_owner_amount_out, keeper_bounty_percent);
+
+        let quoted_amount_out: BigUint = self
+            .pair_proxy(order.pair_address.clone())
+            .get_amount_out_view(order.token_in.clone(), amount_in.clone())
+            .execute_on_dest_context();
+        require!(
+            quoted_amount_out >= min_amount_out,
+            ERROR_TARGET_PRICE_NOT_REACHED
+        );
+
+        let swap_output: EsdtTokenPayment = self
+            .pair_proxy(order.pair_address.clone())
+            .swap_tokens_fixed_input(
+                order.token_out.clone(),
+                min_amount_out,
+                OptionalValue::<u64>::None,
+            )
+            .with_esdt_transfer(EsdtTokenPayment::new(
+                order.token_in.clone(),
+                0,
+                amount_in.clone(),
+            ))
+            .execute_on_dest_context();
+
+        let bounty_amount = &swap_output.amount * keeper_bounty_percent / MAX_PERCENTAGE;
+        let owner_amount_out = &swap_output.amount - &bounty_amount;
+
+        order.remaining_amount_in -= &amount_in;
+        if order.remaining_amount_in == 0u64 {
+            self.remove_order(order_id, &order.owner);
+        } else {
+            self.orders(order_id).set(&order);
+        }
+
+        self.send()
+            .direct_esdt(&order.owner, &order.token_out, 0, &owner_amount_out);
+
+        let caller = self.blockchain().get_caller();
+        let bounty = EsdtTokenPayment::new(order.token_out.clone(), 0, bounty_amount.clone());
+        if bounty.amount > 0u64 {
+            self.send()
+                .direct_esdt(&caller, &bounty.token_identifier, 0, &bounty.amount);
+        }
+
+        self.emit_fill_order_event(order_id, &order, amount_in, owner_amount_out, bounty_amount);
+
+        bounty
+    }
+
+    /// Rounded up, so that the owner still gets `amount_out` after the bounty.
+    fn get_amount_out_before_bounty(
+        &self,
+        amount_out: &BigUint,
+        keeper_bounty_percent: u64,
+    ) -> BigUint {
+        let denominator = MAX_PERCENTAGE - keeper_bounty_percent;
+        (amount_out * MAX_PERCENTAGE + (denominator - 1)) / denominator
+    }
+
+    #[proxy]
+    fn pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+}
@@ -0,0 +1,105 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod config;
+pub mod errors;
+mod events;
+pub mod fill;
+pub mod order;
+
+use errors::*;
+use order::Order;
+use router::factory::ProxyTrait as _;
+
+/// Limit orders, filled against the pairs registered in the router.
+///
+/// Users escrow a token, along with a target amount of another token and an expiry timestamp.
+/// Keepers fill the orders, in full or in parts, once the pair gives at least the target price,
+/// and earn a bounty out of the output. The owner can cancel an order at any time,
+/// and anyone can cancel it after it expires, with the remaining tokens going back to the owner.
+#[multiversx_sc::contract]
+pub trait LimitOrders:
+    config::ConfigModule + order::OrderModule + fill::FillModule + events::EventsModule
+{
+    #[init]
+    fn init(&self, router_address: ManagedAddress, keeper_bounty_percent: u64) {
+        require!(
+            self.blockchain().is_smart_contract(&router_address),
+            ERROR_INVALID_ARGS
+        );
+
+        self.router_address().set(&router_address);
+        self.try_set_keeper_bounty_percent(keeper_bounty_percent);
+    }
+
+    #[endpoint]
+    fn upgrade(&self) {}
+
+    #[payable("*")]
+    #[endpoint(createOrder)]
+    fn create_order(
+        &self,
+        token_out: TokenIdentifier,
+        target_amount_out: BigUint,
+        expiry_timestamp: u64,
+    ) -> u64 {
+        let (token_in, nonce, amount_in) = self.call_value().single_esdt().into_tuple();
+        require!(nonce == 0 && amount_in > 0u64, ERROR_BAD_PAYMENT);
+        require!(
+            token_in != token_out && target_amount_out > 0u64,
+            ERROR_INVALID_ARGS
+        );
+        require!(
+            expiry_timestamp > self.blockchain().get_block_timestamp(),
+            ERROR_ORDER_EXPIRED
+        );
+
+        let pair_address: ManagedAddress = self
+            .router_proxy(self.router_address().get())
+            .get_pair(token_in.clone(), token_out.clone())
+            .execute_on_dest_context();
+        require!(!pair_address.is_zero(), ERROR_NO_PAIR);
+
+        let order = Order {
+            owner: self.blockchain().get_caller(),
+            pair_address,
+            token_in,
+            initial_amount_in: amount_in.clone(),
+            remaining_amount_in: amount_in,
+            token_out,
+            target_amount_out,
+            expiry_timestamp,
+        };
+        let order_id = self.insert_order(&order);
+
+        self.emit_create_order_event(order_id, &order);
+
+        order_id
+    }
+
+    #[endpoint(cancelOrder)]
+    fn cancel_order(&self, order_id: u64) -> EsdtTokenPayment {
+        let order = self.get_order(order_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == order.owner
+                || self.blockchain().get_block_timestamp() > order.expiry_timestamp,
+            ERROR_ORDER_NOT_EXPIRED
+        );
+
+        self.remove_order(order_id, &order.owner);
+
+        let refund =
+            EsdtTokenPayment::new(order.token_in.clone(), 0, order.remaining_amount_in.clone());
+        self.send()
+            .direct_esdt(&order.owner, &refund.token_identifier, 0, &refund.amount);
+
+        self.emit_cancel_order_event(order_id, &order);
+
+        refund
+    }
+
+    #[proxy]
+    fn router_proxy(&self, to: ManagedAddress) -> router::Proxy<Self::Api>;
+}
@@ -0,0 +1,63 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::ERROR_ORDER_NOT_FOUND;
+
+/// `target_amount_out` is the minimum output for the whole `initial_amount_in`.
+/// Partial fills get the same price, pro rata.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Order<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub pair_address: ManagedAddress<M>,
+    pub token_in: TokenIdentifier<M>,
+    pub initial_amount_in: BigUint<M>,
+    pub remaining_amount_in: BigUint<M>,
+    pub token_out: TokenIdentifier<M>,
+    pub target_amount_out: BigUint<M>,
+    pub expiry_timestamp: u64,
+}
+
+impl<M: ManagedTypeApi> Order<M> {
+    /// Rounded up, in favor of the order owner.
+    pub fn get_min_amount_out(&self, amount_in: &BigUint<M>) -> BigUint<M> {
+        let numerator = amount_in * &self.target_amount_out + &self.initial_amount_in - 1u32;
+        numerator / &self.initial_amount_in
+    }
+}
+
+#[multiversx_sc::module]
+pub trait OrderModule {
+    #[view(getOrder)]
+    fn get_order(&self, order_id: u64) -> Order<Self::Api> {
+        let order_mapper = self.orders(order_id);
+        require!(!order_mapper.is_empty(), ERROR_ORDER_NOT_FOUND);
+
+        order_mapper.get()
+    }
+
+    fn insert_order(&self, order: &Order<Self::Api>) -> u64 {
+        let order_id = self.last_order_id().update(|last_order_id| {
+            *last_order_id += 1;
+            *last_order_id
+        });
+        self.orders(order_id).set(order);
+        self.user_orders(&order.owner).insert(order_id);
+
+        order_id
+    }
+
+    fn remove_order(&self, order_id: u64, owner: &ManagedAddress) {
+        self.orders(order_id).clear();
+        self.user_orders(owner).swap_remove(&order_id);
+    }
+
+    #[storage_mapper("lastOrderId")]
+    fn last_order_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("orders")]
+    fn orders(&self, order_id: u64) -> SingleValueMapper<Order<Self::Api>>;
+
+    #[view(getUserOrders)]
+    #[storage_mapper("userOrders")]
+    fn user_orders(&self, user: &ManagedAddress) -> UnorderedSetMapper<u64>;
+}
@@ -0,0 +1,238 @@
+use multiversx_sc::codec::multi_types::OptionalValue;
+use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+};
+
+use limit_orders::fill::FillModule;
+use limit_orders::LimitOrders;
+use pair::config::ConfigModule as PairConfigModule;
+use pair::Pair;
+use pausable::{PausableModule, State};
+use router::factory::{FactoryModule, PairTokens};
+use router::Router;
+
+pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
+pub const ROUTER_WASM_PATH: &str = "router/output/router.wasm";
+pub const LIMIT_ORDERS_WASM_PATH: &str = "limit-orders/output/limit-orders.wasm";
+pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+pub const LPMEX_TOKEN_ID: &[u8] = b"LPMEX-abcdef";
+
+pub const USER_TOTAL_WEGLD_TOKENS: u64 = 5_000_000_000;
+pub const USER_TOTAL_MEX_TOKENS: u64 = 5_000_000_000;
+pub const ADD_LIQUIDITY_TOKENS: u64 = 1_001_000;
+pub const KEEPER_BOUNTY_PERCENT: u64 = 1_000;
+
+#[allow(dead_code)]
+pub struct LimitOrdersSetup<LimitOrdersObjBuilder, RouterObjBuilder, PairObjBuilder>
+where
+    LimitOrdersObjBuilder: 'static + Copy + Fn() -> limit_orders::ContractObj<DebugApi>,
+    RouterObjBuilder: 'static + Copy + Fn() -> router::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub user_address: Address,
+    pub keeper_address: Address,
+    pub limit_orders_wrapper:
+        ContractObjWrapper<limit_orders::ContractObj<DebugApi>, LimitOrdersObjBuilder>,
+    pub router_wrapper: ContractObjWrapper<router::ContractObj<DebugApi>, RouterObjBuilder>,
+    pub pair_wrapper: ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>,
+}
+
+impl<LimitOrdersObjBuilder, RouterObjBuilder, PairObjBuilder>
+    LimitOrdersSetup<LimitOrdersObjBuilder, RouterObjBuilder, PairObjBuilder>
+where
+    LimitOrdersObjBuilder: 'static + Copy + Fn() -> limit_orders::ContractObj<DebugApi>,
+    RouterObjBuilder: 'static + Copy + Fn() -> router::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub fn new(
+        limit_orders_builder: LimitOrdersObjBuilder,
+        router_builder: RouterObjBuilder,
+        pair_builder: PairObjBuilder,
+    ) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_addr = b_mock.create_user_account(&rust_zero);
+        let user_addr = b_mock.create_user_account(&rust_zero);
+        let keeper_addr = b_mock.create_user_account(&rust_zero);
+
+        let router_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            router_builder,
+            ROUTER_WASM_PATH,
+        );
+        let pair_wrapper =
+            b_mock.create_sc_account(&rust_zero, Some(&owner_addr), pair_builder, PAIR_WASM_PATH);
+        let limit_orders_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            limit_orders_builder,
+            LIMIT_ORDERS_WASM_PATH,
+        );
+
+        b_mock
+            .execute_tx(&owner_addr, &pair_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    300,
+                    50,
+                    ManagedAddress::<DebugApi>::zero(),
+                    MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+                );
+
+                sc.lp_token_identifier()
+                    .set(&managed_token_id!(LPMEX_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&owner_addr, &router_wrapper, &rust_zero, |sc| {
+                sc.init(OptionalValue::None);
+
+                sc.pair_map().insert(
+                    PairTokens {
+                        first_token_id: managed_token_id!(WEGLD_TOKEN_ID),
+                        second_token_id: managed_token_id!(MEX_TOKEN_ID),
+                    },
+                    managed_address!(pair_wrapper.address_ref()),
+                );
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&owner_addr, &limit_orders_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_address!(router_wrapper.address_ref()),
+                    KEEPER_BOUNTY_PERCENT,
+                );
+            })
+            .assert_ok();
+
+        let lp_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        b_mock.set_esdt_local_roles(
+            pair_wrapper.address_ref(),
+            LPMEX_TOKEN_ID,
+            &lp_token_roles[..],
+        );
+
+        b_mock.set_esdt_balance(
+            &user_addr,
+            WEGLD_TOKEN_ID,
+            &rust_biguint!(USER_TOTAL_WEGLD_TOKENS),
+        );
+        b_mock.set_esdt_balance(
+            &user_addr,
+            MEX_TOKEN_ID,
+            &rust_biguint!(USER_TOTAL_MEX_TOKENS),
+        );
+
+        let payments = vec![
+            TxTokenTransfer {
+                token_identifier: WEGLD_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(ADD_LIQUIDITY_TOKENS),
+            },
+            TxTokenTransfer {
+                token_identifier: MEX_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(ADD_LIQUIDITY_TOKENS),
+            },
+        ];
+        b_mock
+            .execute_esdt_multi_transfer(&user_addr, &pair_wrapper, &payments, |sc| {
+                sc.add_liquidity(
+                    managed_biguint!(ADD_LIQUIDITY_TOKENS),
+                    managed_biguint!(ADD_LIQUIDITY_TOKENS),
+                );
+            })
+            .assert_ok();
+
+        LimitOrdersSetup {
+            b_mock,
+            owner_address: owner_addr,
+            user_address: user_addr,
+            keeper_address: keeper_addr,
+            limit_orders_wrapper,
+            router_wrapper,
+            pair_wrapper,
+        }
+    }
+
+    pub fn create_order(
+        &mut self,
+        token_in: &[u8],
+        amount_in: u64,
+        token_out: &[u8],
+        target_amount_out: u64,
+        expiry_timestamp: u64,
+    ) -> u64 {
+        let mut order_id = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.limit_orders_wrapper,
+                token_in,
+                0,
+                &rust_biguint!(amount_in),
+                |sc| {
+                    order_id = sc.create_order(
+                        managed_token_id!(token_out),
+                        managed_biguint!(target_amount_out),
+                        expiry_timestamp,
+                    );
+                },
+            )
+            .assert_ok();
+
+        order_id
+    }
+
+    pub fn fill_order(&mut self, order_id: u64, amount_in: u64, expected_bounty_amount: u64) {
+        self.b_mock
+            .execute_tx(
+                &self.keeper_address,
+                &self.limit_orders_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    let bounty = sc.fill_order(order_id, managed_biguint!(amount_in));
+                    assert_eq!(bounty.amount, managed_biguint!(expected_bounty_amount));
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn swap_fixed_input(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount: u64,
+        desired_token_id: &[u8],
+        expected_amount: u64,
+    ) {
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount),
+                |sc| {
+                    let ret = sc.swap_tokens_fixed_input(
+                        managed_token_id!(desired_token_id),
+                        managed_biguint!(1),
+                        OptionalValue::None,
+                    );
+                    assert_eq!(ret.amount, managed_biguint!(expected_amount));
+                },
+            )
+            .assert_ok();
+    }
+}
@@ -0,0 +1,116 @@
+#![allow(deprecated)]
+
+mod limit_orders_setup;
+use limit_orders::{fill::FillModule, order::OrderModule, LimitOrders};
+use limit_orders_setup::*;
+use multiversx_sc_scenario::{managed_address, managed_biguint, managed_token_id, rust_biguint};
+
+#[test]
+fn test_limit_orders_setup() {
+    let _ = LimitOrdersSetup::new(
+        limit_orders::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+    );
+}
+
+#[test]
+fn test_limit_order_fill_and_cancel() {
+    let mut setup = LimitOrdersSetup::new(
+        limit_orders::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+    );
+    setup.b_mock.set_block_timestamp(10);
+
+    let order_id = setup.create_order(WEGLD_TOKEN_ID, 10_000, MEX_TOKEN_ID, 14_000, 100);
+    assert_eq!(order_id, 1);
+
+    // 5_000 WEGLD only give 4_960 MEX, while the order wants 7_000, plus the keeper bounty
+    let keeper = setup.keeper_address.clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.limit_orders_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.fill_order(order_id, managed_biguint!(5_000));
+            },
+        )
+        .assert_user_error("Target price not reached");
+
+    setup.swap_fixed_input(MEX_TOKEN_ID, 200_000, WEGLD_TOKEN_ID, 166_277);
+
+    // the pair now gives 7_129 MEX, 71 of which go to the keeper
+    setup.fill_order(order_id, 5_000, 71);
+    setup
+        .b_mock
+        .check_esdt_balance(&keeper, MEX_TOKEN_ID, &rust_biguint!(71));
+
+    let user = setup.user_address.clone();
+    setup.b_mock.check_esdt_balance(
+        &user,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_MEX_TOKENS - ADD_LIQUIDITY_TOKENS - 200_000 + 7_058),
+    );
+    setup
+        .b_mock
+        .execute_query(&setup.limit_orders_wrapper, |sc| {
+            let order = sc.get_order(order_id);
+            assert_eq!(order.remaining_amount_in, managed_biguint!(5_000));
+            assert_eq!(order.token_out, managed_token_id!(MEX_TOKEN_ID));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.limit_orders_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.cancel_order(order_id);
+            },
+        )
+        .assert_user_error("Only the owner can cancel an order before it expires");
+
+    setup.b_mock.set_block_timestamp(101);
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.limit_orders_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.fill_order(order_id, managed_biguint!(5_000));
+            },
+        )
+        .assert_user_error("Order expired");
+
+    // anyone can cancel an expired order, the tokens go back to the owner
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.limit_orders_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let refund = sc.cancel_order(order_id);
+                assert_eq!(refund.amount, managed_biguint!(5_000));
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &user,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - ADD_LIQUIDITY_TOKENS - 10_000 + 166_277 + 5_000),
+    );
+    setup
+        .b_mock
+        .execute_query(&setup.limit_orders_wrapper, |sc| {
+            assert!(sc.user_orders(&managed_address!(&user)).is_empty());
+        })
+        .assert_ok();
+}
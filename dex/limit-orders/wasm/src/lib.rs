@@ -0,0 +1,37 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Endpoints:                            9
+// Async Callback (empty):               1
+// Total number of exported functions:  11
+
+#![no_std]
+
+// Configuration that works with rustc < 1.73.0.
+// TODO: Recommended rustc version: 1.73.0 or newer.
+#![feature(lang_items)]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    limit_orders
+    (
+        init => init
+        upgrade => upgrade
+        createOrder => create_order
+        cancelOrder => cancel_order
+        setKeeperBountyPercent => set_keeper_bounty_percent
+        getRouterAddress => router_address
+        getKeeperBountyPercent => keeper_bounty_percent
+        getOrder => get_order
+        getUserOrders => user_orders
+        fillOrder => fill_order
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
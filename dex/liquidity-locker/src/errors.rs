@@ -0,0 +1,8 @@
+pub static ERROR_BAD_PAYMENT: &[u8] = b"Bad payment";
+pub static ERROR_INVALID_ARGS: &[u8] = b"Invalid args";
+pub static ERROR_NOT_PAIR_LP_TOKEN: &[u8] = b"Payment is not the LP token of the pair";
+pub static ERROR_BAD_LOCK_PERIOD: &[u8] = b"Bad lock period";
+
+pub static ERROR_LOCK_NOT_FOUND: &[u8] = b"Lock not found";
+pub static ERROR_NOT_LOCK_OWNER: &[u8] = b"Only the lock owner can do this";
+pub static ERROR_NOTHING_TO_CLAIM: &[u8] = b"Nothing to claim";
@@ -0,0 +1,97 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::lock::LpLock;
+
+#[derive(TypeAbi, TopEncode)]
+pub struct ClaimUnlockedEvent<M: ManagedTypeApi> {
+    owner: ManagedAddress<M>,
+    pair_address: ManagedAddress<M>,
+    lp_token_id: TokenIdentifier<M>,
+    claimed_amount: BigUint<M>,
+    total_claimed_amount: BigUint<M>,
+    block: u64,
+    epoch: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_lock_lp_tokens_event(&self, lock_id: u64, lock: &LpLock<Self::Api>) {
+        self.lock_lp_tokens_event(
+            &lock.pair_address,
+            &lock.owner,
+            lock_id,
+            self.blockchain().get_block_epoch(),
+            lock,
+        );
+    }
+
+    fn emit_claim_unlocked_event(
+        &self,
+        lock_id: u64,
+        lock: &LpLock<Self::Api>,
+        claimed_amount: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.claim_unlocked_event(
+            &lock.pair_address,
+            &lock.owner,
+            lock_id,
+            epoch,
+            &ClaimUnlockedEvent {
+                owner: lock.owner.clone(),
+                pair_address: lock.pair_address.clone(),
+                lp_token_id: lock.lp_token_id.clone(),
+                claimed_amount,
+                total_claimed_amount: lock.claimed_amount.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+            },
+        );
+    }
+
+    fn emit_transfer_lock_ownership_event(
+        &self,
+        lock_id: u64,
+        previous_owner: &ManagedAddress,
+        lock: &LpLock<Self::Api>,
+    ) {
+        self.transfer_lock_ownership_event(
+            previous_owner,
+            &lock.owner,
+            lock_id,
+            self.blockchain().get_block_epoch(),
+            lock,
+        );
+    }
+
+    #[event("lock_lp_tokens")]
+    fn lock_lp_tokens_event(
+        &self,
+        #[indexed] pair_address: &ManagedAddress,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] lock_id: u64,
+        #[indexed] epoch: u64,
+        lock: &LpLock<Self::Api>,
+    );
+
+    #[event("claim_unlocked")]
+    fn claim_unlocked_event(
+        &self,
+        #[indexed] pair_address: &ManagedAddress,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] lock_id: u64,
+        #[indexed] epoch: u64,
+        claim_unlocked_event: &ClaimUnlockedEvent<Self::Api>,
+    );
+
+    #[event("transfer_lock_ownership")]
+    fn transfer_lock_ownership_event(
+        &self,
+        #[indexed] previous_owner: &ManagedAddress,
+        #[indexed] new_owner: &ManagedAddress,
+        #[indexed] lock_id: u64,
+        #[indexed] epoch: u64,
+        lock: &LpLock<Self::Api>,
+    );
+}
@@ -0,0 +1,122 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod errors;
+mod events;
+pub mod lock;
+
+use errors::*;
+use lock::LpLock;
+use pair::config::ProxyTrait as _;
+
+pub const MAX_LOCK_EPOCHS: u64 = 36_500;
+
+/// Locks the LP tokens of a pair, with a cliff and a linear unlock schedule.
+///
+/// The locks are public, per pair and per owner, so projects can prove their liquidity is locked.
+/// Nothing can be claimed before the cliff. After it, the LP tokens unlock linearly,
+/// until the end of the vesting period. A lock without vesting unlocks all at once, at the cliff.
+#[multiversx_sc::contract]
+pub trait LiquidityLocker: lock::LockModule + events::EventsModule {
+    #[init]
+    fn init(&self) {}
+
+    #[endpoint]
+    fn upgrade(&self) {}
+
+    #[payable("*")]
+    #[endpoint(lockLpTokens)]
+    fn lock_lp_tokens(
+        &self,
+        pair_address: ManagedAddress,
+        cliff_epochs: u64,
+        vesting_epochs: u64,
+    ) -> u64 {
+        let (lp_token_id, nonce, amount) = self.call_value().single_esdt().into_tuple();
+        require!(nonce == 0 && amount > 0u64, ERROR_BAD_PAYMENT);
+        require!(
+            cliff_epochs + vesting_epochs > 0
+                && cliff_epochs <= MAX_LOCK_EPOCHS
+                && vesting_epochs <= MAX_LOCK_EPOCHS,
+            ERROR_BAD_LOCK_PERIOD
+        );
+        require!(
+            self.blockchain().is_smart_contract(&pair_address),
+            ERROR_INVALID_ARGS
+        );
+
+        let pair_lp_token_id: TokenIdentifier = self
+            .pair_proxy(pair_address.clone())
+            .get_lp_token_identifier()
+            .execute_on_dest_context();
+        require!(lp_token_id == pair_lp_token_id, ERROR_NOT_PAIR_LP_TOKEN);
+
+        let cliff_epoch = self.blockchain().get_block_epoch() + cliff_epochs;
+        let lock = LpLock {
+            owner: self.blockchain().get_caller(),
+            pair_address,
+            lp_token_id,
+            total_amount: amount,
+            claimed_amount: BigUint::zero(),
+            cliff_epoch,
+            end_epoch: cliff_epoch + vesting_epochs,
+        };
+        let lock_id = self.insert_lock(&lock);
+
+        self.emit_lock_lp_tokens_event(lock_id, &lock);
+
+        lock_id
+    }
+
+    #[endpoint(claimUnlocked)]
+    fn claim_unlocked(&self, lock_id: u64) -> EsdtTokenPayment {
+        let mut lock = self.get_lock(lock_id);
+        self.require_caller_lock_owner(&lock);
+
+        let unlocked_amount = lock.get_unlocked_amount(self.blockchain().get_block_epoch());
+        let claimable_amount = &unlocked_amount - &lock.claimed_amount;
+        require!(claimable_amount > 0u64, ERROR_NOTHING_TO_CLAIM);
+
+        lock.claimed_amount = unlocked_amount;
+        if lock.claimed_amount == lock.total_amount {
+            self.remove_lock(lock_id, &lock);
+        } else {
+            self.locks(lock_id).set(&lock);
+        }
+
+        let payment = EsdtTokenPayment::new(lock.lp_token_id.clone(), 0, claimable_amount);
+        self.send()
+            .direct_esdt(&lock.owner, &payment.token_identifier, 0, &payment.amount);
+
+        self.emit_claim_unlocked_event(lock_id, &lock, payment.amount.clone());
+
+        payment
+    }
+
+    /// Lets a project move its locks, e.g. to a multisig, without unlocking the LP tokens.
+    #[endpoint(transferLockOwnership)]
+    fn transfer_lock_ownership(&self, lock_id: u64, new_owner: ManagedAddress) {
+        let mut lock = self.get_lock(lock_id);
+        self.require_caller_lock_owner(&lock);
+        require!(
+            !new_owner.is_zero() && new_owner != lock.owner,
+            ERROR_INVALID_ARGS
+        );
+
+        let previous_owner = core::mem::replace(&mut lock.owner, new_owner);
+        self.owner_locks(&previous_owner).swap_remove(&lock_id);
+        self.owner_locks(&lock.owner).insert(lock_id);
+        self.locks(lock_id).set(&lock);
+
+        self.emit_transfer_lock_ownership_event(lock_id, &previous_owner, &lock);
+    }
+
+    fn require_caller_lock_owner(&self, lock: &LpLock<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        require!(caller == lock.owner, ERROR_NOT_LOCK_OWNER);
+    }
+
+    #[proxy]
+    fn pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+}
@@ -0,0 +1,90 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::ERROR_LOCK_NOT_FOUND;
+
+/// Nothing unlocks before `cliff_epoch`, and everything is unlocked at `end_epoch`,
+/// linearly in between. A lock with `cliff_epoch == end_epoch` unlocks all at once.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct LpLock<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub pair_address: ManagedAddress<M>,
+    pub lp_token_id: TokenIdentifier<M>,
+    pub total_amount: BigUint<M>,
+    pub claimed_amount: BigUint<M>,
+    pub cliff_epoch: u64,
+    pub end_epoch: u64,
+}
+
+impl<M: ManagedTypeApi> LpLock<M> {
+    pub fn get_unlocked_amount(&self, epoch: u64) -> BigUint<M> {
+        if epoch >= self.end_epoch {
+            return self.total_amount.clone();
+        }
+        if epoch < self.cliff_epoch {
+            return BigUint::zero();
+        }
+
+        &self.total_amount * (epoch - self.cliff_epoch) / (self.end_epoch - self.cliff_epoch)
+    }
+
+    /// Includes the amount that is unlocked but not claimed yet.
+    pub fn get_locked_amount(&self, epoch: u64) -> BigUint<M> {
+        &self.total_amount - &self.get_unlocked_amount(epoch)
+    }
+}
+
+#[multiversx_sc::module]
+pub trait LockModule {
+    #[view(getLock)]
+    fn get_lock(&self, lock_id: u64) -> LpLock<Self::Api> {
+        let lock_mapper = self.locks(lock_id);
+        require!(!lock_mapper.is_empty(), ERROR_LOCK_NOT_FOUND);
+
+        lock_mapper.get()
+    }
+
+    /// The LP tokens of the pair that are still locked in the current epoch.
+    #[view(getPairLockedAmount)]
+    fn get_pair_locked_amount(&self, pair_address: ManagedAddress) -> BigUint {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut locked_amount = BigUint::zero();
+        for lock_id in self.pair_locks(&pair_address).iter() {
+            locked_amount += self.locks(lock_id).get().get_locked_amount(current_epoch);
+        }
+
+        locked_amount
+    }
+
+    fn insert_lock(&self, lock: &LpLock<Self::Api>) -> u64 {
+        let lock_id = self.last_lock_id().update(|last_lock_id| {
+            *last_lock_id += 1;
+            *last_lock_id
+        });
+        self.locks(lock_id).set(lock);
+        self.owner_locks(&lock.owner).insert(lock_id);
+        self.pair_locks(&lock.pair_address).insert(lock_id);
+
+        lock_id
+    }
+
+    fn remove_lock(&self, lock_id: u64, lock: &LpLock<Self::Api>) {
+        self.locks(lock_id).clear();
+        self.owner_locks(&lock.owner).swap_remove(&lock_id);
+        self.pair_locks(&lock.pair_address).swap_remove(&lock_id);
+    }
+
+    #[storage_mapper("lastLockId")]
+    fn last_lock_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("locks")]
+    fn locks(&self, lock_id: u64) -> SingleValueMapper<LpLock<Self::Api>>;
+
+    #[view(getOwnerLocks)]
+    #[storage_mapper("ownerLocks")]
+    fn owner_locks(&self, owner: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[view(getPairLocks)]
+    #[storage_mapper("pairLocks")]
+    fn pair_locks(&self, pair_address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+}
@@ -0,0 +1,176 @@
+use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+};
+
+use liquidity_locker::LiquidityLocker;
+use pair::config::ConfigModule as PairConfigModule;
+use pair::Pair;
+use pausable::{PausableModule, State};
+
+pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
+pub const LIQUIDITY_LOCKER_WASM_PATH: &str = "liquidity-locker/output/liquidity-locker.wasm";
+pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+pub const LPMEX_TOKEN_ID: &[u8] = b"LPMEX-abcdef";
+pub const OTHER_TOKEN_ID: &[u8] = b"OTHER-abcdef";
+
+pub const USER_TOTAL_WEGLD_TOKENS: u64 = 5_000_000_000;
+pub const USER_TOTAL_MEX_TOKENS: u64 = 5_000_000_000;
+pub const ADD_LIQUIDITY_TOKENS: u64 = 1_001_000;
+pub const USER_LP_TOKENS: u64 = 1_000_000;
+
+#[allow(dead_code)]
+pub struct LiquidityLockerSetup<LiquidityLockerObjBuilder, PairObjBuilder>
+where
+    LiquidityLockerObjBuilder: 'static + Copy + Fn() -> liquidity_locker::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub user_address: Address,
+    pub other_user_address: Address,
+    pub liquidity_locker_wrapper:
+        ContractObjWrapper<liquidity_locker::ContractObj<DebugApi>, LiquidityLockerObjBuilder>,
+    pub pair_wrapper: ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>,
+}
+
+impl<LiquidityLockerObjBuilder, PairObjBuilder>
+    LiquidityLockerSetup<LiquidityLockerObjBuilder, PairObjBuilder>
+where
+    LiquidityLockerObjBuilder: 'static + Copy + Fn() -> liquidity_locker::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub fn new(
+        liquidity_locker_builder: LiquidityLockerObjBuilder,
+        pair_builder: PairObjBuilder,
+    ) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_addr = b_mock.create_user_account(&rust_zero);
+        let user_addr = b_mock.create_user_account(&rust_zero);
+        let other_user_addr = b_mock.create_user_account(&rust_zero);
+
+        let pair_wrapper =
+            b_mock.create_sc_account(&rust_zero, Some(&owner_addr), pair_builder, PAIR_WASM_PATH);
+        let liquidity_locker_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            liquidity_locker_builder,
+            LIQUIDITY_LOCKER_WASM_PATH,
+        );
+
+        b_mock
+            .execute_tx(&owner_addr, &pair_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    300,
+                    50,
+                    ManagedAddress::<DebugApi>::zero(),
+                    MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+                );
+
+                sc.lp_token_identifier()
+                    .set(&managed_token_id!(LPMEX_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&owner_addr, &liquidity_locker_wrapper, &rust_zero, |sc| {
+                sc.init();
+            })
+            .assert_ok();
+
+        let lp_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        b_mock.set_esdt_local_roles(
+            pair_wrapper.address_ref(),
+            LPMEX_TOKEN_ID,
+            &lp_token_roles[..],
+        );
+
+        b_mock.set_esdt_balance(
+            &user_addr,
+            WEGLD_TOKEN_ID,
+            &rust_biguint!(USER_TOTAL_WEGLD_TOKENS),
+        );
+        b_mock.set_esdt_balance(
+            &user_addr,
+            MEX_TOKEN_ID,
+            &rust_biguint!(USER_TOTAL_MEX_TOKENS),
+        );
+        b_mock.set_esdt_balance(&user_addr, OTHER_TOKEN_ID, &rust_biguint!(USER_LP_TOKENS));
+
+        let payments = vec![
+            TxTokenTransfer {
+                token_identifier: WEGLD_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(ADD_LIQUIDITY_TOKENS),
+            },
+            TxTokenTransfer {
+                token_identifier: MEX_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(ADD_LIQUIDITY_TOKENS),
+            },
+        ];
+        b_mock
+            .execute_esdt_multi_transfer(&user_addr, &pair_wrapper, &payments, |sc| {
+                sc.add_liquidity(
+                    managed_biguint!(ADD_LIQUIDITY_TOKENS),
+                    managed_biguint!(ADD_LIQUIDITY_TOKENS),
+                );
+            })
+            .assert_ok();
+
+        LiquidityLockerSetup {
+            b_mock,
+            owner_address: owner_addr,
+            user_address: user_addr,
+            other_user_address: other_user_addr,
+            liquidity_locker_wrapper,
+            pair_wrapper,
+        }
+    }
+
+    pub fn lock_lp_tokens(&mut self, amount: u64, cliff_epochs: u64, vesting_epochs: u64) -> u64 {
+        let pair_address = self.pair_wrapper.address_ref().clone();
+        let mut lock_id = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.liquidity_locker_wrapper,
+                LPMEX_TOKEN_ID,
+                0,
+                &rust_biguint!(amount),
+                |sc| {
+                    lock_id = sc.lock_lp_tokens(
+                        managed_address!(&pair_address),
+                        cliff_epochs,
+                        vesting_epochs,
+                    );
+                },
+            )
+            .assert_ok();
+
+        lock_id
+    }
+
+    pub fn claim_unlocked(&mut self, caller: &Address, lock_id: u64, expected_amount: u64) {
+        self.b_mock
+            .execute_tx(
+                caller,
+                &self.liquidity_locker_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    let payment = sc.claim_unlocked(lock_id);
+                    assert_eq!(payment.token_identifier, managed_token_id!(LPMEX_TOKEN_ID));
+                    assert_eq!(payment.amount, managed_biguint!(expected_amount));
+                },
+            )
+            .assert_ok();
+    }
+}
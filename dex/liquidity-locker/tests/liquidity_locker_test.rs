@@ -0,0 +1,153 @@
+#![allow(deprecated)]
+
+mod liquidity_locker_setup;
+use liquidity_locker::{lock::LockModule, LiquidityLocker};
+use liquidity_locker_setup::*;
+use multiversx_sc_scenario::{managed_address, managed_biguint, rust_biguint};
+
+#[test]
+fn test_liquidity_locker_setup() {
+    let _ = LiquidityLockerSetup::new(liquidity_locker::contract_obj, pair::contract_obj);
+}
+
+#[test]
+fn test_lock_and_claim_unlocked() {
+    let mut setup = LiquidityLockerSetup::new(liquidity_locker::contract_obj, pair::contract_obj);
+    setup.b_mock.set_block_epoch(5);
+
+    let user = setup.user_address.clone();
+    let other_user = setup.other_user_address.clone();
+    let pair_address = setup.pair_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &setup.liquidity_locker_wrapper,
+            OTHER_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.lock_lp_tokens(managed_address!(&pair_address), 10, 100);
+            },
+        )
+        .assert_user_error("Payment is not the LP token of the pair");
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &setup.liquidity_locker_wrapper,
+            LPMEX_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.lock_lp_tokens(managed_address!(&pair_address), 0, 0);
+            },
+        )
+        .assert_user_error("Bad lock period");
+
+    // unlocks linearly between epochs 15 and 115
+    let vested_lock_id = setup.lock_lp_tokens(400_000, 10, 100);
+    // unlocks all at once, at epoch 25
+    let cliff_lock_id = setup.lock_lp_tokens(100_000, 20, 0);
+    assert_eq!((vested_lock_id, cliff_lock_id), (1, 2));
+
+    setup
+        .b_mock
+        .execute_query(&setup.liquidity_locker_wrapper, |sc| {
+            assert_eq!(
+                sc.get_pair_locked_amount(managed_address!(&pair_address)),
+                managed_biguint!(500_000)
+            );
+            assert_eq!(sc.owner_locks(&managed_address!(&user)).len(), 2);
+            assert_eq!(sc.pair_locks(&managed_address!(&pair_address)).len(), 2);
+
+            let lock = sc.get_lock(vested_lock_id);
+            assert_eq!(lock.cliff_epoch, 15);
+            assert_eq!(lock.end_epoch, 115);
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(10);
+    setup
+        .b_mock
+        .execute_tx(
+            &user,
+            &setup.liquidity_locker_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.claim_unlocked(vested_lock_id);
+            },
+        )
+        .assert_user_error("Nothing to claim");
+
+    setup.b_mock.set_block_epoch(40);
+    setup
+        .b_mock
+        .execute_tx(
+            &other_user,
+            &setup.liquidity_locker_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.claim_unlocked(vested_lock_id);
+            },
+        )
+        .assert_user_error("Only the lock owner can do this");
+
+    // 25 of the 100 vesting epochs have passed
+    setup.claim_unlocked(&user, vested_lock_id, 100_000);
+    setup.claim_unlocked(&user, cliff_lock_id, 100_000);
+    setup.b_mock.check_esdt_balance(
+        &user,
+        LPMEX_TOKEN_ID,
+        &rust_biguint!(USER_LP_TOKENS - 500_000 + 200_000),
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.liquidity_locker_wrapper, |sc| {
+            assert_eq!(
+                sc.get_pair_locked_amount(managed_address!(&pair_address)),
+                managed_biguint!(300_000)
+            );
+            assert_eq!(sc.pair_locks(&managed_address!(&pair_address)).len(), 1);
+            assert!(sc.locks(cliff_lock_id).is_empty());
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &user,
+            &setup.liquidity_locker_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.transfer_lock_ownership(vested_lock_id, managed_address!(&other_user));
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_query(&setup.liquidity_locker_wrapper, |sc| {
+            assert_eq!(sc.owner_locks(&managed_address!(&user)).len(), 0);
+            assert!(sc
+                .owner_locks(&managed_address!(&other_user))
+                .contains(&vested_lock_id));
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(115);
+    setup.claim_unlocked(&other_user, vested_lock_id, 300_000);
+    setup
+        .b_mock
+        .check_esdt_balance(&other_user, LPMEX_TOKEN_ID, &rust_biguint!(300_000));
+    setup
+        .b_mock
+        .execute_query(&setup.liquidity_locker_wrapper, |sc| {
+            assert_eq!(
+                sc.get_pair_locked_amount(managed_address!(&pair_address)),
+                managed_biguint!(0)
+            );
+            assert!(sc.locks(vested_lock_id).is_empty());
+        })
+        .assert_ok();
+}
@@ -0,0 +1,36 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Endpoints:                            8
+// Async Callback (empty):               1
+// Total number of exported functions:  10
+
+#![no_std]
+
+// Configuration that works with rustc < 1.73.0.
+// TODO: Recommended rustc version: 1.73.0 or newer.
+#![feature(lang_items)]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    liquidity_locker
+    (
+        init => init
+        upgrade => upgrade
+        lockLpTokens => lock_lp_tokens
+        claimUnlocked => claim_unlocked
+        transferLockOwnership => transfer_lock_ownership
+        getLock => get_lock
+        getPairLockedAmount => get_pair_locked_amount
+        getOwnerLocks => owner_locks
+        getPairLocks => pair_locks
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
@@ -0,0 +1,39 @@
+multiversx_sc::imports!();
+
+pub const PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+pub const MAX_GRANULARITY: u64 = 100;
+
+#[multiversx_sc::module]
+pub trait ConfigModule {
+    #[only_owner]
+    #[endpoint(setSafePriceViewAddress)]
+    fn set_safe_price_view_address(&self, safe_price_view_address: ManagedAddress) {
+        self.safe_price_view_address().set(&safe_price_view_address);
+    }
+
+    #[only_owner]
+    #[endpoint(setCommonTokenId)]
+    fn set_common_token_id(&self, common_token_id: TokenIdentifier) {
+        self.common_token_id().set(&common_token_id);
+    }
+
+    /// Contract queried through `getSafePrice`, usually the safe price view.
+    #[view(getSafePriceViewAddress)]
+    #[storage_mapper("safePriceViewAddress")]
+    fn safe_price_view_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    /// Token used to derive the price of two tokens that have no registered pair together.
+    #[view(getCommonTokenId)]
+    #[storage_mapper("commonTokenId")]
+    fn common_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    /// At most one observation is recorded per pair in each period.
+    #[view(getPeriodRounds)]
+    #[storage_mapper("periodRounds")]
+    fn period_rounds(&self) -> SingleValueMapper<u64>;
+
+    /// Number of observation slots kept per pair, which bounds the query windows.
+    #[view(getGranularity)]
+    #[storage_mapper("granularity")]
+    fn granularity(&self) -> SingleValueMapper<u64>;
+}
@@ -0,0 +1,8 @@
+pub static ERROR_INVALID_ARGS: &[u8] = b"Invalid args";
+pub static ERROR_PAIR_ALREADY_REGISTERED: &[u8] = b"Pair already registered";
+pub static ERROR_PAIR_NOT_REGISTERED: &[u8] = b"Pair not registered";
+
+pub static ERROR_NO_PRICE_ROUTE: &[u8] = b"No price route for the given tokens";
+pub static ERROR_BAD_WINDOW: &[u8] = b"Bad window";
+pub static ERROR_MISSING_OBSERVATION: &[u8] = b"Missing price observation";
+pub static ERROR_STALE_PRICE: &[u8] = b"Stale price";
@@ -0,0 +1,40 @@
+multiversx_sc::imports!();
+
+use crate::observation::OracleObservation;
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_update_observation_event(
+        &self,
+        pair_address: &ManagedAddress,
+        observation: &OracleObservation<Self::Api>,
+    ) {
+        self.update_observation_event(
+            &self.blockchain().get_caller(),
+            pair_address,
+            observation.round,
+            observation,
+        );
+    }
+
+    fn emit_skip_observation_event(&self, pair_address: &ManagedAddress, round: u64) {
+        self.skip_observation_event(&self.blockchain().get_caller(), pair_address, round);
+    }
+
+    #[event("update_observation")]
+    fn update_observation_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] pair_address: &ManagedAddress,
+        #[indexed] round: u64,
+        observation: &OracleObservation<Self::Api>,
+    );
+
+    #[event("skip_observation")]
+    fn skip_observation_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] pair_address: &ManagedAddress,
+        #[indexed] round: u64,
+    );
+}
@@ -0,0 +1,102 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod config;
+pub mod errors;
+mod events;
+pub mod observation;
+pub mod price;
+pub mod registry;
+
+use config::MAX_GRANULARITY;
+use errors::*;
+use pair::config::ProxyTrait as _;
+
+/// Compact price oracle over a set of registered pairs.
+///
+/// Keepers periodically pull the safe price of each pair since its previous observation,
+/// which is stored, along with Uniswap v2 style cumulative prices, in a fixed number
+/// of slots, one for each period. Queries then find the observations of a window directly,
+/// without searching through the pair's own observations, and serve TWAP and median prices,
+/// checked for staleness, for any two tokens with a registered pair, or derived through
+/// the common token otherwise.
+#[multiversx_sc::contract]
+pub trait OracleAggregator:
+    config::ConfigModule
+    + registry::RegistryModule
+    + observation::ObservationModule
+    + price::PriceModule
+    + events::EventsModule
+{
+    #[init]
+    fn init(
+        &self,
+        safe_price_view_address: ManagedAddress,
+        common_token_id: TokenIdentifier,
+        period_rounds: u64,
+        granularity: u64,
+    ) {
+        require!(
+            period_rounds > 0 && granularity > 1 && granularity <= MAX_GRANULARITY,
+            ERROR_INVALID_ARGS
+        );
+
+        self.safe_price_view_address().set(&safe_price_view_address);
+        self.common_token_id().set(&common_token_id);
+        self.period_rounds().set(period_rounds);
+        self.granularity().set(granularity);
+    }
+
+    #[endpoint]
+    fn upgrade(&self) {}
+
+    #[only_owner]
+    #[endpoint(registerPair)]
+    fn register_pair(&self, pair_address: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&pair_address),
+            ERROR_INVALID_ARGS
+        );
+        require!(
+            self.registered_pairs().insert(pair_address.clone()),
+            ERROR_PAIR_ALREADY_REGISTERED
+        );
+
+        let first_token_id: SingleValue<TokenIdentifier> = self
+            .pair_proxy(pair_address.clone())
+            .first_token_id()
+            .execute_on_dest_context();
+        let second_token_id: SingleValue<TokenIdentifier> = self
+            .pair_proxy(pair_address.clone())
+            .second_token_id()
+            .execute_on_dest_context();
+        let first_token_id = first_token_id.into();
+        let second_token_id = second_token_id.into();
+
+        let pair_mapper = self.pair_by_tokens(&first_token_id, &second_token_id);
+        require!(pair_mapper.is_empty(), ERROR_PAIR_ALREADY_REGISTERED);
+
+        pair_mapper.set(&pair_address);
+        self.pair_by_tokens(&second_token_id, &first_token_id)
+            .set(&pair_address);
+        self.pair_tokens(&pair_address)
+            .set((first_token_id, second_token_id));
+    }
+
+    #[only_owner]
+    #[endpoint(removePair)]
+    fn remove_pair(&self, pair_address: ManagedAddress) {
+        require!(
+            self.registered_pairs().swap_remove(&pair_address),
+            ERROR_PAIR_NOT_REGISTERED
+        );
+
+        let (first_token_id, second_token_id) = self.pair_tokens(&pair_address).take();
+        self.pair_by_tokens(&first_token_id, &second_token_id)
+            .clear();
+        self.pair_by_tokens(&second_token_id, &first_token_id)
+            .clear();
+        self.clear_observations(&pair_address);
+    }
+}
@@ -0,0 +1,164 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{
+    config::{self, PRICE_PRECISION},
+    errors::*,
+    events, registry,
+};
+
+use pair::safe_price_view::ProxyTrait as _;
+
+/// `first_token_price` is the amount of the second token for `PRICE_PRECISION` units
+/// of the first token, and `second_token_price` the other way around. Both are averaged
+/// over the rounds since the previous observation, and added up, weighted by those rounds,
+/// in the cumulative prices. If the pair no longer holds the previous round, the average
+/// starts at its oldest observation instead. The first observation of a pair only marks
+/// the starting round.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct OracleObservation<M: ManagedTypeApi> {
+    pub round: u64,
+    pub first_token_price: BigUint<M>,
+    pub second_token_price: BigUint<M>,
+    pub first_token_price_cumulative: BigUint<M>,
+    pub second_token_price_cumulative: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> OracleObservation<M> {
+    pub fn new_starting_at(round: u64) -> Self {
+        OracleObservation {
+            round,
+            first_token_price: BigUint::zero(),
+            second_token_price: BigUint::zero(),
+            first_token_price_cumulative: BigUint::zero(),
+            second_token_price_cumulative: BigUint::zero(),
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait ObservationModule:
+    config::ConfigModule + registry::RegistryModule + events::EventsModule
+{
+    /// Records a new observation for each of the given pairs, or for all registered pairs
+    /// if none are given. Can be called by anyone, and pairs already updated
+    /// in the current period are skipped, as well as pairs without any price observation
+    /// before the current round.
+    #[endpoint(updateObservations)]
+    fn update_observations(&self, pair_addresses: MultiValueEncoded<ManagedAddress>) {
+        if pair_addresses.is_empty() {
+            for pair_address in self.registered_pairs().iter() {
+                self.update_observation(&pair_address);
+            }
+            return;
+        }
+
+        for pair_address in pair_addresses {
+            require!(
+                self.registered_pairs().contains(&pair_address),
+                ERROR_PAIR_NOT_REGISTERED
+            );
+            self.update_observation(&pair_address);
+        }
+    }
+
+    fn update_observation(&self, pair_address: &ManagedAddress) {
+        let current_round = self.blockchain().get_block_round();
+        let period_rounds = self.period_rounds().get();
+        let current_period = current_round / period_rounds;
+
+        let last_observation_mapper = self.last_observation(pair_address);
+        let new_observation = if last_observation_mapper.is_empty() {
+            OracleObservation::new_starting_at(current_round)
+        } else {
+            let last_observation = last_observation_mapper.get();
+            if last_observation.round / period_rounds == current_period {
+                return;
+            }
+
+            // the pair may have overwritten the observations since the last update
+            let start_round = match self.get_oldest_price_observation_round(pair_address) {
+                Some(oldest_round) => core::cmp::max(last_observation.round, oldest_round),
+                None => current_round,
+            };
+            if start_round >= current_round {
+                self.emit_skip_observation_event(pair_address, current_round);
+                return;
+            }
+
+            let (first_token_id, second_token_id) = self.pair_tokens(pair_address).get();
+            let first_token_price =
+                self.get_safe_price(pair_address, start_round, current_round, first_token_id);
+            let second_token_price =
+                self.get_safe_price(pair_address, start_round, current_round, second_token_id);
+
+            let elapsed_rounds = current_round - last_observation.round;
+            OracleObservation {
+                round: current_round,
+                first_token_price_cumulative: last_observation.first_token_price_cumulative
+                    + &first_token_price * elapsed_rounds,
+                second_token_price_cumulative: last_observation.second_token_price_cumulative
+                    + &second_token_price * elapsed_rounds,
+                first_token_price,
+                second_token_price,
+            }
+        };
+
+        let slot = current_period % self.granularity().get();
+        self.observations(pair_address, slot).set(&new_observation);
+        last_observation_mapper.set(&new_observation);
+
+        self.emit_update_observation_event(pair_address, &new_observation);
+    }
+
+    fn get_oldest_price_observation_round(&self, pair_address: &ManagedAddress) -> Option<u64> {
+        self.pair_proxy(self.safe_price_view_address().get())
+            .get_oldest_price_observation_round(pair_address.clone())
+            .execute_on_dest_context()
+    }
+
+    fn get_safe_price(
+        &self,
+        pair_address: &ManagedAddress,
+        start_round: u64,
+        end_round: u64,
+        token_id: TokenIdentifier,
+    ) -> BigUint {
+        let safe_price_output: EsdtTokenPayment = self
+            .pair_proxy(self.safe_price_view_address().get())
+            .get_safe_price(
+                pair_address.clone(),
+                start_round,
+                end_round,
+                EsdtTokenPayment::new(token_id, 0, BigUint::from(PRICE_PRECISION)),
+            )
+            .execute_on_dest_context();
+
+        safe_price_output.amount
+    }
+
+    fn clear_observations(&self, pair_address: &ManagedAddress) {
+        for slot in 0..self.granularity().get() {
+            self.observations(pair_address, slot).clear();
+        }
+        self.last_observation(pair_address).clear();
+    }
+
+    #[proxy]
+    fn pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+
+    /// Observations are kept in `granularity` slots, one for each period, reused in a cycle.
+    #[storage_mapper("observations")]
+    fn observations(
+        &self,
+        pair_address: &ManagedAddress,
+        slot: u64,
+    ) -> SingleValueMapper<OracleObservation<Self::Api>>;
+
+    #[view(getLastObservation)]
+    #[storage_mapper("lastObservation")]
+    fn last_observation(
+        &self,
+        pair_address: &ManagedAddress,
+    ) -> SingleValueMapper<OracleObservation<Self::Api>>;
+}
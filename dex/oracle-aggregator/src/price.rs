@@ -0,0 +1,248 @@
+multiversx_sc::imports!();
+
+use crate::{
+    config::{self, PRICE_PRECISION},
+    errors::*,
+    events,
+    observation::{self, OracleObservation},
+    registry,
+};
+
+#[derive(Clone, Copy)]
+pub enum PriceKind {
+    Twap,
+    Median,
+}
+
+/// Prices are served over the last `window_periods` periods, from the stored observations,
+/// and fail if the last observation of a pair used is older than `max_staleness_rounds`.
+/// Tokens without a registered pair together are priced through the common token.
+#[multiversx_sc::module]
+pub trait PriceModule:
+    config::ConfigModule
+    + registry::RegistryModule
+    + observation::ObservationModule
+    + events::EventsModule
+{
+    #[view(getTwapPrice)]
+    fn get_twap_price(
+        &self,
+        input_payment: EsdtTokenPayment,
+        token_out: TokenIdentifier,
+        window_periods: u64,
+        max_staleness_rounds: u64,
+    ) -> EsdtTokenPayment {
+        self.get_price_output(
+            input_payment,
+            token_out,
+            window_periods,
+            max_staleness_rounds,
+            PriceKind::Twap,
+        )
+    }
+
+    /// Median of the average prices of each period in the window.
+    #[view(getMedianPrice)]
+    fn get_median_price(
+        &self,
+        input_payment: EsdtTokenPayment,
+        token_out: TokenIdentifier,
+        window_periods: u64,
+        max_staleness_rounds: u64,
+    ) -> EsdtTokenPayment {
+        self.get_price_output(
+            input_payment,
+            token_out,
+            window_periods,
+            max_staleness_rounds,
+            PriceKind::Median,
+        )
+    }
+
+    fn get_price_output(
+        &self,
+        input_payment: EsdtTokenPayment,
+        token_out: TokenIdentifier,
+        window_periods: u64,
+        max_staleness_rounds: u64,
+        price_kind: PriceKind,
+    ) -> EsdtTokenPayment {
+        let price = self.get_price(
+            &input_payment.token_identifier,
+            &token_out,
+            window_periods,
+            max_staleness_rounds,
+            price_kind,
+        );
+        let amount_out = input_payment.amount * price / PRICE_PRECISION;
+
+        EsdtTokenPayment::new(token_out, 0, amount_out)
+    }
+
+    fn get_price(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        window_periods: u64,
+        max_staleness_rounds: u64,
+        price_kind: PriceKind,
+    ) -> BigUint {
+        require!(token_in != token_out, ERROR_INVALID_ARGS);
+
+        let pair_mapper = self.pair_by_tokens(token_in, token_out);
+        if !pair_mapper.is_empty() {
+            return self.get_pair_price(
+                &pair_mapper.get(),
+                token_in,
+                window_periods,
+                max_staleness_rounds,
+                price_kind,
+            );
+        }
+
+        let common_token_id = self.common_token_id().get();
+        let first_pair_mapper = self.pair_by_tokens(token_in, &common_token_id);
+        let second_pair_mapper = self.pair_by_tokens(&common_token_id, token_out);
+        require!(
+            !first_pair_mapper.is_empty() && !second_pair_mapper.is_empty(),
+            ERROR_NO_PRICE_ROUTE
+        );
+
+        let first_price = self.get_pair_price(
+            &first_pair_mapper.get(),
+            token_in,
+            window_periods,
+            max_staleness_rounds,
+            price_kind,
+        );
+        let second_price = self.get_pair_price(
+            &second_pair_mapper.get(),
+            &common_token_id,
+            window_periods,
+            max_staleness_rounds,
+            price_kind,
+        );
+
+        first_price * second_price / PRICE_PRECISION
+    }
+
+    fn get_pair_price(
+        &self,
+        pair_address: &ManagedAddress,
+        token_in: &TokenIdentifier,
+        window_periods: u64,
+        max_staleness_rounds: u64,
+        price_kind: PriceKind,
+    ) -> BigUint {
+        require!(
+            window_periods > 0 && window_periods < self.granularity().get(),
+            ERROR_BAD_WINDOW
+        );
+
+        let last_observation_mapper = self.last_observation(pair_address);
+        require!(
+            !last_observation_mapper.is_empty(),
+            ERROR_MISSING_OBSERVATION
+        );
+        let last_observation = last_observation_mapper.get();
+        let current_round = self.blockchain().get_block_round();
+        require!(
+            current_round - last_observation.round <= max_staleness_rounds,
+            ERROR_STALE_PRICE
+        );
+
+        let current_period = current_round / self.period_rounds().get();
+        require!(current_period >= window_periods, ERROR_MISSING_OBSERVATION);
+        let start_period = current_period - window_periods;
+        let start_observation = match self.get_observation_for_period(pair_address, start_period) {
+            Some(observation) => observation,
+            None => sc_panic!(ERROR_MISSING_OBSERVATION),
+        };
+        require!(
+            last_observation.round > start_observation.round,
+            ERROR_MISSING_OBSERVATION
+        );
+
+        let (first_token_id, _) = self.pair_tokens(pair_address).get();
+        let is_first_token = token_in == &first_token_id;
+        match price_kind {
+            PriceKind::Twap => {
+                let cumulative_price_diff = if is_first_token {
+                    last_observation.first_token_price_cumulative
+                        - start_observation.first_token_price_cumulative
+                } else {
+                    last_observation.second_token_price_cumulative
+                        - start_observation.second_token_price_cumulative
+                };
+                cumulative_price_diff / (last_observation.round - start_observation.round)
+            }
+            PriceKind::Median => self.get_median_pair_price(
+                pair_address,
+                start_period + 1,
+                current_period,
+                is_first_token,
+            ),
+        }
+    }
+
+    fn get_median_pair_price(
+        &self,
+        pair_address: &ManagedAddress,
+        first_period: u64,
+        last_period: u64,
+        is_first_token: bool,
+    ) -> BigUint {
+        let mut sorted_prices: ManagedVec<BigUint> = ManagedVec::new();
+        for period in first_period..=last_period {
+            if let Some(observation) = self.get_observation_for_period(pair_address, period) {
+                let price = if is_first_token {
+                    observation.first_token_price
+                } else {
+                    observation.second_token_price
+                };
+                self.insert_sorted(&mut sorted_prices, price);
+            }
+        }
+
+        let middle_index = sorted_prices.len() / 2;
+        if sorted_prices.len() % 2 == 1 {
+            return (*sorted_prices.get(middle_index)).clone();
+        }
+
+        (&*sorted_prices.get(middle_index - 1) + &*sorted_prices.get(middle_index)) / 2u32
+    }
+
+    fn insert_sorted(&self, sorted_prices: &mut ManagedVec<BigUint>, price: BigUint) {
+        let mut index = sorted_prices.len();
+        sorted_prices.push(price.clone());
+        while index > 0 {
+            let previous_price = (*sorted_prices.get(index - 1)).clone();
+            if previous_price <= price {
+                break;
+            }
+
+            let _ = sorted_prices.set(index, &previous_price);
+            index -= 1;
+        }
+        let _ = sorted_prices.set(index, &price);
+    }
+
+    fn get_observation_for_period(
+        &self,
+        pair_address: &ManagedAddress,
+        period: u64,
+    ) -> Option<OracleObservation<Self::Api>> {
+        let slot = period % self.granularity().get();
+        let observation_mapper = self.observations(pair_address, slot);
+        if observation_mapper.is_empty() {
+            return None;
+        }
+
+        let observation = observation_mapper.get();
+        if observation.round / self.period_rounds().get() != period {
+            return None;
+        }
+
+        Some(observation)
+    }
+}
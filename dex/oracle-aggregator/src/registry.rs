@@ -0,0 +1,23 @@
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait RegistryModule {
+    #[view(getRegisteredPairs)]
+    #[storage_mapper("registeredPairs")]
+    fn registered_pairs(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    /// Set for both orders of the tokens.
+    #[view(getPairByTokens)]
+    #[storage_mapper("pairByTokens")]
+    fn pair_by_tokens(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+    ) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("pairTokens")]
+    fn pair_tokens(
+        &self,
+        pair_address: &ManagedAddress,
+    ) -> SingleValueMapper<(TokenIdentifier, TokenIdentifier)>;
+}
@@ -0,0 +1,217 @@
+use multiversx_sc::codec::multi_types::OptionalValue;
+use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+};
+
+use oracle_aggregator::observation::ObservationModule;
+use oracle_aggregator::OracleAggregator;
+use pair::config::ConfigModule as PairConfigModule;
+use pair::Pair;
+use pausable::{PausableModule, State};
+
+pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
+pub const ORACLE_AGGREGATOR_WASM_PATH: &str = "oracle-aggregator/output/oracle-aggregator.wasm";
+pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+pub const USDC_TOKEN_ID: &[u8] = b"USDC-abcdef";
+pub const LPMEX_TOKEN_ID: &[u8] = b"LPMEX-abcdef";
+pub const LPUSDC_TOKEN_ID: &[u8] = b"LPUSDC-abcdef";
+
+pub const USER_TOTAL_TOKENS: u64 = 5_000_000_000;
+pub const ADD_LIQUIDITY_TOKENS: u64 = 1_001_000;
+pub const PERIOD_ROUNDS: u64 = 10;
+pub const GRANULARITY: u64 = 4;
+
+#[allow(dead_code)]
+pub struct OracleAggregatorSetup<OracleAggregatorObjBuilder, PairObjBuilder>
+where
+    OracleAggregatorObjBuilder: 'static + Copy + Fn() -> oracle_aggregator::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub user_address: Address,
+    pub oracle_aggregator_wrapper:
+        ContractObjWrapper<oracle_aggregator::ContractObj<DebugApi>, OracleAggregatorObjBuilder>,
+    pub mex_pair_wrapper: ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>,
+    pub usdc_pair_wrapper: ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>,
+}
+
+impl<OracleAggregatorObjBuilder, PairObjBuilder>
+    OracleAggregatorSetup<OracleAggregatorObjBuilder, PairObjBuilder>
+where
+    OracleAggregatorObjBuilder: 'static + Copy + Fn() -> oracle_aggregator::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub fn new(
+        oracle_aggregator_builder: OracleAggregatorObjBuilder,
+        pair_builder: PairObjBuilder,
+    ) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_addr = b_mock.create_user_account(&rust_zero);
+        let user_addr = b_mock.create_user_account(&rust_zero);
+
+        for token_id in [WEGLD_TOKEN_ID, MEX_TOKEN_ID, USDC_TOKEN_ID] {
+            b_mock.set_esdt_balance(&user_addr, token_id, &rust_biguint!(USER_TOTAL_TOKENS));
+        }
+
+        // 1 WEGLD = 1 MEX = 2 USDC
+        let mex_pair_wrapper = setup_pair(
+            &mut b_mock,
+            &owner_addr,
+            &user_addr,
+            pair_builder,
+            MEX_TOKEN_ID,
+            LPMEX_TOKEN_ID,
+            ADD_LIQUIDITY_TOKENS,
+        );
+        let usdc_pair_wrapper = setup_pair(
+            &mut b_mock,
+            &owner_addr,
+            &user_addr,
+            pair_builder,
+            USDC_TOKEN_ID,
+            LPUSDC_TOKEN_ID,
+            2 * ADD_LIQUIDITY_TOKENS,
+        );
+
+        let oracle_aggregator_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            oracle_aggregator_builder,
+            ORACLE_AGGREGATOR_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_addr, &oracle_aggregator_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_address!(mex_pair_wrapper.address_ref()),
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    PERIOD_ROUNDS,
+                    GRANULARITY,
+                );
+
+                sc.register_pair(managed_address!(mex_pair_wrapper.address_ref()));
+                sc.register_pair(managed_address!(usdc_pair_wrapper.address_ref()));
+            })
+            .assert_ok();
+
+        OracleAggregatorSetup {
+            b_mock,
+            owner_address: owner_addr,
+            user_address: user_addr,
+            oracle_aggregator_wrapper,
+            mex_pair_wrapper,
+            usdc_pair_wrapper,
+        }
+    }
+
+    pub fn update_observations(&mut self) {
+        self.b_mock
+            .execute_tx(
+                &self.user_address,
+                &self.oracle_aggregator_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.update_observations(MultiValueEncoded::new());
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn swap_fixed_input(
+        &mut self,
+        use_mex_pair: bool,
+        payment_token_id: &[u8],
+        payment_amount: u64,
+        desired_token_id: &[u8],
+        expected_amount: u64,
+    ) {
+        let pair_wrapper = if use_mex_pair {
+            &self.mex_pair_wrapper
+        } else {
+            &self.usdc_pair_wrapper
+        };
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                pair_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount),
+                |sc| {
+                    let ret = sc.swap_tokens_fixed_input(
+                        managed_token_id!(desired_token_id),
+                        managed_biguint!(1),
+                        OptionalValue::None,
+                    );
+                    assert_eq!(ret.amount, managed_biguint!(expected_amount));
+                },
+            )
+            .assert_ok();
+    }
+}
+
+fn setup_pair<PairObjBuilder>(
+    b_mock: &mut BlockchainStateWrapper,
+    owner_addr: &Address,
+    user_addr: &Address,
+    pair_builder: PairObjBuilder,
+    second_token_id: &[u8],
+    lp_token_id: &[u8],
+    second_token_amount: u64,
+) -> ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>
+where
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    let rust_zero = rust_biguint!(0u64);
+    let pair_wrapper =
+        b_mock.create_sc_account(&rust_zero, Some(owner_addr), pair_builder, PAIR_WASM_PATH);
+
+    b_mock
+        .execute_tx(owner_addr, &pair_wrapper, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(WEGLD_TOKEN_ID),
+                managed_token_id!(second_token_id),
+                managed_address!(owner_addr),
+                managed_address!(owner_addr),
+                300,
+                50,
+                ManagedAddress::<DebugApi>::zero(),
+                MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+            );
+
+            sc.lp_token_identifier()
+                .set(&managed_token_id!(lp_token_id));
+            sc.state().set(State::Active);
+        })
+        .assert_ok();
+
+    let lp_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+    b_mock.set_esdt_local_roles(pair_wrapper.address_ref(), lp_token_id, &lp_token_roles[..]);
+
+    let payments = vec![
+        TxTokenTransfer {
+            token_identifier: WEGLD_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(ADD_LIQUIDITY_TOKENS),
+        },
+        TxTokenTransfer {
+            token_identifier: second_token_id.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(second_token_amount),
+        },
+    ];
+    b_mock
+        .execute_esdt_multi_transfer(user_addr, &pair_wrapper, &payments, |sc| {
+            sc.add_liquidity(
+                managed_biguint!(ADD_LIQUIDITY_TOKENS),
+                managed_biguint!(second_token_amount),
+            );
+        })
+        .assert_ok();
+
+    pair_wrapper
+}
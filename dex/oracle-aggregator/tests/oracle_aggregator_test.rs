@@ -0,0 +1,212 @@
+#![allow(deprecated)]
+
+mod oracle_aggregator_setup;
+use multiversx_sc::types::EsdtTokenPayment;
+use multiversx_sc_scenario::{managed_address, managed_biguint, managed_token_id, DebugApi};
+use oracle_aggregator::{observation::ObservationModule, price::PriceModule};
+use oracle_aggregator_setup::*;
+
+fn payment(token_id: &[u8], amount: u64) -> EsdtTokenPayment<DebugApi> {
+    EsdtTokenPayment::new(managed_token_id!(token_id), 0, managed_biguint!(amount))
+}
+
+#[test]
+fn test_oracle_aggregator_setup() {
+    let _ = OracleAggregatorSetup::new(oracle_aggregator::contract_obj, pair::contract_obj);
+}
+
+#[test]
+fn test_oracle_aggregator_prices() {
+    let mut setup = OracleAggregatorSetup::new(oracle_aggregator::contract_obj, pair::contract_obj);
+
+    // the pairs record their first safe price observations
+    setup.b_mock.set_block_round(1);
+    setup.swap_fixed_input(true, MEX_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 996);
+    setup.swap_fixed_input(false, USDC_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 498);
+
+    setup.b_mock.set_block_round(10);
+    setup.update_observations();
+
+    setup.b_mock.set_block_round(15);
+    setup.swap_fixed_input(true, WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 90_842);
+
+    setup.b_mock.set_block_round(20);
+    setup.update_observations();
+    setup.b_mock.set_block_round(30);
+    setup.update_observations();
+
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let price = sc.get_twap_price(
+                payment(WEGLD_TOKEN_ID, 1_000),
+                managed_token_id!(MEX_TOKEN_ID),
+                2,
+                0,
+            );
+            assert_eq!(price.token_identifier, managed_token_id!(MEX_TOKEN_ID));
+            assert_eq!(price.amount, managed_biguint!(869));
+        })
+        .assert_ok();
+
+    // there is no observation in period 0
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let _ = sc.get_twap_price(
+                payment(WEGLD_TOKEN_ID, 1_000),
+                managed_token_id!(MEX_TOKEN_ID),
+                3,
+                0,
+            );
+        })
+        .assert_user_error("Missing price observation");
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let _ = sc.get_twap_price(
+                payment(WEGLD_TOKEN_ID, 1_000),
+                managed_token_id!(MEX_TOKEN_ID),
+                GRANULARITY,
+                0,
+            );
+        })
+        .assert_user_error("Bad window");
+
+    // only one observation per period
+    setup.b_mock.set_block_round(35);
+    setup.update_observations();
+    let mex_pair_address = setup.mex_pair_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let observation = sc
+                .last_observation(&managed_address!(&mex_pair_address))
+                .get();
+            assert_eq!(observation.round, 30);
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_round(40);
+    setup.update_observations();
+
+    // the period prices are 911, 828 and 828 MEX for 1_000 WEGLD
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let input_payment = payment(WEGLD_TOKEN_ID, 1_000);
+            let twap_price =
+                sc.get_twap_price(input_payment.clone(), managed_token_id!(MEX_TOKEN_ID), 3, 0);
+            assert_eq!(twap_price.amount, managed_biguint!(855));
+
+            let median_price =
+                sc.get_median_price(input_payment, managed_token_id!(MEX_TOKEN_ID), 3, 0);
+            assert_eq!(median_price.amount, managed_biguint!(828));
+
+            let observation = sc
+                .last_observation(&managed_address!(&mex_pair_address))
+                .get();
+            assert_eq!(
+                observation.first_token_price,
+                managed_biguint!(828_322_442_463_845_586)
+            );
+        })
+        .assert_ok();
+
+    // MEX and USDC have no pair together, so they are priced through WEGLD
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let input_payment = payment(MEX_TOKEN_ID, 1_000);
+            let twap_price = sc.get_twap_price(
+                input_payment.clone(),
+                managed_token_id!(USDC_TOKEN_ID),
+                3,
+                0,
+            );
+            assert_eq!(twap_price.amount, managed_biguint!(2_343));
+
+            let median_price =
+                sc.get_median_price(input_payment, managed_token_id!(USDC_TOKEN_ID), 3, 0);
+            assert_eq!(median_price.amount, managed_biguint!(2_416));
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_round(55);
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let _ = sc.get_twap_price(
+                payment(WEGLD_TOKEN_ID, 1_000),
+                managed_token_id!(MEX_TOKEN_ID),
+                2,
+                10,
+            );
+        })
+        .assert_user_error("Stale price");
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let price = sc.get_twap_price(
+                payment(WEGLD_TOKEN_ID, 1_000),
+                managed_token_id!(MEX_TOKEN_ID),
+                2,
+                20,
+            );
+            assert_eq!(price.amount, managed_biguint!(828));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_oracle_aggregator_pair_without_observations() {
+    let mut setup = OracleAggregatorSetup::new(oracle_aggregator::contract_obj, pair::contract_obj);
+    let mex_pair_address = setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = setup.usdc_pair_wrapper.address_ref().clone();
+
+    // only the MEX pair records price observations
+    setup.b_mock.set_block_round(1);
+    setup.swap_fixed_input(true, MEX_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 996);
+
+    setup.b_mock.set_block_round(10);
+    setup.update_observations();
+
+    // the USDC pair cannot give a safe price, so it is skipped without failing the update
+    setup.b_mock.set_block_round(20);
+    setup.update_observations();
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let mex_observation = sc
+                .last_observation(&managed_address!(&mex_pair_address))
+                .get();
+            assert_eq!(mex_observation.round, 20);
+
+            let usdc_observation = sc
+                .last_observation(&managed_address!(&usdc_pair_address))
+                .get();
+            assert_eq!(usdc_observation.round, 10);
+        })
+        .assert_ok();
+
+    // the oldest observation of the USDC pair is after its last update, so the price starts there
+    setup.b_mock.set_block_round(25);
+    setup.swap_fixed_input(false, USDC_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 498);
+
+    setup.b_mock.set_block_round(30);
+    setup.update_observations();
+    setup
+        .b_mock
+        .execute_query(&setup.oracle_aggregator_wrapper, |sc| {
+            let usdc_observation = sc
+                .last_observation(&managed_address!(&usdc_pair_address))
+                .get();
+            assert_eq!(usdc_observation.round, 30);
+            assert!(usdc_observation.first_token_price > 0u64);
+            assert_eq!(
+                usdc_observation.first_token_price_cumulative,
+                usdc_observation.first_token_price * 20u64
+            );
+        })
+        .assert_ok();
+}
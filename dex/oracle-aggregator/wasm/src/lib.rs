@@ -0,0 +1,43 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Endpoints:                           15
+// Async Callback (empty):               1
+// Total number of exported functions:  17
+
+#![no_std]
+
+// Configuration that works with rustc < 1.73.0.
+// TODO: Recommended rustc version: 1.73.0 or newer.
+#![feature(lang_items)]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    oracle_aggregator
+    (
+        init => init
+        upgrade => upgrade
+        registerPair => register_pair
+        removePair => remove_pair
+        setSafePriceViewAddress => set_safe_price_view_address
+        setCommonTokenId => set_common_token_id
+        getSafePriceViewAddress => safe_price_view_address
+        getCommonTokenId => common_token_id
+        getPeriodRounds => period_rounds
+        getGranularity => granularity
+        getRegisteredPairs => registered_pairs
+        getPairByTokens => pair_by_tokens
+        updateObservations => update_observations
+        getLastObservation => last_observation
+        getTwapPrice => get_twap_price
+        getMedianPrice => get_median_price
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
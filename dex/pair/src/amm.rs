@@ -2,9 +2,13 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use crate::config::MAX_PERCENTAGE;
+use crate::errors::ERROR_STABLE_SWAP_NOT_CONVERGED;
 
 use super::config;
 
+const NR_POOL_TOKENS: u64 = 2;
+const STABLE_SWAP_MAX_ITERATIONS: usize = 255;
+
 #[multiversx_sc::module]
 pub trait AmmModule:
     config::ConfigModule
@@ -12,11 +16,27 @@ pub trait AmmModule:
     + permissions_module::PermissionsModule
     + pausable::PausableModule
 {
+    /// For stable swap pairs, the invariant D is used instead of x * y.
+    /// Concentrated liquidity pairs keep the invariant separately for each price range,
+    /// during the swap steps, so there is no global constant to check.
     fn calculate_k_constant(
         &self,
         first_token_amount: &BigUint,
         second_token_amount: &BigUint,
     ) -> BigUint {
+        if self.is_concentrated_liquidity() {
+            return BigUint::zero();
+        }
+
+        let amplification_coefficient = self.amplification_coefficient().get();
+        if amplification_coefficient > 0 {
+            return self.compute_stable_swap_invariant(
+                first_token_amount,
+                second_token_amount,
+                amplification_coefficient,
+            );
+        }
+
         first_token_amount * second_token_amount
     }
 
@@ -35,6 +55,16 @@ pub trait AmmModule:
         reserve_in: &BigUint,
         reserve_out: &BigUint,
     ) -> BigUint {
+        let amplification_coefficient = self.amplification_coefficient().get();
+        if amplification_coefficient > 0 {
+            return self.get_stable_swap_amount_out(
+                amount_in,
+                reserve_in,
+                reserve_out,
+                amplification_coefficient,
+            );
+        }
+
         let numerator = amount_in * reserve_out;
         let denominator = reserve_in + amount_in;
 
@@ -47,7 +77,18 @@ pub trait AmmModule:
         reserve_in: &BigUint,
         reserve_out: &BigUint,
     ) -> BigUint {
-        let amount_in_with_fee = amount_in * (MAX_PERCENTAGE - self.total_fee_percent().get());
+        let amount_in_with_fee = amount_in * (MAX_PERCENTAGE - self.get_total_fee_percent());
+
+        let amplification_coefficient = self.amplification_coefficient().get();
+        if amplification_coefficient > 0 {
+            return self.get_stable_swap_amount_out(
+                &(amount_in_with_fee / MAX_PERCENTAGE),
+                reserve_in,
+                reserve_out,
+                amplification_coefficient,
+            );
+        }
+
         let numerator = &amount_in_with_fee * reserve_out;
         let denominator = (reserve_in * MAX_PERCENTAGE) + amount_in_with_fee;
 
@@ -60,14 +101,166 @@ pub trait AmmModule:
         reserve_in: &BigUint,
         reserve_out: &BigUint,
     ) -> BigUint {
+        let amplification_coefficient = self.amplification_coefficient().get();
+        if amplification_coefficient > 0 {
+            let amount_in_no_fee = self.get_stable_swap_amount_in(
+                amount_out,
+                reserve_in,
+                reserve_out,
+                amplification_coefficient,
+            );
+            let numerator = amount_in_no_fee * MAX_PERCENTAGE;
+            let denominator = MAX_PERCENTAGE - self.get_total_fee_percent();
+
+            return (numerator / denominator) + 1u64;
+        }
+
         let numerator = reserve_in * amount_out * MAX_PERCENTAGE;
         let denominator =
-            (reserve_out - amount_out) * (MAX_PERCENTAGE - self.total_fee_percent().get());
+            (reserve_out - amount_out) * (MAX_PERCENTAGE - self.get_total_fee_percent());
 
         (numerator / denominator) + 1u64
     }
 
+    /// Part of `amount_in` to swap, so that the rest and the swap output match
+    /// the reserves ratio after the swap. Exact for the x * y curve, with the total fee
+    /// applied on input. Not valid for stable swap pairs.
+    fn get_optimal_swap_amount(&self, amount_in: &BigUint, reserve_in: &BigUint) -> BigUint {
+        let total_fee_percent = self.get_total_fee_percent();
+        let fee_multiplier = MAX_PERCENTAGE - total_fee_percent;
+        let reserve_multiplier = 2 * MAX_PERCENTAGE - total_fee_percent;
+
+        let scaled_reserve = reserve_in * reserve_multiplier;
+        let discriminant = &scaled_reserve * &scaled_reserve
+            + reserve_in * amount_in * (4 * fee_multiplier * MAX_PERCENTAGE);
+
+        (discriminant.sqrt() - scaled_reserve) / (2 * fee_multiplier)
+    }
+
     fn get_special_fee_from_input(&self, amount_in: &BigUint) -> BigUint {
         amount_in * self.special_fee_percent().get() / MAX_PERCENTAGE
     }
+
+    /// Output amount for the stable swap curve, rounded down in favor of the pool.
+    fn get_stable_swap_amount_out(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        amplification_coefficient: u64,
+    ) -> BigUint {
+        let invariant =
+            self.compute_stable_swap_invariant(reserve_in, reserve_out, amplification_coefficient);
+        let new_reserve_in = reserve_in + amount_in;
+        let new_reserve_out = self.compute_stable_swap_other_reserve(
+            &new_reserve_in,
+            &invariant,
+            amplification_coefficient,
+        );
+
+        let min_reserve_out = new_reserve_out + 1u64;
+        if reserve_out <= &min_reserve_out {
+            return BigUint::zero();
+        }
+
+        reserve_out - &min_reserve_out
+    }
+
+    /// Input amount for the stable swap curve, rounded up in favor of the pool.
+    fn get_stable_swap_amount_in(
+        &self,
+        amount_out: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        amplification_coefficient: u64,
+    ) -> BigUint {
+        let invariant =
+            self.compute_stable_swap_invariant(reserve_in, reserve_out, amplification_coefficient);
+        let new_reserve_out = reserve_out - amount_out;
+        let new_reserve_in = self.compute_stable_swap_other_reserve(
+            &new_reserve_out,
+            &invariant,
+            amplification_coefficient,
+        );
+
+        if &new_reserve_in <= reserve_in {
+            return BigUint::from(1u64);
+        }
+
+        new_reserve_in - reserve_in + 1u64
+    }
+
+    /// Solves A * n^n * (x + y) + D = A * D * n^n + D^(n+1) / (n^n * x * y) for D,
+    /// using Newton's method.
+    fn compute_stable_swap_invariant(
+        &self,
+        first_reserve: &BigUint,
+        second_reserve: &BigUint,
+        amplification_coefficient: u64,
+    ) -> BigUint {
+        if first_reserve == &0u64 || second_reserve == &0u64 {
+            return BigUint::zero();
+        }
+
+        let reserves_sum = first_reserve + second_reserve;
+        let ann = BigUint::from(amplification_coefficient * NR_POOL_TOKENS);
+        let mut invariant = reserves_sum.clone();
+        for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+            let mut invariant_product = invariant.clone();
+            invariant_product = invariant_product * &invariant / (first_reserve * NR_POOL_TOKENS);
+            invariant_product = invariant_product * &invariant / (second_reserve * NR_POOL_TOKENS);
+
+            let previous_invariant = invariant.clone();
+            let numerator =
+                (&ann * &reserves_sum + &invariant_product * NR_POOL_TOKENS) * &invariant;
+            let denominator = (&ann - 1u64) * &invariant + invariant_product * (NR_POOL_TOKENS + 1);
+            invariant = numerator / denominator;
+
+            if self.differ_by_at_most_one(&invariant, &previous_invariant) {
+                return invariant;
+            }
+        }
+
+        sc_panic!(ERROR_STABLE_SWAP_NOT_CONVERGED);
+    }
+
+    /// Given the new reserve of one token, computes the reserve of the other token
+    /// that keeps the invariant D unchanged.
+    fn compute_stable_swap_other_reserve(
+        &self,
+        new_reserve: &BigUint,
+        invariant: &BigUint,
+        amplification_coefficient: u64,
+    ) -> BigUint {
+        require!(new_reserve > &0u64, ERROR_STABLE_SWAP_NOT_CONVERGED);
+
+        let ann = BigUint::from(amplification_coefficient * NR_POOL_TOKENS);
+        let mut c = invariant * invariant / (new_reserve * NR_POOL_TOKENS);
+        c = c * invariant / (&ann * NR_POOL_TOKENS);
+        let b = new_reserve + &(invariant / &ann);
+
+        let mut other_reserve = invariant.clone();
+        for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+            let previous_other_reserve = other_reserve.clone();
+            let numerator = &other_reserve * &other_reserve + &c;
+            let denominator = &other_reserve * 2u64 + &b;
+            require!(&denominator > invariant, ERROR_STABLE_SWAP_NOT_CONVERGED);
+
+            other_reserve = numerator / (denominator - invariant);
+
+            if self.differ_by_at_most_one(&other_reserve, &previous_other_reserve) {
+                return other_reserve;
+            }
+        }
+
+        sc_panic!(ERROR_STABLE_SWAP_NOT_CONVERGED);
+    }
+
+    fn differ_by_at_most_one(&self, first: &BigUint, second: &BigUint) -> bool {
+        if first > second {
+            first - second <= 1u64
+        } else {
+            second - first <= 1u64
+        }
+    }
 }
@@ -0,0 +1,143 @@
+multiversx_sc::imports!();
+
+use crate::config;
+use crate::errors::*;
+
+use super::math;
+use super::ticks;
+
+/// Farms only accept fungible farming tokens, while each position token nonce has its own
+/// price range. To farm with a position, it is deposited in the pair, which mints the same
+/// amount of a fungible farming token. Any regular farm can use this token as farming token.
+///
+/// Only positions with the farming price range set by the owner can be wrapped, so the same
+/// liquidity always stands for the same token amounts, and farming weights cannot be inflated
+/// with narrow ranges.
+///
+/// The position keeps accruing fees while deposited. Only the address that deposited it can
+/// get it back, by returning the same amount of farming tokens.
+///
+/// The farm should be deployed without a pair address and with the local burn role for the
+/// farming token, so exit penalties burn the farming tokens directly. The matching liquidity
+/// remains locked in the pair.
+#[multiversx_sc::module]
+pub trait ConcentratedLiquidityFarmingModule:
+    super::ConcentratedLiquidityModule
+    + config::ConfigModule
+    + math::ConcentratedLiquidityMathModule
+    + ticks::ConcentratedLiquidityTicksModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    /// The farming token is a fungible ESDT, with the local mint and burn roles set for the pair.
+    #[endpoint(setPositionFarmingTokenIdentifier)]
+    fn set_position_farming_token_identifier(&self, token_identifier: TokenIdentifier) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            token_identifier.is_valid_esdt_identifier(),
+            ERROR_NOT_AN_ESDT
+        );
+        require!(
+            token_identifier != self.first_token_id().get()
+                && token_identifier != self.second_token_id().get(),
+            ERROR_LP_TOKEN_SAME_AS_POOL_TOKENS
+        );
+
+        self.position_farming_token_id().set(&token_identifier);
+    }
+
+    /// Can only be set once, as farming tokens of different ranges would not be comparable.
+    #[endpoint(setPositionFarmingRange)]
+    fn set_position_farming_range(&self, lower_tick: i32, upper_tick: i32) {
+        self.require_caller_has_owner_permissions();
+        self.require_concentrated_liquidity();
+        require!(
+            self.position_farming_range().is_empty(),
+            ERROR_POSITION_FARMING_RANGE_ALREADY_SET
+        );
+        self.require_valid_ticks(lower_tick, upper_tick, self.tick_spacing().get());
+
+        self.position_farming_range().set((lower_tick, upper_tick));
+    }
+
+    #[payable("*")]
+    #[endpoint(wrapPositionForFarming)]
+    fn wrap_position_for_farming(&self) -> EsdtTokenPayment {
+        self.require_concentrated_liquidity();
+
+        let caller = self.blockchain().get_caller();
+        let farming_token_id = self.position_farming_token_id().get();
+        require!(
+            farming_token_id.is_valid_esdt_identifier(),
+            ERROR_POSITION_FARMING_TOKEN_NOT_SET
+        );
+        let farming_range_mapper = self.position_farming_range();
+        require!(
+            !farming_range_mapper.is_empty(),
+            ERROR_POSITION_FARMING_RANGE_NOT_SET
+        );
+        let (farming_lower_tick, farming_upper_tick) = farming_range_mapper.get();
+
+        let mut farming_token_amount = BigUint::zero();
+        for payment in self.call_value().all_esdt_transfers().iter() {
+            let attributes = self.get_position_attributes(&payment);
+            require!(
+                attributes.lower_tick == farming_lower_tick
+                    && attributes.upper_tick == farming_upper_tick,
+                ERROR_POSITION_NOT_IN_FARMING_RANGE
+            );
+
+            self.wrapped_positions(&caller, payment.token_nonce)
+                .update(|wrapped_amount| *wrapped_amount += &payment.amount);
+            farming_token_amount += payment.amount;
+        }
+
+        self.send()
+            .esdt_local_mint(&farming_token_id, 0, &farming_token_amount);
+        self.send()
+            .direct_esdt(&caller, &farming_token_id, 0, &farming_token_amount);
+
+        EsdtTokenPayment::new(farming_token_id, 0, farming_token_amount)
+    }
+
+    #[payable("*")]
+    #[endpoint(unwrapPositionFromFarming)]
+    fn unwrap_position_from_farming(&self, position_nonce: u64) -> EsdtTokenPayment {
+        let caller = self.blockchain().get_caller();
+        let (farming_token_id, amount) = self.call_value().single_fungible_esdt();
+        require!(
+            farming_token_id == self.position_farming_token_id().get() && amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+
+        let wrapped_positions_mapper = self.wrapped_positions(&caller, position_nonce);
+        let wrapped_amount = wrapped_positions_mapper.get();
+        require!(wrapped_amount >= amount, ERROR_NOT_ENOUGH_WRAPPED_POSITION);
+        wrapped_positions_mapper.set(&wrapped_amount - &amount);
+
+        self.send().esdt_local_burn(&farming_token_id, 0, &amount);
+
+        let position_token_id = self.position_token_id().get();
+        self.send()
+            .direct_esdt(&caller, &position_token_id, position_nonce, &amount);
+
+        EsdtTokenPayment::new(position_token_id, position_nonce, amount)
+    }
+
+    #[view(getPositionFarmingTokenIdentifier)]
+    #[storage_mapper("cl_position_farming_token_id")]
+    fn position_farming_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getPositionFarmingRange)]
+    #[storage_mapper("cl_position_farming_range")]
+    fn position_farming_range(&self) -> SingleValueMapper<(i32, i32)>;
+
+    #[view(getWrappedPositionAmount)]
+    #[storage_mapper("cl_wrapped_positions")]
+    fn wrapped_positions(
+        &self,
+        user: &ManagedAddress,
+        position_nonce: u64,
+    ) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,286 @@
+multiversx_sc::imports!();
+
+use crate::errors::{ERROR_INVALID_TICK, ERROR_NOT_ENOUGH_RESERVE};
+
+/// Square root prices are fixed point numbers with 18 decimals.
+pub const SQRT_PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+/// sqrt(1.0001), the square root price ratio between two consecutive ticks.
+const SQRT_TICK_BASE: u64 = 1_000_049_998_750_062_496;
+
+pub const MIN_TICK: i32 = -400_000;
+pub const MAX_TICK: i32 = 400_000;
+
+pub struct SwapStep<M: ManagedTypeApi> {
+    pub sqrt_price_next: BigUint<M>,
+    pub amount_in: BigUint<M>,
+    pub amount_out: BigUint<M>,
+}
+
+/// The price of the first token in terms of the second one is 1.0001 ^ tick.
+/// Amounts between two square root prices A < B, for a liquidity L, are:
+///
+/// first token: L * (B - A) / (A * B)
+///
+/// second token: L * (B - A)
+#[multiversx_sc::module]
+pub trait ConcentratedLiquidityMathModule {
+    fn get_sqrt_price_at_tick(&self, tick: i32) -> BigUint {
+        require!((MIN_TICK..=MAX_TICK).contains(&tick), ERROR_INVALID_TICK);
+
+        let mut exponent = tick.unsigned_abs();
+        let mut base = BigUint::from(SQRT_TICK_BASE);
+        let mut result = BigUint::from(SQRT_PRICE_PRECISION);
+        while exponent != 0 {
+            if exponent % 2 != 0 {
+                result = self.mul_sqrt_price(&result, &base);
+            }
+            exponent /= 2;
+            if exponent != 0 {
+                base = self.mul_sqrt_price(&base, &base);
+            }
+        }
+
+        if tick < 0 {
+            BigUint::from(SQRT_PRICE_PRECISION) * SQRT_PRICE_PRECISION / result
+        } else {
+            result
+        }
+    }
+
+    /// The greatest tick in the [lower_tick, upper_tick] interval,
+    /// whose square root price is lower than or equal to the given one.
+    fn get_tick_at_sqrt_price(
+        &self,
+        sqrt_price: &BigUint,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> i32 {
+        let mut low = lower_tick;
+        let mut high = upper_tick;
+        while low < high {
+            let middle = high - (high - low) / 2;
+            if &self.get_sqrt_price_at_tick(middle) <= sqrt_price {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+
+        low
+    }
+
+    fn mul_sqrt_price(&self, first: &BigUint, second: &BigUint) -> BigUint {
+        (first * second + SQRT_PRICE_PRECISION / 2) / SQRT_PRICE_PRECISION
+    }
+
+    fn get_first_token_delta(
+        &self,
+        sqrt_price_a: &BigUint,
+        sqrt_price_b: &BigUint,
+        liquidity: &BigUint,
+        round_up: bool,
+    ) -> BigUint {
+        let (lower, upper) = self.sort_sqrt_prices(sqrt_price_a, sqrt_price_b);
+        let numerator = liquidity * SQRT_PRICE_PRECISION * &(upper - lower);
+        if round_up {
+            let partial = self.div_round_up(&numerator, upper);
+            self.div_round_up(&partial, lower)
+        } else {
+            numerator / upper / lower
+        }
+    }
+
+    fn get_second_token_delta(
+        &self,
+        sqrt_price_a: &BigUint,
+        sqrt_price_b: &BigUint,
+        liquidity: &BigUint,
+        round_up: bool,
+    ) -> BigUint {
+        let (lower, upper) = self.sort_sqrt_prices(sqrt_price_a, sqrt_price_b);
+        let numerator = liquidity * &(upper - lower);
+        if round_up {
+            self.div_round_up(&numerator, &BigUint::from(SQRT_PRICE_PRECISION))
+        } else {
+            numerator / SQRT_PRICE_PRECISION
+        }
+    }
+
+    /// Liquidity that can be provided with the given amounts in the [A, B] price range,
+    /// at the current square root price P.
+    fn get_liquidity_for_amounts(
+        &self,
+        sqrt_price: &BigUint,
+        sqrt_price_a: &BigUint,
+        sqrt_price_b: &BigUint,
+        first_token_amount: &BigUint,
+        second_token_amount: &BigUint,
+    ) -> BigUint {
+        if sqrt_price <= sqrt_price_a {
+            self.get_liquidity_for_first_token(sqrt_price_a, sqrt_price_b, first_token_amount)
+        } else if sqrt_price < sqrt_price_b {
+            let first_liquidity =
+                self.get_liquidity_for_first_token(sqrt_price, sqrt_price_b, first_token_amount);
+            let second_liquidity =
+                self.get_liquidity_for_second_token(sqrt_price_a, sqrt_price, second_token_amount);
+
+            core::cmp::min(first_liquidity, second_liquidity)
+        } else {
+            self.get_liquidity_for_second_token(sqrt_price_a, sqrt_price_b, second_token_amount)
+        }
+    }
+
+    fn get_liquidity_for_first_token(
+        &self,
+        sqrt_price_a: &BigUint,
+        sqrt_price_b: &BigUint,
+        amount: &BigUint,
+    ) -> BigUint {
+        amount * sqrt_price_a * sqrt_price_b
+            / (&(sqrt_price_b - sqrt_price_a) * SQRT_PRICE_PRECISION)
+    }
+
+    fn get_liquidity_for_second_token(
+        &self,
+        sqrt_price_a: &BigUint,
+        sqrt_price_b: &BigUint,
+        amount: &BigUint,
+    ) -> BigUint {
+        amount * SQRT_PRICE_PRECISION / (sqrt_price_b - sqrt_price_a)
+    }
+
+    /// Moves the price towards the target one, using at most `amount_remaining`.
+    /// For fixed input swaps, `amount_remaining` is an input amount, otherwise an output amount.
+    /// Fees are taken before stepping through the price ranges, so no fee is computed here.
+    fn compute_swap_step(
+        &self,
+        sqrt_price_current: &BigUint,
+        sqrt_price_target: &BigUint,
+        liquidity: &BigUint,
+        amount_remaining: &BigUint,
+        exact_input: bool,
+    ) -> SwapStep<Self::Api> {
+        let first_for_second = sqrt_price_current >= sqrt_price_target;
+
+        let sqrt_price_next = if exact_input {
+            let max_amount_in = if first_for_second {
+                self.get_first_token_delta(sqrt_price_target, sqrt_price_current, liquidity, true)
+            } else {
+                self.get_second_token_delta(sqrt_price_current, sqrt_price_target, liquidity, true)
+            };
+
+            if amount_remaining >= &max_amount_in {
+                sqrt_price_target.clone()
+            } else {
+                self.get_next_sqrt_price_from_input(
+                    sqrt_price_current,
+                    liquidity,
+                    amount_remaining,
+                    first_for_second,
+                )
+            }
+        } else {
+            let max_amount_out = if first_for_second {
+                self.get_second_token_delta(sqrt_price_target, sqrt_price_current, liquidity, false)
+            } else {
+                self.get_first_token_delta(sqrt_price_current, sqrt_price_target, liquidity, false)
+            };
+
+            if amount_remaining >= &max_amount_out {
+                sqrt_price_target.clone()
+            } else {
+                self.get_next_sqrt_price_from_output(
+                    sqrt_price_current,
+                    liquidity,
+                    amount_remaining,
+                    first_for_second,
+                )
+            }
+        };
+
+        let (mut amount_in, mut amount_out) = if first_for_second {
+            (
+                self.get_first_token_delta(&sqrt_price_next, sqrt_price_current, liquidity, true),
+                self.get_second_token_delta(&sqrt_price_next, sqrt_price_current, liquidity, false),
+            )
+        } else {
+            (
+                self.get_second_token_delta(sqrt_price_current, &sqrt_price_next, liquidity, true),
+                self.get_first_token_delta(sqrt_price_current, &sqrt_price_next, liquidity, false),
+            )
+        };
+
+        let target_reached = &sqrt_price_next == sqrt_price_target;
+        if exact_input && !target_reached {
+            // whatever is left from the rounding stays in the pool
+            amount_in = amount_remaining.clone();
+        }
+        if !exact_input && &amount_out > amount_remaining {
+            amount_out = amount_remaining.clone();
+        }
+
+        SwapStep {
+            sqrt_price_next,
+            amount_in,
+            amount_out,
+        }
+    }
+
+    fn get_next_sqrt_price_from_input(
+        &self,
+        sqrt_price: &BigUint,
+        liquidity: &BigUint,
+        amount_in: &BigUint,
+        first_for_second: bool,
+    ) -> BigUint {
+        if first_for_second {
+            // L * P / (L + amount * P), rounded up
+            let numerator = liquidity * SQRT_PRICE_PRECISION;
+            let denominator = &numerator + &(amount_in * sqrt_price);
+            self.div_round_up(&(numerator * sqrt_price), &denominator)
+        } else {
+            // P + amount / L, rounded down
+            sqrt_price + &(amount_in * SQRT_PRICE_PRECISION / liquidity)
+        }
+    }
+
+    fn get_next_sqrt_price_from_output(
+        &self,
+        sqrt_price: &BigUint,
+        liquidity: &BigUint,
+        amount_out: &BigUint,
+        first_for_second: bool,
+    ) -> BigUint {
+        if first_for_second {
+            // P - amount / L, rounded down
+            let price_delta = self.div_round_up(&(amount_out * SQRT_PRICE_PRECISION), liquidity);
+            require!(sqrt_price > &price_delta, ERROR_NOT_ENOUGH_RESERVE);
+
+            sqrt_price - &price_delta
+        } else {
+            // L * P / (L - amount * P), rounded up
+            let numerator = liquidity * SQRT_PRICE_PRECISION;
+            let product = amount_out * sqrt_price;
+            require!(numerator > product, ERROR_NOT_ENOUGH_RESERVE);
+
+            let denominator = &numerator - &product;
+            self.div_round_up(&(numerator * sqrt_price), &denominator)
+        }
+    }
+
+    fn sort_sqrt_prices<'a>(
+        &self,
+        first: &'a BigUint,
+        second: &'a BigUint,
+    ) -> (&'a BigUint, &'a BigUint) {
+        if first <= second {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+
+    fn div_round_up(&self, numerator: &BigUint, denominator: &BigUint) -> BigUint {
+        (numerator + denominator - 1u64) / denominator
+    }
+}
@@ -0,0 +1,783 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub mod farming;
+pub mod math;
+pub mod ticks;
+
+use crate::config;
+use crate::config::MAX_PERCENTAGE;
+use crate::contexts::base::{StorageCache, SwapTokensOrder};
+use crate::contexts::swap::SwapContext;
+use crate::errors::*;
+use math::{MAX_TICK, MIN_TICK, SQRT_PRICE_PRECISION};
+use pausable::State;
+
+pub const MAX_TICK_SPACING: u32 = 1_000;
+
+pub type AddConcentratedLiquidityResultType<BigUint> =
+    MultiValue3<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
+
+pub type RemoveConcentratedLiquidityResultType<BigUint> =
+    MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
+
+pub type CollectConcentratedFeesResultType<BigUint> =
+    MultiValue3<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
+
+/// The amount of a position token is its liquidity.
+/// Fees are accrued per unit of liquidity, so any amount of the same nonce
+/// can be removed, or have its fees collected, independently.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct PositionAttributes<M: ManagedTypeApi> {
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    pub fee_growth_inside_first: BigInt<M>,
+    pub fee_growth_inside_second: BigInt<M>,
+}
+
+/// Concentrated liquidity mode for the pair.
+///
+/// Instead of receiving LP tokens, liquidity providers choose a price range and receive
+/// a position token. The liquidity of a position is only used for swaps while the price
+/// is inside its range, and only accrues fees during that time.
+///
+/// The special fee is handled exactly as for regular pairs (fees collector cut and
+/// fee destinations), while the rest of the swap fee is accrued by the positions.
+#[multiversx_sc::module]
+pub trait ConcentratedLiquidityModule:
+    config::ConfigModule
+    + math::ConcentratedLiquidityMathModule
+    + ticks::ConcentratedLiquidityTicksModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + crate::events::EventsModule
+{
+    /// Turns the pair into a concentrated liquidity pair, starting at the price 1.0001 ^ initial_tick.
+    /// Can only be called once, before any liquidity is added.
+    #[endpoint(enableConcentratedLiquidity)]
+    fn enable_concentrated_liquidity(&self, tick_spacing: u32, initial_tick: i32) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            self.lp_token_supply().get() == 0 && !self.is_concentrated_liquidity(),
+            ERROR_CONCENTRATED_LIQUIDITY_LOCKED
+        );
+        require!(
+            self.amplification_coefficient().get() == 0,
+            ERROR_STABLE_SWAP_CONCENTRATED_LIQUIDITY
+        );
+        require!(
+            tick_spacing > 0 && tick_spacing <= MAX_TICK_SPACING,
+            ERROR_INVALID_TICK_SPACING
+        );
+
+        let sqrt_price = self.get_sqrt_price_at_tick(initial_tick);
+        self.tick_spacing().set(tick_spacing);
+        self.current_tick().set(initial_tick);
+        self.current_sqrt_price().set(sqrt_price);
+    }
+
+    /// The position token is a Meta ESDT, with the NFTCreate, NFTAddQuantity and NFTBurn roles
+    /// set for the pair.
+    #[endpoint(setPositionTokenIdentifier)]
+    fn set_position_token_identifier(&self, token_identifier: TokenIdentifier) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            token_identifier.is_valid_esdt_identifier(),
+            ERROR_NOT_AN_ESDT
+        );
+        require!(
+            token_identifier != self.first_token_id().get()
+                && token_identifier != self.second_token_id().get(),
+            ERROR_LP_TOKEN_SAME_AS_POOL_TOKENS
+        );
+
+        self.position_token_id().set(&token_identifier);
+    }
+
+    /// Expects a payment of one or both pool tokens. Only the amounts needed for the
+    /// resulting liquidity are kept, the rest is returned.
+    /// Positions outside of the current price only need one of the tokens.
+    #[payable("*")]
+    #[endpoint(addConcentratedLiquidity)]
+    fn add_concentrated_liquidity(
+        &self,
+        lower_tick: i32,
+        upper_tick: i32,
+        first_token_amount_min: BigUint,
+        second_token_amount_min: BigUint,
+    ) -> AddConcentratedLiquidityResultType<Self::Api> {
+        self.require_concentrated_liquidity();
+        let tick_spacing = self.tick_spacing().get();
+        self.require_valid_ticks(lower_tick, upper_tick, tick_spacing);
+
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let position_token_id = self.position_token_id().get();
+        require!(
+            storage_cache.contract_state == State::Active
+                || storage_cache.contract_state == State::PartialActive,
+            ERROR_NOT_ACTIVE
+        );
+        require!(
+            position_token_id.is_valid_esdt_identifier(),
+            ERROR_POSITION_TOKEN_NOT_SET
+        );
+
+        let mut first_token_paid = BigUint::zero();
+        let mut second_token_paid = BigUint::zero();
+        for payment in self.call_value().all_esdt_transfers().iter() {
+            require!(payment.token_nonce == 0, ERROR_BAD_PAYMENT_TOKENS);
+            if payment.token_identifier == storage_cache.first_token_id && first_token_paid == 0 {
+                first_token_paid = payment.amount;
+            } else if payment.token_identifier == storage_cache.second_token_id
+                && second_token_paid == 0
+            {
+                second_token_paid = payment.amount;
+            } else {
+                sc_panic!(ERROR_BAD_PAYMENT_TOKENS);
+            }
+        }
+
+        let liquidity = self.get_liquidity_for_amounts(
+            &self.current_sqrt_price().get(),
+            &self.get_sqrt_price_at_tick(lower_tick),
+            &self.get_sqrt_price_at_tick(upper_tick),
+            &first_token_paid,
+            &second_token_paid,
+        );
+        require!(liquidity > 0, ERROR_INSUFFICIENT_LIQUIDITY);
+
+        let (first_token_amount, second_token_amount) =
+            self.get_amounts_for_liquidity(lower_tick, upper_tick, &liquidity, true);
+        require!(
+            first_token_amount <= first_token_paid && second_token_amount <= second_token_paid,
+            ERROR_OPTIMAL_GRATER_THAN_PAID
+        );
+        require!(
+            first_token_amount >= first_token_amount_min,
+            ERROR_INSUFFICIENT_FIRST_TOKEN
+        );
+        require!(
+            second_token_amount >= second_token_amount_min,
+            ERROR_INSUFFICIENT_SECOND_TOKEN
+        );
+
+        self.update_position_liquidity(
+            lower_tick,
+            upper_tick,
+            &BigInt::from(liquidity.clone()),
+            tick_spacing,
+        );
+
+        let (fee_growth_inside_first, fee_growth_inside_second) =
+            self.get_fee_growth_inside(lower_tick, upper_tick);
+        let attributes = PositionAttributes {
+            lower_tick,
+            upper_tick,
+            fee_growth_inside_first,
+            fee_growth_inside_second,
+        };
+        let position_nonce =
+            self.send()
+                .esdt_nft_create_compact(&position_token_id, &liquidity, &attributes);
+
+        storage_cache.first_token_reserve += &first_token_amount;
+        storage_cache.second_token_reserve += &second_token_amount;
+
+        let position_payment = EsdtTokenPayment::new(position_token_id, position_nonce, liquidity);
+        let first_refund = EsdtTokenPayment::new(
+            storage_cache.first_token_id.clone(),
+            0,
+            &first_token_paid - &first_token_amount,
+        );
+        let second_refund = EsdtTokenPayment::new(
+            storage_cache.second_token_id.clone(),
+            0,
+            &second_token_paid - &second_token_amount,
+        );
+
+        let mut output_payments = ManagedVec::new();
+        output_payments.push(position_payment.clone());
+        output_payments.push(first_refund.clone());
+        output_payments.push(second_refund.clone());
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        self.emit_position_event(
+            &caller,
+            true,
+            &attributes,
+            &position_payment.amount,
+            &first_token_amount,
+            &second_token_amount,
+        );
+
+        (position_payment, first_refund, second_refund).into()
+    }
+
+    /// Burns the position tokens and returns the underlying tokens, together with the accrued fees.
+    #[payable("*")]
+    #[endpoint(removeConcentratedLiquidity)]
+    fn remove_concentrated_liquidity(
+        &self,
+        first_token_amount_min: BigUint,
+        second_token_amount_min: BigUint,
+    ) -> RemoveConcentratedLiquidityResultType<Self::Api> {
+        self.require_concentrated_liquidity();
+
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        require!(
+            storage_cache.contract_state == State::Active
+                || storage_cache.contract_state == State::PartialActive,
+            ERROR_NOT_ACTIVE
+        );
+
+        let attributes = self.get_position_attributes(&payment);
+        let liquidity = payment.amount.clone();
+        let (first_fees, second_fees) = self.get_position_fees(&attributes, &liquidity);
+        let (first_amount_removed, second_amount_removed) = self.get_amounts_for_liquidity(
+            attributes.lower_tick,
+            attributes.upper_tick,
+            &liquidity,
+            false,
+        );
+
+        self.update_position_liquidity(
+            attributes.lower_tick,
+            attributes.upper_tick,
+            &-BigInt::from(liquidity.clone()),
+            self.tick_spacing().get(),
+        );
+        self.send()
+            .esdt_local_burn(&payment.token_identifier, payment.token_nonce, &liquidity);
+
+        let first_token_amount = first_amount_removed + first_fees;
+        let second_token_amount = second_amount_removed + second_fees;
+        require!(
+            first_token_amount >= first_token_amount_min
+                && second_token_amount >= second_token_amount_min,
+            ERROR_SLIPPAGE_ON_REMOVE
+        );
+
+        storage_cache.first_token_reserve -= &first_token_amount;
+        storage_cache.second_token_reserve -= &second_token_amount;
+
+        let first_payment =
+            EsdtTokenPayment::new(storage_cache.first_token_id.clone(), 0, first_token_amount);
+        let second_payment = EsdtTokenPayment::new(
+            storage_cache.second_token_id.clone(),
+            0,
+            second_token_amount,
+        );
+
+        let mut output_payments = ManagedVec::new();
+        output_payments.push(first_payment.clone());
+        output_payments.push(second_payment.clone());
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        self.emit_position_event(
+            &caller,
+            false,
+            &attributes,
+            &liquidity,
+            &first_payment.amount,
+            &second_payment.amount,
+        );
+
+        (first_payment, second_payment).into()
+    }
+
+    /// Sends the fees accrued by the position, and returns a new position token
+    /// with the same liquidity and price range.
+    #[payable("*")]
+    #[endpoint(collectConcentratedFees)]
+    fn collect_concentrated_fees(&self) -> CollectConcentratedFeesResultType<Self::Api> {
+        self.require_concentrated_liquidity();
+
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+
+        let mut attributes = self.get_position_attributes(&payment);
+        let (first_fees, second_fees) = self.get_position_fees(&attributes, &payment.amount);
+
+        let (fee_growth_inside_first, fee_growth_inside_second) =
+            self.get_fee_growth_inside(attributes.lower_tick, attributes.upper_tick);
+        attributes.fee_growth_inside_first = fee_growth_inside_first;
+        attributes.fee_growth_inside_second = fee_growth_inside_second;
+
+        self.send().esdt_local_burn(
+            &payment.token_identifier,
+            payment.token_nonce,
+            &payment.amount,
+        );
+        let new_position_nonce = self.send().esdt_nft_create_compact(
+            &payment.token_identifier,
+            &payment.amount,
+            &attributes,
+        );
+
+        storage_cache.first_token_reserve -= &first_fees;
+        storage_cache.second_token_reserve -= &second_fees;
+
+        let position_payment =
+            EsdtTokenPayment::new(payment.token_identifier, new_position_nonce, payment.amount);
+        let first_payment =
+            EsdtTokenPayment::new(storage_cache.first_token_id.clone(), 0, first_fees);
+        let second_payment =
+            EsdtTokenPayment::new(storage_cache.second_token_id.clone(), 0, second_fees);
+
+        let mut output_payments = ManagedVec::new();
+        output_payments.push(position_payment.clone());
+        output_payments.push(first_payment.clone());
+        output_payments.push(second_payment.clone());
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        (position_payment, first_payment, second_payment).into()
+    }
+
+    #[view(getPositionFees)]
+    fn get_position_fees_view(
+        &self,
+        position_nonce: u64,
+        liquidity: BigUint,
+    ) -> MultiValue2<BigUint, BigUint> {
+        let attributes: PositionAttributes<Self::Api> = self
+            .blockchain()
+            .get_token_attributes(&self.position_token_id().get(), position_nonce);
+
+        self.get_position_fees(&attributes, &liquidity).into()
+    }
+
+    fn perform_concentrated_swap_fixed_input(
+        &self,
+        context: &mut SwapContext<Self::Api>,
+        storage_cache: &mut StorageCache<Self>,
+        fee_enabled: bool,
+    ) {
+        context.final_input_amount = context.input_token_amount.clone();
+
+        let special_fee_percent = self.get_concentrated_special_fee_percent(fee_enabled);
+        let (_, amount_out) = self.concentrated_swap(
+            context.swap_tokens_order,
+            &context.input_token_amount,
+            true,
+            special_fee_percent,
+            true,
+        );
+        require!(
+            amount_out >= context.output_token_amount,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+        require!(
+            *storage_cache.get_reserve_out(context.swap_tokens_order) > amount_out,
+            ERROR_NOT_ENOUGH_RESERVE
+        );
+        require!(amount_out != 0u64, ERROR_ZERO_AMOUNT);
+
+        context.final_output_amount = amount_out;
+        context.fee_amount = &context.final_input_amount * special_fee_percent / MAX_PERCENTAGE;
+
+        *storage_cache.get_mut_reserve_in(context.swap_tokens_order) +=
+            &context.final_input_amount - &context.fee_amount;
+        *storage_cache.get_mut_reserve_out(context.swap_tokens_order) -=
+            &context.final_output_amount;
+    }
+
+    fn perform_concentrated_swap_fixed_output(
+        &self,
+        context: &mut SwapContext<Self::Api>,
+        storage_cache: &mut StorageCache<Self>,
+        fee_enabled: bool,
+    ) {
+        context.final_output_amount = context.output_token_amount.clone();
+
+        let special_fee_percent = self.get_concentrated_special_fee_percent(fee_enabled);
+        let (amount_in, _) = self.concentrated_swap(
+            context.swap_tokens_order,
+            &context.output_token_amount,
+            false,
+            special_fee_percent,
+            true,
+        );
+        require!(
+            amount_in <= context.input_token_amount,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+        require!(amount_in != 0u64, ERROR_ZERO_AMOUNT);
+
+        context.final_input_amount = amount_in;
+        context.fee_amount = &context.final_input_amount * special_fee_percent / MAX_PERCENTAGE;
+
+        *storage_cache.get_mut_reserve_in(context.swap_tokens_order) +=
+            &context.final_input_amount - &context.fee_amount;
+        *storage_cache.get_mut_reserve_out(context.swap_tokens_order) -=
+            &context.final_output_amount;
+    }
+
+    fn concentrated_swap_no_fee(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        swap_tokens_order: SwapTokensOrder,
+        amount_in: &BigUint,
+    ) -> BigUint {
+        let (_, amount_out) = self
+            .try_concentrated_swap(swap_tokens_order, amount_in, true, false, 0, true)
+            .unwrap_or_else(|| sc_panic!(ERROR_NOT_ENOUGH_RESERVE));
+        require!(
+            *storage_cache.get_reserve_out(swap_tokens_order) > amount_out && amount_out != 0,
+            ERROR_ZERO_AMOUNT
+        );
+
+        *storage_cache.get_mut_reserve_in(swap_tokens_order) += amount_in;
+        *storage_cache.get_mut_reserve_out(swap_tokens_order) -= &amount_out;
+
+        amount_out
+    }
+
+    fn get_concentrated_special_fee_percent(&self, fee_enabled: bool) -> u64 {
+        if fee_enabled {
+            self.special_fee_percent().get()
+        } else {
+            0
+        }
+    }
+
+    /// Steps through the initialized ticks until the whole amount is swapped.
+    /// The total fee is taken from the input before stepping. The part of it which is not
+    /// the special fee is accrued by the liquidity active at each step.
+    ///
+    /// Returns the input amount (fee included) and the output amount.
+    /// Storage is only updated if `commit` is set, so views can use the same computation.
+    fn concentrated_swap(
+        &self,
+        swap_tokens_order: SwapTokensOrder,
+        amount: &BigUint,
+        exact_input: bool,
+        special_fee_percent: u64,
+        commit: bool,
+    ) -> (BigUint, BigUint) {
+        self.try_concentrated_swap(
+            swap_tokens_order,
+            amount,
+            exact_input,
+            true,
+            special_fee_percent,
+            commit,
+        )
+        .unwrap_or_else(|| sc_panic!(ERROR_NOT_ENOUGH_RESERVE))
+    }
+
+    /// Gives `None` if the liquidity runs out before the whole amount is swapped.
+    /// Without `take_fee`, the whole input is swapped and the positions accrue no fees.
+    fn try_concentrated_swap(
+        &self,
+        swap_tokens_order: SwapTokensOrder,
+        amount: &BigUint,
+        exact_input: bool,
+        take_fee: bool,
+        special_fee_percent: u64,
+        commit: bool,
+    ) -> Option<(BigUint, BigUint)> {
+        self.require_concentrated_liquidity();
+
+        let first_for_second = swap_tokens_order == SwapTokensOrder::PoolOrder;
+        let tick_spacing = self.tick_spacing().get();
+        let total_fee_percent = if take_fee {
+            self.get_total_fee_percent()
+        } else {
+            0
+        };
+        let lp_fee_percent = total_fee_percent - special_fee_percent;
+        let fee_denominator = MAX_PERCENTAGE - total_fee_percent;
+
+        let mut sqrt_price = self.current_sqrt_price().get();
+        let mut tick = self.current_tick().get();
+        let mut liquidity = self.active_liquidity().get();
+        let mut fee_growth_global_first = self.fee_growth_global_first().get();
+        let mut fee_growth_global_second = self.fee_growth_global_second().get();
+        let sqrt_price_limit = if first_for_second {
+            self.get_sqrt_price_at_tick(MIN_TICK)
+        } else {
+            self.get_sqrt_price_at_tick(MAX_TICK)
+        };
+
+        let mut amount_remaining = if exact_input {
+            amount * fee_denominator / MAX_PERCENTAGE
+        } else {
+            amount.clone()
+        };
+        let mut amount_in = BigUint::zero();
+        let mut amount_out = BigUint::zero();
+        while amount_remaining > 0 {
+            if sqrt_price == sqrt_price_limit {
+                return None;
+            }
+
+            let (next_tick, initialized) =
+                self.get_next_initialized_tick_within_word(tick, tick_spacing, first_for_second);
+            let sqrt_price_next_tick = self.get_sqrt_price_at_tick(next_tick);
+            let step = self.compute_swap_step(
+                &sqrt_price,
+                &sqrt_price_next_tick,
+                &liquidity,
+                &amount_remaining,
+                exact_input,
+            );
+
+            if exact_input {
+                amount_remaining -= &step.amount_in;
+            } else {
+                amount_remaining -= &step.amount_out;
+            }
+
+            if liquidity > 0 && lp_fee_percent > 0 {
+                let lp_fee = &step.amount_in * lp_fee_percent / fee_denominator;
+                let fee_growth = lp_fee * SQRT_PRICE_PRECISION / &liquidity;
+                if first_for_second {
+                    fee_growth_global_first += fee_growth;
+                } else {
+                    fee_growth_global_second += fee_growth;
+                }
+            }
+
+            amount_in += &step.amount_in;
+            amount_out += &step.amount_out;
+
+            if step.sqrt_price_next == sqrt_price_next_tick {
+                if initialized {
+                    let liquidity_net = if commit {
+                        self.cross_tick(
+                            next_tick,
+                            &fee_growth_global_first,
+                            &fee_growth_global_second,
+                        )
+                    } else {
+                        self.ticks(next_tick).get().liquidity_net
+                    };
+                    let liquidity_delta = if first_for_second {
+                        -liquidity_net
+                    } else {
+                        liquidity_net
+                    };
+
+                    liquidity = (BigInt::from(liquidity) + liquidity_delta)
+                        .into_big_uint()
+                        .unwrap_or_else(BigUint::zero);
+                }
+
+                tick = if first_for_second {
+                    next_tick - 1
+                } else {
+                    next_tick
+                };
+            } else if step.sqrt_price_next != sqrt_price {
+                tick = if first_for_second {
+                    self.get_tick_at_sqrt_price(&step.sqrt_price_next, next_tick, tick)
+                } else {
+                    self.get_tick_at_sqrt_price(&step.sqrt_price_next, tick, next_tick)
+                };
+            }
+
+            sqrt_price = step.sqrt_price_next;
+        }
+
+        if commit {
+            self.current_sqrt_price().set(sqrt_price);
+            self.current_tick().set(tick);
+            self.active_liquidity().set(liquidity);
+            self.fee_growth_global_first().set(fee_growth_global_first);
+            self.fee_growth_global_second()
+                .set(fee_growth_global_second);
+        }
+
+        if exact_input {
+            Some((amount.clone(), amount_out))
+        } else {
+            let amount_in_with_fee = self.div_round_up(
+                &(amount_in * MAX_PERCENTAGE),
+                &BigUint::from(fee_denominator),
+            );
+
+            Some((amount_in_with_fee, amount_out))
+        }
+    }
+
+    fn get_concentrated_amount_out(
+        &self,
+        swap_tokens_order: SwapTokensOrder,
+        amount_in: &BigUint,
+    ) -> BigUint {
+        let (_, amount_out) = self.concentrated_swap(
+            swap_tokens_order,
+            amount_in,
+            true,
+            self.special_fee_percent().get(),
+            false,
+        );
+
+        amount_out
+    }
+
+    fn get_concentrated_amount_in(
+        &self,
+        swap_tokens_order: SwapTokensOrder,
+        amount_out: &BigUint,
+    ) -> BigUint {
+        let (amount_in, _) = self.concentrated_swap(
+            swap_tokens_order,
+            amount_out,
+            false,
+            self.special_fee_percent().get(),
+            false,
+        );
+
+        amount_in
+    }
+
+    /// Equivalent amount at the current price, without any price impact.
+    fn get_concentrated_equivalent(
+        &self,
+        swap_tokens_order: SwapTokensOrder,
+        amount_in: &BigUint,
+    ) -> BigUint {
+        let sqrt_price = self.current_sqrt_price().get();
+        let price = &sqrt_price * &sqrt_price;
+        let precision_squared = BigUint::from(SQRT_PRICE_PRECISION) * SQRT_PRICE_PRECISION;
+
+        match swap_tokens_order {
+            SwapTokensOrder::PoolOrder => amount_in * &price / precision_squared,
+            SwapTokensOrder::ReverseOrder => amount_in * &precision_squared / price,
+        }
+    }
+
+    fn update_position_liquidity(
+        &self,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity_delta: &BigInt,
+        tick_spacing: u32,
+    ) {
+        let _ = self.update_tick(lower_tick, liquidity_delta, false, tick_spacing);
+        let _ = self.update_tick(upper_tick, liquidity_delta, true, tick_spacing);
+
+        let current_tick = self.current_tick().get();
+        if current_tick >= lower_tick && current_tick < upper_tick {
+            self.active_liquidity().update(|active_liquidity| {
+                *active_liquidity = (&BigInt::from(active_liquidity.clone()) + liquidity_delta)
+                    .into_big_uint()
+                    .unwrap_or_else(BigUint::zero);
+            });
+        }
+    }
+
+    fn get_amounts_for_liquidity(
+        &self,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity: &BigUint,
+        round_up: bool,
+    ) -> (BigUint, BigUint) {
+        let current_tick = self.current_tick().get();
+        let sqrt_price_lower = self.get_sqrt_price_at_tick(lower_tick);
+        let sqrt_price_upper = self.get_sqrt_price_at_tick(upper_tick);
+
+        if current_tick < lower_tick {
+            let first_amount = self.get_first_token_delta(
+                &sqrt_price_lower,
+                &sqrt_price_upper,
+                liquidity,
+                round_up,
+            );
+
+            (first_amount, BigUint::zero())
+        } else if current_tick < upper_tick {
+            let sqrt_price = self.current_sqrt_price().get();
+            let first_amount =
+                self.get_first_token_delta(&sqrt_price, &sqrt_price_upper, liquidity, round_up);
+            let second_amount =
+                self.get_second_token_delta(&sqrt_price_lower, &sqrt_price, liquidity, round_up);
+
+            (first_amount, second_amount)
+        } else {
+            let second_amount = self.get_second_token_delta(
+                &sqrt_price_lower,
+                &sqrt_price_upper,
+                liquidity,
+                round_up,
+            );
+
+            (BigUint::zero(), second_amount)
+        }
+    }
+
+    fn get_position_fees(
+        &self,
+        attributes: &PositionAttributes<Self::Api>,
+        liquidity: &BigUint,
+    ) -> (BigUint, BigUint) {
+        let (fee_growth_inside_first, fee_growth_inside_second) =
+            self.get_fee_growth_inside(attributes.lower_tick, attributes.upper_tick);
+
+        let first_fees = self.compute_fees_owed(
+            &fee_growth_inside_first,
+            &attributes.fee_growth_inside_first,
+            liquidity,
+        );
+        let second_fees = self.compute_fees_owed(
+            &fee_growth_inside_second,
+            &attributes.fee_growth_inside_second,
+            liquidity,
+        );
+
+        (first_fees, second_fees)
+    }
+
+    fn compute_fees_owed(
+        &self,
+        fee_growth_inside: &BigInt,
+        fee_growth_inside_last: &BigInt,
+        liquidity: &BigUint,
+    ) -> BigUint {
+        let fee_growth_delta = (fee_growth_inside - fee_growth_inside_last)
+            .into_big_uint()
+            .unwrap_or_else(BigUint::zero);
+
+        fee_growth_delta * liquidity / SQRT_PRICE_PRECISION
+    }
+
+    fn get_position_attributes(&self, payment: &EsdtTokenPayment) -> PositionAttributes<Self::Api> {
+        let position_token_id = self.position_token_id().get();
+        require!(
+            payment.token_identifier == position_token_id && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+
+        self.blockchain()
+            .get_token_attributes(&payment.token_identifier, payment.token_nonce)
+    }
+
+    fn require_valid_ticks(&self, lower_tick: i32, upper_tick: i32, tick_spacing: u32) {
+        require!(
+            lower_tick < upper_tick
+                && lower_tick >= MIN_TICK
+                && upper_tick <= MAX_TICK
+                && lower_tick % tick_spacing as i32 == 0
+                && upper_tick % tick_spacing as i32 == 0,
+            ERROR_INVALID_TICK
+        );
+    }
+
+    fn require_concentrated_liquidity(&self) {
+        require!(
+            self.is_concentrated_liquidity(),
+            ERROR_CONCENTRATED_LIQUIDITY_NOT_ENABLED
+        );
+    }
+
+    #[view(getPositionTokenIdentifier)]
+    #[storage_mapper("cl_position_token_id")]
+    fn position_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+}
@@ -0,0 +1,243 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use super::math::{MAX_TICK, MIN_TICK};
+
+const TICKS_PER_WORD: i32 = 64;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct TickInfo<M: ManagedTypeApi> {
+    pub liquidity_gross: BigUint<M>,
+    pub liquidity_net: BigInt<M>,
+    pub fee_growth_outside_first: BigUint<M>,
+    pub fee_growth_outside_second: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> Default for TickInfo<M> {
+    fn default() -> Self {
+        TickInfo {
+            liquidity_gross: BigUint::zero(),
+            liquidity_net: BigInt::zero(),
+            fee_growth_outside_first: BigUint::zero(),
+            fee_growth_outside_second: BigUint::zero(),
+        }
+    }
+}
+
+/// Per tick bookkeeping for concentrated liquidity.
+///
+/// The fee growth "outside" a tick is the fee growth on the other side of the tick,
+/// relative to the current price. It flips every time the price crosses the tick.
+#[multiversx_sc::module]
+pub trait ConcentratedLiquidityTicksModule {
+    /// Adds (or removes, for a negative delta) liquidity to the given tick.
+    /// Returns true if the tick was flipped from initialized to uninitialized, or vice versa.
+    fn update_tick(
+        &self,
+        tick: i32,
+        liquidity_delta: &BigInt,
+        is_upper_tick: bool,
+        tick_spacing: u32,
+    ) -> bool {
+        let current_tick = self.current_tick().get();
+        let tick_mapper = self.ticks(tick);
+        let mut tick_info = if tick_mapper.is_empty() {
+            TickInfo::default()
+        } else {
+            tick_mapper.get()
+        };
+
+        let was_initialized = tick_info.liquidity_gross > 0;
+        let liquidity_gross = &BigInt::from(tick_info.liquidity_gross.clone()) + liquidity_delta;
+        tick_info.liquidity_gross = liquidity_gross
+            .into_big_uint()
+            .unwrap_or_else(BigUint::zero);
+
+        if !was_initialized && current_tick >= tick {
+            // by convention, all the fee growth so far happened below the tick
+            tick_info.fee_growth_outside_first = self.fee_growth_global_first().get();
+            tick_info.fee_growth_outside_second = self.fee_growth_global_second().get();
+        }
+
+        if is_upper_tick {
+            tick_info.liquidity_net -= liquidity_delta;
+        } else {
+            tick_info.liquidity_net += liquidity_delta;
+        }
+
+        let is_initialized = tick_info.liquidity_gross > 0;
+        if is_initialized {
+            tick_mapper.set(tick_info);
+        } else {
+            tick_mapper.clear();
+        }
+
+        let flipped = was_initialized != is_initialized;
+        if flipped {
+            self.flip_tick(tick, tick_spacing);
+        }
+
+        flipped
+    }
+
+    /// Called when the price moves across an initialized tick.
+    /// Returns the net liquidity of the tick.
+    fn cross_tick(
+        &self,
+        tick: i32,
+        fee_growth_global_first: &BigUint,
+        fee_growth_global_second: &BigUint,
+    ) -> BigInt {
+        self.ticks(tick).update(|tick_info| {
+            tick_info.fee_growth_outside_first =
+                fee_growth_global_first - &tick_info.fee_growth_outside_first;
+            tick_info.fee_growth_outside_second =
+                fee_growth_global_second - &tick_info.fee_growth_outside_second;
+
+            tick_info.liquidity_net.clone()
+        })
+    }
+
+    /// Fee growth per unit of liquidity inside the [lower_tick, upper_tick) range.
+    /// The values are only meaningful relative to a previous snapshot, and can be negative.
+    fn get_fee_growth_inside(&self, lower_tick: i32, upper_tick: i32) -> (BigInt, BigInt) {
+        let current_tick = self.current_tick().get();
+        let lower_tick_info = self.ticks(lower_tick).get();
+        let upper_tick_info = self.ticks(upper_tick).get();
+
+        let fee_growth_inside_first = self.compute_fee_growth_inside(
+            &self.fee_growth_global_first().get(),
+            &lower_tick_info.fee_growth_outside_first,
+            &upper_tick_info.fee_growth_outside_first,
+            current_tick >= lower_tick,
+            current_tick < upper_tick,
+        );
+        let fee_growth_inside_second = self.compute_fee_growth_inside(
+            &self.fee_growth_global_second().get(),
+            &lower_tick_info.fee_growth_outside_second,
+            &upper_tick_info.fee_growth_outside_second,
+            current_tick >= lower_tick,
+            current_tick < upper_tick,
+        );
+
+        (fee_growth_inside_first, fee_growth_inside_second)
+    }
+
+    fn compute_fee_growth_inside(
+        &self,
+        fee_growth_global: &BigUint,
+        lower_outside: &BigUint,
+        upper_outside: &BigUint,
+        above_lower_tick: bool,
+        below_upper_tick: bool,
+    ) -> BigInt {
+        let fee_growth_below = if above_lower_tick {
+            lower_outside.clone()
+        } else {
+            fee_growth_global - lower_outside
+        };
+        let fee_growth_above = if below_upper_tick {
+            upper_outside.clone()
+        } else {
+            fee_growth_global - upper_outside
+        };
+
+        BigInt::from(fee_growth_global.clone())
+            - BigInt::from(fee_growth_below)
+            - BigInt::from(fee_growth_above)
+    }
+
+    fn flip_tick(&self, tick: i32, tick_spacing: u32) {
+        let compressed = tick / tick_spacing as i32;
+        let (word_position, bit_position) = self.get_bitmap_position(compressed);
+        self.tick_bitmap(word_position)
+            .update(|word| *word ^= 1u64 << bit_position);
+    }
+
+    /// The next initialized tick in the same bitmap word as the given tick, searching to the left
+    /// (lower or equal ticks) or to the right (greater ticks).
+    /// If there is none, the last tick of the word is returned, together with `false`.
+    fn get_next_initialized_tick_within_word(
+        &self,
+        tick: i32,
+        tick_spacing: u32,
+        lower_or_equal: bool,
+    ) -> (i32, bool) {
+        let tick_spacing = tick_spacing as i32;
+        let mut compressed = tick / tick_spacing;
+        if tick < 0 && tick % tick_spacing != 0 {
+            compressed -= 1;
+        }
+
+        let (next_tick, initialized) = if lower_or_equal {
+            let (word_position, bit_position) = self.get_bitmap_position(compressed);
+            let mask = if bit_position == 63 {
+                u64::MAX
+            } else {
+                (1u64 << (bit_position + 1)) - 1
+            };
+            let masked = self.tick_bitmap(word_position).get() & mask;
+
+            if masked != 0 {
+                let most_significant_bit = 63 - masked.leading_zeros() as i32;
+                (
+                    (compressed - (bit_position as i32 - most_significant_bit)) * tick_spacing,
+                    true,
+                )
+            } else {
+                ((compressed - bit_position as i32) * tick_spacing, false)
+            }
+        } else {
+            let (word_position, bit_position) = self.get_bitmap_position(compressed + 1);
+            let mask = !((1u64 << bit_position) - 1);
+            let masked = self.tick_bitmap(word_position).get() & mask;
+
+            if masked != 0 {
+                let least_significant_bit = masked.trailing_zeros() as i32;
+                (
+                    (compressed + 1 + (least_significant_bit - bit_position as i32)) * tick_spacing,
+                    true,
+                )
+            } else {
+                (
+                    (compressed + 1 + (TICKS_PER_WORD - 1 - bit_position as i32)) * tick_spacing,
+                    false,
+                )
+            }
+        };
+
+        (next_tick.clamp(MIN_TICK, MAX_TICK), initialized)
+    }
+
+    fn get_bitmap_position(&self, compressed_tick: i32) -> (i32, u32) {
+        (
+            compressed_tick.div_euclid(TICKS_PER_WORD),
+            compressed_tick.rem_euclid(TICKS_PER_WORD) as u32,
+        )
+    }
+
+    #[view(getCurrentTick)]
+    #[storage_mapper("cl_current_tick")]
+    fn current_tick(&self) -> SingleValueMapper<i32>;
+
+    #[view(getCurrentSqrtPrice)]
+    #[storage_mapper("cl_sqrt_price")]
+    fn current_sqrt_price(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getActiveLiquidity)]
+    #[storage_mapper("cl_active_liquidity")]
+    fn active_liquidity(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("cl_fee_growth_global_first")]
+    fn fee_growth_global_first(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("cl_fee_growth_global_second")]
+    fn fee_growth_global_second(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getTickInfo)]
+    #[storage_mapper("cl_ticks")]
+    fn ticks(&self, tick: i32) -> SingleValueMapper<TickInfo<Self::Api>>;
+
+    #[storage_mapper("cl_tick_bitmap")]
+    fn tick_bitmap(&self, word_position: i32) -> SingleValueMapper<u64>;
+}
@@ -7,6 +7,16 @@ use super::errors::*;
 
 pub const MAX_PERCENTAGE: u64 = 100_000;
 pub const MAX_FEE_PERCENTAGE: u64 = 5_000;
+pub const MIN_AMPLIFICATION_COEFFICIENT: u64 = 1;
+pub const MAX_AMPLIFICATION_COEFFICIENT: u64 = 10_000;
+
+#[derive(TypeAbi, TopEncode, TopDecode, Clone, PartialEq, Debug)]
+pub struct DynamicFeeConfig {
+    pub min_fee_percent: u64,
+    pub max_fee_percent: u64,
+    pub window_rounds: u64,
+    pub max_volatility: u64,
+}
 
 #[multiversx_sc::module]
 pub trait ConfigModule:
@@ -22,6 +32,7 @@ pub trait ConfigModule:
     fn set_fee_percent(&self, total_fee_percent: u64, special_fee_percent: u64) {
         self.require_caller_has_owner_or_admin_permissions();
         self.set_fee_percents(total_fee_percent, special_fee_percent);
+        self.require_special_fee_within_dynamic_fee_bounds();
     }
 
     fn set_fee_percents(&self, total_fee_percent: u64, special_fee_percent: u64) {
@@ -33,6 +44,80 @@ pub trait ConfigModule:
         self.special_fee_percent().set(special_fee_percent);
     }
 
+    /// Turns the pair into a stable swap pair, using the given amplification coefficient.
+    /// Can only be set before any liquidity is added.
+    #[endpoint(setAmplificationCoefficient)]
+    fn set_amplification_coefficient(&self, amplification_coefficient: u64) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            self.lp_token_supply().get() == 0,
+            ERROR_AMPLIFICATION_COEFFICIENT_LOCKED
+        );
+        require!(
+            !self.is_concentrated_liquidity(),
+            ERROR_STABLE_SWAP_CONCENTRATED_LIQUIDITY
+        );
+        require!(
+            (MIN_AMPLIFICATION_COEFFICIENT..=MAX_AMPLIFICATION_COEFFICIENT)
+                .contains(&amplification_coefficient),
+            ERROR_BAD_AMPLIFICATION_COEFFICIENT
+        );
+
+        self.amplification_coefficient()
+            .set(amplification_coefficient);
+    }
+
+    /// The fee applied on swaps: the last computed dynamic fee while the dynamic fees are enabled,
+    /// the configured total fee percent otherwise.
+    fn get_total_fee_percent(&self) -> u64 {
+        if self.dynamic_fee_config().is_empty() || self.dynamic_fee_percent().is_empty() {
+            return self.total_fee_percent().get();
+        }
+
+        self.dynamic_fee_percent().get()
+    }
+
+    fn require_special_fee_within_dynamic_fee_bounds(&self) {
+        if self.dynamic_fee_config().is_empty() {
+            return;
+        }
+
+        let dynamic_fee_config = self.dynamic_fee_config().get();
+        require!(
+            self.special_fee_percent().get() <= dynamic_fee_config.min_fee_percent,
+            ERROR_BAD_PERCENTS
+        );
+    }
+
+    /// The deadline is a block timestamp, in seconds.
+    fn require_deadline_not_passed(&self, opt_deadline: OptionalValue<u64>) {
+        if let OptionalValue::Some(deadline) = opt_deadline {
+            require!(
+                self.blockchain().get_block_timestamp() <= deadline,
+                ERROR_DEADLINE_PASSED
+            );
+        }
+    }
+
+    #[inline]
+    fn is_concentrated_liquidity(&self) -> bool {
+        self.tick_spacing().get() > 0
+    }
+
+    fn require_not_concentrated_liquidity(&self) {
+        require!(
+            !self.is_concentrated_liquidity(),
+            ERROR_CONCENTRATED_LIQUIDITY_ENABLED
+        );
+    }
+
+    fn require_not_stable_swap(&self) {
+        require!(
+            self.amplification_coefficient().get() == 0,
+            ERROR_STABLE_SWAP_ENABLED
+        );
+    }
+
     #[view(getLpTokenIdentifier)]
     fn get_lp_token_identifier(&self) -> TokenIdentifier {
         self.lp_token_identifier().get()
@@ -69,6 +154,26 @@ pub trait ConfigModule:
     #[storage_mapper("initial_liquidity_adder")]
     fn initial_liquidity_adder(&self) -> SingleValueMapper<Option<ManagedAddress>>;
 
+    /// Zero for constant product pairs.
+    #[view(getAmplificationCoefficient)]
+    #[storage_mapper("amplification_coefficient")]
+    fn amplification_coefficient(&self) -> SingleValueMapper<u64>;
+
+    /// Zero for regular pairs, set for concentrated liquidity pairs.
+    #[view(getTickSpacing)]
+    #[storage_mapper("cl_tick_spacing")]
+    fn tick_spacing(&self) -> SingleValueMapper<u32>;
+
+    /// Empty when the total fee percent is static.
+    #[view(getDynamicFeeConfig)]
+    #[storage_mapper("dynamic_fee_config")]
+    fn dynamic_fee_config(&self) -> SingleValueMapper<DynamicFeeConfig>;
+
+    /// Empty until the first dynamic fee is computed.
+    #[view(getDynamicFeePercent)]
+    #[storage_mapper("dynamic_fee_percent")]
+    fn dynamic_fee_percent(&self) -> SingleValueMapper<u64>;
+
     #[view(getReserve)]
     #[storage_mapper("reserve")]
     fn pair_reserve(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
@@ -0,0 +1,191 @@
+multiversx_sc::imports!();
+
+use crate::{
+    amm, concentrated_liquidity,
+    config::{self, DynamicFeeConfig, MAX_FEE_PERCENTAGE, MAX_PERCENTAGE},
+    errors::{ERROR_BAD_DYNAMIC_FEE_CONFIG, ERROR_DYNAMIC_FEE_NOT_ENABLED},
+    safe_price::{self, Round},
+    safe_price_view,
+};
+
+pub const VOLATILITY_WINDOWS: u64 = 4;
+pub const VOLATILITY_PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+/// Optionally moves the fee applied on swaps between a lower and an upper bound,
+/// based on the realized volatility of the pair.
+///
+/// The volatility is the average relative change between the safe prices of
+/// `VOLATILITY_WINDOWS` consecutive windows, ending at the current round.
+/// The fee grows linearly from the lower bound, for no volatility,
+/// up to the upper bound, reached at `max_volatility` (expressed in `MAX_PERCENTAGE` units).
+///
+/// The fee is recomputed at most once per round, on swaps, and kept apart from the
+/// configured total fee percent. The special fee is not changed, so it must stay below the lower bound.
+#[multiversx_sc::module]
+pub trait DynamicFeeModule:
+    config::ConfigModule
+    + safe_price::SafePriceModule
+    + safe_price_view::SafePriceViewModule
+    + concentrated_liquidity::ticks::ConcentratedLiquidityTicksModule
+    + token_send::TokenSendModule
+    + amm::AmmModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    #[endpoint(setDynamicFeeConfig)]
+    fn set_dynamic_fee_config(
+        &self,
+        min_fee_percent: u64,
+        max_fee_percent: u64,
+        window_rounds: Round,
+        max_volatility: u64,
+    ) {
+        self.require_caller_has_owner_or_admin_permissions();
+        require!(
+            self.special_fee_percent().get() <= min_fee_percent
+                && min_fee_percent <= max_fee_percent
+                && max_fee_percent <= MAX_FEE_PERCENTAGE,
+            ERROR_BAD_DYNAMIC_FEE_CONFIG
+        );
+        require!(
+            window_rounds > 0 && max_volatility > 0,
+            ERROR_BAD_DYNAMIC_FEE_CONFIG
+        );
+
+        self.dynamic_fee_config().set(DynamicFeeConfig {
+            min_fee_percent,
+            max_fee_percent,
+            window_rounds,
+            max_volatility,
+        });
+        self.dynamic_fee_percent().clear();
+        self.dynamic_fee_last_update_round().clear();
+    }
+
+    /// The swaps go back to the configured total fee percent.
+    #[endpoint(disableDynamicFee)]
+    fn disable_dynamic_fee(&self) {
+        self.require_caller_has_owner_or_admin_permissions();
+        require!(
+            !self.dynamic_fee_config().is_empty(),
+            ERROR_DYNAMIC_FEE_NOT_ENABLED
+        );
+
+        self.dynamic_fee_config().clear();
+        self.dynamic_fee_percent().clear();
+        self.dynamic_fee_last_update_round().clear();
+    }
+
+    fn update_dynamic_fee(&self) {
+        if self.dynamic_fee_config().is_empty() {
+            return;
+        }
+
+        let current_round = self.blockchain().get_block_round();
+        if self.dynamic_fee_last_update_round().get() == current_round {
+            return;
+        }
+        self.dynamic_fee_last_update_round().set(current_round);
+
+        let dynamic_fee_config = self.dynamic_fee_config().get();
+        let volatility = match self.get_volatility(dynamic_fee_config.window_rounds) {
+            Some(volatility) => volatility,
+            None => return,
+        };
+
+        let dynamic_fee_percent = self.compute_dynamic_fee_percent(&dynamic_fee_config, volatility);
+        self.dynamic_fee_percent().set(dynamic_fee_percent);
+    }
+
+    fn compute_dynamic_fee_percent(
+        &self,
+        dynamic_fee_config: &DynamicFeeConfig,
+        volatility: u64,
+    ) -> u64 {
+        let capped_volatility = core::cmp::min(volatility, dynamic_fee_config.max_volatility);
+        let fee_range = dynamic_fee_config.max_fee_percent - dynamic_fee_config.min_fee_percent;
+
+        dynamic_fee_config.min_fee_percent
+            + fee_range * capped_volatility / dynamic_fee_config.max_volatility
+    }
+
+    /// Average relative change between consecutive window prices, in `MAX_PERCENTAGE` units.
+    /// None if the recorded observations do not cover all the windows.
+    fn get_volatility(&self, window_rounds: Round) -> Option<u64> {
+        let price_observations = self.price_observations();
+        if price_observations.is_empty() {
+            return None;
+        }
+
+        let current_round = self.blockchain().get_block_round();
+        let lookback_rounds = window_rounds * VOLATILITY_WINDOWS;
+        if current_round < lookback_rounds {
+            return None;
+        }
+
+        let pair_address = self.blockchain().get_sc_address();
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let safe_price_current_index = self.safe_price_current_index().get();
+
+        let oldest_price_observation = self.get_oldest_price_observation(
+            &pair_address,
+            safe_price_current_index,
+            &price_observations,
+        );
+        let start_round = current_round - lookback_rounds;
+        if oldest_price_observation.recording_round > start_round {
+            return None;
+        }
+
+        let mut window_start_observation = self.get_price_observation(
+            &pair_address,
+            &first_token_id,
+            &second_token_id,
+            safe_price_current_index,
+            &price_observations,
+            start_round,
+        );
+        let mut previous_price: Option<BigUint> = None;
+        let mut total_change = BigUint::zero();
+        for window in 1..=VOLATILITY_WINDOWS {
+            let window_end_observation = self.get_price_observation(
+                &pair_address,
+                &first_token_id,
+                &second_token_id,
+                safe_price_current_index,
+                &price_observations,
+                start_round + window * window_rounds,
+            );
+            let (weighted_first_token_reserve, weighted_second_token_reserve) =
+                self.compute_weighted_reserves(&window_start_observation, &window_end_observation);
+            if weighted_first_token_reserve == 0 {
+                return None;
+            }
+
+            let price = weighted_second_token_reserve * VOLATILITY_PRICE_PRECISION
+                / weighted_first_token_reserve;
+            if let Some(previous_price) = previous_price {
+                if previous_price == 0 {
+                    return None;
+                }
+
+                let price_change = if price >= previous_price {
+                    &price - &previous_price
+                } else {
+                    &previous_price - &price
+                };
+                total_change += price_change * MAX_PERCENTAGE / previous_price;
+            }
+
+            previous_price = Some(price);
+            window_start_observation = window_end_observation;
+        }
+
+        let volatility = total_change / (VOLATILITY_WINDOWS - 1);
+        Some(volatility.to_u64().unwrap_or(u64::MAX))
+    }
+
+    #[storage_mapper("dynamic_fee_last_update_round")]
+    fn dynamic_fee_last_update_round(&self) -> SingleValueMapper<Round>;
+}
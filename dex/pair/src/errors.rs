@@ -28,6 +28,28 @@ pub static ERROR_NOT_AN_ESDT: &[u8] = b"Not a valid esdt id";
 pub static ERROR_SAME_TOKENS: &[u8] = b"Exchange tokens cannot be the same";
 pub static ERROR_POOL_TOKEN_IS_PLT: &[u8] = b"Token ID cannot be the same as LP token ID";
 pub static ERROR_BAD_PERCENTS: &[u8] = b"Bad percents";
+pub static ERROR_BAD_AMPLIFICATION_COEFFICIENT: &[u8] = b"Bad amplification coefficient";
+pub static ERROR_AMPLIFICATION_COEFFICIENT_LOCKED: &[u8] =
+    b"Amplification coefficient can only be set before adding liquidity";
+pub static ERROR_STABLE_SWAP_NOT_CONVERGED: &[u8] = b"Stable swap computation did not converge";
+pub static ERROR_STABLE_SWAP_ENABLED: &[u8] = b"Not available for stable swap pairs";
+pub static ERROR_CONCENTRATED_LIQUIDITY_LOCKED: &[u8] =
+    b"Concentrated liquidity can only be enabled once, before adding liquidity";
+pub static ERROR_STABLE_SWAP_CONCENTRATED_LIQUIDITY: &[u8] =
+    b"Stable swap and concentrated liquidity cannot be used together";
+pub static ERROR_CONCENTRATED_LIQUIDITY_NOT_ENABLED: &[u8] = b"Concentrated liquidity not enabled";
+pub static ERROR_CONCENTRATED_LIQUIDITY_ENABLED: &[u8] =
+    b"Not available for concentrated liquidity pairs";
+pub static ERROR_INVALID_TICK: &[u8] = b"Invalid tick";
+pub static ERROR_INVALID_TICK_SPACING: &[u8] = b"Invalid tick spacing";
+pub static ERROR_POSITION_TOKEN_NOT_SET: &[u8] = b"Position token not set";
+pub static ERROR_POSITION_FARMING_TOKEN_NOT_SET: &[u8] = b"Position farming token not set";
+pub static ERROR_NOT_ENOUGH_WRAPPED_POSITION: &[u8] = b"Not enough wrapped position tokens";
+pub static ERROR_POSITION_FARMING_RANGE_NOT_SET: &[u8] = b"Position farming range not set";
+pub static ERROR_POSITION_FARMING_RANGE_ALREADY_SET: &[u8] = b"Position farming range already set";
+pub static ERROR_POSITION_NOT_IN_FARMING_RANGE: &[u8] = b"Position range is not the farming range";
+pub static ERROR_BAD_DYNAMIC_FEE_CONFIG: &[u8] = b"Bad dynamic fee config";
+pub static ERROR_DYNAMIC_FEE_NOT_ENABLED: &[u8] = b"Dynamic fee not enabled";
 pub static ERROR_NOT_WHITELISTED: &[u8] = b"Not whitelisted";
 pub static ERROR_ALREADY_WHITELISTED: &[u8] = b"Already whitelisted";
 pub static ERROR_PAIR_ALREADY_TRUSTED: &[u8] = b"Pair already trusted";
@@ -44,7 +66,16 @@ pub static ERROR_LP_TOKEN_SAME_AS_POOL_TOKENS: &[u8] =
 
 pub static ERROR_SWAP_NOT_ENABLED: &[u8] = b"Swap is not enabled";
 pub static ERROR_SLIPPAGE_EXCEEDED: &[u8] = b"Slippage exceeded";
+pub static ERROR_DEADLINE_PASSED: &[u8] = b"Deadline passed";
+pub static ERROR_AMOUNT_OUT_BELOW_MIN: &[u8] = b"Final amount out is below the minimum";
+pub static ERROR_FLASH_SWAP_IN_PROGRESS: &[u8] = b"Flash swap already in progress";
+pub static ERROR_FLASH_SWAP_RECEIVER_NOT_SC: &[u8] = b"Flash swap receiver must be a contract";
+pub static ERROR_FLASH_SWAP_NOT_REPAID: &[u8] = b"Flash swap not repaid";
 pub static ERROR_NOTHING_TO_DO_WITH_FEE_SLICE: &[u8] = b"Nothing to do with fee slice";
+pub static ERROR_BAD_FEE_BATCHING_CONFIG: &[u8] = b"Bad fee batching config";
+pub static ERROR_NO_PENDING_FEES: &[u8] = b"No pending fees";
+pub static ERROR_PRICE_DEVIATES_FROM_SAFE_PRICE: &[u8] =
+    b"Price deviates too much from the safe price";
 
 pub static ERROR_SAFE_PRICE_SAME_ROUNDS: &[u8] =
     b"The safe price can be computed only between different rounds";
@@ -52,3 +83,4 @@ pub static ERROR_SAFE_PRICE_CURRENT_INDEX: &[u8] =
     b"The current safe price index is greater than the maximum number of observations";
 pub static ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST: &[u8] =
     b"The price observation does not exist";
+pub static ERROR_BAD_SAFE_PRICE_OBSERVATION_CONFIG: &[u8] = b"Bad safe price observation config";
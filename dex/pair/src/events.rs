@@ -1,3 +1,4 @@
+use crate::concentrated_liquidity::PositionAttributes;
 use crate::contexts::add_liquidity::AddLiquidityContext;
 use crate::contexts::base::StorageCache;
 use crate::contexts::remove_liquidity::RemoveLiquidityContext;
@@ -68,6 +69,45 @@ pub struct RemoveLiquidityEvent<M: ManagedTypeApi> {
     timestamp: u64,
 }
 
+#[derive(TypeAbi, TopEncode)]
+pub struct FlashSwapEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    receiver: ManagedAddress<M>,
+    token_id: TokenIdentifier<M>,
+    token_amount: BigUint<M>,
+    fee_amount: BigUint<M>,
+    token_reserve: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct ProcessFeesEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    first_token_id: TokenIdentifier<M>,
+    first_token_fee_amount: BigUint<M>,
+    second_token_id: TokenIdentifier<M>,
+    second_token_fee_amount: BigUint<M>,
+    first_token_reserves: BigUint<M>,
+    second_token_reserves: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct PositionEvent<M: ManagedTypeApi> {
+    lower_tick: i32,
+    upper_tick: i32,
+    liquidity: BigUint<M>,
+    first_token_amount: BigUint<M>,
+    second_token_amount: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[multiversx_sc::module]
 pub trait EventsModule:
     crate::config::ConfigModule
@@ -188,6 +228,95 @@ pub trait EventsModule:
         )
     }
 
+    fn emit_flash_swap_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        receiver: &ManagedAddress,
+        token_id: &TokenIdentifier,
+        token_amount: &BigUint,
+        fee_amount: &BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        let token_reserve = if token_id == &storage_cache.first_token_id {
+            storage_cache.first_token_reserve.clone()
+        } else {
+            storage_cache.second_token_reserve.clone()
+        };
+        self.flash_swap_event(
+            token_id,
+            &caller,
+            epoch,
+            &FlashSwapEvent {
+                caller: caller.clone(),
+                receiver: receiver.clone(),
+                token_id: token_id.clone(),
+                token_amount: token_amount.clone(),
+                fee_amount: fee_amount.clone(),
+                token_reserve,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_process_fees_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        first_token_fee_amount: &BigUint,
+        second_token_fee_amount: &BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.process_fees_event(
+            &storage_cache.first_token_id,
+            &storage_cache.second_token_id,
+            &caller,
+            epoch,
+            &ProcessFeesEvent {
+                caller: caller.clone(),
+                first_token_id: storage_cache.first_token_id.clone(),
+                first_token_fee_amount: first_token_fee_amount.clone(),
+                second_token_id: storage_cache.second_token_id.clone(),
+                second_token_fee_amount: second_token_fee_amount.clone(),
+                first_token_reserves: storage_cache.first_token_reserve.clone(),
+                second_token_reserves: storage_cache.second_token_reserve.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_position_event(
+        &self,
+        caller: &ManagedAddress,
+        is_add: bool,
+        attributes: &PositionAttributes<Self::Api>,
+        liquidity: &BigUint,
+        first_token_amount: &BigUint,
+        second_token_amount: &BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let position_event = PositionEvent {
+            lower_tick: attributes.lower_tick,
+            upper_tick: attributes.upper_tick,
+            liquidity: liquidity.clone(),
+            first_token_amount: first_token_amount.clone(),
+            second_token_amount: second_token_amount.clone(),
+            block: self.blockchain().get_block_nonce(),
+            epoch,
+            timestamp: self.blockchain().get_block_timestamp(),
+        };
+
+        if is_add {
+            self.add_concentrated_liquidity_event(caller, epoch, &position_event);
+        } else {
+            self.remove_concentrated_liquidity_event(caller, epoch, &position_event);
+        }
+    }
+
     #[event("swap")]
     fn swap_event(
         &self,
@@ -226,4 +355,39 @@ pub trait EventsModule:
         #[indexed] epoch: u64,
         remove_liquidity_event: &RemoveLiquidityEvent<Self::Api>,
     );
+
+    #[event("flash_swap")]
+    fn flash_swap_event(
+        &self,
+        #[indexed] token_id: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        flash_swap_event: &FlashSwapEvent<Self::Api>,
+    );
+
+    #[event("process_fees")]
+    fn process_fees_event(
+        &self,
+        #[indexed] first_token: &TokenIdentifier,
+        #[indexed] second_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        process_fees_event: &ProcessFeesEvent<Self::Api>,
+    );
+
+    #[event("add_concentrated_liquidity")]
+    fn add_concentrated_liquidity_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        position_event: &PositionEvent<Self::Api>,
+    );
+
+    #[event("remove_concentrated_liquidity")]
+    fn remove_concentrated_liquidity_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        position_event: &PositionEvent<Self::Api>,
+    );
 }
@@ -2,6 +2,7 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use super::amm;
+use super::concentrated_liquidity;
 use super::config;
 use super::errors::*;
 use super::liquidity_pool;
@@ -27,6 +28,9 @@ mod self_proxy {
 pub trait FeeModule:
     config::ConfigModule
     + liquidity_pool::LiquidityPoolModule
+    + concentrated_liquidity::ConcentratedLiquidityModule
+    + concentrated_liquidity::math::ConcentratedLiquidityMathModule
+    + concentrated_liquidity::ticks::ConcentratedLiquidityTicksModule
     + amm::AmmModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
@@ -133,6 +137,22 @@ pub trait FeeModule:
             return;
         }
 
+        if self.fee_batching_enabled().get() {
+            self.pending_fees(fee_token)
+                .update(|amount| *amount += fee_amount);
+            return;
+        }
+
+        self.distribute_fee(storage_cache, swap_tokens_order, fee_token, fee_amount);
+    }
+
+    fn distribute_fee(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        swap_tokens_order: SwapTokensOrder,
+        fee_token: &TokenIdentifier,
+        fee_amount: &BigUint,
+    ) {
         let fees_collector_configured = !self.fees_collector_address().is_empty();
         let remaining_fee = if fees_collector_configured {
             let fees_collector_cut_percentage = self.fees_collector_cut_percentage().get();
@@ -411,6 +431,16 @@ pub trait FeeModule:
     #[storage_mapper("feesCollectorCutPercentage")]
     fn fees_collector_cut_percentage(&self) -> SingleValueMapper<u64>;
 
+    /// While set, the fees are kept in the pair until `processFees` is called,
+    /// instead of being sent on every swap.
+    #[view(getFeeBatchingEnabled)]
+    #[storage_mapper("feeBatchingEnabled")]
+    fn fee_batching_enabled(&self) -> SingleValueMapper<bool>;
+
+    #[view(getPendingFees)]
+    #[storage_mapper("pendingFees")]
+    fn pending_fees(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
     #[storage_mapper("fee_destination")]
     fn destination_map(&self) -> MapMapper<ManagedAddress, TokenIdentifier>;
 
@@ -0,0 +1,137 @@
+multiversx_sc::imports!();
+
+use crate::{
+    amm, concentrated_liquidity,
+    config::{self, MAX_PERCENTAGE},
+    contexts::base::{StorageCache, SwapTokensOrder},
+    errors::*,
+    events, fee, flash_swap, liquidity_pool, safe_price, safe_price_view,
+};
+use pausable::State;
+
+/// Keeps the special fees of the swaps in the pair, on top of the reserves,
+/// and sends them to the fees collector and the fee destinations in one go,
+/// so the swaps do not pay for the burns and the cross-contract calls.
+///
+/// Processing is permissionless, and only happens while the price of the pair
+/// is close to its safe price, as the fees may be swapped against the reserves.
+/// It is not allowed during a flash swap, whose repayment check relies on the pending fees.
+#[multiversx_sc::module]
+pub trait FeeBatchingModule:
+    config::ConfigModule
+    + fee::FeeModule
+    + liquidity_pool::LiquidityPoolModule
+    + concentrated_liquidity::ConcentratedLiquidityModule
+    + concentrated_liquidity::math::ConcentratedLiquidityMathModule
+    + concentrated_liquidity::ticks::ConcentratedLiquidityTicksModule
+    + amm::AmmModule
+    + events::EventsModule
+    + safe_price::SafePriceModule
+    + safe_price_view::SafePriceViewModule
+    + flash_swap::FlashSwapModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    /// `max_price_deviation` is out of MAX_PERCENTAGE. Disabling it keeps the pending fees,
+    /// which can still be processed.
+    #[endpoint(setFeeBatching)]
+    fn set_fee_batching(&self, enabled: bool, max_price_deviation: u64) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            max_price_deviation > 0 && max_price_deviation < MAX_PERCENTAGE,
+            ERROR_BAD_FEE_BATCHING_CONFIG
+        );
+
+        self.fee_batching_enabled().set(enabled);
+        self.fee_processing_max_price_deviation()
+            .set(max_price_deviation);
+    }
+
+    #[endpoint(processFees)]
+    fn process_fees(&self) {
+        require!(self.state().get() == State::Active, ERROR_NOT_ACTIVE);
+        require!(
+            !self.flash_swap_in_progress().get(),
+            ERROR_FLASH_SWAP_IN_PROGRESS
+        );
+
+        let mut storage_cache = StorageCache::new(self);
+        let first_token_id = storage_cache.first_token_id.clone();
+        let second_token_id = storage_cache.second_token_id.clone();
+        let first_fee_amount = self.pending_fees(&first_token_id).take();
+        let second_fee_amount = self.pending_fees(&second_token_id).take();
+        require!(
+            first_fee_amount > 0 || second_fee_amount > 0,
+            ERROR_NO_PENDING_FEES
+        );
+
+        self.require_price_close_to_safe_price(&storage_cache);
+        self.update_safe_price(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        if first_fee_amount > 0 {
+            self.distribute_fee(
+                &mut storage_cache,
+                SwapTokensOrder::PoolOrder,
+                &first_token_id,
+                &first_fee_amount,
+            );
+        }
+        if second_fee_amount > 0 {
+            self.distribute_fee(
+                &mut storage_cache,
+                SwapTokensOrder::ReverseOrder,
+                &second_token_id,
+                &second_fee_amount,
+            );
+        }
+
+        self.emit_process_fees_event(&storage_cache, &first_fee_amount, &second_fee_amount);
+    }
+
+    fn require_price_close_to_safe_price(&self, storage_cache: &StorageCache<Self>) {
+        require!(
+            storage_cache.first_token_reserve > 0 && storage_cache.second_token_reserve > 0,
+            ERROR_NOT_ENOUGH_RESERVE
+        );
+
+        let pair_address = self.blockchain().get_sc_address();
+        let (first_price_reserve, second_price_reserve) = self.get_price_reserves(
+            &pair_address,
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        let input_payment = EsdtTokenPayment::new(
+            storage_cache.first_token_id.clone(),
+            0,
+            first_price_reserve.clone(),
+        );
+        let safe_price_amount = self
+            .get_safe_price_by_default_offset(pair_address, input_payment)
+            .amount;
+        let current_price_amount = self.compute_weighted_output_amount(
+            &first_price_reserve,
+            &first_price_reserve,
+            &second_price_reserve,
+            self.amplification_coefficient().get(),
+        );
+
+        let price_difference = if current_price_amount > safe_price_amount {
+            &current_price_amount - &safe_price_amount
+        } else {
+            &safe_price_amount - &current_price_amount
+        };
+        let max_price_deviation = self.fee_processing_max_price_deviation().get();
+        require!(
+            price_difference <= &safe_price_amount * max_price_deviation / MAX_PERCENTAGE,
+            ERROR_PRICE_DEVIATES_FROM_SAFE_PRICE
+        );
+    }
+
+    #[view(getFeeProcessingMaxPriceDeviation)]
+    #[storage_mapper("feeProcessingMaxPriceDeviation")]
+    fn fee_processing_max_price_deviation(&self) -> SingleValueMapper<u64>;
+}
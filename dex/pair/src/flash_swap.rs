@@ -0,0 +1,131 @@
+multiversx_sc::imports!();
+
+use crate::{
+    amm, concentrated_liquidity,
+    config::{self, MAX_PERCENTAGE},
+    contexts::base::{StorageCache, SwapTokensOrder},
+    errors::*,
+    events, fee, liquidity_pool, safe_price,
+};
+use pausable::State;
+
+/// Lends part of the reserves to a receiver contract, for the duration of a call to it.
+///
+/// The receiver endpoint gets the borrowed tokens, the fee amount as first argument,
+/// and then the caller's own arguments. Before returning, it has to send back to the pair
+/// the borrowed amount plus the fee, through a direct transfer.
+///
+/// The repayment is checked against what the pair holds on top of its reserves and pending fees,
+/// so tokens that end up in either during the call (e.g. through a swap) do not count.
+/// The fee is the total fee percent of the borrowed amount, and is split like a swap fee:
+/// the special fee goes through `send_fee`, while the rest stays in the reserves.
+#[multiversx_sc::module]
+pub trait FlashSwapModule:
+    config::ConfigModule
+    + fee::FeeModule
+    + liquidity_pool::LiquidityPoolModule
+    + amm::AmmModule
+    + concentrated_liquidity::ConcentratedLiquidityModule
+    + concentrated_liquidity::math::ConcentratedLiquidityMathModule
+    + concentrated_liquidity::ticks::ConcentratedLiquidityTicksModule
+    + events::EventsModule
+    + safe_price::SafePriceModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    #[endpoint(flashSwap)]
+    fn flash_swap(
+        &self,
+        token_id: TokenIdentifier,
+        amount: BigUint,
+        receiver: ManagedAddress,
+        receiver_endpoint: ManagedBuffer,
+        args: MultiValueEncoded<ManagedBuffer>,
+    ) -> BigUint {
+        self.require_not_concentrated_liquidity();
+        require!(self.state().get() == State::Active, ERROR_SWAP_NOT_ENABLED);
+        require!(
+            !self.flash_swap_in_progress().get(),
+            ERROR_FLASH_SWAP_IN_PROGRESS
+        );
+        require!(
+            self.blockchain().is_smart_contract(&receiver),
+            ERROR_FLASH_SWAP_RECEIVER_NOT_SC
+        );
+
+        let swap_tokens_order = if token_id == self.first_token_id().get() {
+            SwapTokensOrder::PoolOrder
+        } else if token_id == self.second_token_id().get() {
+            SwapTokensOrder::ReverseOrder
+        } else {
+            sc_panic!(ERROR_UNKNOWN_TOKEN);
+        };
+
+        let reserve_before = self.pair_reserve(&token_id).get();
+        require!(
+            amount > 0u64 && amount < reserve_before,
+            ERROR_NOT_ENOUGH_RESERVE
+        );
+
+        let fee_amount = &amount * self.get_total_fee_percent() / MAX_PERCENTAGE;
+        require!(fee_amount > 0u64, ERROR_ZERO_AMOUNT);
+
+        self.update_safe_price(
+            &self.pair_reserve(&self.first_token_id().get()).get(),
+            &self.pair_reserve(&self.second_token_id().get()).get(),
+        );
+
+        let balance_before = self
+            .blockchain()
+            .get_sc_balance(&EgldOrEsdtTokenIdentifier::esdt(token_id.clone()), 0);
+        let pending_fees_before = self.pending_fees(&token_id).get();
+
+        // no storage cache is kept during the call, as the receiver might call back into the pair
+        self.flash_swap_in_progress().set(true);
+        let mut contract_call = self
+            .send()
+            .contract_call::<()>(receiver.clone(), receiver_endpoint)
+            .with_esdt_transfer(EsdtTokenPayment::new(token_id.clone(), 0, amount.clone()));
+        contract_call.push_raw_argument(fee_amount.to_bytes_be_buffer());
+        for arg in args {
+            contract_call.push_raw_argument(arg);
+        }
+        let _: IgnoreValue = contract_call.execute_on_dest_context();
+        self.flash_swap_in_progress().clear();
+
+        // what the pair holds on top of the reserves and the pending fees
+        // must have grown by at least the fee
+        let balance_after = self
+            .blockchain()
+            .get_sc_balance(&EgldOrEsdtTokenIdentifier::esdt(token_id.clone()), 0);
+        let reserve_after = self.pair_reserve(&token_id).get();
+        let pending_fees_after = self.pending_fees(&token_id).get();
+        require!(
+            balance_after + reserve_before + pending_fees_before
+                >= balance_before + reserve_after + pending_fees_after + &fee_amount,
+            ERROR_FLASH_SWAP_NOT_REPAID
+        );
+
+        let mut storage_cache = StorageCache::new(self);
+        let special_fee_amount = if self.is_fee_enabled() {
+            self.get_special_fee_from_input(&amount)
+        } else {
+            BigUint::zero()
+        };
+        *storage_cache.get_mut_reserve_in(swap_tokens_order) += &fee_amount - &special_fee_amount;
+        self.send_fee(
+            &mut storage_cache,
+            swap_tokens_order,
+            &token_id,
+            &special_fee_amount,
+        );
+
+        self.emit_flash_swap_event(&storage_cache, &receiver, &token_id, &amount, &fee_amount);
+
+        fee_amount
+    }
+
+    #[storage_mapper("flash_swap_in_progress")]
+    fn flash_swap_in_progress(&self) -> SingleValueMapper<bool>;
+}
@@ -4,11 +4,15 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 mod amm;
+pub mod concentrated_liquidity;
 pub mod config;
 mod contexts;
+pub mod dynamic_fee;
 pub mod errors;
 mod events;
 pub mod fee;
+pub mod fee_batching;
+pub mod flash_swap;
 mod liquidity_pool;
 pub mod locking_wrapper;
 pub mod safe_price;
@@ -39,12 +43,19 @@ pub type SwapTokensFixedOutputResultType<BigUint> =
 pub trait Pair<ContractReader>:
     amm::AmmModule
     + fee::FeeModule
+    + fee_batching::FeeBatchingModule
     + liquidity_pool::LiquidityPoolModule
+    + concentrated_liquidity::ConcentratedLiquidityModule
+    + concentrated_liquidity::farming::ConcentratedLiquidityFarmingModule
+    + concentrated_liquidity::math::ConcentratedLiquidityMathModule
+    + concentrated_liquidity::ticks::ConcentratedLiquidityTicksModule
     + config::ConfigModule
     + token_send::TokenSendModule
     + events::EventsModule
     + safe_price::SafePriceModule
     + safe_price_view::SafePriceViewModule
+    + dynamic_fee::DynamicFeeModule
+    + flash_swap::FlashSwapModule
     + contexts::output_builder::OutputBuilderModule
     + locking_wrapper::LockingWrapperModule
     + permissions_module::PermissionsModule
@@ -108,6 +119,8 @@ pub trait Pair<ContractReader>:
     #[payable("*")]
     #[endpoint(addInitialLiquidity)]
     fn add_initial_liquidity(&self) -> AddLiquidityResultType<Self::Api> {
+        self.require_not_concentrated_liquidity();
+
         let mut storage_cache = StorageCache::new(self);
         let caller = self.blockchain().get_caller();
 
@@ -178,6 +191,7 @@ pub trait Pair<ContractReader>:
             first_token_amount_min > 0 && second_token_amount_min > 0,
             ERROR_INVALID_ARGS
         );
+        self.require_not_concentrated_liquidity();
 
         let mut storage_cache = StorageCache::new(self);
         let caller = self.blockchain().get_caller();
@@ -257,6 +271,138 @@ pub trait Pair<ContractReader>:
         output
     }
 
+    /// Adds liquidity with only one of the pool tokens. Part of the payment is first swapped
+    /// into the other token, through the pool itself, with the usual fees.
+    /// Whatever could not be added, if anything, is sent back along with the LP tokens.
+    #[payable("*")]
+    #[endpoint(addLiquiditySingleToken)]
+    fn add_liquidity_single_token(
+        &self,
+        lp_token_amount_min: BigUint,
+    ) -> AddLiquidityResultType<Self::Api> {
+        require!(lp_token_amount_min > 0, ERROR_INVALID_ARGS);
+        self.require_not_concentrated_liquidity();
+        // the optimal swap amount is only known for the constant product curve
+        self.require_not_stable_swap();
+
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let (token_in, _, amount_in) = self.call_value().single_esdt().into_tuple();
+
+        let token_out = if token_in == storage_cache.first_token_id {
+            storage_cache.second_token_id.clone()
+        } else if token_in == storage_cache.second_token_id {
+            storage_cache.first_token_id.clone()
+        } else {
+            sc_panic!(ERROR_BAD_PAYMENT_TOKENS);
+        };
+        require!(amount_in > 0, ERROR_BAD_PAYMENT_TOKENS);
+        require!(
+            self.can_swap(storage_cache.contract_state),
+            ERROR_SWAP_NOT_ENABLED
+        );
+        require!(
+            storage_cache.lp_token_id.is_valid_esdt_identifier(),
+            ERROR_LP_TOKEN_NOT_ISSUED
+        );
+        require!(
+            storage_cache.lp_token_supply != 0,
+            ERROR_INITIAL_LIQUIDITY_NOT_ADDED
+        );
+
+        self.update_safe_price(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        self.update_dynamic_fee();
+
+        let initial_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let swap_tokens_order = storage_cache.get_swap_tokens_order(&token_in, &token_out);
+        let swap_amount = self
+            .get_optimal_swap_amount(&amount_in, storage_cache.get_reserve_in(swap_tokens_order));
+        require!(
+            swap_amount > 0 && swap_amount < amount_in,
+            ERROR_ZERO_AMOUNT
+        );
+
+        let mut swap_context = SwapContext::new(
+            token_in.clone(),
+            swap_amount.clone(),
+            token_out.clone(),
+            BigUint::from(1u64),
+            swap_tokens_order,
+        );
+        self.perform_swap_fixed_input(&mut swap_context, &mut storage_cache);
+
+        let swap_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(initial_k <= swap_k, ERROR_K_INVARIANT_FAILED);
+
+        if swap_context.fee_amount > 0 {
+            self.send_fee(
+                &mut storage_cache,
+                swap_context.swap_tokens_order,
+                &swap_context.input_token_id,
+                &swap_context.fee_amount,
+            );
+        }
+
+        let remaining_payment = EsdtTokenPayment::new(token_in, 0, amount_in - swap_amount);
+        let swapped_payment =
+            EsdtTokenPayment::new(token_out, 0, swap_context.final_output_amount.clone());
+        let (first_payment, second_payment) = match swap_tokens_order {
+            SwapTokensOrder::PoolOrder => (remaining_payment, swapped_payment),
+            SwapTokensOrder::ReverseOrder => (swapped_payment, remaining_payment),
+        };
+
+        let add_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let mut add_liq_context = AddLiquidityContext::new(
+            first_payment,
+            second_payment,
+            BigUint::from(1u64),
+            BigUint::from(1u64),
+        );
+        self.set_optimal_amounts(&mut add_liq_context, &storage_cache);
+        add_liq_context.liq_added = self.pool_add_liquidity(
+            &add_liq_context.first_token_optimal_amount,
+            &add_liq_context.second_token_optimal_amount,
+            &mut storage_cache,
+        );
+        require!(
+            add_liq_context.liq_added >= lp_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+
+        let new_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(add_k <= new_k, ERROR_K_INVARIANT_FAILED);
+
+        self.send()
+            .esdt_local_mint(&storage_cache.lp_token_id, 0, &add_liq_context.liq_added);
+
+        let output_payments = self.build_add_liq_output_payments(&storage_cache, &add_liq_context);
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        let output = self.build_add_liq_results(&storage_cache, &add_liq_context);
+
+        self.emit_swap_event(&storage_cache, swap_context);
+        self.emit_add_liquidity_event(&storage_cache, add_liq_context);
+
+        output
+    }
+
     #[payable("*")]
     #[endpoint(removeLiquidity)]
     fn remove_liquidity(
@@ -268,6 +414,7 @@ pub trait Pair<ContractReader>:
             first_token_amount_min > 0 && second_token_amount_min > 0,
             ERROR_INVALID_ARGS
         );
+        self.require_not_concentrated_liquidity();
 
         let mut storage_cache = StorageCache::new(self);
         let caller = self.blockchain().get_caller();
@@ -323,9 +470,121 @@ pub trait Pair<ContractReader>:
         self.build_remove_liq_results(output_payments)
     }
 
+    /// Removes liquidity and swaps the other token into `token_out`, through the pool itself,
+    /// with the usual fees. `amount_out_min` applies to the total amount sent back.
+    #[payable("*")]
+    #[endpoint(removeLiquidityToSingleToken)]
+    fn remove_liquidity_to_single_token(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment {
+        require!(amount_out_min > 0, ERROR_INVALID_ARGS);
+        self.require_not_concentrated_liquidity();
+
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+
+        require!(
+            self.can_swap(storage_cache.contract_state),
+            ERROR_SWAP_NOT_ENABLED
+        );
+        require!(
+            storage_cache.lp_token_id.is_valid_esdt_identifier(),
+            ERROR_LP_TOKEN_NOT_ISSUED
+        );
+        require!(
+            payment.token_identifier == storage_cache.lp_token_id && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+
+        let token_in = if token_out == storage_cache.first_token_id {
+            storage_cache.second_token_id.clone()
+        } else if token_out == storage_cache.second_token_id {
+            storage_cache.first_token_id.clone()
+        } else {
+            sc_panic!(ERROR_UNKNOWN_TOKEN);
+        };
+        let swap_tokens_order = storage_cache.get_swap_tokens_order(&token_in, &token_out);
+
+        self.update_safe_price(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        self.update_dynamic_fee();
+
+        let initial_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let mut remove_liq_context =
+            RemoveLiquidityContext::new(payment.amount, BigUint::from(1u64), BigUint::from(1u64));
+        self.pool_remove_liquidity(&mut remove_liq_context, &mut storage_cache);
+
+        let remove_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(remove_k <= initial_k, ERROR_K_INVARIANT_FAILED);
+
+        let (amount_to_swap, amount_kept) = match swap_tokens_order {
+            SwapTokensOrder::PoolOrder => (
+                remove_liq_context.first_token_amount_removed.clone(),
+                remove_liq_context.second_token_amount_removed.clone(),
+            ),
+            SwapTokensOrder::ReverseOrder => (
+                remove_liq_context.second_token_amount_removed.clone(),
+                remove_liq_context.first_token_amount_removed.clone(),
+            ),
+        };
+
+        let mut swap_context = SwapContext::new(
+            token_in,
+            amount_to_swap,
+            token_out.clone(),
+            BigUint::from(1u64),
+            swap_tokens_order,
+        );
+        self.perform_swap_fixed_input(&mut swap_context, &mut storage_cache);
+
+        let new_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(remove_k <= new_k, ERROR_K_INVARIANT_FAILED);
+
+        let amount_out = amount_kept + &swap_context.final_output_amount;
+        require!(amount_out >= amount_out_min, ERROR_SLIPPAGE_EXCEEDED);
+
+        if swap_context.fee_amount > 0 {
+            self.send_fee(
+                &mut storage_cache,
+                swap_context.swap_tokens_order,
+                &swap_context.input_token_id,
+                &swap_context.fee_amount,
+            );
+        }
+
+        self.burn(
+            &storage_cache.lp_token_id,
+            &remove_liq_context.lp_token_payment_amount,
+        );
+
+        self.send().direct_esdt(&caller, &token_out, 0, &amount_out);
+
+        self.emit_remove_liquidity_event(&storage_cache, remove_liq_context);
+        self.emit_swap_event(&storage_cache, swap_context);
+
+        EsdtTokenPayment::new(token_out, 0, amount_out)
+    }
+
     #[payable("*")]
     #[endpoint(removeLiquidityAndBuyBackAndBurnToken)]
     fn remove_liquidity_and_burn_token(&self, token_to_buyback_and_burn: TokenIdentifier) {
+        self.require_not_concentrated_liquidity();
+
         let mut storage_cache = StorageCache::new(self);
         let caller = self.blockchain().get_caller();
         let payment = self.call_value().single_esdt();
@@ -439,8 +698,10 @@ pub trait Pair<ContractReader>:
         &self,
         token_out: TokenIdentifier,
         amount_out_min: BigUint,
+        opt_deadline: OptionalValue<u64>,
     ) -> SwapTokensFixedInputResultType<Self::Api> {
         require!(amount_out_min > 0, ERROR_INVALID_ARGS);
+        self.require_deadline_not_passed(opt_deadline);
 
         let mut storage_cache = StorageCache::new(self);
         let (token_in, _, amount_in) = self.call_value().single_esdt().into_tuple();
@@ -458,6 +719,7 @@ pub trait Pair<ContractReader>:
             &storage_cache.first_token_reserve,
             &storage_cache.second_token_reserve,
         );
+        self.update_dynamic_fee();
 
         let initial_k = self.calculate_k_constant(
             &storage_cache.first_token_reserve,
@@ -503,8 +765,10 @@ pub trait Pair<ContractReader>:
         &self,
         token_out: TokenIdentifier,
         amount_out: BigUint,
+        opt_deadline: OptionalValue<u64>,
     ) -> SwapTokensFixedOutputResultType<Self::Api> {
         require!(amount_out > 0, ERROR_INVALID_ARGS);
+        self.require_deadline_not_passed(opt_deadline);
 
         let mut storage_cache = StorageCache::new(self);
         let (token_in, _, amount_in_max) = self.call_value().single_esdt().into_tuple();
@@ -522,6 +786,7 @@ pub trait Pair<ContractReader>:
             &storage_cache.first_token_reserve,
             &storage_cache.second_token_reserve,
         );
+        self.update_dynamic_fee();
 
         let initial_k = self.calculate_k_constant(
             &storage_cache.first_token_reserve,
@@ -608,6 +873,18 @@ pub trait Pair<ContractReader>:
         let first_token_reserve = self.pair_reserve(&first_token_id).get();
         let second_token_reserve = self.pair_reserve(&second_token_id).get();
 
+        if self.is_concentrated_liquidity() {
+            let swap_tokens_order = if token_in == first_token_id {
+                SwapTokensOrder::PoolOrder
+            } else if token_in == second_token_id {
+                SwapTokensOrder::ReverseOrder
+            } else {
+                sc_panic!(ERROR_UNKNOWN_TOKEN);
+            };
+
+            return self.get_concentrated_amount_out(swap_tokens_order, &amount_in);
+        }
+
         if token_in == first_token_id {
             require!(second_token_reserve > 0u64, ERROR_NOT_ENOUGH_RESERVE);
             let amount_out =
@@ -625,6 +902,59 @@ pub trait Pair<ContractReader>:
         }
     }
 
+    /// Same as `getAmountOut`, but gives 0 instead of failing,
+    /// so the router can compare pairs without knowing their liquidity.
+    #[view(tryGetAmountOut)]
+    fn try_get_amount_out_view(&self, token_in: TokenIdentifier, amount_in: BigUint) -> BigUint {
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let swap_tokens_order = if token_in == first_token_id {
+            SwapTokensOrder::PoolOrder
+        } else if token_in == second_token_id {
+            SwapTokensOrder::ReverseOrder
+        } else {
+            return BigUint::zero();
+        };
+        if amount_in == 0u64 {
+            return BigUint::zero();
+        }
+
+        if self.is_concentrated_liquidity() {
+            return self
+                .try_concentrated_swap(
+                    swap_tokens_order,
+                    &amount_in,
+                    true,
+                    true,
+                    self.special_fee_percent().get(),
+                    false,
+                )
+                .map(|(_, amount_out)| amount_out)
+                .unwrap_or_else(BigUint::zero);
+        }
+
+        let (reserve_in, reserve_out) = match swap_tokens_order {
+            SwapTokensOrder::PoolOrder => (
+                self.pair_reserve(&first_token_id).get(),
+                self.pair_reserve(&second_token_id).get(),
+            ),
+            SwapTokensOrder::ReverseOrder => (
+                self.pair_reserve(&second_token_id).get(),
+                self.pair_reserve(&first_token_id).get(),
+            ),
+        };
+        if reserve_in == 0u64 || reserve_out == 0u64 {
+            return BigUint::zero();
+        }
+
+        let amount_out = self.get_amount_out(&amount_in, &reserve_in, &reserve_out);
+        if amount_out >= reserve_out {
+            return BigUint::zero();
+        }
+
+        amount_out
+    }
+
     #[view(getAmountIn)]
     fn get_amount_in_view(&self, token_wanted: TokenIdentifier, amount_wanted: BigUint) -> BigUint {
         require!(amount_wanted > 0u64, ERROR_ZERO_AMOUNT);
@@ -634,6 +964,18 @@ pub trait Pair<ContractReader>:
         let first_token_reserve = self.pair_reserve(&first_token_id).get();
         let second_token_reserve = self.pair_reserve(&second_token_id).get();
 
+        if self.is_concentrated_liquidity() {
+            let swap_tokens_order = if token_wanted == second_token_id {
+                SwapTokensOrder::PoolOrder
+            } else if token_wanted == first_token_id {
+                SwapTokensOrder::ReverseOrder
+            } else {
+                sc_panic!(ERROR_UNKNOWN_TOKEN);
+            };
+
+            return self.get_concentrated_amount_in(swap_tokens_order, &amount_wanted);
+        }
+
         if token_wanted == first_token_id {
             require!(
                 first_token_reserve > amount_wanted,
@@ -666,6 +1008,18 @@ pub trait Pair<ContractReader>:
             return zero;
         }
 
+        if self.is_concentrated_liquidity() {
+            let swap_tokens_order = if token_in == first_token_id {
+                SwapTokensOrder::PoolOrder
+            } else if token_in == second_token_id {
+                SwapTokensOrder::ReverseOrder
+            } else {
+                sc_panic!(ERROR_UNKNOWN_TOKEN);
+            };
+
+            return self.get_concentrated_equivalent(swap_tokens_order, &amount_in);
+        }
+
         if token_in == first_token_id {
             self.quote(&amount_in, &first_token_reserve, &second_token_reserve)
         } else if token_in == second_token_id {
@@ -690,6 +1044,12 @@ pub trait Pair<ContractReader>:
         context: &mut SwapContext<Self::Api>,
         storage_cache: &mut StorageCache<Self>,
     ) {
+        if self.is_concentrated_liquidity() {
+            let fee_enabled = self.is_fee_enabled();
+            self.perform_concentrated_swap_fixed_input(context, storage_cache, fee_enabled);
+            return;
+        }
+
         context.final_input_amount = context.input_token_amount.clone();
 
         let reserve_in = storage_cache.get_reserve_in(context.swap_tokens_order);
@@ -724,6 +1084,12 @@ pub trait Pair<ContractReader>:
         context: &mut SwapContext<Self::Api>,
         storage_cache: &mut StorageCache<Self>,
     ) {
+        if self.is_concentrated_liquidity() {
+            let fee_enabled = self.is_fee_enabled();
+            self.perform_concentrated_swap_fixed_output(context, storage_cache, fee_enabled);
+            return;
+        }
+
         context.final_output_amount = context.output_token_amount.clone();
 
         let reserve_in = storage_cache.get_reserve_in(context.swap_tokens_order);
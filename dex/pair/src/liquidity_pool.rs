@@ -7,6 +7,7 @@ use crate::contexts::remove_liquidity::RemoveLiquidityContext;
 use crate::errors::*;
 
 use super::amm;
+use super::concentrated_liquidity;
 use super::config;
 
 const MINIMUM_LIQUIDITY: u64 = 1_000;
@@ -14,6 +15,9 @@ const MINIMUM_LIQUIDITY: u64 = 1_000;
 #[multiversx_sc::module]
 pub trait LiquidityPoolModule:
     amm::AmmModule
+    + concentrated_liquidity::ConcentratedLiquidityModule
+    + concentrated_liquidity::math::ConcentratedLiquidityMathModule
+    + concentrated_liquidity::ticks::ConcentratedLiquidityTicksModule
     + config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
@@ -198,6 +202,10 @@ pub trait LiquidityPoolModule:
         swap_tokens_order: SwapTokensOrder,
         amount_in: &BigUint,
     ) -> BigUint {
+        if self.is_concentrated_liquidity() {
+            return self.concentrated_swap_no_fee(storage_cache, swap_tokens_order, amount_in);
+        }
+
         let reserve_in = storage_cache.get_reserve_in(swap_tokens_order);
         let reserve_out = storage_cache.get_reserve_out(swap_tokens_order);
         require!(*reserve_in != 0, ERROR_ZERO_AMOUNT);
@@ -1,11 +1,18 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use crate::{amm, config, errors::ERROR_SAFE_PRICE_CURRENT_INDEX};
+use crate::{
+    amm,
+    concentrated_liquidity::{math::SQRT_PRICE_PRECISION, ticks},
+    config,
+    errors::{ERROR_BAD_SAFE_PRICE_OBSERVATION_CONFIG, ERROR_SAFE_PRICE_CURRENT_INDEX},
+};
 
 pub type Round = u64;
 
 pub const MAX_OBSERVATIONS: usize = 65_536; // 2^{16} records, to optimise binary search
+pub const MAX_CONFIGURABLE_OBSERVATIONS: usize = 1_048_576;
+pub const STALE_OBSERVATIONS_REMOVED_PER_UPDATE: usize = 10;
 
 #[derive(ManagedVecItem, Clone, TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
 pub struct PriceObservation<M: ManagedTypeApi> {
@@ -29,11 +36,48 @@ impl<M: ManagedTypeApi> Default for PriceObservation<M> {
 #[multiversx_sc::module]
 pub trait SafePriceModule:
     config::ConfigModule
+    + ticks::ConcentratedLiquidityTicksModule
     + token_send::TokenSendModule
     + amm::AmmModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
 {
+    /// Sets the minimum number of rounds between two observations, and the number
+    /// of observations kept. A new buffer size is only applied once the newest observations
+    /// fit in order at the start of the buffer, so existing observations are kept,
+    /// up to the new size, and remain valid. Until then, it stays pending.
+    #[endpoint(setSafePriceObservationConfig)]
+    fn set_safe_price_observation_config(
+        &self,
+        observation_interval: Round,
+        max_observations: usize,
+    ) {
+        self.require_caller_has_owner_or_admin_permissions();
+        require!(
+            observation_interval > 0
+                && max_observations > 1
+                && max_observations <= MAX_CONFIGURABLE_OBSERVATIONS,
+            ERROR_BAD_SAFE_PRICE_OBSERVATION_CONFIG
+        );
+
+        self.safe_price_observation_interval()
+            .set(observation_interval);
+
+        let pair_address = self.blockchain().get_sc_address();
+        if max_observations == self.get_max_observations(&pair_address) {
+            self.safe_price_pending_max_observations().clear();
+            return;
+        }
+
+        self.safe_price_pending_max_observations()
+            .set(max_observations);
+        let _ = self.try_apply_pending_max_observations(self.safe_price_current_index().get());
+    }
+
+    /// The reserves are accumulated on the first call of every round, while a new observation
+    /// is only recorded once the observation interval has passed since the newest one.
+    /// In between, the accumulated values are kept apart, so each reserve only weighs
+    /// for the rounds it was actually in place.
     fn update_safe_price(&self, first_token_reserve: &BigUint, second_token_reserve: &BigUint) {
         if first_token_reserve == &0u64 || second_token_reserve == &0u64 {
             return;
@@ -41,37 +85,175 @@ pub trait SafePriceModule:
 
         let current_round = self.blockchain().get_block_round();
         let safe_price_current_index = self.safe_price_current_index().get();
-        require!(
-            safe_price_current_index <= MAX_OBSERVATIONS,
-            ERROR_SAFE_PRICE_CURRENT_INDEX
-        );
 
         let mut price_observations = self.price_observations();
         let mut last_price_observation = PriceObservation::default();
-        let mut new_index = 1;
         if !price_observations.is_empty() {
             last_price_observation = price_observations.get(safe_price_current_index);
-            new_index = (safe_price_current_index % MAX_OBSERVATIONS) + 1;
         }
 
-        if last_price_observation.recording_round == current_round {
+        let unrecorded_observation_mapper = self.safe_price_unrecorded_observation();
+        let accumulated_observation = if unrecorded_observation_mapper.is_empty() {
+            last_price_observation.clone()
+        } else {
+            unrecorded_observation_mapper.get()
+        };
+        if accumulated_observation.recording_round == current_round {
             return;
         }
 
-        let new_price_observation = self.compute_new_observation(
-            current_round,
+        let (first_price_reserve, second_price_reserve) = self.get_price_reserves(
+            &self.blockchain().get_sc_address(),
             first_token_reserve,
             second_token_reserve,
-            &last_price_observation,
+        );
+        let new_price_observation = self.compute_new_observation(
+            current_round,
+            &first_price_reserve,
+            &second_price_reserve,
+            &accumulated_observation,
+        );
+
+        if !price_observations.is_empty()
+            && current_round
+                < last_price_observation.recording_round + self.get_observation_interval()
+        {
+            unrecorded_observation_mapper.set(&new_price_observation);
+            return;
+        }
+
+        let max_observations = self.try_apply_pending_max_observations(safe_price_current_index);
+        require!(
+            safe_price_current_index <= max_observations,
+            ERROR_SAFE_PRICE_CURRENT_INDEX
         );
 
-        if price_observations.len() == MAX_OBSERVATIONS {
+        let new_index = (safe_price_current_index % max_observations) + 1;
+        if new_index <= price_observations.len() {
             price_observations.set(new_index, &new_price_observation);
         } else {
             price_observations.push(&new_price_observation);
         }
 
         self.safe_price_current_index().set(new_index);
+        unrecorded_observation_mapper.clear();
+        self.remove_stale_observations(&mut price_observations, max_observations);
+    }
+
+    /// Concentrated liquidity reserves do not follow the price, so their observations use
+    /// the virtual reserves of a fixed liquidity L at the current square root price P instead:
+    /// L / P for the first token and L * P for the second one.
+    fn get_price_reserves(
+        &self,
+        pair_address: &ManagedAddress,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+    ) -> (BigUint, BigUint) {
+        if self.tick_spacing().get_from_address(pair_address) == 0 {
+            return (first_token_reserve.clone(), second_token_reserve.clone());
+        }
+
+        let sqrt_price = self.current_sqrt_price().get_from_address(pair_address);
+        let virtual_liquidity = BigUint::from(SQRT_PRICE_PRECISION);
+        let first_virtual_reserve = &virtual_liquidity * SQRT_PRICE_PRECISION / &sqrt_price;
+        let second_virtual_reserve = virtual_liquidity * &sqrt_price / SQRT_PRICE_PRECISION;
+
+        (first_virtual_reserve, second_virtual_reserve)
+    }
+
+    /// Growing is applied when the newest observation is the last one stored,
+    /// and shrinking when the newest observation is the last one that fits in the new size.
+    /// In both cases, the observations up to the newest one are in order from the first index.
+    fn try_apply_pending_max_observations(&self, safe_price_current_index: usize) -> usize {
+        let pair_address = self.blockchain().get_sc_address();
+        let max_observations = self.get_max_observations(&pair_address);
+        let pending_mapper = self.safe_price_pending_max_observations();
+        if pending_mapper.is_empty() {
+            return max_observations;
+        }
+
+        let pending_max_observations = pending_mapper.get();
+        let stored_observations = self.price_observations().len();
+        if pending_max_observations < stored_observations
+            && stored_observations < max_observations
+            && safe_price_current_index == stored_observations
+        {
+            // the buffer is not full yet, so it is first limited to the stored observations,
+            // to start reusing them sooner
+            self.safe_price_max_observations().set(stored_observations);
+            return stored_observations;
+        }
+
+        let can_grow = pending_max_observations > max_observations
+            && stored_observations <= max_observations
+            && safe_price_current_index == stored_observations;
+        let can_shrink = pending_max_observations < max_observations
+            && (safe_price_current_index == pending_max_observations
+                || (safe_price_current_index == stored_observations
+                    && stored_observations <= pending_max_observations));
+        if !can_grow && !can_shrink {
+            return max_observations;
+        }
+
+        self.safe_price_max_observations()
+            .set(pending_max_observations);
+        pending_mapper.clear();
+
+        pending_max_observations
+    }
+
+    /// Observations left past the buffer size after shrinking it are removed
+    /// a few at a time, on each new observation.
+    fn remove_stale_observations(
+        &self,
+        price_observations: &mut VecMapper<PriceObservation<Self::Api>>,
+        max_observations: usize,
+    ) {
+        for _ in 0..STALE_OBSERVATIONS_REMOVED_PER_UPDATE {
+            let stored_observations = price_observations.len();
+            if stored_observations <= max_observations {
+                return;
+            }
+
+            price_observations.swap_remove(stored_observations);
+        }
+    }
+
+    fn get_observation_interval(&self) -> Round {
+        let observation_interval = self.safe_price_observation_interval().get();
+        if observation_interval == 0 {
+            return 1;
+        }
+
+        observation_interval
+    }
+
+    #[view(getSafePriceMaxObservations)]
+    fn get_safe_price_max_observations_view(&self) -> usize {
+        self.get_max_observations(&self.blockchain().get_sc_address())
+    }
+
+    fn get_max_observations(&self, pair_address: &ManagedAddress) -> usize {
+        let max_observations = self
+            .safe_price_max_observations()
+            .get_from_address(pair_address);
+        if max_observations == 0 {
+            return MAX_OBSERVATIONS;
+        }
+
+        max_observations
+    }
+
+    /// Number of observations in use, which excludes the stale ones left after shrinking.
+    fn get_observations_len(
+        &self,
+        pair_address: &ManagedAddress,
+        price_observations: &VecMapper<Self::Api, PriceObservation<Self::Api>>,
+    ) -> usize {
+        core::cmp::min(
+            price_observations.len_at_address(pair_address),
+            self.get_max_observations(pair_address),
+        )
     }
 
     fn compute_new_observation(
@@ -98,9 +280,27 @@ pub trait SafePriceModule:
         new_price_observation
     }
 
+    /// Minimum number of rounds between two observations, 1 if not set.
+    #[view(getSafePriceObservationInterval)]
+    #[storage_mapper("safe_price_observation_interval")]
+    fn safe_price_observation_interval(&self) -> SingleValueMapper<Round>;
+
+    /// `MAX_OBSERVATIONS` if not set.
+    #[storage_mapper("safe_price_max_observations")]
+    fn safe_price_max_observations(&self) -> SingleValueMapper<usize>;
+
+    #[view(getSafePricePendingMaxObservations)]
+    #[storage_mapper("safe_price_pending_max_observations")]
+    fn safe_price_pending_max_observations(&self) -> SingleValueMapper<usize>;
+
     #[storage_mapper("price_observations")]
     fn price_observations(&self) -> VecMapper<PriceObservation<Self::Api>>;
 
+    /// Accumulated values since the newest observation, until the next one is recorded.
+    /// Empty when the newest observation is up to date.
+    #[storage_mapper("safe_price_unrecorded_observation")]
+    fn safe_price_unrecorded_observation(&self) -> SingleValueMapper<PriceObservation<Self::Api>>;
+
     #[view(getSafePriceCurrentIndex)]
     #[storage_mapper("safe_price_current_index")]
     fn safe_price_current_index(&self) -> SingleValueMapper<usize>;
@@ -4,18 +4,20 @@ use common_errors::{ERROR_BAD_INPUT_TOKEN, ERROR_PARAMETERS};
 use core::cmp::Ordering;
 
 use crate::{
-    amm, config,
+    amm, concentrated_liquidity, config,
     errors::{ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST, ERROR_SAFE_PRICE_SAME_ROUNDS},
-    safe_price::{self, PriceObservation, Round, MAX_OBSERVATIONS},
+    safe_price::{self, PriceObservation, Round},
 };
 
 pub const DEFAULT_SAFE_PRICE_ROUNDS_OFFSET: u64 = 10 * 60;
 pub const SECONDS_PER_ROUND: u64 = 6;
+pub const STABLE_SWAP_PRICE_PROBE_DIVISOR: u64 = 10_000;
 
 #[multiversx_sc::module]
 pub trait SafePriceViewModule:
     safe_price::SafePriceModule
     + config::ConfigModule
+    + concentrated_liquidity::ticks::ConcentratedLiquidityTicksModule
     + token_send::TokenSendModule
     + amm::AmmModule
     + permissions_module::PermissionsModule
@@ -274,6 +276,28 @@ pub trait SafePriceViewModule:
         )
     }
 
+    /// None if the pair has no observations yet. Unlike the other views, it does not fail,
+    /// so callers can keep their ranges within the observations the pair still holds.
+    #[label("safe-price-view")]
+    #[view(getOldestPriceObservationRound)]
+    fn get_oldest_price_observation_round(&self, pair_address: ManagedAddress) -> Option<Round> {
+        let price_observations = self.price_observations();
+        if price_observations.is_empty_at_address(&pair_address) {
+            return None;
+        }
+
+        let safe_price_current_index = self
+            .safe_price_current_index()
+            .get_from_address(&pair_address);
+        let oldest_price_observation = self.get_oldest_price_observation(
+            &pair_address,
+            safe_price_current_index,
+            &price_observations,
+        );
+
+        Some(oldest_price_observation.recording_round)
+    }
+
     fn compute_weighted_price(
         &self,
         pair_address: &ManagedAddress,
@@ -284,22 +308,61 @@ pub trait SafePriceViewModule:
         let first_token_id = self.first_token_id().get_from_address(pair_address);
         let second_token_id = self.second_token_id().get_from_address(pair_address);
 
+        let amplification_coefficient = self
+            .amplification_coefficient()
+            .get_from_address(pair_address);
+
         let (weighted_first_token_reserve, weighted_second_token_reserve) =
             self.compute_weighted_reserves(first_price_observation, last_price_observation);
 
         if input_payment.token_identifier == first_token_id {
-            let output_amount =
-                input_payment.amount * weighted_second_token_reserve / weighted_first_token_reserve;
+            let output_amount = self.compute_weighted_output_amount(
+                &input_payment.amount,
+                &weighted_first_token_reserve,
+                &weighted_second_token_reserve,
+                amplification_coefficient,
+            );
             EsdtTokenPayment::new(second_token_id, 0, output_amount)
         } else if input_payment.token_identifier == second_token_id {
-            let output_amount =
-                input_payment.amount * weighted_first_token_reserve / weighted_second_token_reserve;
+            let output_amount = self.compute_weighted_output_amount(
+                &input_payment.amount,
+                &weighted_second_token_reserve,
+                &weighted_first_token_reserve,
+                amplification_coefficient,
+            );
             EsdtTokenPayment::new(first_token_id, 0, output_amount)
         } else {
             sc_panic!(ERROR_BAD_INPUT_TOKEN);
         }
     }
 
+    /// Stable swap pairs are priced at the marginal rate of the curve,
+    /// estimated by swapping a small fraction of the weighted reserve.
+    fn compute_weighted_output_amount(
+        &self,
+        amount_in: &BigUint,
+        weighted_reserve_in: &BigUint,
+        weighted_reserve_out: &BigUint,
+        amplification_coefficient: u64,
+    ) -> BigUint {
+        if amplification_coefficient == 0 {
+            return amount_in * weighted_reserve_out / weighted_reserve_in;
+        }
+
+        let mut probe_amount = weighted_reserve_in / STABLE_SWAP_PRICE_PROBE_DIVISOR;
+        if probe_amount == 0 {
+            probe_amount = BigUint::from(1u64);
+        }
+        let probe_output = self.get_stable_swap_amount_out(
+            &probe_amount,
+            weighted_reserve_in,
+            weighted_reserve_out,
+            amplification_coefficient,
+        );
+
+        amount_in * &probe_output / probe_amount
+    }
+
     fn get_price_observation(
         &self,
         pair_address: &ManagedAddress,
@@ -330,17 +393,39 @@ pub trait SafePriceViewModule:
                 ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST
             );
 
-            let first_token_reserve = self
-                .pair_reserve(first_token_id)
-                .get_from_address(pair_address);
-            let second_token_reserve = self
-                .pair_reserve(second_token_id)
-                .get_from_address(pair_address);
+            // The values accumulated since the last observation, if any, are used as well
+            let unrecorded_observation_mapper = self.safe_price_unrecorded_observation();
+            let mut newest_observation = last_observation;
+            if !unrecorded_observation_mapper.is_empty_at_address(pair_address) {
+                let unrecorded_observation =
+                    unrecorded_observation_mapper.get_from_address(pair_address);
+                match unrecorded_observation.recording_round.cmp(&search_round) {
+                    Ordering::Equal => return unrecorded_observation,
+                    Ordering::Greater => {
+                        return self.interpolate_price_observations(
+                            &newest_observation,
+                            &unrecorded_observation,
+                            search_round,
+                        )
+                    }
+                    Ordering::Less => newest_observation = unrecorded_observation,
+                }
+            }
+
+            let (first_token_reserve, second_token_reserve) = self.get_price_reserves(
+                pair_address,
+                &self
+                    .pair_reserve(first_token_id)
+                    .get_from_address(pair_address),
+                &self
+                    .pair_reserve(second_token_id)
+                    .get_from_address(pair_address),
+            );
             return self.compute_new_observation(
                 search_round,
                 &first_token_reserve,
                 &second_token_reserve,
-                &last_observation,
+                &newest_observation,
             );
         }
 
@@ -378,8 +463,9 @@ pub trait SafePriceViewModule:
 
         // VecMapper index starts at 1
         let mut oldest_observation_index = 1;
-        if price_observations.len_at_address(pair_address) == MAX_OBSERVATIONS {
-            oldest_observation_index = (current_index % MAX_OBSERVATIONS) + 1
+        let max_observations = self.get_max_observations(pair_address);
+        if self.get_observations_len(pair_address, price_observations) == max_observations {
+            oldest_observation_index = (current_index % max_observations) + 1
         }
         price_observations.get_at_address(pair_address, oldest_observation_index)
     }
@@ -400,7 +486,7 @@ pub trait SafePriceViewModule:
             right_index = current_index - 1;
         } else {
             left_index = current_index + 1;
-            right_index = price_observations.len_at_address(pair_address);
+            right_index = self.get_observations_len(pair_address, price_observations);
         }
 
         while left_index <= right_index {
@@ -423,17 +509,18 @@ pub trait SafePriceViewModule:
         search_round: Round,
         search_index: usize,
     ) -> PriceObservation<Self::Api> {
+        let max_observations = self.get_max_observations(pair_address);
         let last_found_observation = price_observations.get_at_address(pair_address, search_index);
         let left_observation;
         let right_observation;
         if last_found_observation.recording_round < search_round {
             left_observation = last_found_observation;
-            let right_observation_index = (search_index % MAX_OBSERVATIONS) + 1;
+            let right_observation_index = (search_index % max_observations) + 1;
             right_observation =
                 price_observations.get_at_address(pair_address, right_observation_index);
         } else {
             let left_observation_index = if search_index == 1 {
-                MAX_OBSERVATIONS
+                max_observations
             } else {
                 search_index - 1
             };
@@ -442,6 +529,15 @@ pub trait SafePriceViewModule:
             right_observation = last_found_observation;
         };
 
+        self.interpolate_price_observations(&left_observation, &right_observation, search_round)
+    }
+
+    fn interpolate_price_observations(
+        &self,
+        left_observation: &PriceObservation<Self::Api>,
+        right_observation: &PriceObservation<Self::Api>,
+        search_round: Round,
+    ) -> PriceObservation<Self::Api> {
         // For a proper linear interpolation calculation, we compute the weights as follows
         // Left observation has a weight equal to the remaining time, starting from the searched round until the end round
         // Right observation has a weight equal to the elapsed time, from starting round until the searched round
@@ -450,11 +546,11 @@ pub trait SafePriceViewModule:
 
         let weight_sum = left_weight + right_weight;
         let first_token_reserve_sum = BigUint::from(left_weight)
-            * left_observation.first_token_reserve_accumulated
-            + BigUint::from(right_weight) * right_observation.first_token_reserve_accumulated;
+            * &left_observation.first_token_reserve_accumulated
+            + BigUint::from(right_weight) * &right_observation.first_token_reserve_accumulated;
         let second_token_reserve_sum = BigUint::from(left_weight)
-            * left_observation.second_token_reserve_accumulated
-            + BigUint::from(right_weight) * right_observation.second_token_reserve_accumulated;
+            * &left_observation.second_token_reserve_accumulated
+            + BigUint::from(right_weight) * &right_observation.second_token_reserve_accumulated;
 
         let first_token_reserve_accumulated = first_token_reserve_sum / weight_sum;
         let second_token_reserve_accumulated = second_token_reserve_sum / weight_sum;
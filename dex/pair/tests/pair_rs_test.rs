@@ -2,19 +2,32 @@
 
 mod pair_setup;
 use fees_collector::{
-    config::ConfigModule, fees_accumulation::FeesAccumulationModule, FeesCollector,
+    config::ConfigModule, fees_accumulation::FeesAccumulationModule,
+    protocol_liquidity::ProtocolLiquidityModule, FeesCollector,
 };
 use multiversx_sc::{
     codec::multi_types::OptionalValue,
     storage::mappers::StorageTokenWrapper,
-    types::{EsdtLocalRole, MultiValueEncoded},
+    types::{Address, EsdtLocalRole, MultiValueEncoded},
 };
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped, rust_biguint,
     whitebox_legacy::TxTokenTransfer, DebugApi,
 };
 // use pair::safe_price::MAX_OBSERVATIONS;
-use pair::{config::MAX_PERCENTAGE, fee::FeeModule, locking_wrapper::LockingWrapperModule, Pair};
+use pair::{
+    concentrated_liquidity::{
+        farming::ConcentratedLiquidityFarmingModule, ticks::ConcentratedLiquidityTicksModule,
+    },
+    config::MAX_PERCENTAGE,
+    dynamic_fee::DynamicFeeModule,
+    fee::FeeModule,
+    fee_batching::FeeBatchingModule,
+    locking_wrapper::LockingWrapperModule,
+    safe_price::SafePriceModule,
+    safe_price_view::SafePriceViewModule,
+    Pair,
+};
 use pair_setup::*;
 use simple_lock::{
     locked_token::{LockedTokenAttributes, LockedTokenModule},
@@ -58,6 +71,599 @@ fn test_swap_fixed_output() {
     pair_setup.swap_fixed_output(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, 96);
 }
 
+#[test]
+fn test_swap_deadline() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+    pair_setup.b_mock.set_block_timestamp(1_000);
+
+    let user = pair_setup.user_address.clone();
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(900),
+                    OptionalValue::Some(999),
+                );
+            },
+        )
+        .assert_user_error("Deadline passed");
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_output(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(900),
+                    OptionalValue::Some(999),
+                );
+            },
+        )
+        .assert_user_error("Deadline passed");
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let ret = sc.swap_tokens_fixed_input(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(900),
+                    OptionalValue::Some(1_000),
+                );
+                assert_eq!(ret.amount, managed_biguint!(996));
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn test_single_token_liquidity() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let user = pair_setup.user_address.clone();
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let _ = sc.add_liquidity_single_token(managed_biguint!(48_736));
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+
+    // 48_883 WEGLD are swapped for 46_473 MEX, and 2 WEGLD are sent back
+    pair_setup.add_liquidity_single_token(WEGLD_TOKEN_ID, 100_000, 48_735, 48_735);
+    pair_setup.b_mock.check_esdt_balance(
+        &user,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - 1_001_000 - 100_000 + 2),
+    );
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&user, LP_TOKEN_ID, &rust_biguint!(1_048_735));
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &pair_setup.pair_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(50_000),
+            |sc| {
+                let _ = sc.remove_liquidity_to_single_token(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(102_242),
+                );
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+
+    // 52_441 WEGLD and 47_678 MEX are removed, and the MEX are swapped for 49_800 WEGLD
+    pair_setup.remove_liquidity_to_single_token(50_000, WEGLD_TOKEN_ID, 102_241, 102_241);
+    pair_setup.b_mock.check_esdt_balance(
+        &user,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - 1_001_000 - 100_000 + 2 + 102_241),
+    );
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&user, LP_TOKEN_ID, &rust_biguint!(998_735));
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let (first_token_reserve, second_token_reserve, lp_token_supply) =
+                sc.get_reserves_and_total_supply().into_tuple();
+            assert_eq!(first_token_reserve, managed_biguint!(998_757));
+            assert_eq!(second_token_reserve, managed_biguint!(1_001_000));
+            assert_eq!(lp_token_supply, managed_biguint!(999_735));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_flash_swap() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let receiver_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        None,
+        flash_swap_receiver_mock::contract_obj,
+        "flash swap receiver mock path",
+    );
+    let receiver_addr = receiver_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&receiver_addr, WEGLD_TOKEN_ID, &rust_biguint!(1_000));
+
+    let user = pair_setup.user_address.clone();
+    pair_setup
+        .flash_swap(&user, 100_000, 0)
+        .assert_user_error("Flash swap receiver must be a contract");
+    pair_setup
+        .flash_swap(&receiver_addr, 1_001_000, 0)
+        .assert_user_error("Not enough reserve");
+    pair_setup
+        .flash_swap(&receiver_addr, 100_000, 1)
+        .assert_user_error("Flash swap not repaid");
+
+    // the whole 300 fee stays in the reserves, as there is no fee destination
+    pair_setup
+        .flash_swap(&receiver_addr, 100_000, 0)
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&receiver_addr, WEGLD_TOKEN_ID, &rust_biguint!(700));
+    pair_setup.b_mock.check_esdt_balance(
+        pair_setup.pair_wrapper.address_ref(),
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(1_001_300),
+    );
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let (first_token_reserve, second_token_reserve, _) =
+                sc.get_reserves_and_total_supply().into_tuple();
+            assert_eq!(first_token_reserve, managed_biguint!(1_001_300));
+            assert_eq!(second_token_reserve, managed_biguint!(1_001_000));
+        })
+        .assert_ok();
+
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, 995);
+}
+
+#[test]
+fn test_stable_swap() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.set_amplification_coefficient(100);
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    // a constant product pair would only return 90_669
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 99_000, 99_600);
+
+    let user = pair_setup.user_address.clone();
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let _ = sc.add_liquidity_single_token(managed_biguint!(1));
+            },
+        )
+        .assert_user_error("Not available for stable swap pairs");
+
+    // the curve can no longer be changed once liquidity was added
+    let rust_zero = rust_biguint!(0u64);
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                pair::config::ConfigModule::set_amplification_coefficient(&sc, 200);
+            },
+        )
+        .assert_user_error("Amplification coefficient can only be set before adding liquidity");
+}
+
+#[test]
+fn test_stable_swap_fixed_output() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.set_amplification_coefficient(100);
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup.swap_fixed_output(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 90_000, 9_647);
+}
+
+#[test]
+fn test_dynamic_fee() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+    pair_setup.set_dynamic_fee_config(100, 1_000, 10, 10_000);
+
+    // not enough observations yet, the configured fee is used
+    pair_setup.b_mock.set_block_round(1);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 10_000, MEX_TOKEN_ID, 9_000, 9_871);
+    pair_setup.check_dynamic_fee_percent(0);
+
+    // the price did not move in the last 4 windows, so the fee goes down to the lower bound
+    pair_setup.b_mock.set_block_round(41);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 10_000, MEX_TOKEN_ID, 9_000, 9_697);
+    pair_setup.check_dynamic_fee_percent(100);
+
+    // the fee is only recomputed once per round
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100, MEX_TOKEN_ID, 1, 96);
+    pair_setup.check_dynamic_fee_percent(100);
+
+    // the previous swaps slightly moved the price
+    pair_setup.b_mock.set_block_round(51);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 500_000, MEX_TOKEN_ID, 300_000, 322_232);
+    pair_setup.check_dynamic_fee_percent(158);
+
+    // the large swap is above the volatility cap, so the fee goes up to the upper bound
+    pair_setup.b_mock.set_block_round(81);
+    pair_setup.swap_fixed_input(MEX_TOKEN_ID, 10_000, WEGLD_TOKEN_ID, 20_000, 22_509);
+    pair_setup.check_dynamic_fee_percent(1_000);
+
+    // the configured fee is left untouched
+    pair_setup.check_total_fee_percent(300);
+
+    // the lower bound cannot be below the special fee
+    let rust_zero = rust_biguint!(0u64);
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.set_dynamic_fee_config(40, 1_000, 10, 10_000);
+            },
+        )
+        .assert_user_error("Bad dynamic fee config");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.disable_dynamic_fee();
+            },
+        )
+        .assert_ok();
+
+    // the swaps go back to the configured fee
+    pair_setup.b_mock.set_block_round(91);
+    pair_setup.swap_fixed_input(MEX_TOKEN_ID, 10_000, WEGLD_TOKEN_ID, 20_000, 22_001);
+    pair_setup.check_dynamic_fee_percent(0);
+    pair_setup.check_total_fee_percent(300);
+}
+
+#[test]
+fn test_concentrated_liquidity() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_concentrated_liquidity(10, 0);
+
+    pair_setup.add_concentrated_liquidity(-1_000, 1_000, 1_000_000, 1_000_000, 1, 20_505_166, 0, 0);
+
+    // the router's quote gives 0 instead of failing when the liquidity runs out
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(
+                sc.try_get_amount_out_view(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(100_000_000)
+                ),
+                managed_biguint!(0)
+            );
+            assert_eq!(
+                sc.try_get_amount_out_view(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(10_000)
+                ),
+                managed_biguint!(9_965)
+            );
+        })
+        .assert_ok();
+
+    // a constant product pair with the same reserves would only return 9_871
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 10_000, MEX_TOKEN_ID, 9_900, 9_965);
+    pair_setup.swap_fixed_output(MEX_TOKEN_ID, 10_000, WEGLD_TOKEN_ID, 5_000, 4_987);
+
+    // the accrued fees are paid together with the liquidity
+    pair_setup.remove_concentrated_liquidity(1, 20_505_166, 1_004_998, 995_045);
+
+    let payments = vec![
+        TxTokenTransfer {
+            token_identifier: WEGLD_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(1_000_000),
+        },
+        TxTokenTransfer {
+            token_identifier: MEX_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(1_000_000),
+        },
+    ];
+    pair_setup
+        .b_mock
+        .execute_esdt_multi_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            &payments,
+            |sc| {
+                let _ = sc.add_liquidity(managed_biguint!(1), managed_biguint!(1));
+            },
+        )
+        .assert_user_error("Not available for concentrated liquidity pairs");
+}
+
+#[test]
+fn test_concentrated_liquidity_swap_no_fee() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_concentrated_liquidity(10, 0);
+
+    pair_setup.add_concentrated_liquidity(-1_000, 1_000, 1_000_000, 1_000_000, 1, 20_505_166, 0, 0);
+
+    let owner = pair_setup.owner_address.clone();
+    let user = pair_setup.user_address.clone();
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.whitelist_endpoint(managed_address!(&user));
+        })
+        .assert_ok();
+
+    let mex_balance_before = pair_setup.b_mock.get_esdt_balance(&user, MEX_TOKEN_ID, 0);
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                sc.swap_no_fee(managed_token_id!(MEX_TOKEN_ID), managed_address!(&user));
+            },
+        )
+        .assert_ok();
+
+    // the same swap with fees only returns 9_965, and the positions accrue nothing
+    let mex_balance_after = pair_setup.b_mock.get_esdt_balance(&user, MEX_TOKEN_ID, 0);
+    assert!(mex_balance_after - mex_balance_before > rust_biguint!(9_965));
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.fee_growth_global_first().get(), managed_biguint!(0));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_concentrated_liquidity_price_ranges() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_concentrated_liquidity(10, 0);
+
+    pair_setup.add_concentrated_liquidity(-1_000, 1_000, 1_000_000, 1_000_000, 1, 20_505_166, 0, 0);
+
+    // above the current price, only the first token is needed
+    pair_setup.add_concentrated_liquidity(1_000, 2_000, 100_000, 100, 2, 2_155_643, 0, 100);
+
+    // the swap uses up the first position, then continues in the second one
+    pair_setup.swap_fixed_input(
+        MEX_TOKEN_ID,
+        1_100_000,
+        WEGLD_TOKEN_ID,
+        1_000_000,
+        1_040_296,
+    );
+
+    pair_setup.remove_concentrated_liquidity(2, 2_155_643, 59_701, 45_564);
+    pair_setup.remove_concentrated_liquidity(1, 20_505_166, 0, 2_054_429);
+}
+
+#[test]
+fn test_concentrated_liquidity_safe_price() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_concentrated_liquidity(10, 0);
+
+    // the reserves are no longer balanced, while the price stays 1
+    pair_setup.add_concentrated_liquidity(-1_000, 1_000, 1_000_000, 1_000_000, 1, 20_505_166, 0, 0);
+    pair_setup.add_concentrated_liquidity(1_000, 2_000, 100_000, 100, 2, 2_155_643, 0, 100);
+
+    pair_setup.b_mock.set_block_round(10);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, 996);
+
+    // observations use the virtual reserves at the square root price before the swap
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let observation = sc.price_observations().get(1);
+            assert_eq!(
+                observation.first_token_reserve_accumulated,
+                managed_biguint!(1_000_000_000_000_000_000)
+            );
+            assert_eq!(
+                observation.second_token_reserve_accumulated,
+                managed_biguint!(1_000_000_000_000_000_000)
+            );
+            assert_eq!(observation.weight_accumulated, 1);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_concentrated_liquidity_farming_wrapper() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_concentrated_liquidity(10, 0);
+
+    pair_setup.add_concentrated_liquidity(-1_000, 1_000, 1_000_000, 1_000_000, 1, 20_505_166, 0, 0);
+    pair_setup.add_concentrated_liquidity(-100, 100, 1_000, 1_000, 2, 200_510, 0, 0);
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            POSITION_TOKEN_ID,
+            1,
+            &rust_biguint!(20_505_166),
+            |sc| {
+                let _ = sc.wrap_position_for_farming();
+            },
+        )
+        .assert_user_error("Position farming range not set");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_position_farming_range(-1_000, 1_000);
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_position_farming_range(-100, 100);
+            },
+        )
+        .assert_user_error("Position farming range already set");
+
+    // narrower ranges would get more farming tokens for the same amounts
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            POSITION_TOKEN_ID,
+            2,
+            &rust_biguint!(200_510),
+            |sc| {
+                let _ = sc.wrap_position_for_farming();
+            },
+        )
+        .assert_user_error("Position range is not the farming range");
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            POSITION_TOKEN_ID,
+            1,
+            &rust_biguint!(20_505_166),
+            |sc| {
+                let farming_tokens = sc.wrap_position_for_farming();
+                assert_eq!(
+                    farming_tokens.token_identifier,
+                    managed_token_id!(POSITION_FARMING_TOKEN_ID)
+                );
+                assert_eq!(farming_tokens.amount, managed_biguint!(20_505_166));
+            },
+        )
+        .assert_ok();
+
+    pair_setup.b_mock.check_esdt_balance(
+        &pair_setup.user_address,
+        POSITION_FARMING_TOKEN_ID,
+        &rust_biguint!(20_505_166),
+    );
+
+    // farming tokens can only be unwrapped into the deposited positions
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            POSITION_FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.unwrap_position_from_farming(2);
+            },
+        )
+        .assert_user_error("Not enough wrapped position tokens");
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            POSITION_FARMING_TOKEN_ID,
+            0,
+            &rust_biguint!(20_505_166),
+            |sc| {
+                let position = sc.unwrap_position_from_farming(1);
+                assert_eq!(position.token_nonce, 1);
+                assert_eq!(position.amount, managed_biguint!(20_505_166));
+            },
+        )
+        .assert_ok();
+
+    pair_setup.remove_concentrated_liquidity(1, 20_505_166, 999_999, 999_999);
+}
+
 #[test]
 fn test_safe_price() {
     let mut pair_setup = PairSetup::new(pair::contract_obj);
@@ -727,14 +1333,190 @@ fn test_both_legacy_and_new_safe_price_from_other_contract() {
         expected_token_payment_amount,
     );
 
-    // Check legacy endpoint
-    // Should be the same as the result from the new get_safe_price view
-    pair_setup.check_safe_price_from_legacy_endpoint(
-        WEGLD_TOKEN_ID,
-        first_token_payment_amount,
-        MEX_TOKEN_ID,
-        expected_token_payment_amount,
-    );
+    // Check legacy endpoint
+    // Should be the same as the result from the new get_safe_price view
+    pair_setup.check_safe_price_from_legacy_endpoint(
+        WEGLD_TOKEN_ID,
+        first_token_payment_amount,
+        MEX_TOKEN_ID,
+        expected_token_payment_amount,
+    );
+}
+
+#[test]
+fn test_safe_price_observation_config() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let pair_address = pair_setup.pair_wrapper.address_ref().clone();
+    let owner = pair_setup.owner_address.clone();
+    let user = pair_setup.user_address.clone();
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup
+        .b_mock
+        .execute_tx(&user, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_safe_price_observation_config(5, 4);
+        })
+        .assert_user_error("Permission denied");
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_safe_price_observation_config(0, 4);
+        })
+        .assert_user_error("Bad safe price observation config");
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_safe_price_observation_config(5, 1);
+        })
+        .assert_user_error("Bad safe price observation config");
+
+    // no observations yet, so the new size is applied right away
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_safe_price_observation_config(5, 4);
+        })
+        .assert_ok();
+    check_safe_price_observations(&mut pair_setup, 0, 0, 4, 0);
+
+    // one observation every 5 rounds at most, so the swap at round 12 records none
+    let mut expected_amount = 996;
+    for block_round in [10, 12, 15, 20, 25, 30] {
+        pair_setup.b_mock.set_block_round(block_round);
+        pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, expected_amount);
+        expected_amount -= 2;
+    }
+    check_safe_price_observations(&mut pair_setup, 1, 4, 4, 0);
+    check_oldest_observation_round(&mut pair_setup, &pair_address, 15);
+
+    // growing waits until the newest observation is the last one stored
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_safe_price_observation_config(5, 6);
+        })
+        .assert_ok();
+    for block_round in [35, 40, 45] {
+        pair_setup.b_mock.set_block_round(block_round);
+        pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, expected_amount);
+        expected_amount -= 2;
+    }
+    check_safe_price_observations(&mut pair_setup, 4, 4, 4, 6);
+    check_oldest_observation_round(&mut pair_setup, &pair_address, 30);
+
+    pair_setup.b_mock.set_block_round(50);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, expected_amount);
+    expected_amount -= 2;
+    check_safe_price_observations(&mut pair_setup, 5, 5, 6, 0);
+    check_oldest_observation_round(&mut pair_setup, &pair_address, 30);
+
+    // shrinking waits until the newest observation is the last one that fits,
+    // and then removes the observations past the new size
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_safe_price_observation_config(5, 3);
+        })
+        .assert_ok();
+    check_safe_price_observations(&mut pair_setup, 5, 5, 5, 3);
+
+    for block_round in [55, 60, 65] {
+        pair_setup.b_mock.set_block_round(block_round);
+        pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, expected_amount);
+        expected_amount -= 2;
+    }
+    check_safe_price_observations(&mut pair_setup, 3, 5, 5, 3);
+    check_oldest_observation_round(&mut pair_setup, &pair_address, 45);
+
+    pair_setup.b_mock.set_block_round(70);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, expected_amount);
+    check_safe_price_observations(&mut pair_setup, 1, 3, 3, 0);
+    check_oldest_observation_round(&mut pair_setup, &pair_address, 60);
+}
+
+#[test]
+fn test_safe_price_observation_interval_accumulation() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let pair_address = pair_setup.pair_wrapper.address_ref().clone();
+    let owner = pair_setup.owner_address.clone();
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_safe_price_observation_config(5, 4);
+        })
+        .assert_ok();
+
+    pair_setup.b_mock.set_block_round(10);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, 996);
+
+    // the reserves of round 10 are accumulated at round 12, without a new observation
+    pair_setup.b_mock.set_block_round(12);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 900, 90_496);
+    check_safe_price_observations(&mut pair_setup, 1, 1, 4, 0);
+    pair_setup.check_price_observation(&pair_address, 11, 2, 2_003_000, 2_001_004);
+    pair_setup.check_price_observation(&pair_address, 12, 3, 3_005_000, 3_001_008);
+
+    pair_setup.b_mock.set_block_round(14);
+    pair_setup.check_price_observation(&pair_address, 14, 5, 5_209_000, 4_820_024);
+
+    // each reserve only weighs for the rounds it was in place
+    pair_setup.b_mock.set_block_round(15);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, 822);
+    check_safe_price_observations(&mut pair_setup, 2, 2, 4, 0);
+    pair_setup.check_price_observation(&pair_address, 15, 6, 6_311_000, 5_729_532);
+}
+
+fn check_safe_price_observations<PairObjBuilder>(
+    pair_setup: &mut PairSetup<PairObjBuilder>,
+    expected_current_index: usize,
+    expected_len: usize,
+    expected_max_observations: usize,
+    expected_pending_max_observations: usize,
+) where
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.safe_price_current_index().get(), expected_current_index);
+            assert_eq!(sc.price_observations().len(), expected_len);
+            assert_eq!(
+                sc.get_safe_price_max_observations_view(),
+                expected_max_observations
+            );
+            assert_eq!(
+                sc.safe_price_pending_max_observations().get(),
+                expected_pending_max_observations
+            );
+        })
+        .assert_ok();
+}
+
+fn check_oldest_observation_round<PairObjBuilder>(
+    pair_setup: &mut PairSetup<PairObjBuilder>,
+    pair_address: &Address,
+    expected_round: u64,
+) where
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let oldest_observation = sc.get_oldest_price_observation(
+                &managed_address!(pair_address),
+                sc.safe_price_current_index().get(),
+                &sc.price_observations(),
+            );
+            assert_eq!(oldest_observation.recording_round, expected_round);
+        })
+        .assert_ok();
 }
 
 // Test is commented as it needs a variable change in order to run succesfully
@@ -947,6 +1729,7 @@ fn test_locked_asset() {
                 let ret = sc.swap_tokens_fixed_input(
                     managed_token_id!(WEGLD_TOKEN_ID),
                     managed_biguint!(10),
+                    OptionalValue::None,
                 );
 
                 assert_eq!(ret.token_identifier, managed_token_id!(LOCKED_TOKEN_ID));
@@ -1133,7 +1916,7 @@ fn add_liquidity_through_simple_lock_proxy() {
     pair_setup.b_mock.set_block_epoch(5);
 
     // add liquidity through simple-lock SC - one locked (WEGLD) token, one unlocked (MEX)
-    let transfers = vec![
+    let transfers = [
         TxTokenTransfer {
             token_identifier: LOCKED_TOKEN_ID.to_vec(),
             nonce: 1,
@@ -1394,3 +2177,402 @@ fn fees_collector_pair_test() {
         })
         .assert_ok();
 }
+
+#[test]
+fn fees_collector_pair_fee_batching_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let owner = pair_setup.owner_address.clone();
+    let user = pair_setup.user_address.clone();
+    let fees_collector_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        None,
+        fees_collector::contract_obj,
+        "fees collector path",
+    );
+    let fees_collector_addr = fees_collector_wrapper.address_ref().clone();
+
+    let pair_addr = pair_setup.pair_wrapper.address_ref().clone();
+    let energy_factory_mock_addr = pair_setup.pair_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &fees_collector_wrapper, &rust_biguint!(0), |sc| {
+            sc.init(
+                managed_token_id!(LOCKED_TOKEN_ID),
+                managed_address!(&energy_factory_mock_addr),
+            );
+            let _ = sc.known_contracts().insert(managed_address!(&pair_addr));
+
+            let mut tokens = MultiValueEncoded::new();
+            tokens.push(managed_token_id!(WEGLD_TOKEN_ID));
+            tokens.push(managed_token_id!(MEX_TOKEN_ID));
+            sc.add_known_tokens(tokens);
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.setup_fees_collector(managed_address!(&fees_collector_addr), MAX_PERCENTAGE / 2);
+            sc.set_fee_batching(true, 1_000);
+        })
+        .assert_ok();
+
+    pair_setup.b_mock.set_block_round(10);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    // the special fees stay in the pair
+    pair_setup.b_mock.set_block_round(15);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 900, 90_669);
+    pair_setup.b_mock.set_block_round(16);
+    pair_setup.swap_fixed_input(MEX_TOKEN_ID, 10_000, WEGLD_TOKEN_ID, 900, 11_927);
+
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&fees_collector_addr, WEGLD_TOKEN_ID, &rust_biguint!(0));
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(
+                sc.pending_fees(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                managed_biguint!(50)
+            );
+            assert_eq!(
+                sc.pending_fees(&managed_token_id!(MEX_TOKEN_ID)).get(),
+                managed_biguint!(5)
+            );
+        })
+        .assert_ok();
+
+    // a large swap in the same round moves the price away from the safe price
+    pair_setup.b_mock.set_block_round(1_000);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 200_000, MEX_TOKEN_ID, 900, 142_432);
+    pair_setup
+        .b_mock
+        .execute_tx(&user, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.process_fees();
+        })
+        .assert_user_error("Price deviates too much from the safe price");
+
+    // the price has been stable over the whole safe price window
+    pair_setup.b_mock.set_block_round(2_000);
+    pair_setup
+        .b_mock
+        .execute_tx(&user, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.process_fees();
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&fees_collector_addr, WEGLD_TOKEN_ID, &rust_biguint!(75));
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&fees_collector_addr, MEX_TOKEN_ID, &rust_biguint!(2));
+    pair_setup
+        .b_mock
+        .execute_query(&fees_collector_wrapper, |sc| {
+            assert_eq!(
+                sc.accumulated_fees(1, &managed_token_id!(WEGLD_TOKEN_ID))
+                    .get(),
+                managed_biguint!(75)
+            );
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(&user, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.process_fees();
+        })
+        .assert_user_error("No pending fees");
+}
+
+#[test]
+fn fees_collector_pair_fee_batching_flash_swap_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let owner = pair_setup.owner_address.clone();
+    let user = pair_setup.user_address.clone();
+    let fees_collector_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        None,
+        fees_collector::contract_obj,
+        "fees collector path",
+    );
+    let fees_collector_addr = fees_collector_wrapper.address_ref().clone();
+
+    let pair_addr = pair_setup.pair_wrapper.address_ref().clone();
+    let energy_factory_mock_addr = pair_setup.pair_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &fees_collector_wrapper, &rust_biguint!(0), |sc| {
+            sc.init(
+                managed_token_id!(LOCKED_TOKEN_ID),
+                managed_address!(&energy_factory_mock_addr),
+            );
+            let _ = sc.known_contracts().insert(managed_address!(&pair_addr));
+
+            let mut tokens = MultiValueEncoded::new();
+            tokens.push(managed_token_id!(WEGLD_TOKEN_ID));
+            tokens.push(managed_token_id!(MEX_TOKEN_ID));
+            sc.add_known_tokens(tokens);
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.setup_fees_collector(managed_address!(&fees_collector_addr), MAX_PERCENTAGE / 2);
+            sc.set_fee_batching(true, 1_000);
+        })
+        .assert_ok();
+
+    pair_setup.b_mock.set_block_round(10);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let receiver_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        None,
+        flash_swap_receiver_mock::contract_obj,
+        "flash swap receiver mock path",
+    );
+    let receiver_addr = receiver_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&receiver_addr, WEGLD_TOKEN_ID, &rust_biguint!(100_000));
+
+    // the 25 special fee of the swap made with the borrowed tokens is not a repayment
+    pair_setup.b_mock.set_block_round(15);
+    let swap_amount_arg = rust_biguint!(50_000).to_bytes_be();
+    let shortfall_arg = rust_biguint!(25).to_bytes_be();
+    pair_setup
+        .flash_swap_with_endpoint(
+            &receiver_addr,
+            100_000,
+            b"receiveFlashSwapAndSwap",
+            &[&shortfall_arg, MEX_TOKEN_ID, &swap_amount_arg],
+        )
+        .assert_user_error("Flash swap not repaid");
+
+    let shortfall_arg = rust_biguint!(0).to_bytes_be();
+    pair_setup
+        .flash_swap_with_endpoint(
+            &receiver_addr,
+            100_000,
+            b"receiveFlashSwapAndSwap",
+            &[&shortfall_arg, MEX_TOKEN_ID, &swap_amount_arg],
+        )
+        .assert_ok();
+
+    // the pair holds its reserves plus the pending fees of both the swap and the flash swap
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&pair_addr, WEGLD_TOKEN_ID, &rust_biguint!(1_051_300));
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let (first_token_reserve, _, _) = sc.get_reserves_and_total_supply().into_tuple();
+            assert_eq!(first_token_reserve, managed_biguint!(1_051_225));
+            assert_eq!(
+                sc.pending_fees(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                managed_biguint!(75)
+            );
+        })
+        .assert_ok();
+
+    // the pending fees cannot be processed during a flash swap
+    pair_setup.b_mock.set_block_round(2_000);
+    pair_setup
+        .flash_swap_with_endpoint(
+            &receiver_addr,
+            100_000,
+            b"receiveFlashSwapAndProcessFees",
+            &[&shortfall_arg],
+        )
+        .assert_user_error("Flash swap already in progress");
+
+    pair_setup
+        .b_mock
+        .execute_tx(&user, &pair_setup.pair_wrapper, &rust_biguint!(0), |sc| {
+            sc.process_fees();
+        })
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&fees_collector_addr, WEGLD_TOKEN_ID, &rust_biguint!(37));
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(
+                sc.pending_fees(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                managed_biguint!(0)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn fees_collector_protocol_liquidity_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let owner = pair_setup.owner_address.clone();
+    let user = pair_setup.user_address.clone();
+    let fees_collector_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&owner),
+        fees_collector::contract_obj,
+        "fees collector path",
+    );
+    let fees_collector_addr = fees_collector_wrapper.address_ref().clone();
+
+    let pair_addr = pair_setup.pair_wrapper.address_ref().clone();
+    let energy_factory_mock_addr = pair_setup.pair_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &fees_collector_wrapper, &rust_biguint!(0), |sc| {
+            sc.init(
+                managed_token_id!(LOCKED_TOKEN_ID),
+                managed_address!(&energy_factory_mock_addr),
+            );
+
+            let mut tokens = MultiValueEncoded::new();
+            tokens.push(managed_token_id!(WEGLD_TOKEN_ID));
+            tokens.push(managed_token_id!(MEX_TOKEN_ID));
+            sc.add_known_tokens(tokens);
+
+            sc.set_protocol_liquidity_config(5_000, 500);
+            sc.add_protocol_liquidity_pair(managed_address!(&pair_addr));
+        })
+        .assert_ok();
+
+    pair_setup.b_mock.set_block_round(10);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    // records the first price observation
+    pair_setup.b_mock.set_block_round(15);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, 996);
+
+    // half of the fees of the pair tokens are diverted, the others are left untouched
+    pair_setup.b_mock.set_esdt_balance(
+        &fees_collector_addr,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(10_000),
+    );
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&fees_collector_addr, MEX_TOKEN_ID, &rust_biguint!(10_000));
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &fees_collector_wrapper, &rust_biguint!(0), |sc| {
+            let wegld_rewards = sc.divert_protocol_liquidity_share(
+                &managed_token_id!(WEGLD_TOKEN_ID),
+                managed_biguint!(10_000),
+            );
+            assert_eq!(wegld_rewards, managed_biguint!(5_000));
+
+            let mex_rewards = sc.divert_protocol_liquidity_share(
+                &managed_token_id!(MEX_TOKEN_ID),
+                managed_biguint!(10_000),
+            );
+            assert_eq!(mex_rewards, managed_biguint!(5_000));
+
+            let locked_token_rewards = sc.divert_protocol_liquidity_share(
+                &managed_token_id!(LOCKED_TOKEN_ID),
+                managed_biguint!(10_000),
+            );
+            assert_eq!(locked_token_rewards, managed_biguint!(10_000));
+        })
+        .assert_ok();
+
+    // 5_000 WEGLD and 4_990 MEX are added, at the price of the pair
+    pair_setup.b_mock.set_block_round(20);
+    pair_setup
+        .b_mock
+        .execute_tx(&user, &fees_collector_wrapper, &rust_biguint!(0), |sc| {
+            let lp_tokens = sc.add_protocol_liquidity(managed_address!(&pair_addr));
+            assert_eq!(lp_tokens.token_identifier, managed_token_id!(LP_TOKEN_ID));
+            assert_eq!(lp_tokens.amount, managed_biguint!(4_994));
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&fees_collector_addr, LP_TOKEN_ID, &rust_biguint!(4_994));
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&fees_collector_addr, MEX_TOKEN_ID, &rust_biguint!(5_010));
+    pair_setup
+        .b_mock
+        .execute_query(&fees_collector_wrapper, |sc| {
+            assert_eq!(
+                sc.protocol_liquidity_reserve(&managed_token_id!(WEGLD_TOKEN_ID))
+                    .get(),
+                managed_biguint!(0)
+            );
+            assert_eq!(
+                sc.protocol_liquidity_reserve(&managed_token_id!(MEX_TOKEN_ID))
+                    .get(),
+                managed_biguint!(10)
+            );
+
+            let protocol_lp_tokens = sc.get_protocol_lp_tokens(managed_address!(&pair_addr));
+            assert_eq!(protocol_lp_tokens.amount, managed_biguint!(4_994));
+        })
+        .assert_ok();
+
+    // a large swap in the same round moves the price away from the safe price
+    pair_setup.b_mock.set_esdt_balance(
+        &fees_collector_addr,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(10_000),
+    );
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &fees_collector_wrapper, &rust_biguint!(0), |sc| {
+            let _ = sc.divert_protocol_liquidity_share(
+                &managed_token_id!(WEGLD_TOKEN_ID),
+                managed_biguint!(10_000),
+            );
+        })
+        .assert_ok();
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 200_000, MEX_TOKEN_ID, 900, 166_110);
+    pair_setup
+        .b_mock
+        .execute_tx(&user, &fees_collector_wrapper, &rust_biguint!(0), |sc| {
+            let _ = sc.add_protocol_liquidity(managed_address!(&pair_addr));
+        })
+        .assert_user_error("Price deviates too much from the safe price");
+
+    // once the pair is removed, the reserves go back to the fees of the current week
+    pair_setup
+        .b_mock
+        .execute_tx(&owner, &fees_collector_wrapper, &rust_biguint!(0), |sc| {
+            sc.remove_protocol_liquidity_pair(managed_address!(&pair_addr));
+        })
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .execute_query(&fees_collector_wrapper, |sc| {
+            assert_eq!(
+                sc.accumulated_fees(1, &managed_token_id!(WEGLD_TOKEN_ID))
+                    .get(),
+                managed_biguint!(5_000)
+            );
+            assert_eq!(
+                sc.accumulated_fees(1, &managed_token_id!(MEX_TOKEN_ID))
+                    .get(),
+                managed_biguint!(10)
+            );
+            assert!(sc
+                .protocol_liquidity_reserve(&managed_token_id!(WEGLD_TOKEN_ID))
+                .is_empty());
+
+            let protocol_lp_tokens = sc.get_protocol_lp_tokens(managed_address!(&pair_addr));
+            assert_eq!(protocol_lp_tokens.amount, managed_biguint!(4_994));
+        })
+        .assert_ok();
+}
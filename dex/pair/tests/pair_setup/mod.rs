@@ -1,6 +1,6 @@
-use multiversx_sc::codec::multi_types::MultiValue3;
+use multiversx_sc::codec::multi_types::{MultiValue3, OptionalValue};
 use multiversx_sc::types::{
-    Address, EsdtLocalRole, EsdtTokenPayment, ManagedAddress, MultiValueEncoded,
+    Address, EsdtLocalRole, EsdtTokenPayment, ManagedAddress, ManagedBuffer, MultiValueEncoded,
 };
 use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
 use multiversx_sc_scenario::{
@@ -12,6 +12,8 @@ pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
 pub const OTHER_TOKEN_ID: &[u8] = b"OTHER-abcdef";
 pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
 pub const LP_TOKEN_ID: &[u8] = b"LPTOK-abcdef";
+pub const POSITION_TOKEN_ID: &[u8] = b"CLPOS-abcdef";
+pub const POSITION_FARMING_TOKEN_ID: &[u8] = b"CLFARM-abcdef";
 
 pub const LOCKED_TOKEN_ID: &[u8] = b"LOCKED-abcdef";
 pub const LP_PROXY_TOKEN_ID: &[u8] = b"LPPROXY-abcdef";
@@ -19,7 +21,11 @@ pub const LP_PROXY_TOKEN_ID: &[u8] = b"LPPROXY-abcdef";
 pub const USER_TOTAL_MEX_TOKENS: u64 = 5_000_000_000;
 pub const USER_TOTAL_WEGLD_TOKENS: u64 = 5_000_000_000;
 
-use pair::config::ConfigModule as PairConfigModule;
+use pair::concentrated_liquidity::farming::ConcentratedLiquidityFarmingModule;
+use pair::concentrated_liquidity::ConcentratedLiquidityModule;
+use pair::config::{ConfigModule as PairConfigModule, MAX_PERCENTAGE};
+use pair::dynamic_fee::DynamicFeeModule;
+use pair::flash_swap::FlashSwapModule;
 use pair::safe_price_view::*;
 use pair::*;
 use pausable::{PausableModule, State};
@@ -177,6 +183,237 @@ where
             .assert_ok();
     }
 
+    pub fn add_liquidity_single_token(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount: u64,
+        lp_token_amount_min: u64,
+        expected_lp_amount: u64,
+    ) {
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount),
+                |sc| {
+                    let MultiValue3 { 0: payments } =
+                        sc.add_liquidity_single_token(managed_biguint!(lp_token_amount_min));
+
+                    assert_eq!(payments.0.token_identifier, managed_token_id!(LP_TOKEN_ID));
+                    assert_eq!(payments.0.amount, managed_biguint!(expected_lp_amount));
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn remove_liquidity_to_single_token(
+        &mut self,
+        lp_token_amount: u64,
+        desired_token_id: &[u8],
+        desired_amount_min: u64,
+        expected_amount: u64,
+    ) {
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                LP_TOKEN_ID,
+                0,
+                &rust_biguint!(lp_token_amount),
+                |sc| {
+                    let ret = sc.remove_liquidity_to_single_token(
+                        managed_token_id!(desired_token_id),
+                        managed_biguint!(desired_amount_min),
+                    );
+
+                    assert_eq!(ret.token_identifier, managed_token_id!(desired_token_id));
+                    assert_eq!(ret.amount, managed_biguint!(expected_amount));
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn set_amplification_coefficient(&mut self, amplification_coefficient: u64) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.pair_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.set_amplification_coefficient(amplification_coefficient);
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn set_dynamic_fee_config(
+        &mut self,
+        min_fee_percent: u64,
+        max_fee_percent: u64,
+        window_rounds: u64,
+        max_volatility: u64,
+    ) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.pair_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.set_dynamic_fee_config(
+                        min_fee_percent,
+                        max_fee_percent,
+                        window_rounds,
+                        max_volatility,
+                    );
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn check_total_fee_percent(&mut self, expected_total_fee_percent: u64) {
+        self.b_mock
+            .execute_query(&self.pair_wrapper, |sc| {
+                assert_eq!(sc.total_fee_percent().get(), expected_total_fee_percent);
+            })
+            .assert_ok();
+    }
+
+    pub fn check_dynamic_fee_percent(&mut self, expected_dynamic_fee_percent: u64) {
+        self.b_mock
+            .execute_query(&self.pair_wrapper, |sc| {
+                assert_eq!(sc.dynamic_fee_percent().get(), expected_dynamic_fee_percent);
+            })
+            .assert_ok();
+    }
+
+    pub fn enable_concentrated_liquidity(&mut self, tick_spacing: u32, initial_tick: i32) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.pair_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.enable_concentrated_liquidity(tick_spacing, initial_tick);
+                    sc.set_position_token_identifier(managed_token_id!(POSITION_TOKEN_ID));
+                    sc.set_position_farming_token_identifier(managed_token_id!(
+                        POSITION_FARMING_TOKEN_ID
+                    ));
+                },
+            )
+            .assert_ok();
+
+        let position_token_roles = [
+            EsdtLocalRole::NftCreate,
+            EsdtLocalRole::NftAddQuantity,
+            EsdtLocalRole::NftBurn,
+        ];
+        self.b_mock.set_esdt_local_roles(
+            self.pair_wrapper.address_ref(),
+            POSITION_TOKEN_ID,
+            &position_token_roles[..],
+        );
+
+        let farming_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        self.b_mock.set_esdt_local_roles(
+            self.pair_wrapper.address_ref(),
+            POSITION_FARMING_TOKEN_ID,
+            &farming_token_roles[..],
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_concentrated_liquidity(
+        &mut self,
+        lower_tick: i32,
+        upper_tick: i32,
+        first_token_amount: u64,
+        second_token_amount: u64,
+        expected_position_nonce: u64,
+        expected_liquidity: u64,
+        expected_first_refund: u64,
+        expected_second_refund: u64,
+    ) {
+        let payments = vec![
+            TxTokenTransfer {
+                token_identifier: WEGLD_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(first_token_amount),
+            },
+            TxTokenTransfer {
+                token_identifier: MEX_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(second_token_amount),
+            },
+        ];
+
+        self.b_mock
+            .execute_esdt_multi_transfer(&self.user_address, &self.pair_wrapper, &payments, |sc| {
+                let (position, first_refund, second_refund) = sc
+                    .add_concentrated_liquidity(
+                        lower_tick,
+                        upper_tick,
+                        managed_biguint!(0),
+                        managed_biguint!(0),
+                    )
+                    .into_tuple();
+
+                assert_eq!(
+                    position.token_identifier,
+                    managed_token_id!(POSITION_TOKEN_ID)
+                );
+                assert_eq!(position.token_nonce, expected_position_nonce);
+                assert_eq!(position.amount, managed_biguint!(expected_liquidity));
+                assert_eq!(first_refund.amount, managed_biguint!(expected_first_refund));
+                assert_eq!(
+                    second_refund.amount,
+                    managed_biguint!(expected_second_refund)
+                );
+            })
+            .assert_ok();
+    }
+
+    pub fn remove_concentrated_liquidity(
+        &mut self,
+        position_nonce: u64,
+        liquidity: u64,
+        expected_first_amount: u64,
+        expected_second_amount: u64,
+    ) {
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                POSITION_TOKEN_ID,
+                position_nonce,
+                &rust_biguint!(liquidity),
+                |sc| {
+                    let (first_payment, second_payment) = sc
+                        .remove_concentrated_liquidity(managed_biguint!(0), managed_biguint!(0))
+                        .into_tuple();
+
+                    assert_eq!(
+                        first_payment.token_identifier,
+                        managed_token_id!(WEGLD_TOKEN_ID)
+                    );
+                    assert_eq!(
+                        first_payment.amount,
+                        managed_biguint!(expected_first_amount)
+                    );
+                    assert_eq!(
+                        second_payment.token_identifier,
+                        managed_token_id!(MEX_TOKEN_ID)
+                    );
+                    assert_eq!(
+                        second_payment.amount,
+                        managed_biguint!(expected_second_amount)
+                    );
+                },
+            )
+            .assert_ok();
+    }
+
     pub fn swap_fixed_input(
         &mut self,
         payment_token_id: &[u8],
@@ -196,6 +433,7 @@ where
                     let ret = sc.swap_tokens_fixed_input(
                         managed_token_id!(desired_token_id),
                         managed_biguint!(desired_amount_min),
+                        OptionalValue::None,
                     );
 
                     assert_eq!(ret.token_identifier, managed_token_id!(desired_token_id));
@@ -235,6 +473,7 @@ where
                     let ret = sc.swap_tokens_fixed_output(
                         managed_token_id!(desired_token_id),
                         managed_biguint!(desired_amount),
+                        OptionalValue::None,
                     );
 
                     let (desired_token_output, payment_token_residuum) = ret.into_tuple();
@@ -272,6 +511,46 @@ where
         );
     }
 
+    /// The receiver mock pays back the borrowed WEGLD and the fee, less `shortfall`.
+    pub fn flash_swap(&mut self, receiver: &Address, amount: u64, shortfall: u64) -> TxResult {
+        let shortfall_arg = rust_biguint!(shortfall).to_bytes_be();
+        self.flash_swap_with_endpoint(receiver, amount, b"receiveFlashSwap", &[&shortfall_arg])
+    }
+
+    pub fn flash_swap_with_endpoint(
+        &mut self,
+        receiver: &Address,
+        amount: u64,
+        receiver_endpoint: &[u8],
+        receiver_args: &[&[u8]],
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.user_address,
+            &self.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut args = MultiValueEncoded::new();
+                for arg in receiver_args {
+                    args.push(ManagedBuffer::from(*arg));
+                }
+
+                let fee_amount = sc.flash_swap(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(amount),
+                    managed_address!(receiver),
+                    ManagedBuffer::from(receiver_endpoint),
+                    args,
+                );
+
+                let total_fee_percent = sc.total_fee_percent().get();
+                assert_eq!(
+                    fee_amount,
+                    managed_biguint!(amount * total_fee_percent / MAX_PERCENTAGE)
+                );
+            },
+        )
+    }
+
     pub fn check_price_observation(
         &mut self,
         pair_address: &Address,
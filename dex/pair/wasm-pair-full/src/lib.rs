@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           64
+// Endpoints:                          103
 // Async Callback (empty):               1
-// Total number of exported functions:  66
+// Total number of exported functions: 105
 
 #![no_std]
 
@@ -25,7 +25,9 @@ multiversx_sc_wasm_adapter::endpoints! {
         upgrade => upgrade
         addInitialLiquidity => add_initial_liquidity
         addLiquidity => add_liquidity
+        addLiquiditySingleToken => add_liquidity_single_token
         removeLiquidity => remove_liquidity
+        removeLiquidityToSingleToken => remove_liquidity_to_single_token
         removeLiquidityAndBuyBackAndBurnToken => remove_liquidity_and_burn_token
         swapNoFeeAndForward => swap_no_fee
         swapTokensFixedInput => swap_tokens_fixed_input
@@ -34,6 +36,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         getTokensForGivenPosition => get_tokens_for_given_position
         getReservesAndTotalSupply => get_reserves_and_total_supply
         getAmountOut => get_amount_out_view
+        tryGetAmountOut => try_get_amount_out_view
         getAmountIn => get_amount_in_view
         getEquivalent => get_equivalent
         getFeeState => is_fee_enabled
@@ -48,8 +51,32 @@ multiversx_sc_wasm_adapter::endpoints! {
         getWhitelistedManagedAddresses => get_whitelisted_managed_addresses
         getFeesCollectorAddress => fees_collector_address
         getFeesCollectorCutPercentage => fees_collector_cut_percentage
+        getFeeBatchingEnabled => fee_batching_enabled
+        getPendingFees => pending_fees
+        setFeeBatching => set_fee_batching
+        processFees => process_fees
+        getFeeProcessingMaxPriceDeviation => fee_processing_max_price_deviation
+        enableConcentratedLiquidity => enable_concentrated_liquidity
+        setPositionTokenIdentifier => set_position_token_identifier
+        addConcentratedLiquidity => add_concentrated_liquidity
+        removeConcentratedLiquidity => remove_concentrated_liquidity
+        collectConcentratedFees => collect_concentrated_fees
+        getPositionFees => get_position_fees_view
+        getPositionTokenIdentifier => position_token_id
+        setPositionFarmingTokenIdentifier => set_position_farming_token_identifier
+        setPositionFarmingRange => set_position_farming_range
+        wrapPositionForFarming => wrap_position_for_farming
+        unwrapPositionFromFarming => unwrap_position_from_farming
+        getPositionFarmingTokenIdentifier => position_farming_token_id
+        getPositionFarmingRange => position_farming_range
+        getWrappedPositionAmount => wrapped_positions
+        getCurrentTick => current_tick
+        getCurrentSqrtPrice => current_sqrt_price
+        getActiveLiquidity => active_liquidity
+        getTickInfo => ticks
         setStateActiveNoSwaps => set_state_active_no_swaps
         setFeePercents => set_fee_percent
+        setAmplificationCoefficient => set_amplification_coefficient
         getLpTokenIdentifier => get_lp_token_identifier
         getTotalFeePercent => total_fee_percent
         getSpecialFee => special_fee_percent
@@ -58,10 +85,21 @@ multiversx_sc_wasm_adapter::endpoints! {
         getSecondTokenId => second_token_id
         getTotalSupply => lp_token_supply
         getInitialLiquidtyAdder => initial_liquidity_adder
+        getAmplificationCoefficient => amplification_coefficient
+        getTickSpacing => tick_spacing
+        getDynamicFeeConfig => dynamic_fee_config
+        getDynamicFeePercent => dynamic_fee_percent
         getReserve => pair_reserve
+        setSafePriceObservationConfig => set_safe_price_observation_config
+        getSafePriceMaxObservations => get_safe_price_max_observations_view
+        getSafePriceObservationInterval => safe_price_observation_interval
+        getSafePricePendingMaxObservations => safe_price_pending_max_observations
         getSafePriceCurrentIndex => safe_price_current_index
         updateAndGetTokensForGivenPositionWithSafePrice => update_and_get_tokens_for_given_position_with_safe_price
         updateAndGetSafePrice => update_and_get_safe_price
+        setDynamicFeeConfig => set_dynamic_fee_config
+        disableDynamicFee => disable_dynamic_fee
+        flashSwap => flash_swap
         setLockingDeadlineEpoch => set_locking_deadline_epoch
         setLockingScAddress => set_locking_sc_address
         setUnlockEpoch => set_unlock_epoch
@@ -86,6 +124,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         getSafePriceByTimestampOffset => get_safe_price_by_timestamp_offset
         getSafePrice => get_safe_price
         getPriceObservation => get_price_observation_view
+        getOldestPriceObservationRound => get_oldest_price_observation_round
     )
 }
 
@@ -6,7 +6,12 @@ multiversx_sc::derive_imports!();
 pub mod enable_swap_by_user;
 mod events;
 pub mod factory;
+pub mod listing_bond;
 pub mod multi_pair_swap;
+pub mod router_swap;
+pub mod smart_routing;
+pub mod split_swap;
+pub mod weighted_pools;
 
 use factory::PairTokens;
 use pair::config::ProxyTrait as _;
@@ -29,6 +34,10 @@ pub trait Router:
     + multi_pair_swap::MultiPairSwap
     + token_send::TokenSendModule
     + enable_swap_by_user::EnableSwapByUserModule
+    + weighted_pools::WeightedPoolsModule
+    + smart_routing::SmartRoutingModule
+    + split_swap::SplitSwapModule
+    + listing_bond::ListingBondModule
 {
     #[init]
     fn init(&self, pair_template_address_opt: OptionalValue<ManagedAddress>) {
@@ -70,7 +79,10 @@ pub trait Router:
         }
     }
 
+    /// When a listing bond is configured, users pay it to create pairs,
+    /// even if pair creation is disabled.
     #[allow_multiple_var_args]
+    #[payable("*")]
     #[endpoint(createPair)]
     fn create_pair_endpoint(
         &self,
@@ -84,7 +96,15 @@ pub trait Router:
         let owner = self.owner().get();
         let caller = self.blockchain().get_caller();
 
-        if caller != owner {
+        let listing_bond_required = caller != owner && self.is_listing_bond_required();
+        if !listing_bond_required {
+            require!(
+                self.call_value().all_esdt_transfers().is_empty()
+                    && *self.call_value().egld_value() == 0,
+                "No payment expected"
+            );
+        }
+        if caller != owner && !listing_bond_required {
             require!(
                 self.pair_creation_enabled().get(),
                 "Pair creation is disabled"
@@ -122,6 +142,12 @@ pub trait Router:
             }
         }
 
+        let opt_listing = if listing_bond_required {
+            Some(self.new_pending_listing(&first_token_id, &second_token_id, caller.clone()))
+        } else {
+            None
+        };
+
         admins.push(caller.clone());
 
         let address = self.create_pair(
@@ -134,6 +160,10 @@ pub trait Router:
             admins,
         );
 
+        if let Some(listing) = opt_listing {
+            self.save_pending_listing(&address, listing);
+        }
+
         self.emit_create_pair_event(
             caller,
             first_token_id,
@@ -145,6 +175,36 @@ pub trait Router:
         address
     }
 
+    /// Same as `createPair`, but the new pair uses the stable swap curve
+    /// with the given amplification coefficient.
+    #[allow_multiple_var_args]
+    #[payable("*")]
+    #[endpoint(createStablePair)]
+    fn create_stable_pair_endpoint(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        initial_liquidity_adder: ManagedAddress,
+        amplification_coefficient: u64,
+        opt_fee_percents: OptionalValue<MultiValue2<u64, u64>>,
+        admins: MultiValueEncoded<ManagedAddress>,
+    ) -> ManagedAddress {
+        let address = self.create_pair_endpoint(
+            first_token_id,
+            second_token_id,
+            initial_liquidity_adder,
+            opt_fee_percents,
+            admins,
+        );
+
+        let _: IgnoreValue = self
+            .pair_contract_proxy(address.clone())
+            .set_amplification_coefficient(amplification_coefficient)
+            .execute_on_dest_context();
+
+        address
+    }
+
     #[only_owner]
     #[endpoint(upgradePair)]
     fn upgrade_pair_endpoint(
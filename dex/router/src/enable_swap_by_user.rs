@@ -1,6 +1,7 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+use liquidity_locker::lock::{LpLock, ProxyTrait as _};
 use pair::config::ProxyTrait as _;
 use pausable::{ProxyTrait as _, State};
 use simple_lock::locked_token::LockedTokenAttributes;
@@ -148,6 +149,78 @@ pub trait EnableSwapByUserModule:
         );
     }
 
+    #[only_owner]
+    #[endpoint(setLiquidityLockerAddress)]
+    fn set_liquidity_locker_address(&self, liquidity_locker_address: ManagedAddress) {
+        require!(
+            self.blockchain()
+                .is_smart_contract(&liquidity_locker_address),
+            "Invalid liquidity locker address"
+        );
+
+        self.liquidity_locker_address()
+            .set(&liquidity_locker_address);
+    }
+
+    /// Same as setSwapEnabledByUser, but the LP tokens stay in a lock of the liquidity locker.
+    /// Only the part of the lock that is still locked after the minimum lock period counts.
+    #[endpoint(setSwapEnabledByUserWithLiquidityLock)]
+    fn set_swap_enabled_by_user_with_liquidity_lock(
+        &self,
+        pair_address: ManagedAddress,
+        lock_id: u64,
+    ) {
+        self.check_is_pair_sc(&pair_address);
+        self.require_state_active_no_swaps(&pair_address);
+
+        let liquidity_locker_mapper = self.liquidity_locker_address();
+        require!(
+            !liquidity_locker_mapper.is_empty(),
+            "No liquidity locker set"
+        );
+
+        let lock: LpLock<Self::Api> = self
+            .liquidity_locker_proxy(liquidity_locker_mapper.get())
+            .get_lock(lock_id)
+            .execute_on_dest_context();
+        let caller = self.blockchain().get_caller();
+        require!(lock.owner == caller, "Caller is not the lock owner");
+        require!(lock.pair_address == pair_address, "Invalid lock pair");
+
+        let pair_lp_token_id = self.get_pair_lp_token_id(&pair_address);
+        require!(
+            lock.lp_token_id == pair_lp_token_id,
+            "Invalid locked LP token"
+        );
+
+        let lp_token_safe_price_result =
+            self.get_lp_token_value(pair_address.clone(), lock.total_amount.clone());
+        let config = self.try_get_config(&lp_token_safe_price_result.common_token_id);
+
+        // the last epoch of the minimum lock period
+        let current_epoch = self.blockchain().get_block_epoch();
+        let min_locked_epoch = current_epoch + config.min_lock_period_epochs.saturating_sub(1);
+        let locked_value = lp_token_safe_price_result.safe_price_in_common_token
+            * lock.get_locked_amount(min_locked_epoch)
+            / &lock.total_amount;
+        require!(
+            locked_value >= config.min_locked_token_value,
+            "Not enough value locked"
+        );
+
+        self.require_caller_initial_liquidity_adder(&pair_address, &caller);
+
+        self.set_fee_percents(pair_address.clone());
+        self.pair_resume(pair_address.clone());
+
+        self.emit_user_swaps_enabled_event(
+            caller,
+            lp_token_safe_price_result.first_token_id,
+            lp_token_safe_price_result.second_token_id,
+            pair_address,
+        );
+    }
+
     #[view(getEnableSwapByUserConfig)]
     fn try_get_config(&self, token_id: &TokenIdentifier) -> EnableSwapByUserConfig<Self::Api> {
         let mapper = self.enable_swap_by_user_config(token_id);
@@ -251,6 +324,13 @@ pub trait EnableSwapByUserModule:
     #[proxy]
     fn user_pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
 
+    #[proxy]
+    fn liquidity_locker_proxy(&self, to: ManagedAddress) -> liquidity_locker::Proxy<Self::Api>;
+
+    #[view(getLiquidityLockerAddress)]
+    #[storage_mapper("liquidityLockerAddress")]
+    fn liquidity_locker_address(&self) -> SingleValueMapper<ManagedAddress>;
+
     #[storage_mapper("enableSwapByUserConfig")]
     fn enable_swap_by_user_config(
         &self,
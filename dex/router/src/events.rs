@@ -1,6 +1,8 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+use crate::listing_bond::PendingListing;
+
 #[derive(TypeAbi, TopEncode)]
 pub struct CreatePairEvent<M: ManagedTypeApi> {
     caller: ManagedAddress<M>,
@@ -22,6 +24,14 @@ pub struct UserPairSwapEnabledEvent<M: ManagedTypeApi> {
     pair_address: ManagedAddress<M>,
 }
 
+#[derive(TypeAbi, TopEncode)]
+pub struct ListingBondEvent<M: ManagedTypeApi> {
+    pair_address: ManagedAddress<M>,
+    listing: PendingListing<M>,
+    block: u64,
+    epoch: u64,
+}
+
 #[multiversx_sc::module]
 pub trait EventsModule {
     fn emit_create_pair_event(
@@ -75,6 +85,62 @@ pub trait EventsModule {
         )
     }
 
+    fn emit_listing_bond_paid_event(
+        &self,
+        pair_address: ManagedAddress,
+        listing: PendingListing<Self::Api>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.listing_bond_paid_event(
+            listing.creator.clone(),
+            pair_address.clone(),
+            epoch,
+            self.build_listing_bond_event(pair_address, listing, epoch),
+        )
+    }
+
+    fn emit_listing_bond_refunded_event(
+        &self,
+        pair_address: ManagedAddress,
+        listing: PendingListing<Self::Api>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.listing_bond_refunded_event(
+            listing.creator.clone(),
+            pair_address.clone(),
+            epoch,
+            self.build_listing_bond_event(pair_address, listing, epoch),
+        )
+    }
+
+    fn emit_listing_bond_forfeited_event(
+        &self,
+        pair_address: ManagedAddress,
+        listing: PendingListing<Self::Api>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.listing_bond_forfeited_event(
+            listing.creator.clone(),
+            pair_address.clone(),
+            epoch,
+            self.build_listing_bond_event(pair_address, listing, epoch),
+        )
+    }
+
+    fn build_listing_bond_event(
+        &self,
+        pair_address: ManagedAddress,
+        listing: PendingListing<Self::Api>,
+        epoch: u64,
+    ) -> ListingBondEvent<Self::Api> {
+        ListingBondEvent {
+            pair_address,
+            listing,
+            block: self.blockchain().get_block_nonce(),
+            epoch,
+        }
+    }
+
     #[event("create_pair")]
     fn create_pair_event(
         self,
@@ -94,4 +160,31 @@ pub trait EventsModule {
         #[indexed] epoch: u64,
         swap_enabled_event: UserPairSwapEnabledEvent<Self::Api>,
     );
+
+    #[event("listingBondPaid")]
+    fn listing_bond_paid_event(
+        &self,
+        #[indexed] creator: ManagedAddress,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] epoch: u64,
+        listing_bond_event: ListingBondEvent<Self::Api>,
+    );
+
+    #[event("listingBondRefunded")]
+    fn listing_bond_refunded_event(
+        &self,
+        #[indexed] creator: ManagedAddress,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] epoch: u64,
+        listing_bond_event: ListingBondEvent<Self::Api>,
+    );
+
+    #[event("listingBondForfeited")]
+    fn listing_bond_forfeited_event(
+        &self,
+        #[indexed] creator: ManagedAddress,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] epoch: u64,
+        listing_bond_event: ListingBondEvent<Self::Api>,
+    );
 }
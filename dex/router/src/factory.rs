@@ -11,9 +11,9 @@ pub struct PairTokens<M: ManagedTypeApi> {
 
 #[derive(ManagedVecItem, TopEncode, TopDecode, PartialEq, TypeAbi)]
 pub struct PairContractMetadata<M: ManagedTypeApi> {
-    first_token_id: TokenIdentifier<M>,
-    second_token_id: TokenIdentifier<M>,
-    address: ManagedAddress<M>,
+    pub first_token_id: TokenIdentifier<M>,
+    pub second_token_id: TokenIdentifier<M>,
+    pub address: ManagedAddress<M>,
 }
 
 #[multiversx_sc::module]
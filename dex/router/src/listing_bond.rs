@@ -0,0 +1,312 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use fees_collector::fees_accumulation::ProxyTrait as _;
+use liquidity_locker::lock::{LpLock, ProxyTrait as _};
+use pair::config::ProxyTrait as _;
+use pausable::ProxyTrait as _;
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct ListingBondConfig<M: ManagedTypeApi> {
+    pub bond_token_id: TokenIdentifier<M>,
+    pub bond_amount: BigUint<M>,
+    pub period_epochs: u64,
+    pub min_lock_epochs: u64,
+}
+
+/// The bond is refunded once the creator locks LP tokens of the pair worth `min_liquidity`
+/// of `common_token_id`, for at least `min_lock_epochs`, until `deadline_epoch`,
+/// and forfeited after it.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct PendingListing<M: ManagedTypeApi> {
+    pub creator: ManagedAddress<M>,
+    pub bond: EsdtTokenPayment<M>,
+    pub common_token_id: TokenIdentifier<M>,
+    pub min_liquidity: BigUint<M>,
+    pub min_lock_epochs: u64,
+    pub deadline_epoch: u64,
+}
+
+/// Lets anyone create pairs by paying a bond, on top of `pair_creation_enabled`.
+///
+/// Pairs that never gather enough locked liquidity lose their bond to the fees collector.
+/// Those without any liquidity are also paused and removed from the router,
+/// so open listing does not fill it with dead pairs.
+#[multiversx_sc::module]
+pub trait ListingBondModule:
+    crate::factory::FactoryModule
+    + crate::events::EventsModule
+    + crate::enable_swap_by_user::EnableSwapByUserModule
+{
+    /// The router must be a known contract of the fees collector,
+    /// and the bond token one of its known tokens, for bonds to be forfeited.
+    #[only_owner]
+    #[endpoint(setListingFeesCollectorAddress)]
+    fn set_listing_fees_collector_address(&self, fees_collector_address: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&fees_collector_address),
+            "Invalid fees collector address"
+        );
+
+        self.listing_fees_collector_address()
+            .set(&fees_collector_address);
+    }
+
+    #[only_owner]
+    #[endpoint(setListingBondConfig)]
+    fn set_listing_bond_config(
+        &self,
+        bond_token_id: TokenIdentifier,
+        bond_amount: BigUint,
+        period_epochs: u64,
+        min_lock_epochs: u64,
+    ) {
+        require!(
+            bond_token_id.is_valid_esdt_identifier(),
+            "Invalid bond token ID"
+        );
+        require!(
+            bond_amount > 0 && period_epochs > 0 && min_lock_epochs > 0,
+            "Invalid listing bond config"
+        );
+        require!(
+            !self.listing_fees_collector_address().is_empty(),
+            "No fees collector set"
+        );
+
+        self.listing_bond_config().set(&ListingBondConfig {
+            bond_token_id,
+            bond_amount,
+            period_epochs,
+            min_lock_epochs,
+        });
+    }
+
+    /// Pending listings keep the config they were created with.
+    #[only_owner]
+    #[endpoint(removeListingBondConfig)]
+    fn remove_listing_bond_config(&self) {
+        self.listing_bond_config().clear();
+    }
+
+    #[only_owner]
+    #[endpoint(setListingMinLiquidity)]
+    fn set_listing_min_liquidity(&self, common_token_id: TokenIdentifier, min_liquidity: BigUint) {
+        require!(
+            self.common_tokens_for_user_pairs()
+                .contains(&common_token_id),
+            "Common token not whitelisted"
+        );
+        require!(min_liquidity > 0, "Invalid min liquidity");
+
+        self.listing_min_liquidity(&common_token_id)
+            .set(&min_liquidity);
+    }
+
+    /// Can be called by anyone, the bond always goes back to the pair creator.
+    /// The lock must belong to the creator, and only the part of it that is still locked
+    /// after the minimum lock period counts, so the liquidity cannot be pulled right away.
+    #[endpoint(refundListingBond)]
+    fn refund_listing_bond(&self, pair_address: ManagedAddress, lock_id: u64) {
+        let listing = self.get_pending_listing(pair_address.clone());
+        require!(
+            self.blockchain().get_block_epoch() <= listing.deadline_epoch,
+            "Listing period ended"
+        );
+
+        let liquidity_locker_mapper = self.liquidity_locker_address();
+        require!(
+            !liquidity_locker_mapper.is_empty(),
+            "No liquidity locker set"
+        );
+
+        let lock: LpLock<Self::Api> = self
+            .liquidity_locker_proxy(liquidity_locker_mapper.get())
+            .get_lock(lock_id)
+            .execute_on_dest_context();
+        require!(
+            lock.owner == listing.creator,
+            "Lock owner is not the pair creator"
+        );
+        require!(lock.pair_address == pair_address, "Invalid lock pair");
+        require!(
+            lock.lp_token_id == self.get_pair_lp_token_id(&pair_address),
+            "Invalid locked LP token"
+        );
+
+        let lp_token_value =
+            self.get_lp_token_value(pair_address.clone(), lock.total_amount.clone());
+        require!(
+            lp_token_value.common_token_id == listing.common_token_id,
+            "Invalid tokens in Pair contract"
+        );
+
+        // the last epoch of the minimum lock period
+        let current_epoch = self.blockchain().get_block_epoch();
+        let min_locked_epoch = current_epoch + listing.min_lock_epochs.saturating_sub(1);
+        let locked_value = lp_token_value.safe_price_in_common_token
+            * lock.get_locked_amount(min_locked_epoch)
+            / &lock.total_amount;
+        require!(
+            locked_value >= listing.min_liquidity,
+            "Listing liquidity threshold not reached"
+        );
+
+        self.remove_pending_listing(&pair_address);
+        self.send().direct_esdt(
+            &listing.creator,
+            &listing.bond.token_identifier,
+            listing.bond.token_nonce,
+            &listing.bond.amount,
+        );
+
+        self.emit_listing_bond_refunded_event(pair_address, listing);
+    }
+
+    /// Can be called by anyone once the listing period ends.
+    /// A pair without liquidity is paused and removed from the router. A pair that holds
+    /// liquidity stays as it is, so its liquidity providers can still withdraw,
+    /// and the router can still manage it.
+    #[endpoint(forfeitListingBond)]
+    fn forfeit_listing_bond(&self, pair_address: ManagedAddress) {
+        let listing = self.get_pending_listing(pair_address.clone());
+        require!(
+            self.blockchain().get_block_epoch() > listing.deadline_epoch,
+            "Listing period not ended"
+        );
+
+        self.remove_pending_listing(&pair_address);
+
+        let lp_token_supply: BigUint = self
+            .listing_pair_proxy(pair_address.clone())
+            .lp_token_supply()
+            .execute_on_dest_context();
+        if lp_token_supply == 0 {
+            let _: IgnoreValue = self
+                .listing_pair_proxy(pair_address.clone())
+                .pause()
+                .execute_on_dest_context();
+            if let Some(pair_tokens) = self.address_pair_map().remove(&pair_address) {
+                let _ = self.pair_map().remove(&pair_tokens);
+            }
+        }
+
+        let _: IgnoreValue = self
+            .listing_fees_collector_proxy(self.listing_fees_collector_address().get())
+            .deposit_swap_fees()
+            .with_esdt_transfer(listing.bond.clone())
+            .execute_on_dest_context();
+
+        self.emit_listing_bond_forfeited_event(pair_address, listing);
+    }
+
+    fn is_listing_bond_required(&self) -> bool {
+        !self.listing_bond_config().is_empty()
+    }
+
+    fn new_pending_listing(
+        &self,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+        creator: ManagedAddress,
+    ) -> PendingListing<Self::Api> {
+        let config = self.listing_bond_config().get();
+        let bond = self.call_value().single_esdt();
+        require!(
+            bond.token_identifier == config.bond_token_id
+                && bond.token_nonce == 0
+                && bond.amount == config.bond_amount,
+            "Invalid listing bond"
+        );
+
+        let whitelist = self.common_tokens_for_user_pairs();
+        let common_token_id = if whitelist.contains(first_token_id) {
+            first_token_id.clone()
+        } else if whitelist.contains(second_token_id) {
+            second_token_id.clone()
+        } else {
+            sc_panic!("No common token in pair");
+        };
+        let min_liquidity_mapper = self.listing_min_liquidity(&common_token_id);
+        require!(
+            !min_liquidity_mapper.is_empty(),
+            "No listing min liquidity for common token"
+        );
+
+        PendingListing {
+            creator,
+            bond,
+            common_token_id,
+            min_liquidity: min_liquidity_mapper.get(),
+            min_lock_epochs: config.min_lock_epochs,
+            deadline_epoch: self.blockchain().get_block_epoch() + config.period_epochs,
+        }
+    }
+
+    fn save_pending_listing(
+        &self,
+        pair_address: &ManagedAddress,
+        listing: PendingListing<Self::Api>,
+    ) {
+        self.pending_listings().insert(pair_address.clone());
+        self.pending_listing(pair_address).set(&listing);
+
+        self.emit_listing_bond_paid_event(pair_address.clone(), listing);
+    }
+
+    fn remove_pending_listing(&self, pair_address: &ManagedAddress) {
+        self.pending_listings().swap_remove(pair_address);
+        self.pending_listing(pair_address).clear();
+    }
+
+    #[view(getPendingListing)]
+    fn get_pending_listing(&self, pair_address: ManagedAddress) -> PendingListing<Self::Api> {
+        let mapper = self.pending_listing(&pair_address);
+        require!(!mapper.is_empty(), "No pending listing for pair");
+
+        mapper.get()
+    }
+
+    #[view(getPendingListings)]
+    fn get_pending_listings(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, PendingListing<Self::Api>>> {
+        let mut result = MultiValueEncoded::new();
+        for pair_address in self.pending_listings().iter() {
+            let listing = self.pending_listing(&pair_address).get();
+            result.push((pair_address, listing).into());
+        }
+
+        result
+    }
+
+    #[proxy]
+    fn listing_pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+
+    #[proxy]
+    fn listing_fees_collector_proxy(&self, to: ManagedAddress) -> fees_collector::Proxy<Self::Api>;
+
+    #[view(getListingBondConfig)]
+    #[storage_mapper("listingBondConfig")]
+    fn listing_bond_config(&self) -> SingleValueMapper<ListingBondConfig<Self::Api>>;
+
+    #[view(getListingFeesCollectorAddress)]
+    #[storage_mapper("listingFeesCollectorAddress")]
+    fn listing_fees_collector_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getListingMinLiquidity)]
+    #[storage_mapper("listingMinLiquidity")]
+    fn listing_min_liquidity(
+        &self,
+        common_token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("pendingListings")]
+    fn pending_listings(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("pendingListing")]
+    fn pending_listing(
+        &self,
+        pair_address: &ManagedAddress,
+    ) -> SingleValueMapper<PendingListing<Self::Api>>;
+}
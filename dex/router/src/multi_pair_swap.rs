@@ -2,7 +2,9 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use super::factory;
+use super::weighted_pools;
 
+use pair::errors::{ERROR_AMOUNT_OUT_BELOW_MIN, ERROR_DEADLINE_PASSED};
 use pair::ProxyTrait as _;
 
 type SwapOperationType<M> =
@@ -12,10 +14,53 @@ pub const SWAP_TOKENS_FIXED_INPUT_FUNC_NAME: &[u8] = b"swapTokensFixedInput";
 pub const SWAP_TOKENS_FIXED_OUTPUT_FUNC_NAME: &[u8] = b"swapTokensFixedOutput";
 
 #[multiversx_sc::module]
-pub trait MultiPairSwap: factory::FactoryModule + token_send::TokenSendModule {
+pub trait MultiPairSwap:
+    factory::FactoryModule + weighted_pools::WeightedPoolsModule + token_send::TokenSendModule
+{
     #[payable("*")]
     #[endpoint(multiPairSwap)]
     fn multi_pair_swap(&self, swap_operations: MultiValueEncoded<SwapOperationType<Self::Api>>) {
+        let payments = self.perform_multi_pair_swap(swap_operations);
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_multi(&caller, &payments);
+    }
+
+    /// Same as `multiPairSwap`, but fails if the amount received from the last operation
+    /// is below `amount_out_min`, or if the deadline (a block timestamp, in seconds) has passed.
+    /// The swap operations take all the trailing arguments of `multiPairSwap`,
+    /// so the limits are given first, with an optional deadline, as for the pair swaps.
+    #[payable("*")]
+    #[endpoint(multiPairSwapWithLimits)]
+    fn multi_pair_swap_with_limits(
+        &self,
+        amount_out_min: BigUint,
+        opt_deadline: Option<u64>,
+        swap_operations: MultiValueEncoded<SwapOperationType<Self::Api>>,
+    ) {
+        if let Some(deadline) = opt_deadline {
+            require!(
+                self.blockchain().get_block_timestamp() <= deadline,
+                ERROR_DEADLINE_PASSED
+            );
+        }
+
+        let payments = self.perform_multi_pair_swap(swap_operations);
+        let final_payment = payments.get(payments.len() - 1);
+        require!(
+            final_payment.amount >= amount_out_min,
+            ERROR_AMOUNT_OUT_BELOW_MIN
+        );
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_multi(&caller, &payments);
+    }
+
+    /// Returns the residuums of the fixed output operations, followed by the final payment.
+    fn perform_multi_pair_swap(
+        &self,
+        swap_operations: MultiValueEncoded<SwapOperationType<Self::Api>>,
+    ) -> ManagedVec<EsdtTokenPayment> {
         let (token_id, nonce, amount) = self.call_value().single_esdt().into_tuple();
         require!(nonce == 0, "Invalid nonce. Should be zero");
         require!(amount > 0u64, "Invalid amount. Should not be zero");
@@ -27,13 +72,12 @@ pub trait MultiPairSwap: factory::FactoryModule + token_send::TokenSendModule {
         let swap_fixed_input_endpoint = ManagedBuffer::from(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME);
         let swap_fixed_output_endpoint = ManagedBuffer::from(SWAP_TOKENS_FIXED_OUTPUT_FUNC_NAME);
 
-        let caller = self.blockchain().get_caller();
         let mut payments = ManagedVec::new();
         let mut last_payment = EsdtTokenPayment::new(token_id, nonce, amount);
 
         for entry in swap_operations.into_iter() {
             let (pair_address, function, token_wanted, amount_wanted) = entry.into_tuple();
-            self.check_is_pair_sc(&pair_address);
+            self.check_is_pool_sc(&pair_address);
 
             if function == swap_fixed_input_endpoint {
                 last_payment = self.actual_swap_fixed_input(
@@ -60,7 +104,8 @@ pub trait MultiPairSwap: factory::FactoryModule + token_send::TokenSendModule {
         }
 
         payments.push(last_payment);
-        self.send().direct_multi(&caller, &payments);
+
+        payments
     }
 
     fn actual_swap_fixed_input(
@@ -72,7 +117,7 @@ pub trait MultiPairSwap: factory::FactoryModule + token_send::TokenSendModule {
         amount_out_min: BigUint,
     ) -> EsdtTokenPayment<Self::Api> {
         self.pair_contract_proxy(pair_address)
-            .swap_tokens_fixed_input(token_out, amount_out_min)
+            .swap_tokens_fixed_input(token_out, amount_out_min, OptionalValue::<u64>::None)
             .with_esdt_transfer((token_in, 0, amount_in))
             .execute_on_dest_context()
     }
@@ -87,7 +132,7 @@ pub trait MultiPairSwap: factory::FactoryModule + token_send::TokenSendModule {
     ) -> (EsdtTokenPayment<Self::Api>, EsdtTokenPayment<Self::Api>) {
         let call_result: MultiValue2<EsdtTokenPayment<Self::Api>, EsdtTokenPayment<Self::Api>> =
             self.pair_contract_proxy(pair_address)
-                .swap_tokens_fixed_output(token_out, amount_out)
+                .swap_tokens_fixed_output(token_out, amount_out, OptionalValue::<u64>::None)
                 .with_esdt_transfer((token_in, 0, amount_in_max))
                 .execute_on_dest_context();
 
@@ -0,0 +1,44 @@
+multiversx_sc::imports!();
+
+use crate::multi_pair_swap::{ProxyTrait as _, SWAP_TOKENS_FIXED_INPUT_FUNC_NAME};
+
+/// For the contracts that swap through the router, not part of the router itself.
+#[multiversx_sc::module]
+pub trait RouterSwapModule {
+    /// Swaps the payment through a single pair, with a fixed input.
+    /// `multiPairSwap` sends the output back without returning it,
+    /// so the received amount is taken from the balance difference.
+    fn swap_through_router(
+        &self,
+        router_address: ManagedAddress,
+        pair_address: ManagedAddress,
+        payment: EsdtTokenPayment,
+        token_out: &TokenIdentifier,
+        min_amount_out: BigUint,
+    ) -> BigUint {
+        let token_out_id = EgldOrEsdtTokenIdentifier::esdt(token_out.clone());
+        let balance_before = self.blockchain().get_sc_balance(&token_out_id, 0);
+
+        let mut swap_operations = MultiValueEncoded::new();
+        swap_operations.push(
+            (
+                pair_address,
+                ManagedBuffer::from(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME),
+                token_out.clone(),
+                min_amount_out,
+            )
+                .into(),
+        );
+        let _: IgnoreValue = self
+            .router_proxy(router_address)
+            .multi_pair_swap(swap_operations)
+            .with_esdt_transfer(payment)
+            .execute_on_dest_context();
+
+        let balance_after = self.blockchain().get_sc_balance(&token_out_id, 0);
+        balance_after - balance_before
+    }
+
+    #[proxy]
+    fn router_proxy(&self, to: ManagedAddress) -> crate::Proxy<Self::Api>;
+}
@@ -0,0 +1,269 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use super::factory;
+use super::multi_pair_swap;
+use super::weighted_pools;
+
+use pair::ProxyTrait as _;
+use pausable::State;
+use weighted_pool::ProxyTrait as _;
+
+pub const MAX_ROUTE_HOPS: usize = 3;
+pub const MAX_ROUTE_QUOTES: usize = 50;
+
+#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct RouteStep<M: ManagedTypeApi> {
+    pub pair_address: ManagedAddress<M>,
+    pub token_out: TokenIdentifier<M>,
+    pub amount_out: BigUint<M>,
+}
+
+#[derive(ManagedVecItem, Clone)]
+pub struct RoutePool<M: ManagedTypeApi> {
+    pub address: ManagedAddress<M>,
+    pub tokens: ManagedVec<M, TokenIdentifier<M>>,
+    pub is_weighted_pool: bool,
+}
+
+/// Finds the route with the best output between two tokens,
+/// through the pairs and weighted pools registered in the router.
+///
+/// Pools that are not active or have an empty reserve are skipped, and a route never
+/// goes through the same token twice. Each pool on the way is asked for its output amount
+/// through `tryGetAmountOut`, so a pool which cannot handle the amount is skipped as well.
+/// The cost of the search grows with the number of pools and `max_hops`, so it fails
+/// if it needs more than `MAX_ROUTE_QUOTES` quotes, instead of settling for a partial result.
+#[multiversx_sc::module]
+pub trait SmartRoutingModule:
+    factory::FactoryModule
+    + weighted_pools::WeightedPoolsModule
+    + multi_pair_swap::MultiPairSwap
+    + token_send::TokenSendModule
+{
+    /// The steps can be used as-is for `multiPairSwap`, with `swapTokensFixedInput`.
+    /// Nothing is returned if there is no route.
+    #[view(getBestRoute)]
+    fn get_best_route_view(
+        &self,
+        token_in: TokenIdentifier,
+        token_out: TokenIdentifier,
+        amount_in: BigUint,
+        max_hops: usize,
+    ) -> MultiValueEncoded<RouteStep<Self::Api>> {
+        self.find_best_route(&token_in, &token_out, &amount_in, max_hops)
+            .into()
+    }
+
+    #[payable("*")]
+    #[endpoint(smartSwap)]
+    fn smart_swap(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+        max_hops: usize,
+    ) -> EsdtTokenPayment {
+        let (token_in, nonce, amount_in) = self.call_value().single_esdt().into_tuple();
+        require!(nonce == 0, "Invalid nonce. Should be zero");
+        require!(amount_in > 0u64, "Invalid amount. Should not be zero");
+        require!(amount_out_min > 0u64, "Invalid minimum amount out");
+
+        let route = self.find_best_route(&token_in, &token_out, &amount_in, max_hops);
+        require!(!route.is_empty(), "No route found");
+
+        let mut last_payment = EsdtTokenPayment::new(token_in, 0, amount_in);
+        for step in route.iter() {
+            // the quoted amounts are only checked at the end, against the caller's minimum
+            last_payment = self.actual_swap_fixed_input(
+                step.pair_address,
+                last_payment.token_identifier,
+                last_payment.amount,
+                step.token_out,
+                BigUint::from(1u64),
+            );
+        }
+        require!(last_payment.amount >= amount_out_min, "Slippage exceeded");
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(
+            &caller,
+            &last_payment.token_identifier,
+            0,
+            &last_payment.amount,
+        );
+
+        last_payment
+    }
+
+    fn find_best_route(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_in: &BigUint,
+        max_hops: usize,
+    ) -> ManagedVec<RouteStep<Self::Api>> {
+        require!(token_in != token_out, "Tokens must be different");
+        require!(amount_in > &0u64, "Invalid amount. Should not be zero");
+        require!(
+            (1..=MAX_ROUTE_HOPS).contains(&max_hops),
+            "Invalid number of hops"
+        );
+
+        let pools = self.get_tradable_pools();
+        let mut visited_tokens = ManagedVec::new();
+        visited_tokens.push(token_in.clone());
+        let mut current_route = ManagedVec::new();
+        let mut best_route = ManagedVec::new();
+        let mut quotes_left = MAX_ROUTE_QUOTES;
+        self.search_routes(
+            &pools,
+            token_in,
+            amount_in,
+            token_out,
+            max_hops,
+            &mut visited_tokens,
+            &mut current_route,
+            &mut best_route,
+            &mut quotes_left,
+        );
+
+        best_route
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_routes(
+        &self,
+        pools: &ManagedVec<RoutePool<Self::Api>>,
+        current_token: &TokenIdentifier,
+        current_amount: &BigUint,
+        token_out: &TokenIdentifier,
+        hops_left: usize,
+        visited_tokens: &mut ManagedVec<TokenIdentifier>,
+        current_route: &mut ManagedVec<RouteStep<Self::Api>>,
+        best_route: &mut ManagedVec<RouteStep<Self::Api>>,
+        quotes_left: &mut usize,
+    ) {
+        for pool in pools.iter() {
+            if !pool.tokens.iter().any(|token| *token == *current_token) {
+                continue;
+            }
+
+            for next_token in pool.tokens.iter() {
+                let next_token = (*next_token).clone();
+                if visited_tokens.iter().any(|token| *token == next_token) {
+                    continue;
+                }
+                // the last hop can only end in the output token
+                if hops_left == 1 && &next_token != token_out {
+                    continue;
+                }
+
+                require!(*quotes_left > 0, "Too many pools to search, use fewer hops");
+                *quotes_left -= 1;
+
+                let amount_out =
+                    self.get_pool_amount_out(&pool, current_token, current_amount, &next_token);
+                if amount_out == 0u64 {
+                    continue;
+                }
+
+                current_route.push(RouteStep {
+                    pair_address: pool.address.clone(),
+                    token_out: next_token.clone(),
+                    amount_out: amount_out.clone(),
+                });
+
+                if &next_token == token_out {
+                    let is_better = best_route.is_empty()
+                        || amount_out > best_route.get(best_route.len() - 1).amount_out;
+                    if is_better {
+                        *best_route = current_route.clone();
+                    }
+                } else if hops_left > 1 {
+                    visited_tokens.push(next_token.clone());
+                    self.search_routes(
+                        pools,
+                        &next_token,
+                        &amount_out,
+                        token_out,
+                        hops_left - 1,
+                        visited_tokens,
+                        current_route,
+                        best_route,
+                        quotes_left,
+                    );
+                    visited_tokens.remove(visited_tokens.len() - 1);
+                }
+
+                current_route.remove(current_route.len() - 1);
+            }
+        }
+    }
+
+    fn get_pool_amount_out(
+        &self,
+        pool: &RoutePool<Self::Api>,
+        token_in: &TokenIdentifier,
+        amount_in: &BigUint,
+        token_out: &TokenIdentifier,
+    ) -> BigUint {
+        if !pool.is_weighted_pool {
+            return self
+                .pair_contract_proxy(pool.address.clone())
+                .try_get_amount_out_view(token_in.clone(), amount_in.clone())
+                .execute_on_dest_context();
+        }
+
+        self.weighted_pool_proxy(pool.address.clone())
+            .try_get_amount_out_view(token_in.clone(), amount_in.clone(), token_out.clone())
+            .execute_on_dest_context()
+    }
+
+    /// State and reserves are read directly from the storage of the pools.
+    fn get_tradable_pools(&self) -> ManagedVec<RoutePool<Self::Api>> {
+        let mut pools = ManagedVec::new();
+        for (pair_tokens, pair_address) in self.pair_map().iter() {
+            let mut tokens = ManagedVec::new();
+            tokens.push(pair_tokens.first_token_id);
+            tokens.push(pair_tokens.second_token_id);
+            self.push_if_tradable(&mut pools, pair_address, tokens, false);
+        }
+        for pool_address in self.weighted_pools().iter() {
+            let tokens = self.get_weighted_pool_tokens(&pool_address);
+            self.push_if_tradable(&mut pools, pool_address, tokens, true);
+        }
+
+        pools
+    }
+
+    fn push_if_tradable(
+        &self,
+        pools: &mut ManagedVec<RoutePool<Self::Api>>,
+        address: ManagedAddress,
+        tokens: ManagedVec<TokenIdentifier>,
+        is_weighted_pool: bool,
+    ) {
+        if self.pool_state().get_from_address(&address) != State::Active {
+            return;
+        }
+        let has_reserves = tokens
+            .iter()
+            .all(|token_id| self.pool_reserve(&token_id).get_from_address(&address) > 0u64);
+        if !has_reserves {
+            return;
+        }
+
+        pools.push(RoutePool {
+            address,
+            tokens,
+            is_weighted_pool,
+        });
+    }
+
+    /// Storage of the pairs and weighted pools, only read with `get_from_address`.
+    #[storage_mapper("state")]
+    fn pool_state(&self) -> SingleValueMapper<State>;
+
+    #[storage_mapper("reserve")]
+    fn pool_reserve(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
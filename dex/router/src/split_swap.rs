@@ -0,0 +1,118 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use super::factory;
+use super::multi_pair_swap;
+use super::weighted_pools;
+
+pub const MAX_SPLIT_PERCENTAGE: u64 = 100_000;
+pub const MAX_SPLIT_ROUTES: usize = 5;
+
+#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct SplitRouteStep<M: ManagedTypeApi> {
+    pub pair_address: ManagedAddress<M>,
+    pub token_out: TokenIdentifier<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct SplitRoute<M: ManagedTypeApi> {
+    pub percentage: u64,
+    pub steps: ManagedVec<M, SplitRouteStep<M>>,
+}
+
+/// Splits the payment between several routes, each one receiving a percentage of it.
+/// The percentages are expressed out of `MAX_SPLIT_PERCENTAGE` and must add up to it.
+/// The last route receives whatever is left after the others, so no dust is lost to rounding.
+#[multiversx_sc::module]
+pub trait SplitSwapModule:
+    factory::FactoryModule
+    + weighted_pools::WeightedPoolsModule
+    + multi_pair_swap::MultiPairSwap
+    + token_send::TokenSendModule
+{
+    #[payable("*")]
+    #[endpoint(splitSwap)]
+    fn split_swap(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+        routes: MultiValueEncoded<SplitRoute<Self::Api>>,
+    ) -> EsdtTokenPayment {
+        let (token_in, nonce, amount_in) = self.call_value().single_esdt().into_tuple();
+        require!(nonce == 0, "Invalid nonce. Should be zero");
+        require!(amount_in > 0u64, "Invalid amount. Should not be zero");
+        require!(amount_out_min > 0u64, "Invalid minimum amount out");
+
+        let routes_count = routes.len();
+        require!(
+            routes_count > 0 && routes_count <= MAX_SPLIT_ROUTES,
+            "Invalid number of routes"
+        );
+
+        let mut total_percentage = 0u64;
+        let mut remaining_amount = amount_in.clone();
+        let mut total_amount_out = BigUint::zero();
+        for (index, route) in routes.into_iter().enumerate() {
+            require!(
+                route.percentage > 0 && !route.steps.is_empty(),
+                "Invalid route"
+            );
+            total_percentage += route.percentage;
+            require!(
+                total_percentage <= MAX_SPLIT_PERCENTAGE,
+                "Route percentages must add up to 100%"
+            );
+
+            let route_amount_in = if index == routes_count - 1 {
+                remaining_amount.clone()
+            } else {
+                &amount_in * route.percentage / MAX_SPLIT_PERCENTAGE
+            };
+            require!(route_amount_in > 0u64, "Route amount is zero");
+            remaining_amount -= &route_amount_in;
+
+            let route_output =
+                self.execute_split_route(token_in.clone(), route_amount_in, &route.steps);
+            require!(
+                route_output.token_identifier == token_out,
+                "Route does not end in the wanted token"
+            );
+
+            total_amount_out += route_output.amount;
+        }
+        require!(
+            total_percentage == MAX_SPLIT_PERCENTAGE,
+            "Route percentages must add up to 100%"
+        );
+        require!(total_amount_out >= amount_out_min, "Slippage exceeded");
+
+        let caller = self.blockchain().get_caller();
+        self.send()
+            .direct_esdt(&caller, &token_out, 0, &total_amount_out);
+
+        EsdtTokenPayment::new(token_out, 0, total_amount_out)
+    }
+
+    fn execute_split_route(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+        steps: &ManagedVec<SplitRouteStep<Self::Api>>,
+    ) -> EsdtTokenPayment {
+        let mut last_payment = EsdtTokenPayment::new(token_in, 0, amount_in);
+        for step in steps.iter() {
+            self.check_is_pool_sc(&step.pair_address);
+
+            // only the aggregated output is checked against the caller's minimum
+            last_payment = self.actual_swap_fixed_input(
+                step.pair_address,
+                last_payment.token_identifier,
+                last_payment.amount,
+                step.token_out,
+                BigUint::from(1u64),
+            );
+        }
+
+        last_payment
+    }
+}
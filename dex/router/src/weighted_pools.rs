@@ -0,0 +1,88 @@
+multiversx_sc::imports!();
+
+use super::factory;
+
+use weighted_pool::config::ProxyTrait as _;
+
+/// Weighted pools are deployed separately and registered by the owner.
+/// Once registered, they can be used as steps of `multiPairSwap`, same as pairs.
+#[multiversx_sc::module]
+pub trait WeightedPoolsModule: factory::FactoryModule {
+    #[only_owner]
+    #[endpoint(addWeightedPool)]
+    fn add_weighted_pool(&self, pool_address: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&pool_address),
+            "Invalid pool address"
+        );
+        require!(
+            !self.address_pair_map().contains_key(&pool_address),
+            "Address is already a pair SC"
+        );
+        require!(
+            self.weighted_pools().insert(pool_address.clone()),
+            "Weighted pool already added"
+        );
+
+        for token_id in self.get_weighted_pool_tokens(&pool_address).iter() {
+            let _ = self
+                .weighted_pools_for_token(&token_id)
+                .insert(pool_address.clone());
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(removeWeightedPool)]
+    fn remove_weighted_pool(&self, pool_address: ManagedAddress) {
+        require!(
+            self.weighted_pools().swap_remove(&pool_address),
+            "Not a weighted pool SC"
+        );
+
+        for token_id in self.get_weighted_pool_tokens(&pool_address).iter() {
+            let _ = self
+                .weighted_pools_for_token(&token_id)
+                .swap_remove(&pool_address);
+        }
+    }
+
+    fn get_weighted_pool_tokens(
+        &self,
+        pool_address: &ManagedAddress,
+    ) -> ManagedVec<TokenIdentifier> {
+        let tokens_and_weights: MultiValueEncoded<MultiValue2<TokenIdentifier, u64>> = self
+            .weighted_pool_proxy(pool_address.clone())
+            .get_tokens_and_weights()
+            .execute_on_dest_context();
+
+        let mut tokens = ManagedVec::new();
+        for token_and_weight in tokens_and_weights {
+            let (token_id, _) = token_and_weight.into_tuple();
+            tokens.push(token_id);
+        }
+
+        tokens
+    }
+
+    fn check_is_pool_sc(&self, address: &ManagedAddress) {
+        require!(
+            self.address_pair_map().contains_key(address)
+                || self.weighted_pools().contains(address),
+            "Not a pair SC"
+        );
+    }
+
+    #[proxy]
+    fn weighted_pool_proxy(&self, to: ManagedAddress) -> weighted_pool::Proxy<Self::Api>;
+
+    #[view(getAllWeightedPools)]
+    #[storage_mapper("weighted_pools")]
+    fn weighted_pools(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getWeightedPoolsForToken)]
+    #[storage_mapper("weighted_pools_for_token")]
+    fn weighted_pools_for_token(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> UnorderedSetMapper<ManagedAddress>;
+}
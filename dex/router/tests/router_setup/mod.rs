@@ -1,4 +1,5 @@
-use multiversx_sc::codec::multi_types::{MultiValue4, OptionalValue};
+use multiversx_sc::codec::multi_types::{MultiValue2, MultiValue4, OptionalValue};
+use multiversx_sc::storage::mappers::StorageTokenWrapper;
 use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
 use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
 use multiversx_sc_scenario::{
@@ -8,11 +9,14 @@ use multiversx_sc_scenario::{
 
 pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
 pub const ROUTER_WASM_PATH: &str = "router/output/router.wasm";
+pub const WEIGHTED_POOL_WASM_PATH: &str = "weighted-pool/output/weighted-pool.wasm";
 pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
 pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
 pub const USDC_TOKEN_ID: &[u8] = b"USDC-abcdef";
 pub const LPMEX_TOKEN_ID: &[u8] = b"LPMEX-abcdef";
 pub const LPUSDC_TOKEN_ID: &[u8] = b"LPUSDC-abcdef";
+pub const LPMEXUSDC_TOKEN_ID: &[u8] = b"LPMEXUSDC-abcdef";
+pub const WEIGHTED_POOL_LP_TOKEN_ID: &[u8] = b"WPLP-abcdef";
 
 pub const USER_TOTAL_MEX_TOKENS: u64 = 5_001_001_000;
 pub const USER_TOTAL_WEGLD_TOKENS: u64 = 5_002_002_000;
@@ -32,7 +36,10 @@ use pair::*;
 use pausable::{PausableModule, State};
 use router::factory::*;
 use router::multi_pair_swap::*;
+use router::smart_routing::*;
+use router::weighted_pools::WeightedPoolsModule;
 use router::*;
+use weighted_pool::{config::ConfigModule as _, WeightedPool};
 
 #[allow(dead_code)]
 pub struct RouterSetup<RouterObjBuilder, PairObjBuilder>
@@ -283,6 +290,189 @@ where
             .assert_ok();
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn smart_swap(
+        &mut self,
+        payment_token: &[u8],
+        payment_amount: u64,
+        token_out: &[u8],
+        amount_out_min: u64,
+        max_hops: usize,
+        expected_amount_out: u64,
+    ) {
+        self.blockchain_wrapper
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.router_wrapper,
+                payment_token,
+                0,
+                &rust_biguint!(payment_amount),
+                |sc| {
+                    let payment = sc.smart_swap(
+                        managed_token_id!(token_out),
+                        managed_biguint!(amount_out_min),
+                        max_hops,
+                    );
+
+                    assert_eq!(payment.token_identifier, managed_token_id!(token_out));
+                    assert_eq!(payment.amount, managed_biguint!(expected_amount_out));
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn create_pair_with_liquidity(
+        &mut self,
+        pair_builder: PairObjBuilder,
+        first_token: &[u8],
+        second_token: &[u8],
+        lp_token: &[u8],
+        liquidity: u64,
+    ) -> ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder> {
+        let rust_zero = rust_biguint!(0u64);
+        let owner_addr = self.owner_address.clone();
+        let pair_wrapper = self.blockchain_wrapper.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            pair_builder,
+            PAIR_WASM_PATH,
+        );
+
+        self.blockchain_wrapper
+            .execute_tx(&owner_addr, &pair_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(first_token),
+                    managed_token_id!(second_token),
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    300,
+                    50,
+                    ManagedAddress::<DebugApi>::zero(),
+                    MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+                );
+
+                sc.lp_token_identifier().set(&managed_token_id!(lp_token));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        self.blockchain_wrapper
+            .execute_tx(&owner_addr, &self.router_wrapper, &rust_zero, |sc| {
+                let pair_address = managed_address!(pair_wrapper.address_ref());
+                sc.pair_map().insert(
+                    PairTokens {
+                        first_token_id: managed_token_id!(first_token),
+                        second_token_id: managed_token_id!(second_token),
+                    },
+                    pair_address.clone(),
+                );
+                sc.address_pair_map().insert(
+                    pair_address,
+                    PairTokens {
+                        first_token_id: managed_token_id!(first_token),
+                        second_token_id: managed_token_id!(second_token),
+                    },
+                );
+            })
+            .assert_ok();
+
+        let lp_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        self.blockchain_wrapper.set_esdt_local_roles(
+            pair_wrapper.address_ref(),
+            lp_token,
+            &lp_token_roles[..],
+        );
+
+        let payments = vec![
+            TxTokenTransfer {
+                token_identifier: first_token.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(liquidity),
+            },
+            TxTokenTransfer {
+                token_identifier: second_token.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(liquidity),
+            },
+        ];
+        self.blockchain_wrapper
+            .execute_esdt_multi_transfer(&self.user_address, &pair_wrapper, &payments, |sc| {
+                sc.add_liquidity(managed_biguint!(liquidity), managed_biguint!(liquidity));
+            })
+            .assert_ok();
+
+        pair_wrapper
+    }
+
+    /// 80% USDC, 20% MEX, registered in the router
+    pub fn create_weighted_pool_with_liquidity<WeightedPoolObjBuilder>(
+        &mut self,
+        weighted_pool_builder: WeightedPoolObjBuilder,
+        liquidity: u64,
+    ) -> ContractObjWrapper<weighted_pool::ContractObj<DebugApi>, WeightedPoolObjBuilder>
+    where
+        WeightedPoolObjBuilder: 'static + Copy + Fn() -> weighted_pool::ContractObj<DebugApi>,
+    {
+        let rust_zero = rust_biguint!(0u64);
+        let owner_addr = self.owner_address.clone();
+        let pool_wrapper = self.blockchain_wrapper.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            weighted_pool_builder,
+            WEIGHTED_POOL_WASM_PATH,
+        );
+
+        self.blockchain_wrapper
+            .execute_tx(&owner_addr, &pool_wrapper, &rust_zero, |sc| {
+                let mut tokens_and_weights = MultiValueEncoded::new();
+                tokens_and_weights
+                    .push(MultiValue2::from((managed_token_id!(USDC_TOKEN_ID), 4u64)));
+                tokens_and_weights.push(MultiValue2::from((managed_token_id!(MEX_TOKEN_ID), 1u64)));
+                sc.init(
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    300,
+                    tokens_and_weights,
+                );
+
+                sc.lp_token()
+                    .set_token_id(managed_token_id!(WEIGHTED_POOL_LP_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+        self.blockchain_wrapper.set_esdt_local_roles(
+            pool_wrapper.address_ref(),
+            WEIGHTED_POOL_LP_TOKEN_ID,
+            &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+        );
+
+        let payments = vec![
+            TxTokenTransfer {
+                token_identifier: USDC_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(liquidity),
+            },
+            TxTokenTransfer {
+                token_identifier: MEX_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(liquidity),
+            },
+        ];
+        self.blockchain_wrapper
+            .execute_esdt_multi_transfer(&self.user_address, &pool_wrapper, &payments, |sc| {
+                let _ = sc.add_liquidity(managed_biguint!(1));
+            })
+            .assert_ok();
+
+        self.blockchain_wrapper
+            .execute_tx(&owner_addr, &self.router_wrapper, &rust_zero, |sc| {
+                sc.add_weighted_pool(managed_address!(pool_wrapper.address_ref()));
+            })
+            .assert_ok();
+
+        pool_wrapper
+    }
+
     pub fn migrate_pair_map(&mut self) {
         self.blockchain_wrapper
             .execute_tx(
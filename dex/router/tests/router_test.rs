@@ -1,8 +1,12 @@
 #![allow(deprecated)]
 
 mod router_setup;
+use fees_collector::{
+    config::ConfigModule as _, fees_accumulation::FeesAccumulationModule, FeesCollector,
+};
+use liquidity_locker::LiquidityLocker;
 use multiversx_sc::{
-    codec::multi_types::OptionalValue,
+    codec::multi_types::{MultiValue4, OptionalValue},
     storage::mappers::StorageTokenWrapper,
     types::{
         Address, EgldOrEsdtTokenIdentifier, EsdtLocalRole, ManagedAddress, ManagedVec,
@@ -14,14 +18,19 @@ use pausable::{PausableModule, State};
 use router::{
     enable_swap_by_user::EnableSwapByUserModule,
     factory::{FactoryModule, PairTokens},
-    multi_pair_swap::SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+    listing_bond::ListingBondModule,
+    multi_pair_swap::{MultiPairSwap, SWAP_TOKENS_FIXED_INPUT_FUNC_NAME},
+    smart_routing::SmartRoutingModule,
+    split_swap::{SplitRoute, SplitRouteStep, SplitSwapModule},
+    weighted_pools::WeightedPoolsModule,
     Router,
 };
 use router_setup::*;
 
 use multiversx_sc_scenario::{
-    managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped, rust_biguint,
-    whitebox_legacy::BlockchainStateWrapper, whitebox_legacy::TxTokenTransfer, DebugApi,
+    managed_address, managed_biguint, managed_buffer, managed_token_id, managed_token_id_wrapped,
+    rust_biguint, whitebox_legacy::BlockchainStateWrapper, whitebox_legacy::TxTokenTransfer,
+    DebugApi,
 };
 use simple_lock::{
     locked_token::{LockedTokenAttributes, LockedTokenModule},
@@ -130,6 +139,356 @@ fn test_router_upgrade_pair() {
         .assert_ok();
 }
 
+#[test]
+fn user_create_pair_with_listing_bond_test() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_zero);
+
+    let bond_amount = 1_000u64;
+    let period_epochs = 10u64;
+    let kept_pair_liquidity = 10_000u64;
+    b_mock.set_block_epoch(5);
+    b_mock.set_esdt_balance(&user, WEGLD_TOKEN_ID, &rust_biguint!(bond_amount * 3));
+    b_mock.set_esdt_balance(
+        &user,
+        CUSTOM_TOKEN_ID,
+        &rust_biguint!(USER_CUSTOM_TOKEN_BALANCE),
+    );
+    b_mock.set_esdt_balance(&user, MEX_TOKEN_ID, &rust_biguint!(kept_pair_liquidity));
+    b_mock.set_esdt_balance(
+        &user,
+        USDC_TOKEN_ID,
+        &rust_biguint!(USER_USDC_BALANCE + kept_pair_liquidity),
+    );
+
+    let router_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        router::contract_obj,
+        ROUTER_WASM_PATH,
+    );
+    let pair_template_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(router_wrapper.address_ref()),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+    let fees_collector_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        None,
+        fees_collector::contract_obj,
+        "fees collector path",
+    );
+    let liquidity_locker_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        liquidity_locker::contract_obj,
+        "liquidity-locker.wasm",
+    );
+
+    b_mock
+        .execute_tx(&owner, &pair_template_wrapper, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(&Address::zero()),
+                managed_address!(&owner),
+                0,
+                0,
+                managed_address!(&user),
+                MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &fees_collector_wrapper, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LOCKED_TOKEN_ID),
+                managed_address!(pair_template_wrapper.address_ref()),
+            );
+            let _ = sc
+                .known_contracts()
+                .insert(managed_address!(router_wrapper.address_ref()));
+
+            let mut tokens = MultiValueEncoded::new();
+            tokens.push(managed_token_id!(WEGLD_TOKEN_ID));
+            sc.add_known_tokens(tokens);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &liquidity_locker_wrapper, &rust_zero, |sc| {
+            sc.init();
+        })
+        .assert_ok();
+
+    // pair creation stays disabled, users pay the bond instead
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.init(OptionalValue::Some(managed_address!(
+                pair_template_wrapper.address_ref()
+            )));
+
+            sc.add_common_tokens_for_user_pairs(MultiValueEncoded::from(ManagedVec::from(vec![
+                managed_token_id!(USDC_TOKEN_ID),
+            ])));
+            sc.set_listing_min_liquidity(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_biguint!(MIN_LOCKED_TOKEN_VALUE),
+            );
+            sc.set_listing_fees_collector_address(managed_address!(
+                fees_collector_wrapper.address_ref()
+            ));
+            sc.set_listing_bond_config(
+                managed_token_id!(WEGLD_TOKEN_ID),
+                managed_biguint!(bond_amount),
+                period_epochs,
+                MIN_LOCKED_PERIOD_EPOCHS,
+            );
+            sc.set_liquidity_locker_address(managed_address!(
+                liquidity_locker_wrapper.address_ref()
+            ));
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_esdt_transfer(
+            &user,
+            &router_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(bond_amount - 1),
+            |sc| {
+                let _ = sc.create_pair_endpoint(
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_address!(&user),
+                    OptionalValue::None,
+                    MultiValueEncoded::new(),
+                );
+            },
+        )
+        .assert_user_error("Invalid listing bond");
+
+    // the listed pair gets its bond back, the kept pair has some liquidity,
+    // and the dead pair has none
+    let mut pair_wrappers = Vec::new();
+    for first_token_id in [CUSTOM_TOKEN_ID, MEX_TOKEN_ID, WEGLD_TOKEN_ID] {
+        let pair_wrapper =
+            b_mock.prepare_deploy_from_sc(router_wrapper.address_ref(), pair::contract_obj);
+        b_mock
+            .execute_esdt_transfer(
+                &user,
+                &router_wrapper,
+                WEGLD_TOKEN_ID,
+                0,
+                &rust_biguint!(bond_amount),
+                |sc| {
+                    let _ = sc.create_pair_endpoint(
+                        managed_token_id!(first_token_id),
+                        managed_token_id!(USDC_TOKEN_ID),
+                        managed_address!(&user),
+                        OptionalValue::None,
+                        MultiValueEncoded::new(),
+                    );
+                },
+            )
+            .assert_ok();
+        pair_wrappers.push(pair_wrapper);
+    }
+    let dead_pair_wrapper = pair_wrappers.pop().unwrap();
+    let kept_pair_wrapper = pair_wrappers.pop().unwrap();
+    let listed_pair_wrapper = pair_wrappers.pop().unwrap();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            assert_eq!(sc.get_pending_listings().len(), 3);
+
+            let listing =
+                sc.get_pending_listing(managed_address!(listed_pair_wrapper.address_ref()));
+            assert_eq!(listing.creator, managed_address!(&user));
+            assert_eq!(listing.bond.amount, managed_biguint!(bond_amount));
+            assert_eq!(listing.common_token_id, managed_token_id!(USDC_TOKEN_ID));
+            assert_eq!(listing.min_lock_epochs, MIN_LOCKED_PERIOD_EPOCHS);
+            assert_eq!(listing.deadline_epoch, 5 + period_epochs);
+        })
+        .assert_ok();
+
+    for (pair_wrapper, lp_token_id, first_token_id, first_token_amount, second_token_amount) in [
+        (
+            &listed_pair_wrapper,
+            LPUSDC_TOKEN_ID,
+            CUSTOM_TOKEN_ID,
+            USER_CUSTOM_TOKEN_BALANCE,
+            USER_USDC_BALANCE,
+        ),
+        (
+            &kept_pair_wrapper,
+            LPMEXUSDC_TOKEN_ID,
+            MEX_TOKEN_ID,
+            kept_pair_liquidity,
+            kept_pair_liquidity,
+        ),
+    ] {
+        b_mock
+            .execute_tx(&owner, pair_wrapper, &rust_zero, |sc| {
+                sc.lp_token_identifier()
+                    .set(&managed_token_id!(lp_token_id));
+            })
+            .assert_ok();
+        b_mock.set_esdt_local_roles(
+            pair_wrapper.address_ref(),
+            lp_token_id,
+            &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+        );
+
+        let payments = vec![
+            TxTokenTransfer {
+                token_identifier: first_token_id.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(first_token_amount),
+            },
+            TxTokenTransfer {
+                token_identifier: USDC_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(second_token_amount),
+            },
+        ];
+        b_mock
+            .execute_esdt_multi_transfer(&user, pair_wrapper, &payments, |sc| {
+                let _ = sc.add_initial_liquidity();
+            })
+            .assert_ok();
+    }
+
+    // 400_000 of the 999_000 LP tokens, most of which unlock during the minimum lock period
+    let mut lock_id = 0;
+    b_mock
+        .execute_esdt_transfer(
+            &user,
+            &liquidity_locker_wrapper,
+            LPUSDC_TOKEN_ID,
+            0,
+            &rust_biguint!(400_000),
+            |sc| {
+                lock_id = sc.lock_lp_tokens(
+                    managed_address!(listed_pair_wrapper.address_ref()),
+                    0,
+                    MIN_LOCKED_PERIOD_EPOCHS * 3 / 2,
+                );
+            },
+        )
+        .assert_ok();
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.refund_listing_bond(managed_address!(listed_pair_wrapper.address_ref()), lock_id);
+        })
+        .assert_user_error("Listing liquidity threshold not reached");
+
+    // the remaining 599_000 LP tokens, locked for the whole minimum lock period
+    b_mock
+        .execute_esdt_transfer(
+            &user,
+            &liquidity_locker_wrapper,
+            LPUSDC_TOKEN_ID,
+            0,
+            &rust_biguint!(599_000),
+            |sc| {
+                lock_id = sc.lock_lp_tokens(
+                    managed_address!(listed_pair_wrapper.address_ref()),
+                    MIN_LOCKED_PERIOD_EPOCHS,
+                    0,
+                );
+            },
+        )
+        .assert_ok();
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.refund_listing_bond(managed_address!(kept_pair_wrapper.address_ref()), lock_id);
+        })
+        .assert_user_error("Invalid lock pair");
+
+    // anyone can refund the bond, which goes back to the pair creator
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.refund_listing_bond(managed_address!(listed_pair_wrapper.address_ref()), lock_id);
+        })
+        .assert_ok();
+    b_mock.check_esdt_balance(&user, WEGLD_TOKEN_ID, &rust_biguint!(bond_amount));
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.forfeit_listing_bond(managed_address!(dead_pair_wrapper.address_ref()));
+        })
+        .assert_user_error("Listing period not ended");
+
+    b_mock.set_block_epoch(6 + period_epochs);
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.refund_listing_bond(managed_address!(dead_pair_wrapper.address_ref()), lock_id);
+        })
+        .assert_user_error("Listing period ended");
+    for pair_wrapper in [&dead_pair_wrapper, &kept_pair_wrapper] {
+        b_mock
+            .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+                sc.forfeit_listing_bond(managed_address!(pair_wrapper.address_ref()));
+            })
+            .assert_ok();
+    }
+
+    // the pair with liquidity stays in the router, and its liquidity can still be removed
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            assert_eq!(sc.get_pending_listings().len(), 0);
+            assert_eq!(
+                sc.get_pair(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID)
+                ),
+                managed_address!(&Address::zero())
+            );
+            assert_eq!(
+                sc.get_pair(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID)
+                ),
+                managed_address!(kept_pair_wrapper.address_ref())
+            );
+            assert_eq!(
+                sc.get_pair(
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID)
+                ),
+                managed_address!(listed_pair_wrapper.address_ref())
+            );
+        })
+        .assert_ok();
+    b_mock
+        .execute_query(&kept_pair_wrapper, |sc| {
+            assert_eq!(sc.state().get(), State::PartialActive);
+        })
+        .assert_ok();
+    b_mock
+        .execute_query(&dead_pair_wrapper, |sc| {
+            assert_eq!(sc.state().get(), State::Inactive);
+        })
+        .assert_ok();
+
+    // the fees collector was set up in epoch 5, so epoch 16 is in its second week
+    b_mock
+        .execute_query(&fees_collector_wrapper, |sc| {
+            assert_eq!(
+                sc.accumulated_fees(2, &managed_token_id!(WEGLD_TOKEN_ID))
+                    .get(),
+                managed_biguint!(bond_amount * 2)
+            );
+        })
+        .assert_ok();
+}
+
 #[test]
 fn test_multi_pair_swap() {
     let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
@@ -187,6 +546,452 @@ fn test_multi_pair_swap() {
     );
 }
 
+#[test]
+fn test_multi_pair_swap_with_limits() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.migrate_pair_map();
+    router_setup.add_liquidity();
+    router_setup.blockchain_wrapper.set_block_timestamp(1_000);
+
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let build_swap_operations = || {
+        let mut swap_operations = MultiValueEncoded::<DebugApi, _>::new();
+        swap_operations.push(MultiValue4::from((
+            managed_address!(&mex_pair_address),
+            managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME),
+            managed_token_id!(WEGLD_TOKEN_ID),
+            managed_biguint!(1),
+        )));
+        swap_operations.push(MultiValue4::from((
+            managed_address!(&usdc_pair_address),
+            managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME),
+            managed_token_id!(USDC_TOKEN_ID),
+            managed_biguint!(1),
+        )));
+        swap_operations
+    };
+
+    let user = router_setup.user_address.clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &router_setup.router_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                sc.multi_pair_swap_with_limits(
+                    managed_biguint!(1),
+                    Some(999),
+                    build_swap_operations(),
+                );
+            },
+        )
+        .assert_user_error("Deadline passed");
+
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &router_setup.router_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                sc.multi_pair_swap_with_limits(
+                    managed_biguint!(82_910),
+                    Some(1_000),
+                    build_swap_operations(),
+                );
+            },
+        )
+        .assert_user_error("Final amount out is below the minimum");
+
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &router_setup.router_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                sc.multi_pair_swap_with_limits(
+                    managed_biguint!(82_909),
+                    None,
+                    build_swap_operations(),
+                );
+            },
+        )
+        .assert_ok();
+
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_082_909),
+    );
+}
+
+#[test]
+fn test_multi_pair_swap_through_weighted_pool() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.migrate_pair_map();
+    router_setup.add_liquidity();
+
+    let pool_wrapper =
+        router_setup.create_weighted_pool_with_liquidity(weighted_pool::contract_obj, 1_000_000);
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            assert!(sc
+                .weighted_pools_for_token(&managed_token_id!(MEX_TOKEN_ID))
+                .contains(&managed_address!(pool_wrapper.address_ref())));
+        })
+        .assert_ok();
+
+    let ops = vec![
+        (
+            router_setup.usdc_pair_wrapper.address_ref().clone(),
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            USDC_TOKEN_ID,
+            1,
+        ),
+        (
+            pool_wrapper.address_ref().clone(),
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            MEX_TOKEN_ID,
+            1,
+        ),
+    ];
+    router_setup.multi_pair_swap(WEGLD_TOKEN_ID, 100_000, &ops);
+
+    // 100_000 WEGLD -> 90_669 USDC -> 292_603 MEX
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(4_999_000_000u64 + 292_603),
+    );
+}
+
+#[test]
+fn test_smart_swap() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.migrate_pair_map();
+    router_setup.add_liquidity();
+
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let route: ManagedVec<DebugApi, _> = sc
+                .get_best_route_view(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_biguint!(100_000),
+                    3,
+                )
+                .to_vec();
+            assert_eq!(route.len(), 2);
+
+            let first_step = route.get(0);
+            assert_eq!(first_step.pair_address, managed_address!(&mex_pair_address));
+            assert_eq!(first_step.token_out, managed_token_id!(WEGLD_TOKEN_ID));
+            assert_eq!(first_step.amount_out, managed_biguint!(90_669));
+
+            let second_step = route.get(1);
+            assert_eq!(
+                second_step.pair_address,
+                managed_address!(&usdc_pair_address)
+            );
+            assert_eq!(second_step.token_out, managed_token_id!(USDC_TOKEN_ID));
+            assert_eq!(second_step.amount_out, managed_biguint!(82_909));
+
+            // there is no direct pair
+            let route = sc.get_best_route_view(
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_biguint!(100_000),
+                1,
+            );
+            assert!(route.is_empty());
+        })
+        .assert_ok();
+
+    let user = router_setup.user_address.clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &router_setup.router_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let _ = sc.smart_swap(
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_biguint!(82_910),
+                    3,
+                );
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+
+    router_setup.smart_swap(MEX_TOKEN_ID, 100_000, USDC_TOKEN_ID, 82_909, 3, 82_909);
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_082_909),
+    );
+}
+
+#[test]
+fn test_smart_swap_picks_best_route() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.migrate_pair_map();
+    router_setup.add_liquidity();
+
+    // a direct pair with slightly less liquidity still beats the route through WEGLD
+    let direct_pair_wrapper = router_setup.create_pair_with_liquidity(
+        pair::contract_obj,
+        MEX_TOKEN_ID,
+        USDC_TOKEN_ID,
+        LPMEXUSDC_TOKEN_ID,
+        1_000_000,
+    );
+
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let direct_pair_address = direct_pair_wrapper.address_ref().clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let route: ManagedVec<DebugApi, _> = sc
+                .get_best_route_view(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_biguint!(100_000),
+                    3,
+                )
+                .to_vec();
+            assert_eq!(route.len(), 1);
+            assert_eq!(
+                route.get(0).pair_address,
+                managed_address!(&direct_pair_address)
+            );
+            assert_eq!(route.get(0).amount_out, managed_biguint!(90_661));
+        })
+        .assert_ok();
+
+    router_setup.smart_swap(MEX_TOKEN_ID, 100_000, USDC_TOKEN_ID, 90_000, 3, 90_661);
+}
+
+#[test]
+fn test_smart_swap_through_weighted_pool() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.migrate_pair_map();
+    router_setup.add_liquidity();
+
+    let pool_wrapper =
+        router_setup.create_weighted_pool_with_liquidity(weighted_pool::contract_obj, 1_000_000);
+
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let pool_address = pool_wrapper.address_ref().clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            // 100_000 WEGLD -> 90_669 USDC -> 292_603 MEX beats the direct pair
+            let route: ManagedVec<DebugApi, _> = sc
+                .get_best_route_view(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(100_000),
+                    2,
+                )
+                .to_vec();
+            assert_eq!(route.len(), 2);
+            assert_eq!(
+                route.get(0).pair_address,
+                managed_address!(&usdc_pair_address)
+            );
+            assert_eq!(route.get(1).pair_address, managed_address!(&pool_address));
+            assert_eq!(route.get(1).amount_out, managed_biguint!(292_603));
+        })
+        .assert_ok();
+
+    // the output through the weighted pool would be over a third of its MEX reserve,
+    // so the pool is skipped instead of failing the search
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let route: ManagedVec<DebugApi, _> = sc
+                .get_best_route_view(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(150_000),
+                    2,
+                )
+                .to_vec();
+            assert_eq!(route.len(), 1);
+            assert_eq!(
+                route.get(0).pair_address,
+                managed_address!(&mex_pair_address)
+            );
+            assert_eq!(route.get(0).amount_out, managed_biguint!(130_111));
+        })
+        .assert_ok();
+
+    // no pool holds the weighted pool's LP token
+    let user = router_setup.user_address.clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &router_setup.router_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let _ = sc.smart_swap(
+                    managed_token_id!(WEIGHTED_POOL_LP_TOKEN_ID),
+                    managed_biguint!(1),
+                    2,
+                );
+            },
+        )
+        .assert_user_error("No route found");
+
+    router_setup.smart_swap(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 290_000, 2, 292_603);
+
+    // an inactive pair is skipped, leaving only the direct route
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &router_setup.owner_address,
+            &router_setup.usdc_pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.state().set(State::Inactive);
+            },
+        )
+        .assert_ok();
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let route: ManagedVec<DebugApi, _> = sc
+                .get_best_route_view(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(100_000),
+                    2,
+                )
+                .to_vec();
+            assert_eq!(route.len(), 1);
+            assert_eq!(
+                route.get(0).pair_address,
+                managed_address!(&mex_pair_address)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_split_swap() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.migrate_pair_map();
+    router_setup.add_liquidity();
+
+    let direct_pair_wrapper = router_setup.create_pair_with_liquidity(
+        pair::contract_obj,
+        MEX_TOKEN_ID,
+        USDC_TOKEN_ID,
+        LPMEXUSDC_TOKEN_ID,
+        1_000_000,
+    );
+
+    let direct_pair_address = direct_pair_wrapper.address_ref().clone();
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let build_routes = |direct_percentage: u64, indirect_percentage: u64| {
+        let mut direct_steps = ManagedVec::new();
+        direct_steps.push(SplitRouteStep {
+            pair_address: managed_address!(&direct_pair_address),
+            token_out: managed_token_id!(USDC_TOKEN_ID),
+        });
+
+        let mut indirect_steps = ManagedVec::new();
+        indirect_steps.push(SplitRouteStep {
+            pair_address: managed_address!(&mex_pair_address),
+            token_out: managed_token_id!(WEGLD_TOKEN_ID),
+        });
+        indirect_steps.push(SplitRouteStep {
+            pair_address: managed_address!(&usdc_pair_address),
+            token_out: managed_token_id!(USDC_TOKEN_ID),
+        });
+
+        let mut routes = MultiValueEncoded::<DebugApi, _>::new();
+        routes.push(SplitRoute {
+            percentage: direct_percentage,
+            steps: direct_steps,
+        });
+        routes.push(SplitRoute {
+            percentage: indirect_percentage,
+            steps: indirect_steps,
+        });
+        routes
+    };
+
+    let user = router_setup.user_address.clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &router_setup.router_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(200_000),
+            |sc| {
+                let _ = sc.split_swap(
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_biguint!(1),
+                    build_routes(60_000, 30_000),
+                );
+            },
+        )
+        .assert_user_error("Route percentages must add up to 100%");
+
+    // 120_000 MEX -> 106_855 USDC directly
+    // 80_000 MEX -> 73_873 WEGLD -> 68_603 USDC
+    // the whole amount through the direct pair would only return 166_249 USDC
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &router_setup.router_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(200_000),
+            |sc| {
+                let payment = sc.split_swap(
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_biguint!(170_000),
+                    build_routes(60_000, 40_000),
+                );
+                assert_eq!(payment.amount, managed_biguint!(175_458));
+            },
+        )
+        .assert_ok();
+
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_000_000u64 - 1_000_000 + 175_458),
+    );
+}
+
 #[test]
 fn user_enable_pair_swaps_through_router_test() {
     let rust_zero = rust_biguint!(0u64);
@@ -398,6 +1203,200 @@ fn user_enable_pair_swaps_through_router_test() {
     );
 }
 
+#[test]
+fn user_enable_pair_swaps_with_liquidity_lock_test() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_zero);
+
+    let current_epoch = 5;
+    b_mock.set_block_epoch(current_epoch);
+
+    b_mock.set_esdt_balance(
+        &user,
+        CUSTOM_TOKEN_ID,
+        &rust_biguint!(USER_CUSTOM_TOKEN_BALANCE),
+    );
+    b_mock.set_esdt_balance(&user, USDC_TOKEN_ID, &rust_biguint!(USER_USDC_BALANCE));
+
+    let router_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        router::contract_obj,
+        ROUTER_WASM_PATH,
+    );
+    let pair_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(router_wrapper.address_ref()),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+    let liquidity_locker_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        liquidity_locker::contract_obj,
+        "liquidity-locker.wasm",
+    );
+
+    b_mock
+        .execute_tx(&owner, &liquidity_locker_wrapper, &rust_zero, |sc| {
+            sc.init();
+        })
+        .assert_ok();
+
+    // setup router
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.init(OptionalValue::None);
+
+            sc.pair_map().insert(
+                PairTokens {
+                    first_token_id: managed_token_id!(CUSTOM_TOKEN_ID),
+                    second_token_id: managed_token_id!(USDC_TOKEN_ID),
+                },
+                managed_address!(pair_wrapper.address_ref()),
+            );
+
+            sc.migrate_pair_map();
+
+            sc.add_common_tokens_for_user_pairs(MultiValueEncoded::from(ManagedVec::from(vec![
+                managed_token_id!(USDC_TOKEN_ID),
+            ])));
+
+            sc.config_enable_by_user_parameters(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(LOCKED_TOKEN_ID),
+                managed_biguint!(MIN_LOCKED_TOKEN_VALUE),
+                MIN_LOCKED_PERIOD_EPOCHS,
+            );
+
+            sc.set_liquidity_locker_address(managed_address!(
+                liquidity_locker_wrapper.address_ref()
+            ));
+        })
+        .assert_ok();
+
+    // setup pair
+    b_mock
+        .execute_tx(&owner, &pair_wrapper, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(router_wrapper.address_ref()),
+                managed_address!(&owner),
+                0,
+                0,
+                managed_address!(&user),
+                MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+            );
+
+            sc.lp_token_identifier()
+                .set(&managed_token_id!(LPUSDC_TOKEN_ID));
+        })
+        .assert_ok();
+
+    b_mock.set_esdt_local_roles(
+        pair_wrapper.address_ref(),
+        LPUSDC_TOKEN_ID,
+        &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+    );
+
+    // add liquidity
+    let payments = vec![
+        TxTokenTransfer {
+            token_identifier: CUSTOM_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(USER_CUSTOM_TOKEN_BALANCE),
+        },
+        TxTokenTransfer {
+            token_identifier: USDC_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(USER_USDC_BALANCE),
+        },
+    ];
+
+    let user_lp_tokens_balance = 999_000u64;
+    b_mock
+        .execute_esdt_multi_transfer(&user, &pair_wrapper, &payments, |sc| {
+            let _ = sc.add_initial_liquidity();
+        })
+        .assert_ok();
+
+    // pass blocks time to update safe price
+    b_mock.set_block_nonce(1_000_000);
+
+    // 2/3 of the LP tokens unlock during the minimum lock period
+    let mut lock_id = 0;
+    b_mock
+        .execute_esdt_transfer(
+            &user,
+            &liquidity_locker_wrapper,
+            LPUSDC_TOKEN_ID,
+            0,
+            &rust_biguint!(user_lp_tokens_balance),
+            |sc| {
+                lock_id = sc.lock_lp_tokens(
+                    managed_address!(pair_wrapper.address_ref()),
+                    0,
+                    MIN_LOCKED_PERIOD_EPOCHS * 3 / 2,
+                );
+            },
+        )
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.set_swap_enabled_by_user_with_liquidity_lock(
+                managed_address!(pair_wrapper.address_ref()),
+                lock_id,
+            );
+        })
+        .assert_user_error("Not enough value locked");
+
+    b_mock.set_block_epoch(current_epoch + MIN_LOCKED_PERIOD_EPOCHS * 3 / 2);
+    b_mock
+        .execute_tx(&user, &liquidity_locker_wrapper, &rust_zero, |sc| {
+            let _ = sc.claim_unlocked(lock_id);
+        })
+        .assert_ok();
+
+    // only 1/3 of the LP tokens unlock during the minimum lock period
+    b_mock
+        .execute_esdt_transfer(
+            &user,
+            &liquidity_locker_wrapper,
+            LPUSDC_TOKEN_ID,
+            0,
+            &rust_biguint!(user_lp_tokens_balance),
+            |sc| {
+                lock_id = sc.lock_lp_tokens(managed_address!(pair_wrapper.address_ref()), 0, 300);
+            },
+        )
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.set_swap_enabled_by_user_with_liquidity_lock(
+                managed_address!(pair_wrapper.address_ref()),
+                lock_id,
+            );
+        })
+        .assert_ok();
+
+    // check pair state is active, while the LP tokens stay locked
+    b_mock
+        .execute_query(&pair_wrapper, |sc| {
+            assert_eq!(sc.state().get(), State::Active);
+        })
+        .assert_ok();
+    b_mock.check_esdt_balance(
+        liquidity_locker_wrapper.address_ref(),
+        LPUSDC_TOKEN_ID,
+        &rust_biguint!(user_lp_tokens_balance),
+    );
+}
+
 #[test]
 fn user_enable_pair_swaps_fail_test() {
     let rust_zero = rust_biguint!(0u64);
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           32
+// Endpoints:                           55
 // Async Callback:                       1
-// Total number of exported functions:  34
+// Total number of exported functions:  57
 
 #![no_std]
 
@@ -26,6 +26,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         pause => pause
         resume => resume
         createPair => create_pair_endpoint
+        createStablePair => create_stable_pair_endpoint
         upgradePair => upgrade_pair_endpoint
         issueLpToken => issue_lp_token
         setLocalRoles => set_local_roles
@@ -48,12 +49,34 @@ multiversx_sc_wasm_adapter::endpoints! {
         getPairTemplateAddress => pair_template_address
         getTemporaryOwnerPeriod => temporary_owner_period
         multiPairSwap => multi_pair_swap
+        multiPairSwapWithLimits => multi_pair_swap_with_limits
         configEnableByUserParameters => config_enable_by_user_parameters
         addCommonTokensForUserPairs => add_common_tokens_for_user_pairs
         removeCommonTokensForUserPairs => remove_common_tokens_for_user_pairs
         setSwapEnabledByUser => set_swap_enabled_by_user
+        setLiquidityLockerAddress => set_liquidity_locker_address
+        setSwapEnabledByUserWithLiquidityLock => set_swap_enabled_by_user_with_liquidity_lock
         getEnableSwapByUserConfig => try_get_config
+        getLiquidityLockerAddress => liquidity_locker_address
         getCommonTokensForUserPairs => common_tokens_for_user_pairs
+        addWeightedPool => add_weighted_pool
+        removeWeightedPool => remove_weighted_pool
+        getAllWeightedPools => weighted_pools
+        getWeightedPoolsForToken => weighted_pools_for_token
+        getBestRoute => get_best_route_view
+        smartSwap => smart_swap
+        splitSwap => split_swap
+        setListingFeesCollectorAddress => set_listing_fees_collector_address
+        setListingBondConfig => set_listing_bond_config
+        removeListingBondConfig => remove_listing_bond_config
+        setListingMinLiquidity => set_listing_min_liquidity
+        refundListingBond => refund_listing_bond
+        forfeitListingBond => forfeit_listing_bond
+        getPendingListing => get_pending_listing
+        getPendingListings => get_pending_listings
+        getListingBondConfig => listing_bond_config
+        getListingFeesCollectorAddress => listing_fees_collector_address
+        getListingMinLiquidity => listing_min_liquidity
     )
 }
 
@@ -0,0 +1,40 @@
+multiversx_sc::imports!();
+
+use crate::errors::ERROR_BAD_ROUND_OFFSET;
+
+pub const MAX_PERCENTAGE: u64 = 100_000;
+
+#[multiversx_sc::module]
+pub trait ConfigModule {
+    #[only_owner]
+    #[endpoint(setSafePriceViewAddress)]
+    fn set_safe_price_view_address(&self, safe_price_view_address: ManagedAddress) {
+        self.safe_price_view_address().set(&safe_price_view_address);
+    }
+
+    #[only_owner]
+    #[endpoint(setSafePriceRoundOffset)]
+    fn set_safe_price_round_offset(&self, safe_price_round_offset: u64) {
+        self.try_set_safe_price_round_offset(safe_price_round_offset);
+    }
+
+    fn try_set_safe_price_round_offset(&self, safe_price_round_offset: u64) {
+        require!(safe_price_round_offset > 0, ERROR_BAD_ROUND_OFFSET);
+
+        self.safe_price_round_offset().set(safe_price_round_offset);
+    }
+
+    #[view(getRouterAddress)]
+    #[storage_mapper("routerAddress")]
+    fn router_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    /// Contract queried through `getSafePriceByRoundOffset`, usually the safe price view.
+    #[view(getSafePriceViewAddress)]
+    #[storage_mapper("safePriceViewAddress")]
+    fn safe_price_view_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    /// Length, in rounds, of the window the safe price is averaged over.
+    #[view(getSafePriceRoundOffset)]
+    #[storage_mapper("safePriceRoundOffset")]
+    fn safe_price_round_offset(&self) -> SingleValueMapper<u64>;
+}
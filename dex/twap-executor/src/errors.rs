@@ -0,0 +1,10 @@
+pub static ERROR_BAD_PAYMENT: &[u8] = b"Bad payment";
+pub static ERROR_INVALID_ARGS: &[u8] = b"Invalid args";
+pub static ERROR_NO_PAIR: &[u8] = b"No pair for the given tokens";
+pub static ERROR_BAD_ROUND_OFFSET: &[u8] = b"Bad safe price round offset";
+
+pub static ERROR_ORDER_NOT_FOUND: &[u8] = b"Order not found";
+pub static ERROR_NOT_ORDER_OWNER: &[u8] = b"Only the owner can cancel an order";
+pub static ERROR_SLICE_NOT_DUE: &[u8] = b"Slice not due yet";
+pub static ERROR_SLICE_TOO_SMALL: &[u8] = b"Slice too small";
+pub static ERROR_PRICE_DEVIATION_TOO_HIGH: &[u8] = b"Price deviates too much from the safe price";
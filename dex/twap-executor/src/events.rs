@@ -0,0 +1,91 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::order::TwapOrder;
+
+#[derive(TypeAbi, TopEncode)]
+pub struct ExecuteSliceEvent<M: ManagedTypeApi> {
+    owner: ManagedAddress<M>,
+    token_in: TokenIdentifier<M>,
+    amount_in: BigUint<M>,
+    token_out: TokenIdentifier<M>,
+    amount_out: BigUint<M>,
+    executed_slices: u64,
+    remaining_amount_in: BigUint<M>,
+    block: u64,
+    round: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_create_order_event(&self, order_id: u64, order: &TwapOrder<Self::Api>) {
+        self.create_twap_order_event(
+            &order.owner,
+            order_id,
+            self.blockchain().get_block_round(),
+            order,
+        );
+    }
+
+    fn emit_execute_slice_event(
+        &self,
+        order_id: u64,
+        order: &TwapOrder<Self::Api>,
+        amount_in: BigUint,
+        amount_out: &BigUint,
+    ) {
+        let round = self.blockchain().get_block_round();
+        self.execute_slice_event(
+            &self.blockchain().get_caller(),
+            order_id,
+            round,
+            &ExecuteSliceEvent {
+                owner: order.owner.clone(),
+                token_in: order.token_in.clone(),
+                amount_in,
+                token_out: order.token_out.clone(),
+                amount_out: amount_out.clone(),
+                executed_slices: order.executed_slices,
+                remaining_amount_in: order.remaining_amount_in.clone(),
+                block: self.blockchain().get_block_nonce(),
+                round,
+            },
+        );
+    }
+
+    fn emit_cancel_order_event(&self, order_id: u64, order: &TwapOrder<Self::Api>) {
+        self.cancel_twap_order_event(
+            &order.owner,
+            order_id,
+            self.blockchain().get_block_round(),
+            order,
+        );
+    }
+
+    #[event("create_twap_order")]
+    fn create_twap_order_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] order_id: u64,
+        #[indexed] round: u64,
+        order: &TwapOrder<Self::Api>,
+    );
+
+    #[event("execute_slice")]
+    fn execute_slice_event(
+        &self,
+        #[indexed] keeper: &ManagedAddress,
+        #[indexed] order_id: u64,
+        #[indexed] round: u64,
+        execute_slice_event: &ExecuteSliceEvent<Self::Api>,
+    );
+
+    #[event("cancel_twap_order")]
+    fn cancel_twap_order_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] order_id: u64,
+        #[indexed] round: u64,
+        order: &TwapOrder<Self::Api>,
+    );
+}
@@ -0,0 +1,87 @@
+multiversx_sc::imports!();
+
+use crate::{
+    config::{self, MAX_PERCENTAGE},
+    errors::*,
+    events, order,
+};
+
+use pair::safe_price_view::ProxyTrait as _;
+use pair::ProxyTrait as _;
+
+#[multiversx_sc::module]
+pub trait ExecuteModule:
+    config::ConfigModule
+    + order::OrderModule
+    + events::EventsModule
+    + router::router_swap::RouterSwapModule
+{
+    /// Swaps the next slice of the order through the router, once its round is reached.
+    /// Anyone can call it. The slice fails if the pair gives less than the safe price
+    /// over the configured round offset, minus the order's maximum deviation,
+    /// so a keeper cannot execute it right after moving the price.
+    #[endpoint(executeSlice)]
+    fn execute_slice(&self, order_id: u64) -> EsdtTokenPayment {
+        let mut order = self.get_order(order_id);
+        let current_round = self.blockchain().get_block_round();
+        require!(
+            current_round >= order.get_next_slice_round(),
+            ERROR_SLICE_NOT_DUE
+        );
+
+        let amount_in = order.get_next_slice_amount();
+        let safe_price_output: EsdtTokenPayment = self
+            .pair_proxy(self.safe_price_view_address().get())
+            .get_safe_price_by_round_offset(
+                order.pair_address.clone(),
+                self.safe_price_round_offset().get(),
+                EsdtTokenPayment::new(order.token_in.clone(), 0, amount_in.clone()),
+            )
+            .execute_on_dest_context();
+        let min_amount_out = safe_price_output.amount
+            * (MAX_PERCENTAGE - order.max_deviation_percent)
+            / MAX_PERCENTAGE;
+        require!(min_amount_out > 0u64, ERROR_SLICE_TOO_SMALL);
+
+        let quoted_amount_out: BigUint = self
+            .pair_proxy(order.pair_address.clone())
+            .get_amount_out_view(order.token_in.clone(), amount_in.clone())
+            .execute_on_dest_context();
+        require!(
+            quoted_amount_out >= min_amount_out,
+            ERROR_PRICE_DEVIATION_TOO_HIGH
+        );
+
+        let amount_out = self.swap_through_router(
+            self.router_address().get(),
+            order.pair_address.clone(),
+            EsdtTokenPayment::new(order.token_in.clone(), 0, amount_in.clone()),
+            &order.token_out,
+            min_amount_out,
+        );
+
+        order.executed_slices += 1;
+        order.last_execution_round = current_round;
+        order.remaining_amount_in -= &amount_in;
+        if order.executed_slices == order.total_slices {
+            self.remove_order(order_id, &order.owner);
+        } else {
+            self.orders(order_id).set(&order);
+        }
+
+        let output_payment = EsdtTokenPayment::new(order.token_out.clone(), 0, amount_out);
+        self.send().direct_esdt(
+            &order.owner,
+            &output_payment.token_identifier,
+            0,
+            &output_payment.amount,
+        );
+
+        self.emit_execute_slice_event(order_id, &order, amount_in, &output_payment.amount);
+
+        output_payment
+    }
+
+    #[proxy]
+    fn pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+}
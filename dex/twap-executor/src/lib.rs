@@ -0,0 +1,117 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod config;
+pub mod errors;
+mod events;
+pub mod execute;
+pub mod order;
+
+use config::MAX_PERCENTAGE;
+use errors::*;
+use order::TwapOrder;
+use router::factory::ProxyTrait as _;
+
+/// Time-weighted swaps, executed through the router in slices.
+///
+/// Users deposit a token, along with the number of slices and the number of rounds
+/// to spread them over. Keepers execute each slice once its round is reached,
+/// as long as the pair price is within the order's maximum deviation from the safe price.
+/// The owner can cancel the order at any time, getting back the tokens not swapped yet.
+#[multiversx_sc::contract]
+pub trait TwapExecutor:
+    config::ConfigModule
+    + order::OrderModule
+    + execute::ExecuteModule
+    + events::EventsModule
+    + router::router_swap::RouterSwapModule
+{
+    #[init]
+    fn init(
+        &self,
+        router_address: ManagedAddress,
+        safe_price_view_address: ManagedAddress,
+        safe_price_round_offset: u64,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&router_address),
+            ERROR_INVALID_ARGS
+        );
+
+        self.router_address().set(&router_address);
+        self.safe_price_view_address().set(&safe_price_view_address);
+        self.try_set_safe_price_round_offset(safe_price_round_offset);
+    }
+
+    #[endpoint]
+    fn upgrade(&self) {}
+
+    #[payable("*")]
+    #[endpoint(createOrder)]
+    fn create_order(
+        &self,
+        token_out: TokenIdentifier,
+        total_slices: u64,
+        total_rounds: u64,
+        max_deviation_percent: u64,
+    ) -> u64 {
+        let (token_in, nonce, amount_in) = self.call_value().single_esdt().into_tuple();
+        require!(
+            nonce == 0 && amount_in >= total_slices && total_slices > 0,
+            ERROR_BAD_PAYMENT
+        );
+        require!(
+            token_in != token_out
+                && total_rounds >= total_slices
+                && max_deviation_percent < MAX_PERCENTAGE,
+            ERROR_INVALID_ARGS
+        );
+
+        let pair_address: ManagedAddress = self
+            .router_proxy(self.router_address().get())
+            .get_pair(token_in.clone(), token_out.clone())
+            .execute_on_dest_context();
+        require!(!pair_address.is_zero(), ERROR_NO_PAIR);
+
+        let order = TwapOrder {
+            owner: self.blockchain().get_caller(),
+            pair_address,
+            token_in,
+            total_amount_in: amount_in.clone(),
+            remaining_amount_in: amount_in,
+            token_out,
+            total_slices,
+            executed_slices: 0,
+            start_round: self.blockchain().get_block_round(),
+            total_rounds,
+            max_deviation_percent,
+            last_execution_round: 0,
+        };
+        let order_id = self.insert_order(&order);
+
+        self.emit_create_order_event(order_id, &order);
+
+        order_id
+    }
+
+    #[endpoint(cancelOrder)]
+    fn cancel_order(&self, order_id: u64) -> EsdtTokenPayment {
+        let order = self.get_order(order_id);
+        require!(
+            self.blockchain().get_caller() == order.owner,
+            ERROR_NOT_ORDER_OWNER
+        );
+
+        self.remove_order(order_id, &order.owner);
+
+        let refund =
+            EsdtTokenPayment::new(order.token_in.clone(), 0, order.remaining_amount_in.clone());
+        self.send()
+            .direct_esdt(&order.owner, &refund.token_identifier, 0, &refund.amount);
+
+        self.emit_cancel_order_event(order_id, &order);
+
+        refund
+    }
+}
@@ -0,0 +1,80 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::ERROR_ORDER_NOT_FOUND;
+
+/// `total_amount_in` is swapped in `total_slices` equal slices, spread evenly over
+/// `total_rounds` rounds, starting with the round the order was created in.
+/// Each slice is due `total_rounds / total_slices` rounds after the previous execution,
+/// so late slices cannot be executed back to back.
+/// The last slice also takes the rounding remainder.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct TwapOrder<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub pair_address: ManagedAddress<M>,
+    pub token_in: TokenIdentifier<M>,
+    pub total_amount_in: BigUint<M>,
+    pub remaining_amount_in: BigUint<M>,
+    pub token_out: TokenIdentifier<M>,
+    pub total_slices: u64,
+    pub executed_slices: u64,
+    pub start_round: u64,
+    pub total_rounds: u64,
+    pub max_deviation_percent: u64,
+    pub last_execution_round: u64,
+}
+
+impl<M: ManagedTypeApi> TwapOrder<M> {
+    pub fn get_next_slice_round(&self) -> u64 {
+        if self.executed_slices == 0 {
+            return self.start_round;
+        }
+
+        self.last_execution_round + self.total_rounds / self.total_slices
+    }
+
+    pub fn get_next_slice_amount(&self) -> BigUint<M> {
+        if self.executed_slices + 1 == self.total_slices {
+            return self.remaining_amount_in.clone();
+        }
+
+        &self.total_amount_in / self.total_slices
+    }
+}
+
+#[multiversx_sc::module]
+pub trait OrderModule {
+    #[view(getOrder)]
+    fn get_order(&self, order_id: u64) -> TwapOrder<Self::Api> {
+        let order_mapper = self.orders(order_id);
+        require!(!order_mapper.is_empty(), ERROR_ORDER_NOT_FOUND);
+
+        order_mapper.get()
+    }
+
+    fn insert_order(&self, order: &TwapOrder<Self::Api>) -> u64 {
+        let order_id = self.last_order_id().update(|last_order_id| {
+            *last_order_id += 1;
+            *last_order_id
+        });
+        self.orders(order_id).set(order);
+        self.user_orders(&order.owner).insert(order_id);
+
+        order_id
+    }
+
+    fn remove_order(&self, order_id: u64, owner: &ManagedAddress) {
+        self.orders(order_id).clear();
+        self.user_orders(owner).swap_remove(&order_id);
+    }
+
+    #[storage_mapper("lastOrderId")]
+    fn last_order_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("orders")]
+    fn orders(&self, order_id: u64) -> SingleValueMapper<TwapOrder<Self::Api>>;
+
+    #[view(getUserOrders)]
+    #[storage_mapper("userOrders")]
+    fn user_orders(&self, user: &ManagedAddress) -> UnorderedSetMapper<u64>;
+}
@@ -0,0 +1,192 @@
+#![allow(deprecated)]
+
+mod twap_executor_setup;
+use multiversx_sc_scenario::{managed_address, managed_biguint, managed_token_id, rust_biguint};
+use twap_executor::{order::OrderModule, TwapExecutor};
+use twap_executor_setup::*;
+
+#[test]
+fn test_twap_executor_setup() {
+    let _ = TwapExecutorSetup::new(
+        twap_executor::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+    );
+}
+
+#[test]
+fn test_twap_order_slices() {
+    let mut setup = TwapExecutorSetup::new(
+        twap_executor::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+    );
+
+    // records the first price observation, so the safe price is available afterwards
+    setup.b_mock.set_block_round(2);
+    setup.swap_fixed_input(MEX_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 996);
+
+    // 3 slices of 10_000 WEGLD, at rounds 10, 20 and 30
+    setup.b_mock.set_block_round(10);
+    let order_id = setup.create_order(WEGLD_TOKEN_ID, 30_000, MEX_TOKEN_ID, 3, 30, 3_000);
+    assert_eq!(order_id, 1);
+
+    setup.execute_slice(order_id).assert_ok();
+
+    setup.b_mock.set_block_round(19);
+    setup
+        .execute_slice(order_id)
+        .assert_user_error("Slice not due yet");
+
+    // the price moved in the same round is far from the safe price, which is still 9_822 MEX
+    setup.b_mock.set_block_round(20);
+    setup.swap_fixed_input(WEGLD_TOKEN_ID, 200_000, MEX_TOKEN_ID, 163_573);
+    setup
+        .execute_slice(order_id)
+        .assert_user_error("Price deviates too much from the safe price");
+
+    setup.swap_fixed_input(MEX_TOKEN_ID, 163_573, WEGLD_TOKEN_ID, 198_998);
+    setup.execute_slice(order_id).assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.twap_executor_wrapper, |sc| {
+            let order = sc.get_order(order_id);
+            assert_eq!(order.executed_slices, 2);
+            assert_eq!(order.remaining_amount_in, managed_biguint!(10_000));
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_round(30);
+    setup.execute_slice(order_id).assert_ok();
+
+    let user = setup.user_address.clone();
+    setup.b_mock.check_esdt_balance(
+        &user,
+        MEX_TOKEN_ID,
+        &rust_biguint!(
+            USER_TOTAL_MEX_TOKENS - ADD_LIQUIDITY_TOKENS - 1_000 + 9_891 + 9_688 + 9_500
+        ),
+    );
+    setup.b_mock.check_esdt_balance(
+        &user,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(
+            USER_TOTAL_WEGLD_TOKENS - ADD_LIQUIDITY_TOKENS + 996 - 30_000 - 200_000 + 198_998
+        ),
+    );
+    setup
+        .b_mock
+        .execute_query(&setup.twap_executor_wrapper, |sc| {
+            assert!(sc.user_orders(&managed_address!(&user)).is_empty());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_twap_order_late_slices() {
+    let mut setup = TwapExecutorSetup::new(
+        twap_executor::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+    );
+
+    setup.b_mock.set_block_round(2);
+    setup.swap_fixed_input(MEX_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 996);
+
+    // fewer rounds than slices
+    let user = setup.user_address.clone();
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user,
+            &setup.twap_executor_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(30_000),
+            |sc| {
+                let _ = sc.create_order(managed_token_id!(MEX_TOKEN_ID), 3, 2, 3_000);
+            },
+        )
+        .assert_user_error("Invalid args");
+
+    setup.b_mock.set_block_round(10);
+    let order_id = setup.create_order(WEGLD_TOKEN_ID, 30_000, MEX_TOKEN_ID, 3, 30, 3_000);
+    setup.execute_slice(order_id).assert_ok();
+
+    // nobody executed the second slice at round 20, so the third one waits 10 rounds after it
+    setup.b_mock.set_block_round(40);
+    setup.execute_slice(order_id).assert_ok();
+    setup
+        .execute_slice(order_id)
+        .assert_user_error("Slice not due yet");
+
+    setup.b_mock.set_block_round(49);
+    setup
+        .execute_slice(order_id)
+        .assert_user_error("Slice not due yet");
+
+    setup.b_mock.set_block_round(50);
+    setup.execute_slice(order_id).assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.twap_executor_wrapper, |sc| {
+            assert!(sc.user_orders(&managed_address!(&user)).is_empty());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_twap_order_cancel() {
+    let mut setup = TwapExecutorSetup::new(
+        twap_executor::contract_obj,
+        router::contract_obj,
+        pair::contract_obj,
+    );
+
+    setup.b_mock.set_block_round(2);
+    setup.swap_fixed_input(MEX_TOKEN_ID, 1_000, WEGLD_TOKEN_ID, 996);
+
+    setup.b_mock.set_block_round(10);
+    let order_id = setup.create_order(WEGLD_TOKEN_ID, 30_000, MEX_TOKEN_ID, 3, 30, 3_000);
+    setup.execute_slice(order_id).assert_ok();
+
+    let keeper = setup.keeper_address.clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper,
+            &setup.twap_executor_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.cancel_order(order_id);
+            },
+        )
+        .assert_user_error("Only the owner can cancel an order");
+
+    let user = setup.user_address.clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &user,
+            &setup.twap_executor_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let refund = sc.cancel_order(order_id);
+                assert_eq!(refund.amount, managed_biguint!(20_000));
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.check_esdt_balance(
+        &user,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - ADD_LIQUIDITY_TOKENS + 996 - 10_000),
+    );
+    setup.b_mock.check_esdt_balance(
+        &user,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_MEX_TOKENS - ADD_LIQUIDITY_TOKENS - 1_000 + 9_891),
+    );
+}
@@ -0,0 +1,39 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Endpoints:                           11
+// Async Callback (empty):               1
+// Total number of exported functions:  13
+
+#![no_std]
+
+// Configuration that works with rustc < 1.73.0.
+// TODO: Recommended rustc version: 1.73.0 or newer.
+#![feature(lang_items)]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    twap_executor
+    (
+        init => init
+        upgrade => upgrade
+        createOrder => create_order
+        cancelOrder => cancel_order
+        setSafePriceViewAddress => set_safe_price_view_address
+        setSafePriceRoundOffset => set_safe_price_round_offset
+        getRouterAddress => router_address
+        getSafePriceViewAddress => safe_price_view_address
+        getSafePriceRoundOffset => safe_price_round_offset
+        getOrder => get_order
+        getUserOrders => user_orders
+        executeSlice => execute_slice
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
@@ -0,0 +1,115 @@
+multiversx_sc::imports!();
+
+use crate::errors::*;
+use pausable::State;
+
+pub const MAX_PERCENTAGE: u64 = 100_000;
+pub const MAX_FEE_PERCENTAGE: u64 = 5_000;
+pub const MIN_POOL_TOKENS: usize = 2;
+pub const MAX_POOL_TOKENS: usize = 8;
+pub const MIN_TOKEN_WEIGHT: u64 = 1;
+pub const MAX_TOTAL_WEIGHT: u64 = 50;
+pub const LP_TOKEN_DECIMALS: usize = 18;
+
+#[multiversx_sc::module]
+pub trait ConfigModule:
+    permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[payable("EGLD")]
+    #[endpoint(issueLpToken)]
+    fn issue_lp_token(&self, token_display_name: ManagedBuffer, token_ticker: ManagedBuffer) {
+        self.require_caller_has_owner_permissions();
+
+        let payment_amount = self.call_value().egld_value().clone_value();
+        self.lp_token().issue_and_set_all_roles(
+            payment_amount,
+            token_display_name,
+            token_ticker,
+            LP_TOKEN_DECIMALS,
+            None,
+        );
+    }
+
+    #[endpoint(setFeePercent)]
+    fn set_fee_percent_endpoint(&self, total_fee_percent: u64) {
+        self.require_caller_has_owner_or_admin_permissions();
+        self.set_fee_percent(total_fee_percent);
+    }
+
+    fn set_fee_percent(&self, total_fee_percent: u64) {
+        require!(total_fee_percent <= MAX_FEE_PERCENTAGE, ERROR_BAD_PERCENTS);
+        self.total_fee_percent().set(total_fee_percent);
+    }
+
+    /// Weights are relative to each other, so 4/1 is an 80/20 pool and 1/1/1 an equal-weight pool.
+    fn set_pool_tokens(
+        &self,
+        tokens_and_weights: MultiValueEncoded<MultiValue2<TokenIdentifier, u64>>,
+    ) {
+        let nr_tokens = tokens_and_weights.len();
+        require!(
+            (MIN_POOL_TOKENS..=MAX_POOL_TOKENS).contains(&nr_tokens),
+            ERROR_BAD_TOKENS_COUNT
+        );
+
+        let mut pool_tokens = self.pool_tokens();
+        let mut total_weight = 0;
+        for token_and_weight in tokens_and_weights {
+            let (token_id, weight) = token_and_weight.into_tuple();
+            require!(token_id.is_valid_esdt_identifier(), ERROR_NOT_AN_ESDT);
+            require!(weight >= MIN_TOKEN_WEIGHT, ERROR_BAD_WEIGHT);
+            require!(pool_tokens.insert(token_id.clone()), ERROR_DUPLICATE_TOKEN);
+
+            self.token_weight(&token_id).set(weight);
+            total_weight += weight;
+        }
+
+        require!(total_weight <= MAX_TOTAL_WEIGHT, ERROR_BAD_TOTAL_WEIGHT);
+    }
+
+    #[view(getTokensAndWeights)]
+    fn get_tokens_and_weights(&self) -> MultiValueEncoded<MultiValue2<TokenIdentifier, u64>> {
+        let mut result = MultiValueEncoded::new();
+        for token_id in self.pool_tokens().iter() {
+            let weight = self.token_weight(&token_id).get();
+            result.push((token_id, weight).into());
+        }
+
+        result
+    }
+
+    fn require_pool_token(&self, token_id: &TokenIdentifier) {
+        require!(self.pool_tokens().contains(token_id), ERROR_UNKNOWN_TOKEN);
+    }
+
+    #[inline]
+    fn is_active(&self) -> bool {
+        self.state().get() == State::Active
+    }
+
+    #[view(getLpTokenIdentifier)]
+    #[storage_mapper("lpTokenIdentifier")]
+    fn lp_token(&self) -> FungibleTokenMapper;
+
+    #[view(getTotalSupply)]
+    #[storage_mapper("lp_token_supply")]
+    fn lp_token_supply(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalFeePercent)]
+    #[storage_mapper("total_fee_percent")]
+    fn total_fee_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getTokens)]
+    #[storage_mapper("pool_tokens")]
+    fn pool_tokens(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[view(getTokenWeight)]
+    #[storage_mapper("token_weight")]
+    fn token_weight(&self, token_id: &TokenIdentifier) -> SingleValueMapper<u64>;
+
+    #[view(getReserve)]
+    #[storage_mapper("reserve")]
+    fn reserve(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
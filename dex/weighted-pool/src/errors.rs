@@ -0,0 +1,22 @@
+pub static ERROR_NOT_ACTIVE: &[u8] = b"Not active";
+pub static ERROR_LP_TOKEN_NOT_ISSUED: &[u8] = b"LP token not issued";
+
+pub static ERROR_BAD_PAYMENT_TOKENS: &[u8] = b"Bad payment tokens";
+pub static ERROR_INVALID_ARGS: &[u8] = b"Invalid args";
+
+pub static ERROR_NOT_AN_ESDT: &[u8] = b"Not a valid esdt id";
+pub static ERROR_SAME_TOKENS: &[u8] = b"Exchange tokens cannot be the same";
+pub static ERROR_UNKNOWN_TOKEN: &[u8] = b"Unknown token";
+pub static ERROR_BAD_PERCENTS: &[u8] = b"Bad percents";
+
+pub static ERROR_BAD_TOKENS_COUNT: &[u8] = b"Bad number of pool tokens";
+pub static ERROR_DUPLICATE_TOKEN: &[u8] = b"Duplicate pool token";
+pub static ERROR_BAD_WEIGHT: &[u8] = b"Bad token weight";
+pub static ERROR_BAD_TOTAL_WEIGHT: &[u8] = b"Bad total weight";
+
+pub static ERROR_INSUFFICIENT_LIQUIDITY: &[u8] = b"Insufficient liquidity minted";
+pub static ERROR_SLIPPAGE_EXCEEDED: &[u8] = b"Slippage exceeded";
+pub static ERROR_ZERO_AMOUNT: &[u8] = b"Zero amount";
+pub static ERROR_MAX_IN_RATIO: &[u8] = b"Input amount too large for the pool reserves";
+pub static ERROR_MAX_OUT_RATIO: &[u8] = b"Output amount too large for the pool reserves";
+pub static ERROR_POW_BASE_OUT_OF_BOUNDS: &[u8] = b"Power base out of bounds";
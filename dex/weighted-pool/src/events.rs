@@ -0,0 +1,139 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[derive(TypeAbi, TopEncode)]
+pub struct SwapEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    token_id_in: TokenIdentifier<M>,
+    token_amount_in: BigUint<M>,
+    token_id_out: TokenIdentifier<M>,
+    token_amount_out: BigUint<M>,
+    fee_amount: BigUint<M>,
+    token_in_reserve: BigUint<M>,
+    token_out_reserve: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct LiquidityEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    token_amounts: ManagedVec<M, EsdtTokenPayment<M>>,
+    lp_token_id: TokenIdentifier<M>,
+    lp_token_amount: BigUint<M>,
+    lp_supply: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule:
+    crate::config::ConfigModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    fn emit_swap_event(
+        &self,
+        input: &EsdtTokenPayment,
+        output: &EsdtTokenPayment,
+        fee_amount: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.swap_event(
+            &input.token_identifier,
+            &output.token_identifier,
+            &caller,
+            epoch,
+            &SwapEvent {
+                caller: caller.clone(),
+                token_id_in: input.token_identifier.clone(),
+                token_amount_in: input.amount.clone(),
+                token_id_out: output.token_identifier.clone(),
+                token_amount_out: output.amount.clone(),
+                fee_amount,
+                token_in_reserve: self.reserve(&input.token_identifier).get(),
+                token_out_reserve: self.reserve(&output.token_identifier).get(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_add_liquidity_event(
+        &self,
+        token_amounts: ManagedVec<EsdtTokenPayment>,
+        lp_token_amount: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.add_liquidity_event(
+            &caller,
+            epoch,
+            &self.build_liquidity_event(caller.clone(), epoch, token_amounts, lp_token_amount),
+        )
+    }
+
+    fn emit_remove_liquidity_event(
+        &self,
+        token_amounts: ManagedVec<EsdtTokenPayment>,
+        lp_token_amount: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.remove_liquidity_event(
+            &caller,
+            epoch,
+            &self.build_liquidity_event(caller.clone(), epoch, token_amounts, lp_token_amount),
+        )
+    }
+
+    fn build_liquidity_event(
+        &self,
+        caller: ManagedAddress,
+        epoch: u64,
+        token_amounts: ManagedVec<EsdtTokenPayment>,
+        lp_token_amount: BigUint,
+    ) -> LiquidityEvent<Self::Api> {
+        LiquidityEvent {
+            caller,
+            token_amounts,
+            lp_token_id: self.lp_token().get_token_id(),
+            lp_token_amount,
+            lp_supply: self.lp_token_supply().get(),
+            block: self.blockchain().get_block_nonce(),
+            epoch,
+            timestamp: self.blockchain().get_block_timestamp(),
+        }
+    }
+
+    #[event("swap")]
+    fn swap_event(
+        &self,
+        #[indexed] token_in: &TokenIdentifier,
+        #[indexed] token_out: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        swap_event: &SwapEvent<Self::Api>,
+    );
+
+    #[event("add_liquidity")]
+    fn add_liquidity_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        add_liquidity_event: &LiquidityEvent<Self::Api>,
+    );
+
+    #[event("remove_liquidity")]
+    fn remove_liquidity_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        remove_liquidity_event: &LiquidityEvent<Self::Api>,
+    );
+}
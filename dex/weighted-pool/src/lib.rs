@@ -0,0 +1,413 @@
+#![no_std]
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub mod config;
+pub mod errors;
+mod events;
+pub mod weighted_math;
+
+use crate::config::MAX_PERCENTAGE;
+use crate::errors::*;
+
+use pausable::State;
+use permissions_module::Permissions;
+
+pub const MINIMUM_LIQUIDITY: u64 = 1_000;
+pub const INITIAL_LP_TOKEN_SUPPLY: u64 = 1_000_000_000_000_000_000;
+
+pub type SwapTokensFixedInputResultType<BigUint> = EsdtTokenPayment<BigUint>;
+
+pub type SwapTokensFixedOutputResultType<BigUint> =
+    MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
+
+/// Pool with between 2 and 8 tokens, each with its own weight.
+/// Swaps keep `prod(reserve_i ^ weight_i)` constant, fees excluded.
+///
+/// The swap endpoints have the same signature as the ones of the pair contract,
+/// so the router can route through weighted pools using `multiPairSwap`.
+#[multiversx_sc::contract]
+pub trait WeightedPool:
+    config::ConfigModule
+    + events::EventsModule
+    + weighted_math::WeightedMathModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[init]
+    fn init(
+        &self,
+        router_address: ManagedAddress,
+        router_owner_address: ManagedAddress,
+        total_fee_percent: u64,
+        tokens_and_weights: MultiValueEncoded<MultiValue2<TokenIdentifier, u64>>,
+    ) {
+        self.set_fee_percent(total_fee_percent);
+        self.set_pool_tokens(tokens_and_weights);
+        self.state().set(State::Inactive);
+
+        let all_permissions = Permissions::OWNER | Permissions::ADMIN | Permissions::PAUSE;
+        self.add_permissions(router_address, all_permissions.clone());
+        self.add_permissions(router_owner_address, all_permissions);
+    }
+
+    #[endpoint]
+    fn upgrade(&self) {}
+
+    /// Payments must contain every pool token, in the order returned by `getTokens`.
+    /// Returns the LP tokens followed by the unused amount of each pool token.
+    #[payable("*")]
+    #[endpoint(addLiquidity)]
+    fn add_liquidity(
+        &self,
+        lp_token_amount_min: BigUint,
+    ) -> MultiValueEncoded<EsdtTokenPayment<Self::Api>> {
+        require!(self.is_active(), ERROR_NOT_ACTIVE);
+        require!(!self.lp_token().is_empty(), ERROR_LP_TOKEN_NOT_ISSUED);
+
+        let payments = self.call_value().all_esdt_transfers().clone_value();
+        let pool_tokens = self.pool_tokens();
+        require!(
+            payments.len() == pool_tokens.len(),
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+        for (payment, token_id) in payments.iter().zip(pool_tokens.iter()) {
+            require!(
+                payment.token_identifier == token_id && payment.token_nonce == 0,
+                ERROR_BAD_PAYMENT_TOKENS
+            );
+            require!(payment.amount > 0, ERROR_ZERO_AMOUNT);
+        }
+
+        let lp_token_supply = self.lp_token_supply().get();
+        let (liquidity, user_liquidity) = if lp_token_supply == 0 {
+            let liquidity = BigUint::from(INITIAL_LP_TOKEN_SUPPLY);
+            let user_liquidity = &liquidity - MINIMUM_LIQUIDITY;
+            (liquidity, user_liquidity)
+        } else {
+            let liquidity = self.compute_liquidity(&payments, &lp_token_supply);
+            (liquidity.clone(), liquidity)
+        };
+        require!(
+            user_liquidity >= lp_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+
+        let mut deposited_amounts = ManagedVec::new();
+        let mut output_payments = ManagedVec::new();
+        output_payments.push(self.lp_token().mint(user_liquidity.clone()));
+        for payment in payments.iter() {
+            let reserve_mapper = self.reserve(&payment.token_identifier);
+            let deposited_amount = if lp_token_supply == 0 {
+                payment.amount.clone()
+            } else {
+                (&liquidity * &reserve_mapper.get() + &lp_token_supply - 1u64) / &lp_token_supply
+            };
+
+            reserve_mapper.update(|reserve| *reserve += &deposited_amount);
+            output_payments.push(EsdtTokenPayment::new(
+                payment.token_identifier.clone(),
+                0,
+                &payment.amount - &deposited_amount,
+            ));
+            deposited_amounts.push(EsdtTokenPayment::new(
+                payment.token_identifier,
+                0,
+                deposited_amount,
+            ));
+        }
+
+        if lp_token_supply == 0 {
+            let _ = self.lp_token().mint(BigUint::from(MINIMUM_LIQUIDITY));
+        }
+        self.lp_token_supply()
+            .update(|supply| *supply += &liquidity);
+
+        let caller = self.blockchain().get_caller();
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        self.emit_add_liquidity_event(deposited_amounts, user_liquidity);
+
+        output_payments.into()
+    }
+
+    /// `token_amounts_min` contains one minimum amount for each pool token,
+    /// in the order returned by `getTokens`.
+    #[payable("*")]
+    #[endpoint(removeLiquidity)]
+    fn remove_liquidity(
+        &self,
+        token_amounts_min: MultiValueEncoded<BigUint>,
+    ) -> MultiValueEncoded<EsdtTokenPayment<Self::Api>> {
+        require!(self.is_active(), ERROR_NOT_ACTIVE);
+        require!(!self.lp_token().is_empty(), ERROR_LP_TOKEN_NOT_ISSUED);
+
+        let payment = self.call_value().single_esdt();
+        require!(
+            payment.token_identifier == self.lp_token().get_token_id() && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+
+        let pool_tokens = self.pool_tokens();
+        require!(
+            token_amounts_min.len() == pool_tokens.len(),
+            ERROR_INVALID_ARGS
+        );
+
+        let lp_token_supply = self.lp_token_supply().get();
+        let mut output_payments = ManagedVec::new();
+        for (token_id, amount_min) in pool_tokens.iter().zip(token_amounts_min.into_iter()) {
+            let reserve_mapper = self.reserve(&token_id);
+            let amount = &payment.amount * &reserve_mapper.get() / &lp_token_supply;
+            require!(amount >= amount_min, ERROR_SLIPPAGE_EXCEEDED);
+
+            reserve_mapper.update(|reserve| *reserve -= &amount);
+            output_payments.push(EsdtTokenPayment::new(token_id, 0, amount));
+        }
+
+        self.lp_token().burn(&payment.amount);
+        self.lp_token_supply()
+            .update(|supply| *supply -= &payment.amount);
+
+        let caller = self.blockchain().get_caller();
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        self.emit_remove_liquidity_event(output_payments.clone(), payment.amount);
+
+        output_payments.into()
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedInput)]
+    fn swap_tokens_fixed_input(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> SwapTokensFixedInputResultType<Self::Api> {
+        require!(amount_out_min > 0, ERROR_INVALID_ARGS);
+        require!(self.is_active(), ERROR_NOT_ACTIVE);
+
+        let (token_in, amount_in) = self.call_value().single_fungible_esdt();
+        let amount_out =
+            self.get_amount_out_view(token_in.clone(), amount_in.clone(), token_out.clone());
+        require!(amount_out >= amount_out_min, ERROR_SLIPPAGE_EXCEEDED);
+
+        let fee_amount = &amount_in * self.total_fee_percent().get() / MAX_PERCENTAGE;
+        self.reserve(&token_in)
+            .update(|reserve| *reserve += &amount_in);
+        self.reserve(&token_out)
+            .update(|reserve| *reserve -= &amount_out);
+
+        let output_payment = EsdtTokenPayment::new(token_out, 0, amount_out);
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(
+            &caller,
+            &output_payment.token_identifier,
+            0,
+            &output_payment.amount,
+        );
+
+        self.emit_swap_event(
+            &EsdtTokenPayment::new(token_in, 0, amount_in),
+            &output_payment,
+            fee_amount,
+        );
+
+        output_payment
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedOutput)]
+    fn swap_tokens_fixed_output(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out: BigUint,
+    ) -> SwapTokensFixedOutputResultType<Self::Api> {
+        require!(amount_out > 0, ERROR_INVALID_ARGS);
+        require!(self.is_active(), ERROR_NOT_ACTIVE);
+
+        let (token_in, amount_in_max) = self.call_value().single_fungible_esdt();
+        let amount_in =
+            self.get_amount_in_view(token_in.clone(), token_out.clone(), amount_out.clone());
+        require!(amount_in <= amount_in_max, ERROR_SLIPPAGE_EXCEEDED);
+
+        let fee_amount = &amount_in * self.total_fee_percent().get() / MAX_PERCENTAGE;
+        self.reserve(&token_in)
+            .update(|reserve| *reserve += &amount_in);
+        self.reserve(&token_out)
+            .update(|reserve| *reserve -= &amount_out);
+
+        let output_payment = EsdtTokenPayment::new(token_out, 0, amount_out);
+        let residuum = EsdtTokenPayment::new(token_in.clone(), 0, &amount_in_max - &amount_in);
+        let mut output_payments = ManagedVec::new();
+        output_payments.push(output_payment.clone());
+        output_payments.push(residuum.clone());
+
+        let caller = self.blockchain().get_caller();
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        self.emit_swap_event(
+            &EsdtTokenPayment::new(token_in, 0, amount_in),
+            &output_payment,
+            fee_amount,
+        );
+
+        (output_payment, residuum).into()
+    }
+
+    #[view(getAmountOut)]
+    fn get_amount_out_view(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+        token_out: TokenIdentifier,
+    ) -> BigUint {
+        self.require_swap_tokens(&token_in, &token_out);
+        require!(amount_in > 0, ERROR_ZERO_AMOUNT);
+
+        let reserve_in = self.reserve(&token_in).get();
+        let reserve_out = self.reserve(&token_out).get();
+        require!(amount_in <= &reserve_in / 2u64, ERROR_MAX_IN_RATIO);
+
+        let amount_out = self.get_amount_out_with_fee(
+            &token_in,
+            &amount_in,
+            &reserve_in,
+            &token_out,
+            &reserve_out,
+        );
+        require!(amount_out > 0, ERROR_ZERO_AMOUNT);
+        require!(amount_out <= &reserve_out / 3u64, ERROR_MAX_OUT_RATIO);
+
+        amount_out
+    }
+
+    /// Same as `getAmountOut`, but gives 0 instead of failing,
+    /// so it can be used to compare pools without knowing their limits.
+    #[view(tryGetAmountOut)]
+    fn try_get_amount_out_view(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+        token_out: TokenIdentifier,
+    ) -> BigUint {
+        let pool_tokens = self.pool_tokens();
+        if token_in == token_out
+            || !pool_tokens.contains(&token_in)
+            || !pool_tokens.contains(&token_out)
+            || amount_in == 0
+        {
+            return BigUint::zero();
+        }
+
+        let reserve_in = self.reserve(&token_in).get();
+        let reserve_out = self.reserve(&token_out).get();
+        if reserve_in == 0 || reserve_out == 0 || amount_in > &reserve_in / 2u64 {
+            return BigUint::zero();
+        }
+
+        let amount_out = self.get_amount_out_with_fee(
+            &token_in,
+            &amount_in,
+            &reserve_in,
+            &token_out,
+            &reserve_out,
+        );
+        if amount_out > &reserve_out / 3u64 {
+            return BigUint::zero();
+        }
+
+        amount_out
+    }
+
+    #[view(getAmountIn)]
+    fn get_amount_in_view(
+        &self,
+        token_in: TokenIdentifier,
+        token_out: TokenIdentifier,
+        amount_out: BigUint,
+    ) -> BigUint {
+        self.require_swap_tokens(&token_in, &token_out);
+        require!(amount_out > 0, ERROR_ZERO_AMOUNT);
+
+        let reserve_in = self.reserve(&token_in).get();
+        let reserve_out = self.reserve(&token_out).get();
+        require!(amount_out <= &reserve_out / 3u64, ERROR_MAX_OUT_RATIO);
+
+        let amount_in_no_fee = self.calculate_weighted_amount_in(
+            &amount_out,
+            &reserve_in,
+            self.token_weight(&token_in).get(),
+            &reserve_out,
+            self.token_weight(&token_out).get(),
+        );
+        let amount_in = amount_in_no_fee * MAX_PERCENTAGE
+            / (MAX_PERCENTAGE - self.total_fee_percent().get())
+            + 1u64;
+        require!(amount_in <= &reserve_in / 2u64, ERROR_MAX_IN_RATIO);
+
+        amount_in
+    }
+
+    /// Price of one unit of `token_out`, expressed in `token_in`, with 18 decimals.
+    /// Fees are not included.
+    #[view(getSpotPrice)]
+    fn get_spot_price(&self, token_in: TokenIdentifier, token_out: TokenIdentifier) -> BigUint {
+        self.require_swap_tokens(&token_in, &token_out);
+
+        let reserve_out = self.reserve(&token_out).get();
+        require!(reserve_out > 0, ERROR_ZERO_AMOUNT);
+
+        self.reserve(&token_in).get() * self.token_weight(&token_out).get() * weighted_math::ONE
+            / (reserve_out * self.token_weight(&token_in).get())
+    }
+
+    fn compute_liquidity(
+        &self,
+        payments: &ManagedVec<EsdtTokenPayment>,
+        lp_token_supply: &BigUint,
+    ) -> BigUint {
+        let mut liquidity: Option<BigUint> = None;
+        for payment in payments.iter() {
+            let reserve = self.reserve(&payment.token_identifier).get();
+            let potential_liquidity = &payment.amount * lp_token_supply / &reserve;
+            liquidity = match liquidity {
+                Some(current) if current <= potential_liquidity => Some(current),
+                _ => Some(potential_liquidity),
+            };
+        }
+
+        let liquidity = liquidity.unwrap_or_else(BigUint::zero);
+        require!(liquidity > 0, ERROR_INSUFFICIENT_LIQUIDITY);
+
+        liquidity
+    }
+
+    fn get_amount_out_with_fee(
+        &self,
+        token_in: &TokenIdentifier,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        token_out: &TokenIdentifier,
+        reserve_out: &BigUint,
+    ) -> BigUint {
+        let amount_in_with_fee =
+            amount_in * (MAX_PERCENTAGE - self.total_fee_percent().get()) / MAX_PERCENTAGE;
+        self.calculate_weighted_amount_out(
+            &amount_in_with_fee,
+            reserve_in,
+            self.token_weight(token_in).get(),
+            reserve_out,
+            self.token_weight(token_out).get(),
+        )
+    }
+
+    fn require_swap_tokens(&self, token_in: &TokenIdentifier, token_out: &TokenIdentifier) {
+        require!(token_in != token_out, ERROR_SAME_TOKENS);
+        self.require_pool_token(token_in);
+        self.require_pool_token(token_out);
+    }
+}
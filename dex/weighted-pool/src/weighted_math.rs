@@ -0,0 +1,148 @@
+multiversx_sc::imports!();
+
+use crate::errors::ERROR_POW_BASE_OUT_OF_BOUNDS;
+
+pub const ONE: u64 = 1_000_000_000_000_000_000;
+const MAX_POW_BASE: u64 = 2 * ONE - 1;
+const POW_PRECISION: u64 = ONE / 10_000_000_000;
+
+/// Fixed point math (18 decimals) for the weighted constant product invariant,
+/// `prod(reserve_i ^ weight_i) = k`.
+#[multiversx_sc::module]
+pub trait WeightedMathModule {
+    /// amount_out = reserve_out * (1 - (reserve_in / (reserve_in + amount_in)) ^ (w_in / w_out))
+    fn calculate_weighted_amount_out(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        weight_in: u64,
+        reserve_out: &BigUint,
+        weight_out: u64,
+    ) -> BigUint {
+        let weight_ratio = BigUint::from(weight_in) * ONE / weight_out;
+        let new_reserve_in = reserve_in + amount_in;
+
+        // rounding the base up keeps the output rounded down
+        let base = reserve_in * ONE / &new_reserve_in + 1u64;
+        let power = self.pow(&base, &weight_ratio);
+        if power >= ONE {
+            return BigUint::zero();
+        }
+
+        reserve_out * &(BigUint::from(ONE) - power) / ONE
+    }
+
+    /// amount_in = reserve_in * ((reserve_out / (reserve_out - amount_out)) ^ (w_out / w_in) - 1)
+    fn calculate_weighted_amount_in(
+        &self,
+        amount_out: &BigUint,
+        reserve_in: &BigUint,
+        weight_in: u64,
+        reserve_out: &BigUint,
+        weight_out: u64,
+    ) -> BigUint {
+        let weight_ratio = BigUint::from(weight_out) * ONE / weight_in;
+        let new_reserve_out = reserve_out - amount_out;
+
+        let base = reserve_out * ONE / &new_reserve_out + 1u64;
+        let power = self.pow(&base, &weight_ratio);
+
+        reserve_in * &(power - ONE) / ONE + 1u64
+    }
+
+    fn mul_fixed(&self, first: &BigUint, second: &BigUint) -> BigUint {
+        (first * second + ONE / 2) / ONE
+    }
+
+    fn div_fixed(&self, numerator: &BigUint, denominator: &BigUint) -> BigUint {
+        (numerator * ONE + denominator / 2u64) / denominator
+    }
+
+    /// base ^ exponent, computed as base ^ whole(exponent) * base ^ fraction(exponent).
+    fn pow(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        require!(
+            base > &0u64 && base <= &MAX_POW_BASE,
+            ERROR_POW_BASE_OUT_OF_BOUNDS
+        );
+
+        let whole = exponent / ONE;
+        let remainder = exponent - &(&whole * ONE);
+        let whole_power = match whole.to_u64() {
+            Some(whole_exponent) => self.pow_integer(base, whole_exponent),
+            None => sc_panic!(ERROR_POW_BASE_OUT_OF_BOUNDS),
+        };
+        if remainder == 0 {
+            return whole_power;
+        }
+
+        let partial_power = self.pow_approx(base, &remainder);
+        self.mul_fixed(&whole_power, &partial_power)
+    }
+
+    fn pow_integer(&self, base: &BigUint, mut exponent: u64) -> BigUint {
+        let mut base = base.clone();
+        let mut result = if exponent % 2 != 0 {
+            base.clone()
+        } else {
+            BigUint::from(ONE)
+        };
+
+        exponent /= 2;
+        while exponent != 0 {
+            base = self.mul_fixed(&base, &base);
+            if exponent % 2 != 0 {
+                result = self.mul_fixed(&result, &base);
+            }
+            exponent /= 2;
+        }
+
+        result
+    }
+
+    /// Binomial series approximation of base ^ exponent, for 0 < exponent < 1.
+    /// The sign of each term is tracked separately, as BigUint is unsigned.
+    fn pow_approx(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        let one = BigUint::from(ONE);
+        let (x, x_negative) = if base >= &one {
+            (base - &one, false)
+        } else {
+            (&one - base, true)
+        };
+
+        let mut term = one.clone();
+        let mut sum = one;
+        let mut negative = false;
+        let mut i = 1u64;
+        while term >= POW_PRECISION {
+            let big_k = BigUint::from(i) * ONE;
+            let k_minus_one = &big_k - ONE;
+            let (c, c_negative) = if exponent >= &k_minus_one {
+                (exponent - &k_minus_one, false)
+            } else {
+                (k_minus_one - exponent, true)
+            };
+
+            term = self.mul_fixed(&term, &self.mul_fixed(&c, &x));
+            term = self.div_fixed(&term, &big_k);
+            if term == 0 {
+                break;
+            }
+
+            if x_negative {
+                negative = !negative;
+            }
+            if c_negative {
+                negative = !negative;
+            }
+            if negative {
+                sum -= &term;
+            } else {
+                sum += &term;
+            }
+
+            i += 1;
+        }
+
+        sum
+    }
+}
@@ -0,0 +1,206 @@
+use multiversx_sc::codec::multi_types::MultiValue2;
+use multiversx_sc::storage::mappers::StorageTokenWrapper;
+use multiversx_sc::types::{Address, EsdtLocalRole, MultiValueEncoded};
+use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+};
+
+pub const WEIGHTED_POOL_WASM_PATH: &str = "weighted-pool/output/weighted-pool.wasm";
+pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+pub const USDC_TOKEN_ID: &[u8] = b"USDC-abcdef";
+pub const LP_TOKEN_ID: &[u8] = b"LPTOK-abcdef";
+
+pub const POOL_TOKENS: [&[u8]; 3] = [WEGLD_TOKEN_ID, MEX_TOKEN_ID, USDC_TOKEN_ID];
+pub const POOL_WEIGHTS: [u64; 3] = [2, 1, 1];
+pub const USER_BALANCE: u64 = 5_000_000_000;
+
+use pausable::{PausableModule, State};
+use weighted_pool::config::ConfigModule;
+use weighted_pool::*;
+
+#[allow(dead_code)]
+pub struct WeightedPoolSetup<WeightedPoolObjBuilder>
+where
+    WeightedPoolObjBuilder: 'static + Copy + Fn() -> weighted_pool::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub user_address: Address,
+    pub pool_wrapper:
+        ContractObjWrapper<weighted_pool::ContractObj<DebugApi>, WeightedPoolObjBuilder>,
+}
+
+impl<WeightedPoolObjBuilder> WeightedPoolSetup<WeightedPoolObjBuilder>
+where
+    WeightedPoolObjBuilder: 'static + Copy + Fn() -> weighted_pool::ContractObj<DebugApi>,
+{
+    pub fn new(pool_builder: WeightedPoolObjBuilder) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_addr = b_mock.create_user_account(&rust_zero);
+        let pool_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            pool_builder,
+            WEIGHTED_POOL_WASM_PATH,
+        );
+
+        b_mock
+            .execute_tx(&owner_addr, &pool_wrapper, &rust_zero, |sc| {
+                let mut tokens_and_weights = MultiValueEncoded::new();
+                for (token_id, weight) in POOL_TOKENS.iter().zip(POOL_WEIGHTS.iter()) {
+                    tokens_and_weights
+                        .push(MultiValue2::from((managed_token_id!(*token_id), *weight)));
+                }
+
+                sc.init(
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    300,
+                    tokens_and_weights,
+                );
+
+                sc.lp_token().set_token_id(managed_token_id!(LP_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        let lp_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        b_mock.set_esdt_local_roles(pool_wrapper.address_ref(), LP_TOKEN_ID, &lp_token_roles[..]);
+
+        let user_addr = b_mock.create_user_account(&rust_zero);
+        for token_id in POOL_TOKENS.iter() {
+            b_mock.set_esdt_balance(&user_addr, token_id, &rust_biguint!(USER_BALANCE));
+        }
+
+        WeightedPoolSetup {
+            b_mock,
+            owner_address: owner_addr,
+            user_address: user_addr,
+            pool_wrapper,
+        }
+    }
+
+    pub fn add_liquidity(&mut self, amounts: [u64; 3], expected_lp_amount: u64) {
+        let payments: Vec<TxTokenTransfer> = POOL_TOKENS
+            .iter()
+            .zip(amounts.iter())
+            .map(|(token_id, amount)| TxTokenTransfer {
+                token_identifier: token_id.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(*amount),
+            })
+            .collect();
+
+        self.b_mock
+            .execute_esdt_multi_transfer(&self.user_address, &self.pool_wrapper, &payments, |sc| {
+                let output_payments = sc.add_liquidity(managed_biguint!(1)).to_vec();
+
+                let lp_payment = output_payments.get(0);
+                assert_eq!(lp_payment.token_identifier, managed_token_id!(LP_TOKEN_ID));
+                assert_eq!(lp_payment.amount, managed_biguint!(expected_lp_amount));
+            })
+            .assert_ok();
+    }
+
+    pub fn remove_liquidity(&mut self, lp_amount: u64, expected_amounts: [u64; 3]) {
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pool_wrapper,
+                LP_TOKEN_ID,
+                0,
+                &rust_biguint!(lp_amount),
+                |sc| {
+                    let mut token_amounts_min = MultiValueEncoded::new();
+                    for _ in 0..POOL_TOKENS.len() {
+                        token_amounts_min.push(managed_biguint!(1));
+                    }
+
+                    let output_payments = sc.remove_liquidity(token_amounts_min).to_vec();
+                    for (i, expected_amount) in expected_amounts.iter().enumerate() {
+                        let payment = output_payments.get(i);
+                        assert_eq!(payment.token_identifier, managed_token_id!(POOL_TOKENS[i]));
+                        assert_eq!(payment.amount, managed_biguint!(*expected_amount));
+                    }
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn swap_fixed_input(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount: u64,
+        desired_token_id: &[u8],
+        expected_amount: u64,
+    ) {
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pool_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount),
+                |sc| {
+                    let ret = sc.swap_tokens_fixed_input(
+                        managed_token_id!(desired_token_id),
+                        managed_biguint!(1),
+                    );
+
+                    assert_eq!(ret.token_identifier, managed_token_id!(desired_token_id));
+                    assert_eq!(ret.amount, managed_biguint!(expected_amount));
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn swap_fixed_output(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount_max: u64,
+        desired_token_id: &[u8],
+        desired_amount: u64,
+        expected_residuum: u64,
+    ) {
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pool_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount_max),
+                |sc| {
+                    let (output, residuum) = sc
+                        .swap_tokens_fixed_output(
+                            managed_token_id!(desired_token_id),
+                            managed_biguint!(desired_amount),
+                        )
+                        .into_tuple();
+
+                    assert_eq!(output.amount, managed_biguint!(desired_amount));
+                    assert_eq!(
+                        residuum.token_identifier,
+                        managed_token_id!(payment_token_id)
+                    );
+                    assert_eq!(residuum.amount, managed_biguint!(expected_residuum));
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn check_reserves(&mut self, expected_reserves: [u64; 3]) {
+        self.b_mock
+            .execute_query(&self.pool_wrapper, |sc| {
+                for (token_id, expected_reserve) in POOL_TOKENS.iter().zip(expected_reserves) {
+                    assert_eq!(
+                        sc.reserve(&managed_token_id!(*token_id)).get(),
+                        managed_biguint!(expected_reserve)
+                    );
+                }
+            })
+            .assert_ok();
+    }
+}
@@ -0,0 +1,62 @@
+#![allow(deprecated)]
+
+mod weighted_pool_setup;
+use multiversx_sc_scenario::{managed_biguint, managed_token_id, rust_biguint};
+use weighted_pool::WeightedPool;
+use weighted_pool_setup::*;
+
+#[test]
+fn test_weighted_pool_setup() {
+    let _ = WeightedPoolSetup::new(weighted_pool::contract_obj);
+}
+
+#[test]
+fn test_weighted_pool_swaps_and_liquidity() {
+    let mut setup = WeightedPoolSetup::new(weighted_pool::contract_obj);
+
+    // 50% WEGLD, 25% MEX, 25% USDC
+    setup.add_liquidity([2_000_000, 1_000_000, 1_000_000], 999_999_999_999_999_000);
+    setup.check_reserves([2_000_000, 1_000_000, 1_000_000]);
+
+    // MEX has half the weight of WEGLD, so the price is 2 MEX per WEGLD
+    setup.swap_fixed_input(MEX_TOKEN_ID, 10_000, WEGLD_TOKEN_ID, 9_896);
+    setup.check_reserves([1_990_104, 1_010_000, 1_000_000]);
+
+    setup.swap_fixed_output(USDC_TOKEN_ID, 10_000, MEX_TOKEN_ID, 5_000, 5_009);
+    setup.check_reserves([1_990_104, 1_005_000, 1_004_991]);
+
+    // only the proportional amounts are kept, the rest is returned
+    setup.add_liquidity([200_000, 100_000, 110_000], 99_502_487_562_189_054);
+    setup.check_reserves([2_188_125, 1_105_000, 1_104_991]);
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE - 1_000_000 - 4_991 - 100_000),
+    );
+
+    setup.remove_liquidity(99_502_487_562_189_054, [198_020, 99_999, 99_999]);
+    setup.check_reserves([1_990_105, 1_005_001, 1_004_992]);
+}
+
+#[test]
+fn test_weighted_pool_max_in_ratio() {
+    let mut setup = WeightedPoolSetup::new(weighted_pool::contract_obj);
+    setup.add_liquidity([2_000_000, 1_000_000, 1_000_000], 999_999_999_999_999_000);
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.pool_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(500_001),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(1),
+                );
+            },
+        )
+        .assert_user_error("Input amount too large for the pool reserves");
+}
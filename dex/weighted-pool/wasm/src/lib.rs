@@ -0,0 +1,55 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Endpoints:                           27
+// Async Callback:                       1
+// Total number of exported functions:  29
+
+#![no_std]
+
+// Configuration that works with rustc < 1.73.0.
+// TODO: Recommended rustc version: 1.73.0 or newer.
+#![feature(lang_items)]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    weighted_pool
+    (
+        init => init
+        upgrade => upgrade
+        addLiquidity => add_liquidity
+        removeLiquidity => remove_liquidity
+        swapTokensFixedInput => swap_tokens_fixed_input
+        swapTokensFixedOutput => swap_tokens_fixed_output
+        getAmountOut => get_amount_out_view
+        tryGetAmountOut => try_get_amount_out_view
+        getAmountIn => get_amount_in_view
+        getSpotPrice => get_spot_price
+        issueLpToken => issue_lp_token
+        setFeePercent => set_fee_percent_endpoint
+        getTokensAndWeights => get_tokens_and_weights
+        getLpTokenIdentifier => lp_token
+        getTotalSupply => lp_token_supply
+        getTotalFeePercent => total_fee_percent
+        getTokens => pool_tokens
+        getTokenWeight => token_weight
+        getReserve => reserve
+        addAdmin => add_admin_endpoint
+        removeAdmin => remove_admin_endpoint
+        updateOwnerOrAdmin => update_owner_or_admin_endpoint
+        getPermissions => permissions
+        addToPauseWhitelist => add_to_pause_whitelist
+        removeFromPauseWhitelist => remove_from_pause_whitelist
+        pause => pause
+        resume => resume
+        getState => state
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback! { weighted_pool }
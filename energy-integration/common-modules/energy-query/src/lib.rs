@@ -4,6 +4,7 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 pub use energy_factory::energy::Energy;
+use energy_factory::energy::ProxyTrait as _;
 
 static USER_ENERGY_STORAGE_KEY: &[u8] = b"userEnergy";
 static LOCKED_TOKEN_ID_STORAGE_KEY: &[u8] = b"lockedTokenId";
@@ -34,6 +35,18 @@ pub trait EnergyQueryModule {
         energy
     }
 
+    /// Energy of the user at the start of the given epoch, as recorded by the energy factory.
+    fn get_energy_amount_at_epoch(&self, user: &ManagedAddress, epoch: u64) -> BigUint {
+        if self.energy_factory_address().is_empty() {
+            return BigUint::zero();
+        }
+
+        let energy_factory_address = self.energy_factory_address().get();
+        self.energy_factory_proxy(energy_factory_address)
+            .get_energy_amount_for_user_at_epoch(user.clone(), epoch)
+            .execute_on_dest_context()
+    }
+
     fn get_energy_entry(&self, user: &ManagedAddress) -> Energy<Self::Api> {
         let current_epoch = self.blockchain().get_block_epoch();
         if self.energy_factory_address().is_empty() {
@@ -29,6 +29,12 @@ pub trait EnergyFactoryMock {
         self.get_energy_entry_for_user(user).get_energy_amount()
     }
 
+    /// The mock keeps no history, so the current energy is returned for any epoch.
+    #[view(getEnergyAmountForUserAtEpoch)]
+    fn get_energy_amount_for_user_at_epoch(&self, user: ManagedAddress, _epoch: u64) -> BigUint {
+        self.get_energy_amount_for_user(user)
+    }
+
     #[view(getEnergyEntryForUser)]
     fn get_energy_entry_for_user(&self, user: ManagedAddress) -> Energy<Self::Api> {
         let current_epoch = self.blockchain().get_block_epoch();
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                            4
+// Endpoints:                            5
 // Async Callback (empty):               1
-// Total number of exported functions:   6
+// Total number of exported functions:   7
 
 #![no_std]
 
@@ -24,6 +24,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         init => init
         setUserEnergy => set_user_energy
         getEnergyAmountForUser => get_energy_amount_for_user
+        getEnergyAmountForUserAtEpoch => get_energy_amount_for_user_at_epoch
         getEnergyEntryForUser => get_energy_entry_for_user
         setUserEnergyAfterLockedTokenTransfer => set_user_energy_after_locked_token_transfer
     )
@@ -13,6 +13,23 @@ pub trait FeesCollectorEventsModule {
         self.deposit_swap_fees_event(caller, current_week, payment);
     }
 
+    fn emit_add_protocol_liquidity_event(
+        self,
+        caller: ManagedAddress,
+        pair_address: ManagedAddress,
+        first_token_payment: EsdtTokenPayment<Self::Api>,
+        second_token_payment: EsdtTokenPayment<Self::Api>,
+        lp_tokens: EsdtTokenPayment<Self::Api>,
+    ) {
+        self.add_protocol_liquidity_event(
+            caller,
+            pair_address,
+            first_token_payment,
+            second_token_payment,
+            lp_tokens,
+        );
+    }
+
     #[event("deposit_swap_fees_event")]
     fn deposit_swap_fees_event(
         &self,
@@ -20,4 +37,14 @@ pub trait FeesCollectorEventsModule {
         #[indexed] current_week: Week,
         #[indexed] payment: EsdtTokenPayment<Self::Api>,
     );
+
+    #[event("add_protocol_liquidity_event")]
+    fn add_protocol_liquidity_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] first_token_payment: EsdtTokenPayment<Self::Api>,
+        #[indexed] second_token_payment: EsdtTokenPayment<Self::Api>,
+        #[indexed] lp_tokens: EsdtTokenPayment<Self::Api>,
+    );
 }
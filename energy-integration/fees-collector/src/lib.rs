@@ -10,6 +10,7 @@ pub mod additional_locked_tokens;
 pub mod config;
 pub mod events;
 pub mod fees_accumulation;
+pub mod protocol_liquidity;
 
 #[multiversx_sc::contract]
 pub trait FeesCollector:
@@ -21,6 +22,7 @@ pub trait FeesCollector:
     + weekly_rewards_splitting::locked_token_buckets::WeeklyRewardsLockedTokenBucketsModule
     + weekly_rewards_splitting::update_claim_progress_energy::UpdateClaimProgressEnergyModule
     + fees_accumulation::FeesAccumulationModule
+    + protocol_liquidity::ProtocolLiquidityModule
     + additional_locked_tokens::AdditionalLockedTokensModule
     + locking_module::lock_with_energy_module::LockWithEnergyModule
     + energy_query::EnergyQueryModule
@@ -142,7 +144,10 @@ where
         for token in &all_tokens {
             let opt_accumulated_fees = sc.get_and_clear_accumulated_fees(week, &token);
             if let Some(accumulated_fees) = opt_accumulated_fees {
-                results.push(EsdtTokenPayment::new(token, 0, accumulated_fees));
+                let rewards_amount = sc.divert_protocol_liquidity_share(&token, accumulated_fees);
+                if rewards_amount > 0 {
+                    results.push(EsdtTokenPayment::new(token, 0, rewards_amount));
+                }
             }
         }
 
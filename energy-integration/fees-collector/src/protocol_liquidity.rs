@@ -0,0 +1,327 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub const MAX_PERCENTAGE: u64 = 10_000;
+
+type AddLiquidityResultType<M> =
+    MultiValue3<EsdtTokenPayment<M>, EsdtTokenPayment<M>, EsdtTokenPayment<M>>;
+type ReservesAndTotalSupplyResultType<M> = MultiValue3<BigUint<M>, BigUint<M>, BigUint<M>>;
+
+// Must manually declare, as Pair SC already depends on fees-collector
+// This avoids circular dependency
+pub mod pair_proxy {
+    multiversx_sc::imports!();
+    use super::{AddLiquidityResultType, ReservesAndTotalSupplyResultType};
+
+    #[multiversx_sc::proxy]
+    pub trait PairProxy {
+        #[payable("*")]
+        #[endpoint(addLiquidity)]
+        fn add_liquidity(
+            &self,
+            first_token_amount_min: BigUint,
+            second_token_amount_min: BigUint,
+        ) -> AddLiquidityResultType<Self::Api>;
+
+        #[endpoint(updateAndGetSafePrice)]
+        fn update_and_get_safe_price(
+            &self,
+            input: EsdtTokenPayment<Self::Api>,
+        ) -> EsdtTokenPayment<Self::Api>;
+
+        #[view(getReservesAndTotalSupply)]
+        fn get_reserves_and_total_supply(&self) -> ReservesAndTotalSupplyResultType<Self::Api>;
+
+        #[view(getFirstTokenId)]
+        fn first_token_id(&self) -> TokenIdentifier;
+
+        #[view(getSecondTokenId)]
+        fn second_token_id(&self) -> TokenIdentifier;
+
+        #[view(getLpTokenIdentifier)]
+        fn lp_token_identifier(&self) -> TokenIdentifier;
+    }
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct ProtocolLiquidityPairTokens<M: ManagedTypeApi> {
+    pub first_token_id: TokenIdentifier<M>,
+    pub second_token_id: TokenIdentifier<M>,
+    pub lp_token_id: TokenIdentifier<M>,
+}
+
+/// Diverts a percentage of each week's fees, for the tokens of the selected pairs,
+/// into a reserve that is then added as liquidity to those pairs.
+/// The resulting LP tokens are held by the contract.
+#[multiversx_sc::module]
+pub trait ProtocolLiquidityModule:
+    crate::config::ConfigModule
+    + crate::events::FeesCollectorEventsModule
+    + crate::fees_accumulation::FeesAccumulationModule
+    + week_timekeeping::WeekTimekeepingModule
+    + utils::UtilsModule
+{
+    /// Both values are out of MAX_PERCENTAGE (10_000).
+    /// A percentage of 0 disables the diversion of fees.
+    #[only_owner]
+    #[endpoint(setProtocolLiquidityConfig)]
+    fn set_protocol_liquidity_config(&self, percentage: u64, max_price_deviation: u64) {
+        require!(percentage <= MAX_PERCENTAGE, "Invalid percentage");
+        require!(
+            max_price_deviation > 0 && max_price_deviation < MAX_PERCENTAGE,
+            "Invalid max price deviation"
+        );
+
+        self.protocol_liquidity_percentage().set(percentage);
+        self.protocol_liquidity_max_price_deviation()
+            .set(max_price_deviation);
+    }
+
+    #[only_owner]
+    #[endpoint(addProtocolLiquidityPair)]
+    fn add_protocol_liquidity_pair(&self, pair_address: ManagedAddress) {
+        self.require_sc_address(&pair_address);
+        require!(
+            !self.protocol_liquidity_pairs().contains(&pair_address),
+            "Pair already added"
+        );
+
+        let first_token_id: TokenIdentifier = self
+            .pair_proxy(pair_address.clone())
+            .first_token_id()
+            .execute_on_dest_context();
+        let second_token_id: TokenIdentifier = self
+            .pair_proxy(pair_address.clone())
+            .second_token_id()
+            .execute_on_dest_context();
+        let lp_token_id: TokenIdentifier = self
+            .pair_proxy(pair_address.clone())
+            .lp_token_identifier()
+            .execute_on_dest_context();
+        require!(
+            self.known_tokens().contains(&first_token_id)
+                && self.known_tokens().contains(&second_token_id),
+            "Pair tokens must be known tokens"
+        );
+
+        let _ = self.protocol_liquidity_pairs().insert(pair_address.clone());
+        let _ = self
+            .protocol_liquidity_token_pairs(&first_token_id)
+            .insert(pair_address.clone());
+        let _ = self
+            .protocol_liquidity_token_pairs(&second_token_id)
+            .insert(pair_address.clone());
+        self.protocol_liquidity_pair_tokens(&pair_address)
+            .set(ProtocolLiquidityPairTokens {
+                first_token_id,
+                second_token_id,
+                lp_token_id,
+            });
+    }
+
+    /// The LP tokens of the pair stay with the contract.
+    #[only_owner]
+    #[endpoint(removeProtocolLiquidityPair)]
+    fn remove_protocol_liquidity_pair(&self, pair_address: ManagedAddress) {
+        require!(
+            self.protocol_liquidity_pairs().swap_remove(&pair_address),
+            "Unknown pair"
+        );
+
+        let pair_tokens = self.protocol_liquidity_pair_tokens(&pair_address).get();
+        self.remove_protocol_liquidity_token_pair(&pair_tokens.first_token_id, &pair_address);
+        self.remove_protocol_liquidity_token_pair(&pair_tokens.second_token_id, &pair_address);
+    }
+
+    /// Adds the reserve of the pair's tokens as liquidity, at the current price of the pair,
+    /// as long as it is close enough to its safe price. What the pair does not use stays in the reserve.
+    #[endpoint(addProtocolLiquidity)]
+    fn add_protocol_liquidity(&self, pair_address: ManagedAddress) -> EsdtTokenPayment {
+        require!(
+            self.protocol_liquidity_pairs().contains(&pair_address),
+            "Unknown pair"
+        );
+
+        let pair_tokens = self.protocol_liquidity_pair_tokens(&pair_address).get();
+        let first_reserve_mapper = self.protocol_liquidity_reserve(&pair_tokens.first_token_id);
+        let second_reserve_mapper = self.protocol_liquidity_reserve(&pair_tokens.second_token_id);
+        let first_token_amount = first_reserve_mapper.get();
+        let second_token_amount = second_reserve_mapper.get();
+        require!(
+            first_token_amount > 0 && second_token_amount > 0,
+            "Not enough reserve"
+        );
+
+        self.require_price_close_to_safe_price(
+            &pair_address,
+            &pair_tokens.first_token_id,
+            &first_token_amount,
+        );
+
+        let mut payments = ManagedVec::new();
+        payments.push(EsdtTokenPayment::new(
+            pair_tokens.first_token_id.clone(),
+            0,
+            first_token_amount,
+        ));
+        payments.push(EsdtTokenPayment::new(
+            pair_tokens.second_token_id.clone(),
+            0,
+            second_token_amount,
+        ));
+        let add_liquidity_result: AddLiquidityResultType<Self::Api> = self
+            .pair_proxy(pair_address.clone())
+            .add_liquidity(BigUint::from(1u64), BigUint::from(1u64))
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (lp_tokens, first_token_used, second_token_used) = add_liquidity_result.into_tuple();
+        require!(
+            lp_tokens.token_identifier == pair_tokens.lp_token_id
+                && first_token_used.token_identifier == pair_tokens.first_token_id
+                && second_token_used.token_identifier == pair_tokens.second_token_id,
+            "Invalid payments received from pair"
+        );
+
+        first_reserve_mapper.update(|amount| *amount -= &first_token_used.amount);
+        second_reserve_mapper.update(|amount| *amount -= &second_token_used.amount);
+        self.protocol_lp_amount(&pair_address)
+            .update(|amount| *amount += &lp_tokens.amount);
+
+        self.emit_add_protocol_liquidity_event(
+            self.blockchain().get_caller(),
+            pair_address,
+            first_token_used,
+            second_token_used,
+            lp_tokens.clone(),
+        );
+
+        lp_tokens
+    }
+
+    /// Returns the part of the fees left for the energy holders.
+    fn divert_protocol_liquidity_share(
+        &self,
+        token_id: &TokenIdentifier,
+        amount: BigUint,
+    ) -> BigUint {
+        let percentage = self.protocol_liquidity_percentage().get();
+        if percentage == 0 || self.protocol_liquidity_token_pairs(token_id).is_empty() {
+            return amount;
+        }
+
+        let protocol_liquidity_share = &amount * percentage / MAX_PERCENTAGE;
+        if protocol_liquidity_share == 0 {
+            return amount;
+        }
+
+        self.protocol_liquidity_reserve(token_id)
+            .update(|reserve| *reserve += &protocol_liquidity_share);
+
+        amount - protocol_liquidity_share
+    }
+
+    /// Once no selected pair uses the token anymore, its reserve goes back to the fees of the current week.
+    fn remove_protocol_liquidity_token_pair(
+        &self,
+        token_id: &TokenIdentifier,
+        pair_address: &ManagedAddress,
+    ) {
+        let mut token_pairs_mapper = self.protocol_liquidity_token_pairs(token_id);
+        let _ = token_pairs_mapper.swap_remove(pair_address);
+        if !token_pairs_mapper.is_empty() {
+            return;
+        }
+
+        let reserve = self.protocol_liquidity_reserve(token_id).take();
+        if reserve > 0 {
+            let current_week = self.get_current_week();
+            self.accumulated_fees(current_week, token_id)
+                .update(|amount| *amount += reserve);
+        }
+    }
+
+    fn require_price_close_to_safe_price(
+        &self,
+        pair_address: &ManagedAddress,
+        first_token_id: &TokenIdentifier,
+        first_token_amount: &BigUint,
+    ) {
+        let reserves_and_total_supply: ReservesAndTotalSupplyResultType<Self::Api> = self
+            .pair_proxy(pair_address.clone())
+            .get_reserves_and_total_supply()
+            .execute_on_dest_context();
+        let (first_token_reserve, second_token_reserve, _) = reserves_and_total_supply.into_tuple();
+        require!(first_token_reserve > 0, "Pair has no liquidity");
+        let current_price_amount =
+            first_token_amount * &second_token_reserve / &first_token_reserve;
+
+        let safe_price_payment: EsdtTokenPayment = self
+            .pair_proxy(pair_address.clone())
+            .update_and_get_safe_price(EsdtTokenPayment::new(
+                first_token_id.clone(),
+                0,
+                first_token_amount.clone(),
+            ))
+            .execute_on_dest_context();
+        let safe_price_amount = safe_price_payment.amount;
+
+        let price_difference = if current_price_amount > safe_price_amount {
+            &current_price_amount - &safe_price_amount
+        } else {
+            &safe_price_amount - &current_price_amount
+        };
+        let max_price_deviation = self.protocol_liquidity_max_price_deviation().get();
+        let max_price_difference = &safe_price_amount * max_price_deviation / MAX_PERCENTAGE;
+        require!(
+            price_difference <= max_price_difference,
+            "Price deviates too much from the safe price"
+        );
+    }
+
+    #[view(getProtocolLpTokens)]
+    fn get_protocol_lp_tokens(&self, pair_address: ManagedAddress) -> EsdtTokenPayment {
+        let pair_tokens_mapper = self.protocol_liquidity_pair_tokens(&pair_address);
+        require!(!pair_tokens_mapper.is_empty(), "Unknown pair");
+
+        EsdtTokenPayment::new(
+            pair_tokens_mapper.get().lp_token_id,
+            0,
+            self.protocol_lp_amount(&pair_address).get(),
+        )
+    }
+
+    #[proxy]
+    fn pair_proxy(&self, sc_address: ManagedAddress) -> pair_proxy::Proxy<Self::Api>;
+
+    #[view(getProtocolLiquidityPercentage)]
+    #[storage_mapper("protocolLiquidityPercentage")]
+    fn protocol_liquidity_percentage(&self) -> SingleValueMapper<u64>;
+
+    #[view(getProtocolLiquidityMaxPriceDeviation)]
+    #[storage_mapper("protocolLiquidityMaxPriceDeviation")]
+    fn protocol_liquidity_max_price_deviation(&self) -> SingleValueMapper<u64>;
+
+    #[view(getProtocolLiquidityPairs)]
+    #[storage_mapper("protocolLiquidityPairs")]
+    fn protocol_liquidity_pairs(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("protocolLiquidityTokenPairs")]
+    fn protocol_liquidity_token_pairs(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getProtocolLiquidityPairTokens)]
+    #[storage_mapper("protocolLiquidityPairTokens")]
+    fn protocol_liquidity_pair_tokens(
+        &self,
+        pair_address: &ManagedAddress,
+    ) -> SingleValueMapper<ProtocolLiquidityPairTokens<Self::Api>>;
+
+    #[view(getProtocolLiquidityReserve)]
+    #[storage_mapper("protocolLiquidityReserve")]
+    fn protocol_liquidity_reserve(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("protocolLpAmount")]
+    fn protocol_lp_amount(&self, pair_address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+}
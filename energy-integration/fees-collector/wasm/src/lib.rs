@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           38
+// Endpoints:                           48
 // Async Callback (empty):               1
-// Total number of exported functions:  40
+// Total number of exported functions:  50
 
 #![no_std]
 
@@ -43,6 +43,16 @@ multiversx_sc_wasm_adapter::endpoints! {
         getCurrentClaimProgress => current_claim_progress
         depositSwapFees => deposit_swap_fees
         getAccumulatedFees => accumulated_fees
+        setProtocolLiquidityConfig => set_protocol_liquidity_config
+        addProtocolLiquidityPair => add_protocol_liquidity_pair
+        removeProtocolLiquidityPair => remove_protocol_liquidity_pair
+        addProtocolLiquidity => add_protocol_liquidity
+        getProtocolLpTokens => get_protocol_lp_tokens
+        getProtocolLiquidityPercentage => protocol_liquidity_percentage
+        getProtocolLiquidityMaxPriceDeviation => protocol_liquidity_max_price_deviation
+        getProtocolLiquidityPairs => protocol_liquidity_pairs
+        getProtocolLiquidityPairTokens => protocol_liquidity_pair_tokens
+        getProtocolLiquidityReserve => protocol_liquidity_reserve
         setLockedTokensPerBlock => set_locked_tokens_per_block
         getLastLockedTokensAddWeek => last_locked_token_add_week
         getLockedTokensPerBlock => locked_tokens_per_block
@@ -0,0 +1,198 @@
+multiversx_sc::imports!();
+
+use common_types::PaymentsVec;
+use week_timekeeping::Week;
+
+use crate::errors::*;
+
+/// Bounds the gas used when a voter claims the bribes of a gauge for a week.
+pub const MAX_BRIBE_TOKENS: usize = 10;
+
+#[multiversx_sc::module]
+pub trait BribesModule:
+    crate::gauges::GaugesModule
+    + crate::voting::VotingModule
+    + crate::events::EventsModule
+    + energy_query::EnergyQueryModule
+    + week_timekeeping::WeekTimekeepingModule
+{
+    /// Anyone can deposit fungible tokens as incentives for the voters of a gauge,
+    /// for the current week or a future one.
+    /// The voters of that week can claim them starting with the next week,
+    /// proportionally to the energy they voted the gauge with.
+    #[payable("*")]
+    #[endpoint(depositBribe)]
+    fn deposit_bribe(&self, gauge: ManagedAddress, week: Week) {
+        self.require_known_gauge(&gauge);
+        require!(week >= self.get_current_week(), ERROR_INVALID_WEEK);
+
+        let payments = self.call_value().all_esdt_transfers().clone_value();
+        require!(!payments.is_empty(), ERROR_NO_PAYMENT);
+
+        let caller = self.blockchain().get_caller();
+        let mut bribe_tokens = self.bribe_tokens(week, &gauge);
+        for payment in &payments {
+            require!(payment.token_nonce == 0, ERROR_INVALID_PAYMENT);
+
+            let _ = bribe_tokens.insert(payment.token_identifier.clone());
+            self.bribe_amount(week, &gauge, &payment.token_identifier)
+                .update(|amount| *amount += &payment.amount);
+            self.bribe_deposit(week, &gauge, &caller, &payment.token_identifier)
+                .update(|amount| *amount += &payment.amount);
+        }
+        require!(
+            bribe_tokens.len() <= MAX_BRIBE_TOKENS,
+            ERROR_TOO_MANY_BRIBE_TOKENS
+        );
+
+        self.emit_deposit_bribe_event(&caller, week, &gauge, &payments);
+    }
+
+    /// Claims the bribes of a gauge, for at most `max_weeks` of the past weeks
+    /// in which the caller voted for it and did not claim yet.
+    /// The remaining weeks can be claimed with further calls.
+    #[endpoint(claimBribes)]
+    fn claim_bribes(&self, gauge: ManagedAddress, max_weeks: usize) -> PaymentsVec<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let current_week = self.get_current_week();
+        let mut vote_weeks = self.user_gauge_vote_weeks(&caller, &gauge);
+        let mut claimed_weeks: ManagedVec<Week> = ManagedVec::new();
+        let mut rewards = ManagedVec::new();
+        for week in vote_weeks.iter() {
+            if claimed_weeks.len() == max_weeks {
+                break;
+            }
+            if week >= current_week {
+                continue;
+            }
+
+            claimed_weeks.push(week);
+            self.add_user_bribes_for_week(&caller, &gauge, week, &mut rewards);
+        }
+        for week in claimed_weeks.iter() {
+            let _ = vote_weeks.swap_remove(&week);
+        }
+
+        if !rewards.is_empty() {
+            self.send().direct_multi(&caller, &rewards);
+        }
+
+        rewards
+    }
+
+    /// Sends back the bribes the caller deposited for a past week
+    /// in which nobody voted for the gauge.
+    #[endpoint(refundBribes)]
+    fn refund_bribes(&self, gauge: ManagedAddress, week: Week) -> PaymentsVec<Self::Api> {
+        require!(week < self.get_current_week(), ERROR_INVALID_WEEK);
+        require!(
+            self.gauge_votes(week, &gauge).get() == 0,
+            ERROR_GAUGE_HAD_VOTES
+        );
+
+        let caller = self.blockchain().get_caller();
+        let mut refunds = ManagedVec::new();
+        for token_id in self.bribe_tokens(week, &gauge).iter() {
+            let amount = self.bribe_deposit(week, &gauge, &caller, &token_id).take();
+            if amount == 0 {
+                continue;
+            }
+
+            self.bribe_amount(week, &gauge, &token_id)
+                .update(|bribe_amount| *bribe_amount -= &amount);
+            refunds.push(EsdtTokenPayment::new(token_id, 0, amount));
+        }
+        require!(!refunds.is_empty(), ERROR_NOTHING_TO_REFUND);
+
+        self.send().direct_multi(&caller, &refunds);
+
+        refunds
+    }
+
+    /// The bribes are split by the votes of the gauge, instead of the total energy of the week.
+    /// The last voter to claim also gets the rounding leftovers.
+    fn add_user_bribes_for_week(
+        &self,
+        user: &ManagedAddress,
+        gauge: &ManagedAddress,
+        week: Week,
+        rewards: &mut PaymentsVec<Self::Api>,
+    ) {
+        let user_votes = self.user_gauge_votes(week, gauge, user).take();
+        let gauge_votes = self.gauge_votes(week, gauge).get();
+        if user_votes == 0 || gauge_votes == 0 {
+            return;
+        }
+
+        let claimed_votes_mapper = self.claimed_gauge_votes(week, gauge);
+        let claimed_votes = claimed_votes_mapper.get() + &user_votes;
+        let is_last_claim = claimed_votes == gauge_votes;
+        claimed_votes_mapper.set(&claimed_votes);
+
+        for token_id in self.bribe_tokens(week, gauge).iter() {
+            let bribe_amount = self.bribe_amount(week, gauge, &token_id).get();
+            let claimed_amount_mapper = self.claimed_bribe_amount(week, gauge, &token_id);
+            let reward_amount = if is_last_claim {
+                bribe_amount - claimed_amount_mapper.get()
+            } else {
+                bribe_amount * &user_votes / &gauge_votes
+            };
+            if reward_amount == 0 {
+                continue;
+            }
+
+            claimed_amount_mapper.update(|claimed_amount| *claimed_amount += &reward_amount);
+
+            let existing_index = rewards
+                .iter()
+                .position(|reward| reward.token_identifier == token_id);
+            match existing_index {
+                Some(index) => {
+                    let mut reward = (*rewards.get(index)).clone();
+                    reward.amount += reward_amount;
+                    let _ = rewards.set(index, &reward);
+                }
+                None => rewards.push(EsdtTokenPayment::new(token_id, 0, reward_amount)),
+            }
+        }
+    }
+
+    #[view(getBribeTokens)]
+    #[storage_mapper("bribeTokens")]
+    fn bribe_tokens(
+        &self,
+        week: Week,
+        gauge: &ManagedAddress,
+    ) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[view(getBribeAmount)]
+    #[storage_mapper("bribeAmount")]
+    fn bribe_amount(
+        &self,
+        week: Week,
+        gauge: &ManagedAddress,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("claimedGaugeVotes")]
+    fn claimed_gauge_votes(&self, week: Week, gauge: &ManagedAddress)
+        -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("claimedBribeAmount")]
+    fn claimed_bribe_amount(
+        &self,
+        week: Week,
+        gauge: &ManagedAddress,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getBribeDeposit)]
+    #[storage_mapper("bribeDeposit")]
+    fn bribe_deposit(
+        &self,
+        week: Week,
+        gauge: &ManagedAddress,
+        depositor: &ManagedAddress,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+}
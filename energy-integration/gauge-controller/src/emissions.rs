@@ -0,0 +1,138 @@
+multiversx_sc::imports!();
+
+use week_timekeeping::Week;
+
+use crate::errors::*;
+
+static PRODUCE_REWARDS_ENABLED_STORAGE_KEY: &[u8] = b"produce_rewards_enabled";
+
+// Both the farms and the staking farms expose these endpoints,
+// so they are declared here instead of depending on one of the contracts
+pub mod farm_proxy {
+    multiversx_sc::imports!();
+
+    #[multiversx_sc::proxy]
+    pub trait FarmProxy {
+        #[endpoint(setPerBlockRewardAmount)]
+        fn set_per_block_reward_amount(&self, per_block_amount: BigUint);
+
+        #[endpoint(startProduceRewards)]
+        fn start_produce_rewards(&self);
+
+        #[endpoint(endProduceRewards)]
+        fn end_produce_rewards(&self);
+
+        #[view(hasActiveEmissionSchedule)]
+        fn has_active_emission_schedule(&self) -> bool;
+    }
+}
+
+#[multiversx_sc::module]
+pub trait EmissionsModule:
+    crate::gauges::GaugesModule
+    + crate::voting::VotingModule
+    + crate::events::EventsModule
+    + energy_query::EnergyQueryModule
+    + week_timekeeping::WeekTimekeepingModule
+{
+    /// The budget split between the gauges, starting with the next weights applied.
+    #[only_owner]
+    #[endpoint(setEmissionPerBlock)]
+    fn set_emission_per_block(&self, emission_per_block: BigUint) {
+        self.try_set_emission_per_block(emission_per_block);
+    }
+
+    /// Can be called by anyone, once per week.
+    /// Splits the emission budget between the gauges, based on the votes of the previous week,
+    /// and sets the resulting per block reward amount in each farm.
+    /// Farms without votes stop producing rewards, until they get votes again.
+    /// If nobody voted for the registered gauges, the farms keep their previous amounts.
+    /// Farms with an active emission schedule follow it instead, so they are skipped.
+    #[endpoint(applyGaugeWeights)]
+    fn apply_gauge_weights(&self) {
+        let current_week = self.get_current_week();
+        require!(
+            current_week > self.last_applied_week().get(),
+            ERROR_WEIGHTS_ALREADY_APPLIED
+        );
+
+        let votes_week = current_week - 1;
+        let mut total_votes = BigUint::zero();
+        for gauge in self.gauges().iter() {
+            total_votes += self.gauge_votes(votes_week, &gauge).get();
+        }
+
+        self.last_applied_week().set(current_week);
+        if total_votes == 0 {
+            return;
+        }
+
+        let emission_per_block = self.emission_per_block().get();
+        for gauge in self.gauges().iter() {
+            let has_active_emission_schedule: bool = self
+                .farm_proxy(gauge.clone())
+                .has_active_emission_schedule()
+                .execute_on_dest_context();
+            if has_active_emission_schedule {
+                continue;
+            }
+
+            let per_block_amount =
+                &emission_per_block * &self.gauge_votes(votes_week, &gauge).get() / &total_votes;
+            self.push_per_block_amount(gauge.clone(), &per_block_amount);
+
+            self.emit_apply_gauge_weight_event(current_week, &gauge, &per_block_amount);
+            self.gauge_per_block_amount(&gauge).set(per_block_amount);
+        }
+    }
+
+    fn push_per_block_amount(&self, farm: ManagedAddress, per_block_amount: &BigUint) {
+        let produce_rewards_enabled: bool = self.storage_raw().read_from_address(
+            &farm,
+            ManagedBuffer::new_from_bytes(PRODUCE_REWARDS_ENABLED_STORAGE_KEY),
+        );
+
+        if *per_block_amount == 0u64 {
+            if produce_rewards_enabled {
+                let _: IgnoreValue = self
+                    .farm_proxy(farm)
+                    .end_produce_rewards()
+                    .execute_on_dest_context();
+            }
+
+            return;
+        }
+
+        let _: IgnoreValue = self
+            .farm_proxy(farm.clone())
+            .set_per_block_reward_amount(per_block_amount)
+            .execute_on_dest_context();
+        if !produce_rewards_enabled {
+            let _: IgnoreValue = self
+                .farm_proxy(farm)
+                .start_produce_rewards()
+                .execute_on_dest_context();
+        }
+    }
+
+    fn try_set_emission_per_block(&self, emission_per_block: BigUint) {
+        require!(emission_per_block > 0, ERROR_ZERO_AMOUNT);
+
+        self.emission_per_block().set(emission_per_block);
+    }
+
+    #[proxy]
+    fn farm_proxy(&self, sc_address: ManagedAddress) -> farm_proxy::Proxy<Self::Api>;
+
+    #[view(getEmissionPerBlock)]
+    #[storage_mapper("emissionPerBlock")]
+    fn emission_per_block(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getLastAppliedWeek)]
+    #[storage_mapper("lastAppliedWeek")]
+    fn last_applied_week(&self) -> SingleValueMapper<Week>;
+
+    #[view(getGaugePerBlockAmount)]
+    #[storage_mapper("gaugePerBlockAmount")]
+    fn gauge_per_block_amount(&self, gauge: &ManagedAddress) -> SingleValueMapper<BigUint>;
+}
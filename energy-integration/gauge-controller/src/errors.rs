@@ -0,0 +1,18 @@
+pub static ERROR_INVALID_ADDRESS: &[u8] = b"Invalid address";
+pub static ERROR_ZERO_AMOUNT: &[u8] = b"Zero amount";
+pub static ERROR_TOO_MANY_GAUGES: &[u8] = b"Too many gauges";
+pub static ERROR_UNKNOWN_GAUGE: &[u8] = b"Unknown gauge";
+
+pub static ERROR_NO_VOTES: &[u8] = b"No votes";
+pub static ERROR_NO_ENERGY: &[u8] = b"No energy";
+pub static ERROR_INVALID_VOTE_WEIGHTS: &[u8] = b"Invalid vote weights";
+pub static ERROR_DUPLICATE_GAUGE: &[u8] = b"Duplicate gauge";
+
+pub static ERROR_WEIGHTS_ALREADY_APPLIED: &[u8] = b"Gauge weights already applied for this week";
+
+pub static ERROR_INVALID_WEEK: &[u8] = b"Invalid week";
+pub static ERROR_NO_PAYMENT: &[u8] = b"No payment";
+pub static ERROR_INVALID_PAYMENT: &[u8] = b"Only fungible tokens can be deposited";
+pub static ERROR_TOO_MANY_BRIBE_TOKENS: &[u8] = b"Too many bribe tokens";
+pub static ERROR_GAUGE_HAD_VOTES: &[u8] = b"The gauge had votes that week";
+pub static ERROR_NOTHING_TO_REFUND: &[u8] = b"Nothing to refund";
@@ -0,0 +1,56 @@
+multiversx_sc::imports!();
+
+use week_timekeeping::Week;
+
+use crate::voting::UserVotes;
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_vote_event(&self, user: &ManagedAddress, user_votes: &UserVotes<Self::Api>) {
+        self.vote_event(user, user_votes.week, user_votes);
+    }
+
+    fn emit_apply_gauge_weight_event(
+        &self,
+        week: Week,
+        gauge: &ManagedAddress,
+        per_block_amount: &BigUint,
+    ) {
+        self.apply_gauge_weight_event(gauge, week, per_block_amount);
+    }
+
+    fn emit_deposit_bribe_event(
+        &self,
+        caller: &ManagedAddress,
+        week: Week,
+        gauge: &ManagedAddress,
+        payments: &ManagedVec<EsdtTokenPayment>,
+    ) {
+        self.deposit_bribe_event(caller, gauge, week, payments);
+    }
+
+    #[event("vote")]
+    fn vote_event(
+        &self,
+        #[indexed] user: &ManagedAddress,
+        #[indexed] week: Week,
+        user_votes: &UserVotes<Self::Api>,
+    );
+
+    #[event("apply_gauge_weight")]
+    fn apply_gauge_weight_event(
+        &self,
+        #[indexed] gauge: &ManagedAddress,
+        #[indexed] week: Week,
+        per_block_amount: &BigUint,
+    );
+
+    #[event("deposit_bribe")]
+    fn deposit_bribe_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] gauge: &ManagedAddress,
+        #[indexed] week: Week,
+        payments: &ManagedVec<EsdtTokenPayment>,
+    );
+}
@@ -0,0 +1,45 @@
+multiversx_sc::imports!();
+
+use crate::errors::*;
+
+/// Bounds the gas used when the weights are pushed to all the farms at once.
+pub const MAX_GAUGES: usize = 50;
+
+#[multiversx_sc::module]
+pub trait GaugesModule {
+    /// The controller must be an admin of each farm, to set its per block reward amount.
+    #[only_owner]
+    #[endpoint(addGauges)]
+    fn add_gauges(&self, farms: MultiValueEncoded<ManagedAddress>) {
+        let mut gauges = self.gauges();
+        for farm in farms {
+            require!(
+                self.blockchain().is_smart_contract(&farm),
+                ERROR_INVALID_ADDRESS
+            );
+
+            let _ = gauges.insert(farm);
+        }
+
+        require!(gauges.len() <= MAX_GAUGES, ERROR_TOO_MANY_GAUGES);
+    }
+
+    /// Votes already cast for the removed gauges are ignored.
+    /// Their farms keep the last per block reward amount they received.
+    #[only_owner]
+    #[endpoint(removeGauges)]
+    fn remove_gauges(&self, farms: MultiValueEncoded<ManagedAddress>) {
+        let mut gauges = self.gauges();
+        for farm in farms {
+            let _ = gauges.swap_remove(&farm);
+        }
+    }
+
+    fn require_known_gauge(&self, farm: &ManagedAddress) {
+        require!(self.gauges().contains(farm), ERROR_UNKNOWN_GAUGE);
+    }
+
+    #[view(getGauges)]
+    #[storage_mapper("gauges")]
+    fn gauges(&self) -> UnorderedSetMapper<ManagedAddress>;
+}
@@ -0,0 +1,43 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod bribes;
+pub mod emissions;
+pub mod errors;
+mod events;
+pub mod gauges;
+pub mod voting;
+
+use week_timekeeping::FIRST_WEEK;
+
+/// Splits a global emission budget between farms, based on weekly energy votes.
+///
+/// Energy holders vote each week on how the budget is split between the registered farms (gauges).
+/// Once the week ends, anyone can apply the votes, which sets the per block reward amount
+/// of each farm. The controller must be an admin of all the farms it controls.
+///
+/// Anyone can also deposit bribes for the voters of a gauge, which they claim pro-rata to their votes.
+#[multiversx_sc::contract]
+pub trait GaugeController:
+    gauges::GaugesModule
+    + voting::VotingModule
+    + emissions::EmissionsModule
+    + bribes::BribesModule
+    + events::EventsModule
+    + energy_query::EnergyQueryModule
+    + week_timekeeping::WeekTimekeepingModule
+{
+    #[init]
+    fn init(&self, energy_factory_address: ManagedAddress, emission_per_block: BigUint) {
+        self.set_energy_factory_address(energy_factory_address);
+        self.try_set_emission_per_block(emission_per_block);
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.first_week_start_epoch().set_if_empty(current_epoch);
+        self.last_applied_week().set_if_empty(FIRST_WEEK);
+    }
+
+    #[endpoint]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,130 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use week_timekeeping::Week;
+
+use crate::errors::*;
+
+pub const MAX_PERCENTAGE: u64 = 10_000;
+
+#[derive(
+    ManagedVecItem, TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq,
+)]
+pub struct GaugeVote<M: ManagedTypeApi> {
+    pub gauge: ManagedAddress<M>,
+    pub energy: BigUint<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, Clone, PartialEq)]
+pub struct UserVotes<M: ManagedTypeApi> {
+    pub week: Week,
+    pub votes: ManagedVec<M, GaugeVote<M>>,
+}
+
+#[multiversx_sc::module]
+pub trait VotingModule:
+    crate::gauges::GaugesModule
+    + crate::events::EventsModule
+    + energy_query::EnergyQueryModule
+    + week_timekeeping::WeekTimekeepingModule
+{
+    /// Splits the energy of the caller between gauges, with weights out of MAX_PERCENTAGE.
+    /// The weights may add up to less than MAX_PERCENTAGE, leaving part of the energy unused.
+    ///
+    /// Votes only count for the current week, and a new vote replaces the previous one.
+    /// The energy is taken at the start of the week, so energy locked during the week,
+    /// and unlocked right after voting, does not count.
+    #[endpoint]
+    fn vote(&self, votes: MultiValueEncoded<MultiValue2<ManagedAddress, u64>>) {
+        require!(!votes.is_empty(), ERROR_NO_VOTES);
+
+        let caller = self.blockchain().get_caller();
+        let current_week = self.get_current_week();
+        let week_start_epoch = self.get_start_epoch_for_week(current_week);
+        let energy = self.get_energy_amount_at_epoch(&caller, week_start_epoch);
+        require!(energy > 0, ERROR_NO_ENERGY);
+
+        self.remove_user_votes(&caller, current_week);
+
+        let mut total_weight = 0;
+        let mut user_votes: ManagedVec<GaugeVote<Self::Api>> = ManagedVec::new();
+        for vote in votes {
+            let (gauge, weight) = vote.into_tuple();
+            self.require_known_gauge(&gauge);
+            require!(weight > 0, ERROR_INVALID_VOTE_WEIGHTS);
+            require!(
+                !user_votes.iter().any(|vote| vote.gauge == gauge),
+                ERROR_DUPLICATE_GAUGE
+            );
+            total_weight += weight;
+
+            let vote_energy = &energy * weight / MAX_PERCENTAGE;
+            self.gauge_votes(current_week, &gauge)
+                .update(|gauge_votes| *gauge_votes += &vote_energy);
+            self.user_gauge_votes(current_week, &gauge, &caller)
+                .set(&vote_energy);
+            let _ = self
+                .user_gauge_vote_weeks(&caller, &gauge)
+                .insert(current_week);
+
+            user_votes.push(GaugeVote {
+                gauge,
+                energy: vote_energy,
+            });
+        }
+        require!(total_weight <= MAX_PERCENTAGE, ERROR_INVALID_VOTE_WEIGHTS);
+
+        let user_votes = UserVotes {
+            week: current_week,
+            votes: user_votes,
+        };
+        self.user_votes(&caller).set(&user_votes);
+
+        self.emit_vote_event(&caller, &user_votes);
+    }
+
+    fn remove_user_votes(&self, user: &ManagedAddress, current_week: Week) {
+        let user_votes_mapper = self.user_votes(user);
+        if user_votes_mapper.is_empty() {
+            return;
+        }
+
+        let user_votes = user_votes_mapper.get();
+        if user_votes.week != current_week {
+            return;
+        }
+
+        for vote in user_votes.votes.iter() {
+            self.gauge_votes(current_week, &vote.gauge)
+                .update(|gauge_votes| *gauge_votes -= &vote.energy);
+            self.user_gauge_votes(current_week, &vote.gauge, user)
+                .clear();
+        }
+    }
+
+    #[view(getUserVotes)]
+    #[storage_mapper("userVotes")]
+    fn user_votes(&self, user: &ManagedAddress) -> SingleValueMapper<UserVotes<Self::Api>>;
+
+    #[view(getGaugeVotes)]
+    #[storage_mapper("gaugeVotes")]
+    fn gauge_votes(&self, week: Week, gauge: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getUserGaugeVotes)]
+    #[storage_mapper("userGaugeVotes")]
+    fn user_gauge_votes(
+        &self,
+        week: Week,
+        gauge: &ManagedAddress,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<BigUint>;
+
+    /// Weeks in which the user voted for the gauge, until their bribes are claimed.
+    #[view(getUserGaugeVoteWeeks)]
+    #[storage_mapper("userGaugeVoteWeeks")]
+    fn user_gauge_vote_weeks(
+        &self,
+        user: &ManagedAddress,
+        gauge: &ManagedAddress,
+    ) -> UnorderedSetMapper<Week>;
+}
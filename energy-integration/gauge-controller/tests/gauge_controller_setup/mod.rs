@@ -0,0 +1,223 @@
+use config::ConfigModule;
+use energy_factory_mock::EnergyFactoryMock;
+use energy_query::Energy;
+use farm::Farm;
+use farm_token::FarmTokenModule;
+use gauge_controller::{gauges::GaugesModule, voting::VotingModule, GaugeController};
+use multiversx_sc::storage::mappers::StorageTokenWrapper;
+use multiversx_sc::types::{Address, BigInt, EsdtLocalRole, MultiValueEncoded};
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint,
+    whitebox_legacy::{BlockchainStateWrapper, ContractObjWrapper},
+    DebugApi,
+};
+use pausable::{PausableModule, State};
+
+pub static REWARD_TOKEN_ID: &[u8] = b"MEX-123456";
+pub static FIRST_FARMING_TOKEN_ID: &[u8] = b"LPFIRST-123456";
+pub static SECOND_FARMING_TOKEN_ID: &[u8] = b"LPSECOND-123456";
+pub static BRIBE_TOKEN_ID: &[u8] = b"BRIBE-123456";
+pub static FARM_TOKEN_ID: &[u8] = b"FARM-123456";
+pub const DIV_SAFETY: u64 = 1_000_000_000_000;
+pub const EMISSION_PER_BLOCK: u64 = 1_000;
+pub const FIRST_USER_ENERGY: u64 = 1_000;
+pub const SECOND_USER_ENERGY: u64 = 3_000;
+pub const BRIBER_BALANCE: u64 = 10_000;
+
+pub struct GaugeControllerSetup<GaugeControllerObjBuilder, FarmObjBuilder>
+where
+    GaugeControllerObjBuilder: 'static + Copy + Fn() -> gauge_controller::ContractObj<DebugApi>,
+    FarmObjBuilder: 'static + Copy + Fn() -> farm::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner: Address,
+    pub first_user: Address,
+    pub second_user: Address,
+    pub briber: Address,
+    pub gauge_controller_wrapper:
+        ContractObjWrapper<gauge_controller::ContractObj<DebugApi>, GaugeControllerObjBuilder>,
+    pub first_farm_wrapper: ContractObjWrapper<farm::ContractObj<DebugApi>, FarmObjBuilder>,
+    pub second_farm_wrapper: ContractObjWrapper<farm::ContractObj<DebugApi>, FarmObjBuilder>,
+}
+
+impl<GaugeControllerObjBuilder, FarmObjBuilder>
+    GaugeControllerSetup<GaugeControllerObjBuilder, FarmObjBuilder>
+where
+    GaugeControllerObjBuilder: 'static + Copy + Fn() -> gauge_controller::ContractObj<DebugApi>,
+    FarmObjBuilder: 'static + Copy + Fn() -> farm::ContractObj<DebugApi>,
+{
+    pub fn new(
+        gauge_controller_builder: GaugeControllerObjBuilder,
+        farm_builder: FarmObjBuilder,
+    ) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner = b_mock.create_user_account(&rust_zero);
+        let first_user = b_mock.create_user_account(&rust_zero);
+        let second_user = b_mock.create_user_account(&rust_zero);
+        let briber = b_mock.create_user_account(&rust_zero);
+        b_mock.set_esdt_balance(&briber, BRIBE_TOKEN_ID, &rust_biguint!(BRIBER_BALANCE));
+
+        let energy_factory_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner),
+            energy_factory_mock::contract_obj,
+            "energy factory path",
+        );
+        b_mock
+            .execute_tx(&owner, &energy_factory_wrapper, &rust_zero, |sc| {
+                sc.init();
+                sc.user_energy(&managed_address!(&first_user))
+                    .set(&Energy::new(
+                        BigInt::from(managed_biguint!(FIRST_USER_ENERGY)),
+                        0,
+                        managed_biguint!(0),
+                    ));
+                sc.user_energy(&managed_address!(&second_user))
+                    .set(&Energy::new(
+                        BigInt::from(managed_biguint!(SECOND_USER_ENERGY)),
+                        0,
+                        managed_biguint!(0),
+                    ));
+            })
+            .assert_ok();
+
+        let gauge_controller_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner),
+            gauge_controller_builder,
+            "gauge controller path",
+        );
+        b_mock
+            .execute_tx(&owner, &gauge_controller_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_address!(energy_factory_wrapper.address_ref()),
+                    managed_biguint!(EMISSION_PER_BLOCK),
+                );
+            })
+            .assert_ok();
+
+        let first_farm_wrapper = Self::setup_farm(
+            &mut b_mock,
+            &owner,
+            gauge_controller_wrapper.address_ref(),
+            farm_builder,
+            FIRST_FARMING_TOKEN_ID,
+        );
+        let second_farm_wrapper = Self::setup_farm(
+            &mut b_mock,
+            &owner,
+            gauge_controller_wrapper.address_ref(),
+            farm_builder,
+            SECOND_FARMING_TOKEN_ID,
+        );
+
+        GaugeControllerSetup {
+            b_mock,
+            owner,
+            first_user,
+            second_user,
+            briber,
+            gauge_controller_wrapper,
+            first_farm_wrapper,
+            second_farm_wrapper,
+        }
+    }
+
+    fn setup_farm(
+        b_mock: &mut BlockchainStateWrapper,
+        owner: &Address,
+        gauge_controller_address: &Address,
+        farm_builder: FarmObjBuilder,
+        farming_token_id: &[u8],
+    ) -> ContractObjWrapper<farm::ContractObj<DebugApi>, FarmObjBuilder> {
+        let rust_zero = rust_biguint!(0u64);
+        let farm_wrapper =
+            b_mock.create_sc_account(&rust_zero, Some(owner), farm_builder, "farm.wasm");
+        b_mock
+            .execute_tx(owner, &farm_wrapper, &rust_zero, |sc| {
+                let mut admins = MultiValueEncoded::new();
+                admins.push(managed_address!(gauge_controller_address));
+
+                sc.init(
+                    managed_token_id!(REWARD_TOKEN_ID),
+                    managed_token_id!(farming_token_id),
+                    managed_biguint!(DIV_SAFETY),
+                    managed_address!(&Address::zero()),
+                    managed_address!(owner),
+                    admins,
+                );
+
+                sc.farm_token()
+                    .set_token_id(managed_token_id!(FARM_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            REWARD_TOKEN_ID,
+            &[EsdtLocalRole::Mint],
+        );
+
+        farm_wrapper
+    }
+
+    pub fn add_gauges(&mut self) {
+        let first_farm = self.first_farm_wrapper.address_ref().clone();
+        let second_farm = self.second_farm_wrapper.address_ref().clone();
+        self.b_mock
+            .execute_tx(
+                &self.owner,
+                &self.gauge_controller_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    let mut farms = MultiValueEncoded::new();
+                    farms.push(managed_address!(&first_farm));
+                    farms.push(managed_address!(&second_farm));
+                    sc.add_gauges(farms);
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn vote(&mut self, user: &Address, votes: &[(&Address, u64)]) {
+        self.b_mock
+            .execute_tx(
+                user,
+                &self.gauge_controller_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    let mut args = MultiValueEncoded::new();
+                    for (gauge, weight) in votes {
+                        args.push((managed_address!(*gauge), *weight).into());
+                    }
+
+                    sc.vote(args);
+                },
+            )
+            .assert_ok();
+    }
+}
+
+pub fn check_farm_rewards<FarmObjBuilder>(
+    b_mock: &mut BlockchainStateWrapper,
+    farm_wrapper: &ContractObjWrapper<farm::ContractObj<DebugApi>, FarmObjBuilder>,
+    expected_per_block_amount: u64,
+    expected_produce_rewards_enabled: bool,
+) where
+    FarmObjBuilder: 'static + Copy + Fn() -> farm::ContractObj<DebugApi>,
+{
+    b_mock
+        .execute_query(farm_wrapper, |sc| {
+            assert_eq!(
+                sc.per_block_reward_amount().get(),
+                managed_biguint!(expected_per_block_amount)
+            );
+            assert_eq!(
+                sc.produce_rewards_enabled().get(),
+                expected_produce_rewards_enabled
+            );
+        })
+        .assert_ok();
+}
@@ -0,0 +1,623 @@
+#![allow(deprecated)]
+
+mod gauge_controller_setup;
+use farm::Farm;
+use gauge_controller::{
+    bribes::BribesModule, emissions::EmissionsModule, gauges::GaugesModule, voting::VotingModule,
+};
+use gauge_controller_setup::*;
+use multiversx_sc::types::MultiValueEncoded;
+use multiversx_sc_scenario::{managed_address, managed_biguint, rust_biguint};
+
+const MAX_CLAIM_WEEKS: usize = 10;
+
+#[test]
+fn test_gauge_controller_setup() {
+    let _ = GaugeControllerSetup::new(gauge_controller::contract_obj, farm::contract_obj);
+}
+
+#[test]
+fn test_vote_and_apply_gauge_weights() {
+    let mut setup = GaugeControllerSetup::new(gauge_controller::contract_obj, farm::contract_obj);
+    let owner = setup.owner.clone();
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let first_farm = setup.first_farm_wrapper.address_ref().clone();
+    let second_farm = setup.second_farm_wrapper.address_ref().clone();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &owner,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut farms = MultiValueEncoded::new();
+                farms.push(managed_address!(&first_farm));
+                farms.push(managed_address!(&second_farm));
+                sc.add_gauges(farms);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut votes = MultiValueEncoded::new();
+                votes.push((managed_address!(&owner), 10_000).into());
+                sc.vote(votes);
+            },
+        )
+        .assert_user_error("Unknown gauge");
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut votes = MultiValueEncoded::new();
+                votes.push((managed_address!(&first_farm), 6_000).into());
+                votes.push((managed_address!(&second_farm), 5_000).into());
+                sc.vote(votes);
+            },
+        )
+        .assert_user_error("Invalid vote weights");
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut votes = MultiValueEncoded::new();
+                votes.push((managed_address!(&first_farm), 5_000).into());
+                votes.push((managed_address!(&first_farm), 5_000).into());
+                sc.vote(votes);
+            },
+        )
+        .assert_user_error("Duplicate gauge");
+
+    setup.vote(&first_user, &[(&first_farm, 10_000)]);
+    setup.vote(&second_user, &[(&first_farm, 5_000), (&second_farm, 5_000)]);
+    // the new vote replaces the previous one
+    setup.vote(&second_user, &[(&second_farm, 10_000)]);
+
+    setup
+        .b_mock
+        .execute_query(&setup.gauge_controller_wrapper, |sc| {
+            assert_eq!(
+                sc.gauge_votes(1, &managed_address!(&first_farm)).get(),
+                managed_biguint!(FIRST_USER_ENERGY)
+            );
+            assert_eq!(
+                sc.gauge_votes(1, &managed_address!(&second_farm)).get(),
+                managed_biguint!(SECOND_USER_ENERGY)
+            );
+        })
+        .assert_ok();
+
+    // the votes can only be applied once the week ends
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.apply_gauge_weights();
+            },
+        )
+        .assert_user_error("Gauge weights already applied for this week");
+
+    setup.b_mock.set_block_epoch(7);
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.apply_gauge_weights();
+            },
+        )
+        .assert_ok();
+
+    check_farm_rewards(
+        &mut setup.b_mock,
+        &setup.first_farm_wrapper,
+        EMISSION_PER_BLOCK / 4,
+        true,
+    );
+    check_farm_rewards(
+        &mut setup.b_mock,
+        &setup.second_farm_wrapper,
+        EMISSION_PER_BLOCK * 3 / 4,
+        true,
+    );
+
+    setup
+        .b_mock
+        .execute_tx(
+            &second_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.apply_gauge_weights();
+            },
+        )
+        .assert_user_error("Gauge weights already applied for this week");
+
+    // only the first user votes in the second week, for the second farm
+    setup.vote(&first_user, &[(&second_farm, 10_000)]);
+
+    setup.b_mock.set_block_epoch(14);
+    setup
+        .b_mock
+        .execute_tx(
+            &second_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.apply_gauge_weights();
+            },
+        )
+        .assert_ok();
+
+    // the first farm keeps its last amount, but stops producing rewards
+    check_farm_rewards(
+        &mut setup.b_mock,
+        &setup.first_farm_wrapper,
+        EMISSION_PER_BLOCK / 4,
+        false,
+    );
+    check_farm_rewards(
+        &mut setup.b_mock,
+        &setup.second_farm_wrapper,
+        EMISSION_PER_BLOCK,
+        true,
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.gauge_controller_wrapper, |sc| {
+            assert_eq!(sc.last_applied_week().get(), 3);
+            assert_eq!(
+                sc.gauge_per_block_amount(&managed_address!(&first_farm))
+                    .get(),
+                managed_biguint!(0)
+            );
+            assert_eq!(
+                sc.gauge_per_block_amount(&managed_address!(&second_farm))
+                    .get(),
+                managed_biguint!(EMISSION_PER_BLOCK)
+            );
+        })
+        .assert_ok();
+
+    // nobody voted in the third week, so the farms keep their amounts
+    setup.b_mock.set_block_epoch(21);
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.apply_gauge_weights();
+            },
+        )
+        .assert_ok();
+
+    check_farm_rewards(
+        &mut setup.b_mock,
+        &setup.second_farm_wrapper,
+        EMISSION_PER_BLOCK,
+        true,
+    );
+    setup
+        .b_mock
+        .execute_query(&setup.gauge_controller_wrapper, |sc| {
+            assert_eq!(sc.last_applied_week().get(), 4);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_apply_gauge_weights_skips_emission_schedules() {
+    let mut setup = GaugeControllerSetup::new(gauge_controller::contract_obj, farm::contract_obj);
+    let owner = setup.owner.clone();
+    let first_user = setup.first_user.clone();
+    let first_farm = setup.first_farm_wrapper.address_ref().clone();
+    let second_farm = setup.second_farm_wrapper.address_ref().clone();
+    setup.add_gauges();
+
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.first_farm_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_decaying_emission_schedule_endpoint(100, managed_biguint!(1_000), 100, 5_000, 2);
+        })
+        .assert_ok();
+
+    setup.vote(&first_user, &[(&first_farm, 5_000), (&second_farm, 5_000)]);
+
+    setup.b_mock.set_block_epoch(7);
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.apply_gauge_weights();
+            },
+        )
+        .assert_ok();
+
+    // the first farm follows its schedule
+    check_farm_rewards(&mut setup.b_mock, &setup.first_farm_wrapper, 0, false);
+    check_farm_rewards(
+        &mut setup.b_mock,
+        &setup.second_farm_wrapper,
+        EMISSION_PER_BLOCK / 2,
+        true,
+    );
+
+    // the schedule ended, even if its entries were not removed yet
+    setup.vote(&first_user, &[(&first_farm, 5_000), (&second_farm, 5_000)]);
+
+    setup.b_mock.set_block_nonce(400);
+    setup.b_mock.set_block_epoch(14);
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.apply_gauge_weights();
+            },
+        )
+        .assert_ok();
+
+    check_farm_rewards(
+        &mut setup.b_mock,
+        &setup.first_farm_wrapper,
+        EMISSION_PER_BLOCK / 2,
+        true,
+    );
+}
+
+#[test]
+fn test_deposit_and_claim_bribes() {
+    let mut setup = GaugeControllerSetup::new(gauge_controller::contract_obj, farm::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let briber = setup.briber.clone();
+    let first_farm = setup.first_farm_wrapper.address_ref().clone();
+    let second_farm = setup.second_farm_wrapper.address_ref().clone();
+    setup.add_gauges();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &briber,
+            &setup.gauge_controller_wrapper,
+            BRIBE_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                sc.deposit_bribe(managed_address!(&briber), 1);
+            },
+        )
+        .assert_user_error("Unknown gauge");
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &briber,
+            &setup.gauge_controller_wrapper,
+            BRIBE_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                sc.deposit_bribe(managed_address!(&first_farm), 1);
+            },
+        )
+        .assert_ok();
+
+    // the first farm gets 1_000 + 1_500 energy
+    setup.vote(&first_user, &[(&first_farm, 10_000)]);
+    setup.vote(&second_user, &[(&first_farm, 5_000), (&second_farm, 5_000)]);
+
+    // the bribes of the current week can't be claimed yet
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let rewards = sc.claim_bribes(managed_address!(&first_farm), MAX_CLAIM_WEEKS);
+                assert!(rewards.is_empty());
+            },
+        )
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(7);
+
+    // bribes can't be deposited for past weeks
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &briber,
+            &setup.gauge_controller_wrapper,
+            BRIBE_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                sc.deposit_bribe(managed_address!(&first_farm), 1);
+            },
+        )
+        .assert_user_error("Invalid week");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let rewards = sc.claim_bribes(managed_address!(&first_farm), MAX_CLAIM_WEEKS);
+                assert_eq!(rewards.len(), 1);
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_tx(
+            &second_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let rewards = sc.claim_bribes(managed_address!(&first_farm), MAX_CLAIM_WEEKS);
+                assert_eq!(rewards.len(), 1);
+            },
+        )
+        .assert_ok();
+
+    // there were no bribes for the second farm
+    setup
+        .b_mock
+        .execute_tx(
+            &second_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let rewards = sc.claim_bribes(managed_address!(&second_farm), MAX_CLAIM_WEEKS);
+                assert!(rewards.is_empty());
+            },
+        )
+        .assert_ok();
+
+    // claiming again gives nothing
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let rewards = sc.claim_bribes(managed_address!(&first_farm), MAX_CLAIM_WEEKS);
+                assert!(rewards.is_empty());
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, BRIBE_TOKEN_ID, &rust_biguint!(400));
+    setup
+        .b_mock
+        .check_esdt_balance(&second_user, BRIBE_TOKEN_ID, &rust_biguint!(600));
+    setup.b_mock.check_esdt_balance(
+        &briber,
+        BRIBE_TOKEN_ID,
+        &rust_biguint!(BRIBER_BALANCE - 1_000),
+    );
+}
+
+#[test]
+fn test_claim_bribes_in_parts() {
+    let mut setup = GaugeControllerSetup::new(gauge_controller::contract_obj, farm::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let briber = setup.briber.clone();
+    let first_farm = setup.first_farm_wrapper.address_ref().clone();
+    setup.add_gauges();
+
+    for week in [1, 2] {
+        setup
+            .b_mock
+            .execute_esdt_transfer(
+                &briber,
+                &setup.gauge_controller_wrapper,
+                BRIBE_TOKEN_ID,
+                0,
+                &rust_biguint!(1_001),
+                |sc| {
+                    sc.deposit_bribe(managed_address!(&first_farm), week);
+                },
+            )
+            .assert_ok();
+    }
+
+    // the first farm gets 1_000 + 1_500 energy in both weeks
+    setup.vote(&first_user, &[(&first_farm, 10_000)]);
+    setup.vote(&second_user, &[(&first_farm, 5_000)]);
+    setup.b_mock.set_block_epoch(7);
+    setup.vote(&first_user, &[(&first_farm, 10_000)]);
+    setup.vote(&second_user, &[(&first_farm, 5_000)]);
+    setup.b_mock.set_block_epoch(14);
+
+    // only one week is claimed
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let rewards = sc.claim_bribes(managed_address!(&first_farm), 1);
+                assert_eq!(rewards.len(), 1);
+                assert_eq!(rewards.get(0).amount, managed_biguint!(400));
+            },
+        )
+        .assert_ok();
+
+    // 600 for each week, plus the leftover of the week the first user claimed
+    setup
+        .b_mock
+        .execute_tx(
+            &second_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let rewards = sc.claim_bribes(managed_address!(&first_farm), MAX_CLAIM_WEEKS);
+                assert_eq!(rewards.len(), 1);
+                assert_eq!(rewards.get(0).amount, managed_biguint!(1_201));
+            },
+        )
+        .assert_ok();
+
+    // the first user claims the other week, with its leftover
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let rewards = sc.claim_bribes(managed_address!(&first_farm), MAX_CLAIM_WEEKS);
+                assert_eq!(rewards.len(), 1);
+                assert_eq!(rewards.get(0).amount, managed_biguint!(401));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, BRIBE_TOKEN_ID, &rust_biguint!(801));
+    setup
+        .b_mock
+        .check_esdt_balance(&second_user, BRIBE_TOKEN_ID, &rust_biguint!(1_201));
+    setup.b_mock.check_esdt_balance(
+        &briber,
+        BRIBE_TOKEN_ID,
+        &rust_biguint!(BRIBER_BALANCE - 2_002),
+    );
+}
+
+#[test]
+fn test_claim_old_bribes_and_refund() {
+    let mut setup = GaugeControllerSetup::new(gauge_controller::contract_obj, farm::contract_obj);
+    let first_user = setup.first_user.clone();
+    let briber = setup.briber.clone();
+    let first_farm = setup.first_farm_wrapper.address_ref().clone();
+    let second_farm = setup.second_farm_wrapper.address_ref().clone();
+    setup.add_gauges();
+
+    for (farm, amount) in [(&first_farm, 1_000u64), (&second_farm, 500u64)] {
+        setup
+            .b_mock
+            .execute_esdt_transfer(
+                &briber,
+                &setup.gauge_controller_wrapper,
+                BRIBE_TOKEN_ID,
+                0,
+                &rust_biguint!(amount),
+                |sc| {
+                    sc.deposit_bribe(managed_address!(farm), 1);
+                },
+            )
+            .assert_ok();
+    }
+
+    // nobody votes for the second farm
+    setup.vote(&first_user, &[(&first_farm, 10_000)]);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &briber,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.refund_bribes(managed_address!(&second_farm), 1);
+            },
+        )
+        .assert_user_error("Invalid week");
+
+    // bribes can still be claimed after more than 4 weeks
+    setup.b_mock.set_block_epoch(42);
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let rewards = sc.claim_bribes(managed_address!(&first_farm), MAX_CLAIM_WEEKS);
+                assert_eq!(rewards.len(), 1);
+                assert_eq!(rewards.get(0).amount, managed_biguint!(1_000));
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &briber,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.refund_bribes(managed_address!(&first_farm), 1);
+            },
+        )
+        .assert_user_error("The gauge had votes that week");
+    setup
+        .b_mock
+        .execute_tx(
+            &briber,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let refunds = sc.refund_bribes(managed_address!(&second_farm), 1);
+                assert_eq!(refunds.len(), 1);
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_tx(
+            &briber,
+            &setup.gauge_controller_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.refund_bribes(managed_address!(&second_farm), 1);
+            },
+        )
+        .assert_user_error("Nothing to refund");
+
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, BRIBE_TOKEN_ID, &rust_biguint!(1_000));
+    setup.b_mock.check_esdt_balance(
+        &briber,
+        BRIBE_TOKEN_ID,
+        &rust_biguint!(BRIBER_BALANCE - 1_000),
+    );
+}
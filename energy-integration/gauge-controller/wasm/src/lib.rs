@@ -0,0 +1,52 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Endpoints:                           24
+// Async Callback (empty):               1
+// Total number of exported functions:  26
+
+#![no_std]
+
+// Configuration that works with rustc < 1.73.0.
+// TODO: Recommended rustc version: 1.73.0 or newer.
+#![feature(lang_items)]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    gauge_controller
+    (
+        init => init
+        upgrade => upgrade
+        addGauges => add_gauges
+        removeGauges => remove_gauges
+        getGauges => gauges
+        vote => vote
+        getUserVotes => user_votes
+        getGaugeVotes => gauge_votes
+        getUserGaugeVotes => user_gauge_votes
+        getUserGaugeVoteWeeks => user_gauge_vote_weeks
+        setEmissionPerBlock => set_emission_per_block
+        applyGaugeWeights => apply_gauge_weights
+        getEmissionPerBlock => emission_per_block
+        getLastAppliedWeek => last_applied_week
+        getGaugePerBlockAmount => gauge_per_block_amount
+        depositBribe => deposit_bribe
+        claimBribes => claim_bribes
+        refundBribes => refund_bribes
+        getBribeTokens => bribe_tokens
+        getBribeAmount => bribe_amount
+        getBribeDeposit => bribe_deposit
+        setEnergyFactoryAddress => set_energy_factory_address
+        getEnergyFactoryAddress => energy_factory_address
+        getCurrentWeek => get_current_week
+        getFirstWeekStartEpoch => first_week_start_epoch
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
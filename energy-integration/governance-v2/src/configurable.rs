@@ -34,6 +34,8 @@ const MIN_VOTING_DELAY: u64 = 1;
 const MAX_VOTING_DELAY: u64 = 100_800; // 1 Week
 const MIN_VOTING_PERIOD: u64 = 14_400; // 24 Hours
 const MAX_VOTING_PERIOD: u64 = 201_600; // 2 Weeks
+const MIN_LOCK_TIME_AFTER_VOTING_ENDS: u64 = 1;
+const MAX_LOCK_TIME_AFTER_VOTING_ENDS: u64 = 201_600; // 2 Weeks
 const MIN_QUORUM: u64 = 1_000; // 10%
 const MAX_QUORUM: u64 = 6_000; // 60%
 const MIN_MIN_FEE_FOR_PROPOSE: u64 = 2_000_000;
@@ -85,6 +87,19 @@ pub trait ConfigurablePropertiesModule:
         self.try_change_voting_period_in_blocks(new_value);
     }
 
+    #[only_owner]
+    #[endpoint(changeLockTimeAfterVotingEndsInBlocks)]
+    fn change_lock_time_after_voting_ends_in_blocks(&self, new_value: u64) {
+        self.try_change_lock_time_after_voting_ends_in_blocks(new_value);
+    }
+
+    /// Delegators need at least this much energy, so delegate slots cannot be filled with dust.
+    #[only_owner]
+    #[endpoint(changeMinEnergyForDelegation)]
+    fn change_min_energy_for_delegation(&self, new_value: BigUint) {
+        self.min_energy_for_delegation().set(&new_value);
+    }
+
     fn try_change_min_energy_for_propose(&self, new_value: BigUint) {
         self.min_energy_for_propose().set(&new_value);
     }
@@ -129,6 +144,17 @@ pub trait ConfigurablePropertiesModule:
         self.voting_period_in_blocks().set(new_voting_period);
     }
 
+    fn try_change_lock_time_after_voting_ends_in_blocks(&self, new_lock_time: u64) {
+        require!(
+            (MIN_LOCK_TIME_AFTER_VOTING_ENDS..MAX_LOCK_TIME_AFTER_VOTING_ENDS)
+                .contains(&new_lock_time),
+            "Not valid value for lock time after voting ends!"
+        );
+
+        self.lock_time_after_voting_ends_in_blocks()
+            .set(new_lock_time);
+    }
+
     fn try_change_withdraw_percentage_defeated(&self, new_withdraw_percentage: u64) {
         require!(
             new_withdraw_percentage <= FULL_PERCENTAGE,
@@ -151,6 +177,10 @@ pub trait ConfigurablePropertiesModule:
     #[storage_mapper("minEnergyForPropose")]
     fn min_energy_for_propose(&self) -> SingleValueMapper<BigUint>;
 
+    #[view(getMinEnergyForDelegation)]
+    #[storage_mapper("minEnergyForDelegation")]
+    fn min_energy_for_delegation(&self) -> SingleValueMapper<BigUint>;
+
     #[view(getMinFeeForPropose)]
     #[storage_mapper("minFeeForPropose")]
     fn min_fee_for_propose(&self) -> SingleValueMapper<BigUint>;
@@ -167,6 +197,10 @@ pub trait ConfigurablePropertiesModule:
     #[storage_mapper("votingPeriodInBlocks")]
     fn voting_period_in_blocks(&self) -> SingleValueMapper<u64>;
 
+    #[view(getLockTimeAfterVotingEndsInBlocks)]
+    #[storage_mapper("lockTimeAfterVotingEndsInBlocks")]
+    fn lock_time_after_voting_ends_in_blocks(&self) -> SingleValueMapper<u64>;
+
     #[view(getFeeTokenId)]
     #[storage_mapper("feeTokenId")]
     fn fee_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
@@ -0,0 +1,195 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::*;
+use crate::proposal::ProposalId;
+use crate::proposal_storage::VoteType;
+
+// each eligible delegator costs an energy factory call when the delegate votes
+pub const MAX_DELEGATORS_PER_DELEGATE: usize = 10;
+
+#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Debug)]
+pub struct UserDelegation<M: ManagedTypeApi> {
+    pub delegate: ManagedAddress<M>,
+    pub delegation_block: u64,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Debug)]
+pub struct DelegatedVote<M: ManagedTypeApi> {
+    pub delegate: ManagedAddress<M>,
+    pub vote_type: VoteType,
+    pub voting_power: BigUint<M>,
+    pub user_quorum: BigUint<M>,
+}
+
+/// Delegation is not transitive: a delegate only votes with the energy of its direct delegators.
+/// Only delegations made before a proposal was created count towards that proposal,
+/// and a delegator voting directly overrides the vote cast on their behalf.
+#[multiversx_sc::module]
+pub trait DelegationModule:
+    crate::configurable::ConfigurablePropertiesModule
+    + crate::proposal_storage::ProposalStorageModule
+    + crate::events::EventsModule
+    + crate::views::ViewsModule
+    + energy_query::EnergyQueryModule
+    + permissions_module::PermissionsModule
+{
+    /// Assign the caller's energy-derived voting power to `delegate`.
+    /// Replaces any previous delegation of the caller.
+    /// The caller needs at least `minEnergyForDelegation` energy.
+    #[endpoint]
+    fn delegate(&self, delegate: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(caller != delegate, CANNOT_DELEGATE_TO_SELF);
+        require!(
+            !self.blockchain().is_smart_contract(&delegate),
+            DELEGATE_NOT_ALLOWED_FOR_SC
+        );
+
+        let energy = self.get_energy_amount(&caller);
+        require!(
+            energy > 0 && energy >= self.min_energy_for_delegation().get(),
+            NOT_ENOUGH_ENERGY_FOR_DELEGATION
+        );
+
+        self.remove_delegation(&caller);
+
+        let mut delegators_mapper = self.delegators(&delegate);
+        require!(
+            delegators_mapper.len() < MAX_DELEGATORS_PER_DELEGATE,
+            TOO_MANY_DELEGATORS
+        );
+        let _ = delegators_mapper.insert(caller.clone());
+
+        let current_block = self.blockchain().get_block_nonce();
+        self.user_delegation(&caller).set(&UserDelegation {
+            delegate: delegate.clone(),
+            delegation_block: current_block,
+        });
+
+        self.delegate_event(&caller, &delegate, current_block);
+    }
+
+    /// Remove the caller's delegation.
+    /// Votes already cast on the caller's behalf remain, unless the caller votes directly.
+    #[endpoint]
+    fn undelegate(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(!self.user_delegation(&caller).is_empty(), NO_DELEGATION);
+
+        self.remove_delegation(&caller);
+    }
+
+    /// Lets a delegate drop delegators it does not want to vote for,
+    /// freeing their slots for other users.
+    #[endpoint(removeDelegators)]
+    fn remove_delegators(&self, delegators: MultiValueEncoded<ManagedAddress>) {
+        let caller = self.blockchain().get_caller();
+        let delegators_mapper = self.delegators(&caller);
+        for delegator in delegators {
+            require!(delegators_mapper.contains(&delegator), NOT_A_DELEGATOR);
+
+            self.remove_delegation(&delegator);
+        }
+    }
+
+    fn remove_delegation(&self, delegator: &ManagedAddress) {
+        let delegation_mapper = self.user_delegation(delegator);
+        if delegation_mapper.is_empty() {
+            return;
+        }
+
+        let delegation = delegation_mapper.take();
+        let _ = self.delegators(&delegation.delegate).swap_remove(delegator);
+
+        self.undelegate_event(delegator, &delegation.delegate);
+    }
+
+    /// Adds the voting power of each eligible delegator of `delegate` to the proposal.
+    /// Delegators that already voted, or delegated after the proposal was created, are skipped.
+    /// Returns the total quorum of the votes cast.
+    fn cast_delegated_votes(
+        &self,
+        proposal_id: ProposalId,
+        proposal_start_block: u64,
+        delegate: &ManagedAddress,
+        vote_type: &VoteType,
+    ) -> BigUint {
+        let mut total_quorum = BigUint::zero();
+        for delegator in self.delegators(delegate).iter() {
+            if self.user_voted_proposals(&delegator).contains(&proposal_id) {
+                continue;
+            }
+
+            let delegation = self.user_delegation(&delegator).get();
+            if delegation.delegation_block >= proposal_start_block {
+                continue;
+            }
+
+            let user_quorum = self.get_voting_energy(proposal_id, &delegator);
+            if user_quorum == 0 {
+                continue;
+            }
+
+            total_quorum += &user_quorum;
+            let voting_power = self.smoothing_function(&user_quorum);
+            self.proposal_votes(proposal_id).update(|proposal_votes| {
+                proposal_votes.add_vote(vote_type, &voting_power, &user_quorum)
+            });
+            self.delegated_vote_cast_event(
+                delegate,
+                &delegator,
+                proposal_id,
+                &voting_power,
+                &user_quorum,
+            );
+
+            self.delegated_vote(proposal_id, &delegator)
+                .set(&DelegatedVote {
+                    delegate: delegate.clone(),
+                    vote_type: vote_type.clone(),
+                    voting_power,
+                    user_quorum,
+                });
+        }
+
+        total_quorum
+    }
+
+    /// Removes the vote a delegate cast on behalf of `delegator`, if any.
+    fn revoke_delegated_vote(&self, proposal_id: ProposalId, delegator: &ManagedAddress) {
+        let delegated_vote_mapper = self.delegated_vote(proposal_id, delegator);
+        if delegated_vote_mapper.is_empty() {
+            return;
+        }
+
+        let delegated_vote = delegated_vote_mapper.take();
+        self.proposal_votes(proposal_id).update(|proposal_votes| {
+            proposal_votes.remove_vote(
+                &delegated_vote.vote_type,
+                &delegated_vote.voting_power,
+                &delegated_vote.user_quorum,
+            )
+        });
+        self.delegated_vote_revoked_event(&delegated_vote.delegate, delegator, proposal_id);
+    }
+
+    #[view(getUserDelegation)]
+    #[storage_mapper("userDelegation")]
+    fn user_delegation(
+        &self,
+        delegator: &ManagedAddress,
+    ) -> SingleValueMapper<UserDelegation<Self::Api>>;
+
+    #[view(getDelegators)]
+    #[storage_mapper("delegators")]
+    fn delegators(&self, delegate: &ManagedAddress) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getDelegatedVote)]
+    #[storage_mapper("delegatedVote")]
+    fn delegated_vote(
+        &self,
+        proposal_id: ProposalId,
+        delegator: &ManagedAddress,
+    ) -> SingleValueMapper<DelegatedVote<Self::Api>>;
+}
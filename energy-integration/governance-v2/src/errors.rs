@@ -12,3 +12,15 @@ pub const FEE_ALREADY_WITHDRAWN: &[u8] = b"Fee already withdrawn!";
 pub const NO_PROPOSAL: &[u8] = b"Proposal does not exist";
 pub const WITHDRAW_NOT_ALLOWED: &[u8] = b"You may not withdraw funds from this proposal!";
 pub const PROPOSAL_NOT_ALLOWED_FOR_SC: &[u8] = b"Smart Contracts are not allowed to propose!";
+
+pub const PROPOSAL_NOT_SUCCEEDED: &[u8] = b"Only successful proposals can be queued";
+pub const PROPOSAL_NOT_QUEUED: &[u8] = b"Can only execute queued proposals";
+pub const PROPOSAL_IN_TIMELOCK: &[u8] = b"Proposal is in timelock status. Try again later";
+pub const NOT_ENOUGH_GAS_FOR_EXECUTION: &[u8] = b"Not enough gas to execute all proposal actions";
+pub const CANNOT_DELEGATE_TO_SELF: &[u8] = b"Cannot delegate to self";
+pub const DELEGATE_NOT_ALLOWED_FOR_SC: &[u8] = b"Cannot delegate to a Smart Contract";
+pub const TOO_MANY_DELEGATORS: &[u8] = b"Delegate has too many delegators";
+pub const NO_VOTING_ENERGY: &[u8] = b"No energy at proposal start";
+pub const NO_DELEGATION: &[u8] = b"No delegation for this user";
+pub const NOT_ENOUGH_ENERGY_FOR_DELEGATION: &[u8] = b"Not enough energy to delegate";
+pub const NOT_A_DELEGATOR: &[u8] = b"User is not a delegator of the caller";
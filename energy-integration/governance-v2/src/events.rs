@@ -50,9 +50,53 @@ pub trait EventsModule {
         #[indexed] user_quorum: &BigUint,
     );
 
+    #[event("delegatedVoteCast")]
+    fn delegated_vote_cast_event(
+        &self,
+        #[indexed] delegate: &ManagedAddress,
+        #[indexed] delegator: &ManagedAddress,
+        #[indexed] proposal_id: ProposalId,
+        #[indexed] voting_power: &BigUint,
+        #[indexed] user_quorum: &BigUint,
+    );
+
+    #[event("delegatedVoteRevoked")]
+    fn delegated_vote_revoked_event(
+        &self,
+        #[indexed] delegate: &ManagedAddress,
+        #[indexed] delegator: &ManagedAddress,
+        #[indexed] proposal_id: ProposalId,
+    );
+
+    #[event("delegate")]
+    fn delegate_event(
+        &self,
+        #[indexed] delegator: &ManagedAddress,
+        #[indexed] delegate: &ManagedAddress,
+        #[indexed] delegation_block: u64,
+    );
+
+    #[event("undelegate")]
+    fn undelegate_event(
+        &self,
+        #[indexed] delegator: &ManagedAddress,
+        #[indexed] delegate: &ManagedAddress,
+    );
+
     #[event("proposalCanceled")]
     fn proposal_canceled_event(&self, #[indexed] proposal_id: ProposalId);
 
+    #[event("proposalQueued")]
+    fn proposal_queued_event(
+        &self,
+        #[indexed] proposal_id: ProposalId,
+        #[indexed] queued_block: u64,
+        #[indexed] eta: u64,
+    );
+
+    #[event("proposalExecuted")]
+    fn proposal_executed_event(&self, #[indexed] proposal_id: ProposalId);
+
     #[event("proposalWithdrawAfterDefeated")]
     fn proposal_withdraw_after_defeated_event(&self, #[indexed] proposal_id: ProposalId);
 }
@@ -3,6 +3,7 @@
 multiversx_sc::imports!();
 
 pub mod configurable;
+pub mod delegation;
 mod errors;
 pub mod events;
 pub mod proposal;
@@ -25,6 +26,7 @@ pub trait GovernanceV2:
     + events::EventsModule
     + proposal_storage::ProposalStorageModule
     + views::ViewsModule
+    + delegation::DelegationModule
     + energy_query::EnergyQueryModule
     + permissions_module::PermissionsModule
 {
@@ -33,6 +35,7 @@ pub trait GovernanceV2:
     /// - `quorum_percentage` - the minimum number of (`votes` minus `downvotes`) at the end of voting period  
     /// - `votingDelayInBlocks` - Number of blocks to wait after a block is proposed before being able to vote/downvote that proposal
     /// - `votingPeriodInBlocks` - Number of blocks the voting period lasts (voting delay does not count towards this)  
+    /// - `lockTimeAfterVotingEndsInBlocks` - Number of blocks to wait before a queued proposal can be executed
     /// - `withdraw_percentage_defeated` - Percetange of the fee to be returned if proposal defetead
     /// - `energy_factory_address`
     /// - `fees_collector_address`
@@ -45,6 +48,7 @@ pub trait GovernanceV2:
         quorum_percentage: u64,
         voting_delay_in_blocks: u64,
         voting_period_in_blocks: u64,
+        lock_time_after_voting_ends_in_blocks: u64,
         withdraw_percentage_defeated: u64,
         energy_factory_address: ManagedAddress,
         fees_collector_address: ManagedAddress,
@@ -55,6 +59,9 @@ pub trait GovernanceV2:
         self.try_change_quorum_percentage(quorum_percentage);
         self.try_change_voting_delay_in_blocks(voting_delay_in_blocks);
         self.try_change_voting_period_in_blocks(voting_period_in_blocks);
+        self.try_change_lock_time_after_voting_ends_in_blocks(
+            lock_time_after_voting_ends_in_blocks,
+        );
         self.try_change_withdraw_percentage_defeated(withdraw_percentage_defeated);
         self.set_energy_factory_address(energy_factory_address);
         self.fees_collector_address().set(&fees_collector_address);
@@ -141,6 +148,8 @@ pub trait GovernanceV2:
             fee_withdrawn: false,
         };
         let proposal_id = self.proposals().push(&proposal);
+        self.proposal_start_epoch(proposal_id)
+            .set(self.blockchain().get_block_epoch());
 
         self.proposal_votes(proposal_id)
             .set(ProposalVotes::default());
@@ -149,7 +158,11 @@ pub trait GovernanceV2:
         proposal_id
     }
 
-    /// Vote on a proposal. The voting power depends on the user's energy.
+    /// Vote on a proposal. The voting power depends on the user's energy
+    /// at the start of the epoch the proposal was created in.
+    ///
+    /// The vote is also cast on behalf of the caller's delegators that have not voted yet.
+    /// A delegator voting directly replaces the vote cast on their behalf.
     #[endpoint]
     fn vote(&self, proposal_id: ProposalId, vote: VoteType) {
         self.require_valid_proposal_id(proposal_id);
@@ -182,39 +195,83 @@ pub trait GovernanceV2:
             self.proposals().set(proposal_id, &proposal);
         }
 
-        let user_quorum = self.get_energy_amount_non_zero(&voter);
+        // Voting directly overrides any vote cast on the user's behalf
+        self.revoke_delegated_vote(proposal_id, &voter);
+
+        let user_quorum = self.get_voting_energy(proposal_id, &voter);
         let voting_power = self.smoothing_function(&user_quorum);
 
+        self.proposal_votes(proposal_id)
+            .update(|proposal_votes| proposal_votes.add_vote(&vote, &voting_power, &user_quorum));
         match vote {
             VoteType::UpVote => {
-                self.proposal_votes(proposal_id).update(|proposal_votes| {
-                    proposal_votes.up_votes += &voting_power.clone();
-                    proposal_votes.quorum += &user_quorum.clone();
-                });
                 self.up_vote_cast_event(&voter, proposal_id, &voting_power, &user_quorum);
             }
             VoteType::DownVote => {
-                self.proposal_votes(proposal_id).update(|proposal_votes| {
-                    proposal_votes.down_votes += &voting_power.clone();
-                    proposal_votes.quorum += &user_quorum.clone();
-                });
                 self.down_vote_cast_event(&voter, proposal_id, &voting_power, &user_quorum);
             }
             VoteType::DownVetoVote => {
-                self.proposal_votes(proposal_id).update(|proposal_votes| {
-                    proposal_votes.down_veto_votes += &voting_power.clone();
-                    proposal_votes.quorum += &user_quorum.clone();
-                });
                 self.down_veto_vote_cast_event(&voter, proposal_id, &voting_power, &user_quorum);
             }
             VoteType::AbstainVote => {
-                self.proposal_votes(proposal_id).update(|proposal_votes| {
-                    proposal_votes.abstain_votes += &voting_power.clone();
-                    proposal_votes.quorum += &user_quorum.clone();
-                });
                 self.abstain_vote_cast_event(&voter, proposal_id, &voting_power, &user_quorum);
             }
         }
+
+        let proposal_start_block = self.proposals().get(proposal_id).proposal_start_block;
+        let delegated_quorum =
+            self.cast_delegated_votes(proposal_id, proposal_start_block, &voter, &vote);
+        require!(user_quorum > 0 || delegated_quorum > 0, NO_VOTING_ENERGY);
+    }
+
+    /// Queue a proposal for execution.
+    /// This can be done only after the voting period ended and the proposal Succeeded.
+    #[endpoint]
+    fn queue(&self, proposal_id: ProposalId) {
+        require!(
+            self.get_proposal_status(proposal_id) == GovernanceProposalStatus::Succeeded,
+            PROPOSAL_NOT_SUCCEEDED
+        );
+
+        let current_block = self.blockchain().get_block_nonce();
+        let lock_blocks = self.lock_time_after_voting_ends_in_blocks().get();
+        let eta = current_block + lock_blocks;
+        self.proposal_eta(proposal_id).set(eta);
+
+        self.proposal_queued_event(proposal_id, current_block, eta);
+    }
+
+    /// Execute a previously queued proposal.
+    /// Each action is dispatched with its own gas limit,
+    /// once the block set when the proposal was queued is reached.
+    /// Later changes of `lockTimeAfterVotingEndsInBlocks` do not affect queued proposals.
+    #[endpoint]
+    fn execute(&self, proposal_id: ProposalId) {
+        require!(
+            self.get_proposal_status(proposal_id) == GovernanceProposalStatus::Queued,
+            PROPOSAL_NOT_QUEUED
+        );
+
+        let current_block = self.blockchain().get_block_nonce();
+        let eta = self.proposal_eta(proposal_id).get();
+        require!(current_block >= eta, PROPOSAL_IN_TIMELOCK);
+
+        let proposal = self.proposals().get(proposal_id);
+        let total_gas_needed = self.total_gas_needed(&proposal.actions);
+        let gas_left = self.blockchain().get_gas_left();
+        require!(gas_left > total_gas_needed, NOT_ENOUGH_GAS_FOR_EXECUTION);
+
+        self.proposal_executed(proposal_id).set(true);
+
+        for action in proposal.actions {
+            self.send()
+                .contract_call::<()>(action.dest_address, action.function_name)
+                .with_raw_arguments(action.arguments.into())
+                .with_gas_limit(action.gas_limit)
+                .transfer_execute();
+        }
+
+        self.proposal_executed_event(proposal_id);
     }
 
     /// Cancel a proposed action. This can be done only during Pending status
@@ -249,7 +306,10 @@ pub trait GovernanceV2:
             GovernanceProposalStatus::None => {
                 sc_panic!(NO_PROPOSAL);
             }
-            GovernanceProposalStatus::Succeeded | GovernanceProposalStatus::Defeated => {
+            GovernanceProposalStatus::Succeeded
+            | GovernanceProposalStatus::Queued
+            | GovernanceProposalStatus::Executed
+            | GovernanceProposalStatus::Defeated => {
                 let mut proposal = self.proposals().get(proposal_id);
 
                 require!(caller == proposal.proposer, ONLY_PROPOSER_WITHDRAW);
@@ -18,6 +18,8 @@ pub enum GovernanceProposalStatus {
     Defeated,
     DefeatedWithVeto,
     Succeeded,
+    Queued,
+    Executed,
 }
 
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
@@ -3,7 +3,7 @@ multiversx_sc::derive_imports!();
 
 use crate::proposal::{GovernanceProposal, ProposalId};
 
-#[derive(TypeAbi, TopEncode, TopDecode)]
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
 pub enum VoteType {
     UpVote,
     DownVote,
@@ -39,6 +39,36 @@ impl<M: ManagedTypeApi> ProposalVotes<M> {
     pub fn get_total_votes(&self) -> BigUint<M> {
         &self.up_votes + &self.down_votes + &self.down_veto_votes + &self.abstain_votes
     }
+
+    pub fn add_vote(
+        &mut self,
+        vote_type: &VoteType,
+        voting_power: &BigUint<M>,
+        user_quorum: &BigUint<M>,
+    ) {
+        match vote_type {
+            VoteType::UpVote => self.up_votes += voting_power,
+            VoteType::DownVote => self.down_votes += voting_power,
+            VoteType::DownVetoVote => self.down_veto_votes += voting_power,
+            VoteType::AbstainVote => self.abstain_votes += voting_power,
+        }
+        self.quorum += user_quorum;
+    }
+
+    pub fn remove_vote(
+        &mut self,
+        vote_type: &VoteType,
+        voting_power: &BigUint<M>,
+        user_quorum: &BigUint<M>,
+    ) {
+        match vote_type {
+            VoteType::UpVote => self.up_votes -= voting_power,
+            VoteType::DownVote => self.down_votes -= voting_power,
+            VoteType::DownVetoVote => self.down_veto_votes -= voting_power,
+            VoteType::AbstainVote => self.abstain_votes -= voting_power,
+        }
+        self.quorum -= user_quorum;
+    }
 }
 
 #[multiversx_sc::module]
@@ -46,6 +76,9 @@ pub trait ProposalStorageModule {
     fn clear_proposal(&self, proposal_id: ProposalId) {
         self.proposals().clear_entry(proposal_id);
         self.proposal_votes(proposal_id).clear();
+        self.proposal_eta(proposal_id).clear();
+        self.proposal_executed(proposal_id).clear();
+        self.proposal_start_epoch(proposal_id).clear();
     }
 
     #[view(getProposals)]
@@ -62,4 +95,17 @@ pub trait ProposalStorageModule {
         &self,
         proposal_id: ProposalId,
     ) -> SingleValueMapper<ProposalVotes<Self::Api>>;
+
+    #[view(getProposalStartEpoch)]
+    #[storage_mapper("proposalStartEpoch")]
+    fn proposal_start_epoch(&self, proposal_id: ProposalId) -> SingleValueMapper<u64>;
+
+    /// The block from which a queued proposal can be executed.
+    #[view(getProposalEta)]
+    #[storage_mapper("proposalEta")]
+    fn proposal_eta(&self, proposal_id: ProposalId) -> SingleValueMapper<u64>;
+
+    #[view(isProposalExecuted)]
+    #[storage_mapper("proposalExecuted")]
+    fn proposal_executed(&self, proposal_id: ProposalId) -> SingleValueMapper<bool>;
 }
@@ -17,6 +17,12 @@ pub trait ViewsModule:
         if !self.proposal_exists(proposal_id) {
             return GovernanceProposalStatus::None;
         }
+        if self.proposal_executed(proposal_id).get() {
+            return GovernanceProposalStatus::Executed;
+        }
+        if !self.proposal_eta(proposal_id).is_empty() {
+            return GovernanceProposalStatus::Queued;
+        }
 
         let current_block = self.blockchain().get_block_nonce();
         let proposal = self.proposals().get(proposal_id);
@@ -79,6 +85,18 @@ pub trait ViewsModule:
         current_quorum * FULL_PERCENTAGE >= required_minimum_percentage * total_quorum_for_proposal
     }
 
+    /// Voting energy is taken at the start of the epoch the proposal was created in,
+    /// so locking tokens after a proposal is created does not increase voting power.
+    /// Proposals created before snapshots were introduced use the current energy.
+    fn get_voting_energy(&self, proposal_id: ProposalId, user: &ManagedAddress) -> BigUint {
+        let start_epoch_mapper = self.proposal_start_epoch(proposal_id);
+        if start_epoch_mapper.is_empty() {
+            return self.get_energy_amount(user);
+        }
+
+        self.get_energy_amount_at_epoch(user, start_epoch_mapper.get())
+    }
+
     fn require_valid_proposal_id(&self, proposal_id: ProposalId) {
         require!(
             self.is_valid_proposal_id(proposal_id),
@@ -4,11 +4,12 @@ mod gov_test_setup;
 
 use gov_test_setup::*;
 use governance_v2::{
-    configurable::ConfigurablePropertiesModule, proposal::GovernanceProposalStatus,
-    proposal_storage::ProposalStorageModule, views::ViewsModule,
+    configurable::ConfigurablePropertiesModule, delegation::DelegationModule,
+    proposal::GovernanceProposalStatus, proposal_storage::ProposalStorageModule,
+    views::ViewsModule,
 };
 use multiversx_sc::{types::ManagedVec};
-use multiversx_sc_scenario::{managed_buffer, rust_biguint};
+use multiversx_sc_scenario::{managed_address, managed_biguint, managed_buffer, rust_biguint};
 
 #[test]
 fn init_gov_test() {
@@ -71,7 +72,7 @@ fn gov_propose_test() {
         .b_mock
         .execute_query(&gov_setup.gov_wrapper, |sc| {
             let proposal = sc.proposals().get(1);
-            let action = proposal.actions.get(0).unwrap();
+            let action = proposal.actions.first().unwrap();
             let mut args_managed = ManagedVec::new();
             args_managed.push(managed_buffer!(&1_000u64.to_be_bytes()));
 
@@ -84,6 +85,273 @@ fn gov_propose_test() {
         .assert_ok();
 }
 
+#[test]
+fn gov_queue_and_execute_test() {
+    let mut gov_setup = GovSetup::new(governance_v2::contract_obj);
+
+    let first_user_addr = gov_setup.first_user.clone();
+    let second_user_addr = gov_setup.second_user.clone();
+    let owner_addr = gov_setup.owner.clone();
+    let min_fee = rust_biguint!(MIN_FEE_FOR_PROPOSE) * DECIMALS_CONST;
+    // Give proposer the minimum fee
+    gov_setup
+        .b_mock
+        .set_esdt_balance(&first_user_addr, MEX_TOKEN_ID, &min_fee);
+
+    let (result, proposal_id) = gov_setup.propose(
+        &first_user_addr,
+        &min_fee,
+        &owner_addr,
+        b"changeTODO",
+        vec![1_000u64.to_be_bytes().to_vec()],
+    );
+    result.assert_ok();
+    assert_eq!(proposal_id, 1);
+
+    gov_setup.increment_block_nonce(VOTING_DELAY_BLOCKS);
+
+    gov_setup.up_vote(&first_user_addr, proposal_id).assert_ok();
+    gov_setup
+        .up_vote(&second_user_addr, proposal_id)
+        .assert_ok();
+
+    // queue before the voting period ends
+    gov_setup
+        .queue(&first_user_addr, proposal_id)
+        .assert_user_error("Only successful proposals can be queued");
+
+    gov_setup.increment_block_nonce(VOTING_PERIOD_BLOCKS);
+
+    // execute before queue
+    gov_setup
+        .execute(&first_user_addr, proposal_id)
+        .assert_user_error("Can only execute queued proposals");
+
+    gov_setup.queue(&second_user_addr, proposal_id).assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert!(
+                sc.get_proposal_status(1) == GovernanceProposalStatus::Queued,
+                "Action should have been Queued"
+            );
+        })
+        .assert_ok();
+
+    // execute during the timelock
+    gov_setup
+        .execute(&first_user_addr, proposal_id)
+        .assert_user_error("Proposal is in timelock status. Try again later");
+
+    // a longer lock time only applies to proposals queued afterwards
+    gov_setup
+        .change_lock_time(LOCKING_PERIOD_BLOCKS * 2)
+        .assert_ok();
+
+    gov_setup.increment_block_nonce(LOCKING_PERIOD_BLOCKS);
+
+    gov_setup.execute(&first_user_addr, proposal_id).assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert!(
+                sc.get_proposal_status(1) == GovernanceProposalStatus::Executed,
+                "Action should have been Executed"
+            );
+        })
+        .assert_ok();
+
+    // executed proposals cannot be executed again
+    gov_setup
+        .execute(&first_user_addr, proposal_id)
+        .assert_user_error("Can only execute queued proposals");
+
+    // proposer can still recover the fee
+    gov_setup
+        .withdraw_after_defeated(&first_user_addr, proposal_id)
+        .assert_ok();
+
+    gov_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, MEX_TOKEN_ID, &min_fee);
+}
+
+#[test]
+fn gov_delegated_vote_test() {
+    let mut gov_setup = GovSetup::new(governance_v2::contract_obj);
+
+    let first_user_addr = gov_setup.first_user.clone();
+    let second_user_addr = gov_setup.second_user.clone();
+    let third_user_addr = gov_setup.third_user.clone();
+    let sc_addr = gov_setup.gov_wrapper.address_ref().clone();
+    let min_fee = rust_biguint!(MIN_FEE_FOR_PROPOSE) * DECIMALS_CONST;
+    // Give proposer the minimum fee
+    gov_setup
+        .b_mock
+        .set_esdt_balance(&first_user_addr, MEX_TOKEN_ID, &min_fee);
+
+    gov_setup
+        .delegate(&first_user_addr, &first_user_addr)
+        .assert_user_error("Cannot delegate to self");
+    gov_setup
+        .undelegate(&first_user_addr)
+        .assert_user_error("No delegation for this user");
+
+    gov_setup
+        .delegate(&first_user_addr, &second_user_addr)
+        .assert_ok();
+
+    gov_setup.increment_block_nonce(1);
+
+    let (result, proposal_id) = gov_setup.propose(
+        &first_user_addr,
+        &min_fee,
+        &sc_addr,
+        b"changeTODO",
+        vec![1_000u64.to_be_bytes().to_vec()],
+    );
+    result.assert_ok();
+
+    // delegations made after the proposal was created are not counted
+    gov_setup
+        .delegate(&third_user_addr, &second_user_addr)
+        .assert_ok();
+
+    gov_setup.increment_block_nonce(VOTING_DELAY_BLOCKS);
+
+    // second user votes with their own energy and the energy of the first user
+    gov_setup
+        .up_vote(&second_user_addr, proposal_id)
+        .assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            let proposal_votes = sc.proposal_votes(proposal_id).get();
+            assert_eq!(proposal_votes.up_votes, managed_biguint!(2_000));
+            assert_eq!(proposal_votes.quorum, managed_biguint!(USER_ENERGY * 2));
+            assert!(sc
+                .delegated_vote(proposal_id, &managed_address!(&third_user_addr))
+                .is_empty());
+        })
+        .assert_ok();
+
+    // first user overrides the delegated vote
+    gov_setup
+        .down_vote(&first_user_addr, proposal_id)
+        .assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            let proposal_votes = sc.proposal_votes(proposal_id).get();
+            assert_eq!(proposal_votes.up_votes, managed_biguint!(1_000));
+            assert_eq!(proposal_votes.down_votes, managed_biguint!(1_000));
+            assert_eq!(proposal_votes.quorum, managed_biguint!(USER_ENERGY * 2));
+            assert!(sc
+                .delegated_vote(proposal_id, &managed_address!(&first_user_addr))
+                .is_empty());
+        })
+        .assert_ok();
+
+    gov_setup.undelegate(&first_user_addr).assert_ok();
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert!(sc
+                .user_delegation(&managed_address!(&first_user_addr))
+                .is_empty());
+            assert_eq!(sc.delegators(&managed_address!(&second_user_addr)).len(), 1);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn gov_delegate_without_eligible_delegators_test() {
+    let mut gov_setup = GovSetup::new(governance_v2::contract_obj);
+
+    let first_user_addr = gov_setup.first_user.clone();
+    let third_user_addr = gov_setup.third_user.clone();
+    let no_energy_user = gov_setup.no_energy_user.clone();
+    let sc_addr = gov_setup.gov_wrapper.address_ref().clone();
+    let min_fee = rust_biguint!(MIN_FEE_FOR_PROPOSE) * DECIMALS_CONST;
+    gov_setup
+        .b_mock
+        .set_esdt_balance(&first_user_addr, MEX_TOKEN_ID, &min_fee);
+
+    let (result, proposal_id) = gov_setup.propose(
+        &first_user_addr,
+        &min_fee,
+        &sc_addr,
+        b"changeTODO",
+        vec![1_000u64.to_be_bytes().to_vec()],
+    );
+    result.assert_ok();
+
+    // the only delegation was made after the proposal was created
+    gov_setup
+        .delegate(&third_user_addr, &no_energy_user)
+        .assert_ok();
+
+    gov_setup.increment_block_nonce(VOTING_DELAY_BLOCKS);
+
+    gov_setup
+        .up_vote(&no_energy_user, proposal_id)
+        .assert_user_error("No energy at proposal start");
+}
+
+#[test]
+fn gov_delegation_limits_test() {
+    let mut gov_setup = GovSetup::new(governance_v2::contract_obj);
+
+    let first_user_addr = gov_setup.first_user.clone();
+    let second_user_addr = gov_setup.second_user.clone();
+    let third_user_addr = gov_setup.third_user.clone();
+    let no_energy_user = gov_setup.no_energy_user.clone();
+
+    gov_setup
+        .delegate(&no_energy_user, &second_user_addr)
+        .assert_user_error("Not enough energy to delegate");
+
+    gov_setup
+        .change_min_energy_for_delegation(USER_ENERGY + 1)
+        .assert_ok();
+    gov_setup
+        .delegate(&first_user_addr, &second_user_addr)
+        .assert_user_error("Not enough energy to delegate");
+
+    gov_setup
+        .change_min_energy_for_delegation(USER_ENERGY)
+        .assert_ok();
+    gov_setup
+        .delegate(&first_user_addr, &second_user_addr)
+        .assert_ok();
+
+    // only the delegate's own delegators can be removed
+    gov_setup
+        .remove_delegators(&second_user_addr, &[&third_user_addr])
+        .assert_user_error("User is not a delegator of the caller");
+    gov_setup
+        .remove_delegators(&third_user_addr, &[&first_user_addr])
+        .assert_user_error("User is not a delegator of the caller");
+
+    gov_setup
+        .remove_delegators(&second_user_addr, &[&first_user_addr])
+        .assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert!(sc
+                .user_delegation(&managed_address!(&first_user_addr))
+                .is_empty());
+            assert_eq!(sc.delegators(&managed_address!(&second_user_addr)).len(), 0);
+        })
+        .assert_ok();
+}
+
 #[test]
 fn gov_propose_total_energy_0_test() {
     let mut gov_setup = GovSetup::new(governance_v2::contract_obj);
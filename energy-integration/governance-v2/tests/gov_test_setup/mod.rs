@@ -5,6 +5,7 @@ use energy_query::Energy;
 use fees_collector::FeesCollector;
 use governance_v2::{
     configurable::ConfigurablePropertiesModule,
+    delegation::DelegationModule,
     proposal_storage::{ProposalStorageModule, VoteType},
     GovernanceV2,
 };
@@ -168,6 +169,7 @@ where
                     QUORUM_PERCENTAGE,
                     VOTING_DELAY_BLOCKS,
                     VOTING_PERIOD_BLOCKS,
+                    LOCKING_PERIOD_BLOCKS,
                     WITHDRAW_PERCENTAGE,
                     managed_address!(energy_factory_wrapper.address_ref()),
                     managed_address!(fees_collector_wrapper.address_ref()),
@@ -271,6 +273,52 @@ where
             })
     }
 
+    pub fn delegate(&mut self, delegator: &Address, delegate: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(delegator, &self.gov_wrapper, &rust_biguint!(0), |sc| {
+                sc.delegate(managed_address!(delegate));
+            })
+    }
+
+    pub fn undelegate(&mut self, delegator: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(delegator, &self.gov_wrapper, &rust_biguint!(0), |sc| {
+                sc.undelegate();
+            })
+    }
+
+    pub fn remove_delegators(&mut self, delegate: &Address, delegators: &[&Address]) -> TxResult {
+        self.b_mock
+            .execute_tx(delegate, &self.gov_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                for delegator in delegators {
+                    args.push(managed_address!(*delegator));
+                }
+                sc.remove_delegators(args);
+            })
+    }
+
+    pub fn change_min_energy_for_delegation(&mut self, min_energy: u64) -> TxResult {
+        self.b_mock
+            .execute_tx(&self.owner, &self.gov_wrapper, &rust_biguint!(0), |sc| {
+                sc.change_min_energy_for_delegation(managed_biguint!(min_energy));
+            })
+    }
+
+    pub fn queue(&mut self, caller: &Address, proposal_id: usize) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.gov_wrapper, &rust_biguint!(0), |sc| {
+                sc.queue(proposal_id);
+            })
+    }
+
+    pub fn execute(&mut self, caller: &Address, proposal_id: usize) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.gov_wrapper, &rust_biguint!(0), |sc| {
+                sc.execute(proposal_id);
+            })
+    }
+
     pub fn withdraw_after_defeated(&mut self, caller: &Address, proposal_id: usize) -> TxResult {
         self.b_mock
             .execute_tx(caller, &self.gov_wrapper, &rust_biguint!(0), |sc| {
@@ -285,6 +333,13 @@ where
             })
     }
 
+    pub fn change_lock_time(&mut self, lock_time_blocks: u64) -> TxResult {
+        self.b_mock
+            .execute_tx(&self.owner, &self.gov_wrapper, &rust_biguint!(0), |sc| {
+                sc.change_lock_time_after_voting_ends_in_blocks(lock_time_blocks);
+            })
+    }
+
     pub fn change_min_energy(&mut self, min_energy_for_propose: usize) -> TxResult {
         self.b_mock
             .execute_tx(&self.owner, &self.gov_wrapper, &rust_biguint!(0), |sc| {
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           29
+// Endpoints:                           44
 // Async Callback (empty):               1
-// Total number of exported functions:  31
+// Total number of exported functions:  46
 
 #![no_std]
 
@@ -25,6 +25,8 @@ multiversx_sc_wasm_adapter::endpoints! {
         upgrade => upgrade
         propose => propose
         vote => vote
+        queue => queue
+        execute => execute
         cancel => cancel
         withdrawDeposit => withdraw_deposit
         changeMinEnergyForProposal => change_min_energy_for_propose
@@ -33,18 +35,31 @@ multiversx_sc_wasm_adapter::endpoints! {
         changeWithdrawPercentage => change_withdraw_percentage
         changeVotingDelayInBlocks => change_voting_delay_in_blocks
         changeVotingPeriodInBlocks => change_voting_period_in_blocks
+        changeLockTimeAfterVotingEndsInBlocks => change_lock_time_after_voting_ends_in_blocks
+        changeMinEnergyForDelegation => change_min_energy_for_delegation
         getMinEnergyForPropose => min_energy_for_propose
+        getMinEnergyForDelegation => min_energy_for_delegation
         getMinFeeForPropose => min_fee_for_propose
         getQuorum => quorum_percentage
         getVotingDelayInBlocks => voting_delay_in_blocks
         getVotingPeriodInBlocks => voting_period_in_blocks
+        getLockTimeAfterVotingEndsInBlocks => lock_time_after_voting_ends_in_blocks
         getFeeTokenId => fee_token_id
         getWithdrawPercentageDefeated => withdraw_percentage_defeated
         getProposals => proposals
         getUserVotedProposals => user_voted_proposals
         getProposalVotes => proposal_votes
+        getProposalStartEpoch => proposal_start_epoch
+        getProposalEta => proposal_eta
+        isProposalExecuted => proposal_executed
         getProposalStatus => get_proposal_status
         getFeesCollectorAddress => fees_collector_address
+        delegate => delegate
+        undelegate => undelegate
+        removeDelegators => remove_delegators
+        getUserDelegation => user_delegation
+        getDelegators => delegators
+        getDelegatedVote => delegated_vote
         setEnergyFactoryAddress => set_energy_factory_address
         getEnergyFactoryAddress => energy_factory_address
         addAdmin => add_admin_endpoint
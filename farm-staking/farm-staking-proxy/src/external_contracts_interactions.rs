@@ -1,8 +1,8 @@
 multiversx_sc::imports!();
 
 use farm::{
-    base_functions::{ClaimRewardsResultType, DoubleMultiPayment},
-    EnterFarmResultType, ExitFarmWithPartialPosResultType,
+    base_functions::DoubleMultiPayment, ClaimRewardsMultiResultType, EnterFarmResultType,
+    ExitFarmMultiResultType,
 };
 use farm_staking::{
     claim_stake_farm_rewards::ProxyTrait as _, stake_farm::ProxyTrait as _,
@@ -30,12 +30,13 @@ pub trait ExternalContractsInteractionsModule:
         lp_farm_token_amount: BigUint,
     ) -> LpFarmClaimRewardsResult<Self::Api> {
         let lp_farm_address = self.lp_farm_address().get();
-        let lp_farm_result: ClaimRewardsResultType<Self::Api> = self
+        let lp_farm_result: ClaimRewardsMultiResultType<Self::Api> = self
             .lp_farm_proxy_obj(lp_farm_address)
             .claim_rewards_endpoint(orig_caller)
             .with_esdt_transfer((lp_farm_token_id, lp_farm_token_nonce, lp_farm_token_amount))
             .execute_on_dest_context();
-        let (new_lp_farm_tokens, lp_farm_rewards) = lp_farm_result.into_tuple();
+        // the farms send the additional rewards directly to the user
+        let (new_lp_farm_tokens, lp_farm_rewards, _) = lp_farm_result.into_tuple();
 
         LpFarmClaimRewardsResult {
             new_lp_farm_tokens,
@@ -51,12 +52,12 @@ pub trait ExternalContractsInteractionsModule:
     ) -> LpFarmExitResult<Self::Api> {
         let lp_farm_token_id = self.lp_farm_token_id().get();
         let lp_farm_address = self.lp_farm_address().get();
-        let exit_farm_result: ExitFarmWithPartialPosResultType<Self::Api> = self
+        let exit_farm_result: ExitFarmMultiResultType<Self::Api> = self
             .lp_farm_proxy_obj(lp_farm_address)
             .exit_farm_endpoint(orig_caller)
             .with_esdt_transfer((lp_farm_token_id, lp_farm_token_nonce, lp_farm_token_amount))
             .execute_on_dest_context();
-        let (lp_tokens, lp_farm_rewards) = exit_farm_result.into_tuple();
+        let (lp_tokens, lp_farm_rewards, _) = exit_farm_result.into_tuple();
 
         LpFarmExitResult {
             lp_tokens,
@@ -116,7 +117,7 @@ pub trait ExternalContractsInteractionsModule:
         new_staking_farm_value: BigUint,
     ) -> StakingFarmClaimRewardsResult<Self::Api> {
         let staking_farm_address = self.staking_farm_address().get();
-        let staking_farm_result: ClaimRewardsResultType<Self::Api> = self
+        let staking_farm_result: ClaimRewardsMultiResultType<Self::Api> = self
             .staking_farm_proxy_obj(staking_farm_address)
             .claim_rewards_with_new_value(new_staking_farm_value, orig_caller)
             .with_esdt_transfer((
@@ -125,7 +126,7 @@ pub trait ExternalContractsInteractionsModule:
                 staking_farm_token_amount,
             ))
             .execute_on_dest_context();
-        let (new_staking_farm_tokens, staking_farm_rewards) = staking_farm_result.into_tuple();
+        let (new_staking_farm_tokens, staking_farm_rewards, _) = staking_farm_result.into_tuple();
 
         StakingFarmClaimRewardsResult {
             new_staking_farm_tokens,
@@ -149,12 +150,12 @@ pub trait ExternalContractsInteractionsModule:
         ));
 
         let staking_farm_address = self.staking_farm_address().get();
-        let unstake_result: ExitFarmWithPartialPosResultType<Self::Api> = self
+        let unstake_result: ExitFarmMultiResultType<Self::Api> = self
             .staking_farm_proxy_obj(staking_farm_address)
             .unstake_farm_through_proxy(orig_caller)
             .with_multi_token_transfer(payments)
             .execute_on_dest_context();
-        let (unbond_staking_farm_token, staking_rewards) = unstake_result.into_tuple();
+        let (unbond_staking_farm_token, staking_rewards, _) = unstake_result.into_tuple();
 
         StakingFarmExitResult {
             unbond_staking_farm_token,
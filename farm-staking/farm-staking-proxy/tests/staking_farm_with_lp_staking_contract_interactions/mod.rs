@@ -434,7 +434,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (unbond_farm_tokens, reward_tokens) =
+                    let (unbond_farm_tokens, reward_tokens, _) =
                         sc.unstake_farm(OptionalValue::None).into_tuple();
                     unbond_token_nonce = unbond_farm_tokens.token_nonce;
 
@@ -484,7 +484,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (_lp_tokens, _boosted_rewards_payment) =
+                    let (_lp_tokens, _boosted_rewards_payment, _) =
                         sc.exit_farm_endpoint(OptionalValue::None).into_tuple();
                 },
             )
@@ -507,7 +507,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (output_farm_token, boosted_rewards_payment) =
+                    let (output_farm_token, boosted_rewards_payment, _) =
                         sc.claim_rewards_endpoint(OptionalValue::None).into_tuple();
                     assert_eq!(output_farm_token.amount, farm_token_amount);
                     assert_eq!(boosted_rewards_payment.amount, expected_lp_farm_rewards);
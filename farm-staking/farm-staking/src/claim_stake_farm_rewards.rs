@@ -1,6 +1,6 @@
 multiversx_sc::imports!();
 
-use farm::base_functions::ClaimRewardsResultType;
+use farm::ClaimRewardsMultiResultType;
 
 use crate::base_impl_wrapper::FarmStakingWrapper;
 
@@ -17,6 +17,7 @@ pub trait ClaimStakeFarmRewardsModule:
     + pausable::PausableModule
     + permissions_module::PermissionsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + farm::additional_rewards::AdditionalRewardsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::claim_rewards::BaseClaimRewardsModule
@@ -36,7 +37,7 @@ pub trait ClaimStakeFarmRewardsModule:
     fn claim_rewards(
         &self,
         opt_original_caller: OptionalValue<ManagedAddress>,
-    ) -> ClaimRewardsResultType<Self::Api> {
+    ) -> ClaimRewardsMultiResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let original_caller = self.get_orig_caller_from_opt(&caller, opt_original_caller);
 
@@ -49,7 +50,7 @@ pub trait ClaimStakeFarmRewardsModule:
         &self,
         new_farming_amount: BigUint,
         original_caller: ManagedAddress,
-    ) -> ClaimRewardsResultType<Self::Api> {
+    ) -> ClaimRewardsMultiResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         self.require_sc_address_whitelisted(&caller);
 
@@ -60,9 +61,12 @@ pub trait ClaimStakeFarmRewardsModule:
         &self,
         original_caller: ManagedAddress,
         opt_new_farming_amount: Option<BigUint>,
-    ) -> ClaimRewardsResultType<Self::Api> {
+    ) -> ClaimRewardsMultiResultType<Self::Api> {
         self.migrate_old_farm_positions(&original_caller);
         let payment = self.call_value().single_esdt();
+
+        self.generate_additional_rewards();
+        let additional_rewards = self.claim_and_send_additional_rewards(&original_caller, &payment);
         let mut claim_result = self
             .claim_rewards_base_no_farm_token_mint::<FarmStakingWrapper<Self>>(
                 original_caller.clone(),
@@ -88,6 +92,10 @@ pub trait ClaimStakeFarmRewardsModule:
             &virtual_farm_token.attributes,
         );
         virtual_farm_token.payment.token_nonce = new_farm_token_nonce;
+        self.set_position_additional_reward_per_share(
+            &virtual_farm_token.payment,
+            &ManagedVec::new(),
+        );
 
         let caller = self.blockchain().get_caller();
         self.send_payment_non_zero(&caller, &virtual_farm_token.payment);
@@ -102,6 +110,11 @@ pub trait ClaimStakeFarmRewardsModule:
             claim_result.storage_cache,
         );
 
-        (virtual_farm_token.payment, claim_result.rewards).into()
+        (
+            virtual_farm_token.payment,
+            claim_result.rewards,
+            additional_rewards.into(),
+        )
+            .into()
     }
 }
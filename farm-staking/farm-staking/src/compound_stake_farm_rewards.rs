@@ -15,6 +15,7 @@ pub trait CompoundStakeFarmRewardsModule:
     + pausable::PausableModule
     + permissions_module::PermissionsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + farm::additional_rewards::AdditionalRewardsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::compound_rewards::BaseCompoundRewardsModule
@@ -35,10 +36,17 @@ pub trait CompoundStakeFarmRewardsModule:
         let caller = self.blockchain().get_caller();
         self.migrate_old_farm_positions(&caller);
         let payments = self.get_non_empty_payments();
+        let mut additional_payments = payments.clone();
+        let first_farm_token = self.pop_first_payment(&mut additional_payments);
+
+        // only the main rewards can be compounded, the additional ones are sent to the caller
+        self.generate_additional_rewards();
+        let _ = self.claim_and_send_additional_rewards(&caller, &first_farm_token);
         let compound_result =
             self.compound_rewards_base::<FarmStakingWrapper<Self>>(caller.clone(), payments);
 
         let new_farm_token = compound_result.new_farm_token.payment.clone();
+        self.set_position_additional_reward_per_share(&new_farm_token, &additional_payments);
         self.send_payment_non_zero(&caller, &new_farm_token);
 
         self.set_farm_supply_for_current_week(&compound_result.storage_cache.farm_token_supply);
@@ -1,9 +1,10 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use common_structs::Epoch;
+use common_structs::{Epoch, Nonce};
 use contexts::storage_cache::StorageCache;
 use farm_base_impl::base_traits_impl::FarmContract;
+use rewards::EmissionScheduleEntries;
 
 use crate::base_impl_wrapper::FarmStakingWrapper;
 
@@ -43,6 +44,29 @@ pub trait CustomRewardsModule:
         self.reward_capacity().update(|r| *r += payment_amount);
     }
 
+    /// Withdraws reward tokens that were not distributed yet.
+    /// The rest must still cover the emission schedule.
+    #[endpoint(withdrawRewards)]
+    fn withdraw_rewards(&self, withdraw_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+        require!(withdraw_amount != 0, "Amount cannot be zero");
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let remaining_capacity = self.reward_capacity().get() - self.accumulated_rewards().get();
+        require!(
+            withdraw_amount <= remaining_capacity,
+            "Withdraw amount is higher than the remaining rewards"
+        );
+
+        self.reward_capacity().update(|r| *r -= &withdraw_amount);
+        self.require_emission_schedule_funded();
+
+        let caller = self.blockchain().get_caller();
+        self.send_tokens_non_zero(&caller, &storage_cache.reward_token_id, 0, &withdraw_amount);
+    }
+
     #[endpoint(endProduceRewards)]
     fn end_produce_rewards(&self) {
         self.require_caller_has_admin_permissions();
@@ -59,6 +83,7 @@ pub trait CustomRewardsModule:
 
         let mut storage_cache = StorageCache::new(self);
         FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.require_no_emission_schedule();
         self.per_block_reward_amount().set(&per_block_amount);
     }
 
@@ -98,6 +123,63 @@ pub trait CustomRewardsModule:
         self.start_produce_rewards();
     }
 
+    /// Each entry is a `(start_block, per_block_amount)` pair, and a zero amount ends the emission.
+    /// Replaces the upcoming schedule.
+    #[endpoint(setEmissionSchedule)]
+    fn set_emission_schedule_endpoint(
+        &self,
+        entries: MultiValueEncoded<MultiValue2<Nonce, BigUint>>,
+    ) {
+        self.require_caller_has_admin_permissions();
+        let schedule = self.build_emission_schedule(entries);
+        self.set_funded_emission_schedule(schedule);
+    }
+
+    /// Decreases the per block amount by `decay_percentage` every period, e.g. 5_000 for halvings.
+    /// The emission ends after `nr_periods` periods.
+    #[endpoint(setDecayingEmissionSchedule)]
+    fn set_decaying_emission_schedule_endpoint(
+        &self,
+        start_block: Nonce,
+        initial_per_block_amount: BigUint,
+        period_in_blocks: Nonce,
+        decay_percentage: u64,
+        nr_periods: usize,
+    ) {
+        self.require_caller_has_admin_permissions();
+        let schedule = self.build_decaying_emission_schedule(
+            start_block,
+            initial_per_block_amount,
+            period_in_blocks,
+            decay_percentage,
+            nr_periods,
+        );
+        self.set_funded_emission_schedule(schedule);
+    }
+
+    /// The schedule must end, and the rewards it produces must be covered by the remaining reward capacity.
+    /// An empty schedule removes the current one.
+    fn set_funded_emission_schedule(&self, schedule: EmissionScheduleEntries<Self::Api>) {
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.replace_emission_schedule(schedule);
+        self.require_emission_schedule_funded();
+    }
+
+    /// Without a schedule, the per block rewards simply stop once the reward capacity is reached.
+    fn require_emission_schedule_funded(&self) {
+        if self.emission_schedule().is_empty() {
+            return;
+        }
+
+        let scheduled_rewards = self.get_remaining_scheduled_rewards();
+        let remaining_capacity = self.reward_capacity().get() - self.accumulated_rewards().get();
+        require!(
+            scheduled_rewards <= remaining_capacity,
+            "Emission schedule is not funded"
+        );
+    }
+
     #[view(getAccumulatedRewards)]
     #[storage_mapper("accumulatedRewards")]
     fn accumulated_rewards(&self) -> SingleValueMapper<BigUint>;
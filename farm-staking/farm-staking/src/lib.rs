@@ -6,8 +6,9 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use base_impl_wrapper::FarmStakingWrapper;
+use common_structs::Nonce;
 use contexts::storage_cache::StorageCache;
-use farm::base_functions::DoubleMultiPayment;
+use farm::{base_functions::DoubleMultiPayment, CalculateRewardsResultType};
 use farm_base_impl::base_traits_impl::FarmContract;
 use fixed_supply_token::FixedSupplyToken;
 use token_attributes::StakingFarmTokenAttributes;
@@ -37,6 +38,7 @@ pub trait FarmStaking:
     + pausable::PausableModule
     + permissions_module::PermissionsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + farm::additional_rewards::AdditionalRewardsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -114,34 +116,48 @@ pub trait FarmStaking:
         let payments = self.get_non_empty_payments();
         let token_mapper = self.farm_token();
         let output_attributes: StakingFarmTokenAttributes<Self::Api> =
-            self.merge_from_payments_and_burn(payments, &token_mapper);
+            self.merge_from_payments_and_burn(payments.clone(), &token_mapper);
         let new_token_amount = output_attributes.get_total_supply();
 
         let merged_farm_token = token_mapper.nft_create(new_token_amount, &output_attributes);
+        self.set_position_additional_reward_per_share(&merged_farm_token, &payments);
         self.send_payment_non_zero(&caller, &merged_farm_token);
         self.send_payment_non_zero(&caller, &boosted_rewards_payment);
 
         (merged_farm_token, boosted_rewards_payment).into()
     }
 
+    /// The additional rewards are kept by farm token nonce, not in the attributes,
+    /// so they are only returned if the nonce is given.
     #[view(calculateRewardsForGivenPosition)]
     fn calculate_rewards_for_given_position(
         &self,
         farm_token_amount: BigUint,
         attributes: StakingFarmTokenAttributes<Self::Api>,
-    ) -> BigUint {
+        opt_farm_token_nonce: OptionalValue<Nonce>,
+    ) -> CalculateRewardsResultType<Self::Api> {
         self.require_queried();
 
         let mut storage_cache = StorageCache::new(self);
         FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
 
-        FarmStakingWrapper::<Self>::calculate_rewards(
+        let rewards = FarmStakingWrapper::<Self>::calculate_rewards(
             self,
             &ManagedAddress::zero(),
             &farm_token_amount,
             &attributes,
             &storage_cache,
-        )
+        );
+
+        self.generate_additional_rewards();
+        let additional_rewards = match opt_farm_token_nonce {
+            OptionalValue::Some(farm_token_nonce) => {
+                self.calculate_additional_rewards(farm_token_nonce, &farm_token_amount)
+            }
+            OptionalValue::None => ManagedVec::new(),
+        };
+
+        (rewards, additional_rewards.into()).into()
     }
 
     fn require_queried(&self) {
@@ -18,6 +18,7 @@ pub trait StakeFarmModule:
     + pausable::PausableModule
     + permissions_module::PermissionsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + farm::additional_rewards::AdditionalRewardsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -77,10 +78,15 @@ pub trait StakeFarmModule:
         let boosted_rewards_payment =
             EsdtTokenPayment::new(self.reward_token_id().get(), 0, boosted_rewards);
 
+        let mut additional_farm_tokens = payments.clone();
+        additional_farm_tokens.remove(0);
+
+        self.generate_additional_rewards();
         let enter_result =
             self.enter_farm_base::<FarmStakingWrapper<Self>>(original_caller.clone(), payments);
 
         let new_farm_token = enter_result.new_farm_token.payment.clone();
+        self.set_position_additional_reward_per_share(&new_farm_token, &additional_farm_tokens);
         self.send_payment_non_zero(&caller, &new_farm_token);
         self.send_payment_non_zero(&caller, &boosted_rewards_payment);
 
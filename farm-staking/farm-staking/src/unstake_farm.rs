@@ -1,6 +1,6 @@
 multiversx_sc::imports!();
 
-use farm::ExitFarmWithPartialPosResultType;
+use farm::ExitFarmMultiResultType;
 
 use crate::{base_impl_wrapper::FarmStakingWrapper, token_attributes::UnbondSftAttributes};
 
@@ -17,6 +17,7 @@ pub trait UnstakeFarmModule:
     + pausable::PausableModule
     + permissions_module::PermissionsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + farm::additional_rewards::AdditionalRewardsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::exit_farm::BaseExitFarmModule
@@ -36,7 +37,7 @@ pub trait UnstakeFarmModule:
     fn unstake_farm(
         &self,
         opt_original_caller: OptionalValue<ManagedAddress>,
-    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+    ) -> ExitFarmMultiResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let original_caller = self.get_orig_caller_from_opt(&caller, opt_original_caller);
         let payment = self.call_value().single_esdt();
@@ -49,7 +50,7 @@ pub trait UnstakeFarmModule:
     fn unstake_farm_through_proxy(
         &self,
         original_caller: ManagedAddress,
-    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+    ) -> ExitFarmMultiResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         self.require_sc_address_whitelisted(&caller);
 
@@ -71,9 +72,11 @@ pub trait UnstakeFarmModule:
         original_caller: ManagedAddress,
         payment: EsdtTokenPayment,
         opt_unbond_amount: Option<BigUint>,
-    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+    ) -> ExitFarmMultiResultType<Self::Api> {
         let migrated_amount = self.migrate_old_farm_positions(&original_caller);
 
+        self.generate_additional_rewards();
+        let additional_rewards = self.claim_and_send_additional_rewards(&original_caller, &payment);
         let exit_result =
             self.exit_farm_base::<FarmStakingWrapper<Self>>(original_caller.clone(), payment);
 
@@ -100,7 +103,12 @@ pub trait UnstakeFarmModule:
             exit_result.storage_cache,
         );
 
-        (unbond_farm_token, exit_result.reward_payment).into()
+        (
+            unbond_farm_token,
+            exit_result.reward_payment,
+            additional_rewards.into(),
+        )
+            .into()
     }
 
     fn create_and_send_unbond_tokens(
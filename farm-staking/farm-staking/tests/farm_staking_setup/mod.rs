@@ -14,6 +14,7 @@ pub type RustBigUint = num_bigint::BigUint;
 use config::*;
 use energy_factory::energy::EnergyModule;
 use energy_query::{Energy, EnergyQueryModule};
+use farm::additional_rewards::AdditionalRewardsModule;
 use farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule;
 use farm_boosted_yields::FarmBoostedYieldsModule;
 use farm_staking::claim_stake_farm_rewards::ClaimStakeFarmRewardsModule;
@@ -25,10 +26,12 @@ use farm_staking::unstake_farm::UnstakeFarmModule;
 use farm_staking::*;
 use farm_token::FarmTokenModule;
 use pausable::{PausableModule, State};
+use rewards::RewardsModule;
 
 pub static REWARD_TOKEN_ID: &[u8] = b"RIDE-abcdef"; // reward token ID
 pub static FARMING_TOKEN_ID: &[u8] = b"RIDE-abcdef"; // farming token ID
 pub static FARM_TOKEN_ID: &[u8] = b"FARM-abcdef";
+pub static ADDITIONAL_REWARD_TOKEN_ID: &[u8] = b"PARTNER-abcdef";
 pub const DIVISION_SAFETY_CONSTANT: u64 = 1_000_000_000_000;
 pub const MIN_UNBOND_EPOCHS: u64 = 5;
 pub const MAX_APR: u64 = 2_500; // 25%
@@ -154,6 +157,43 @@ where
         }
     }
 
+    pub fn add_additional_reward_token(&mut self, per_block_amount: u64, deposit_amount: u64) {
+        self.b_mock.set_esdt_balance(
+            &self.owner_address,
+            ADDITIONAL_REWARD_TOKEN_ID,
+            &rust_biguint!(deposit_amount),
+        );
+
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.farm_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    let token_id = managed_token_id!(ADDITIONAL_REWARD_TOKEN_ID);
+                    sc.add_additional_reward_token(token_id.clone());
+                    sc.set_additional_reward_per_block(
+                        token_id,
+                        managed_biguint!(per_block_amount),
+                    );
+                },
+            )
+            .assert_ok();
+
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.owner_address,
+                &self.farm_wrapper,
+                ADDITIONAL_REWARD_TOKEN_ID,
+                0,
+                &rust_biguint!(deposit_amount),
+                |sc| {
+                    sc.deposit_additional_rewards();
+                },
+            )
+            .assert_ok();
+    }
+
     pub fn stake_farm(
         &mut self,
         farm_in_amount: u64,
@@ -229,7 +269,7 @@ where
                 &rust_biguint!(farm_token_amount),
                 |sc| {
                     let multi_result = sc.claim_rewards(OptionalValue::None);
-                    let (first_result, second_result) = multi_result.into_tuple();
+                    let (first_result, second_result, _) = multi_result.into_tuple();
 
                     assert_eq!(
                         first_result.token_identifier,
@@ -330,7 +370,7 @@ where
                 |sc| {
                     let multi_result = sc.unstake_farm(OptionalValue::None);
 
-                    let (first_result, second_result) = multi_result.into_tuple();
+                    let (first_result, second_result, _) = multi_result.into_tuple();
 
                     assert_eq!(
                         first_result.token_identifier,
@@ -459,6 +499,77 @@ where
             .assert_ok();
     }
 
+    pub fn set_decaying_emission_schedule(
+        &mut self,
+        start_block: u64,
+        initial_per_block_amount: u64,
+        period_in_blocks: u64,
+        decay_percentage: u64,
+        nr_periods: usize,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_address,
+            &self.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_decaying_emission_schedule_endpoint(
+                    start_block,
+                    managed_biguint!(initial_per_block_amount),
+                    period_in_blocks,
+                    decay_percentage,
+                    nr_periods,
+                );
+            },
+        )
+    }
+
+    pub fn set_emission_schedule(&mut self, entries: &[(u64, u64)]) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_address,
+            &self.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut managed_entries = MultiValueEncoded::new();
+                for (start_block, per_block_amount) in entries {
+                    managed_entries
+                        .push((*start_block, managed_biguint!(*per_block_amount)).into());
+                }
+
+                sc.set_emission_schedule_endpoint(managed_entries);
+            },
+        )
+    }
+
+    pub fn set_per_block_rewards(&mut self, per_block_amount: u64) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_address,
+            &self.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_per_block_rewards(managed_biguint!(per_block_amount));
+            },
+        )
+    }
+
+    pub fn withdraw_rewards(&mut self, withdraw_amount: u64) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_address,
+            &self.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.withdraw_rewards(managed_biguint!(withdraw_amount));
+            },
+        )
+    }
+
+    pub fn check_emission_schedule_len(&mut self, expected_len: usize) {
+        self.b_mock
+            .execute_query(&self.farm_wrapper, |sc| {
+                assert_eq!(sc.emission_schedule().len(), expected_len);
+            })
+            .assert_ok();
+    }
+
     pub fn set_boosted_yields_rewards_percentage(&mut self, percentage: u64) {
         self.b_mock
             .execute_tx(
@@ -1,11 +1,17 @@
 #![allow(deprecated)]
 
-use multiversx_sc_scenario::{rust_biguint, whitebox_legacy::TxTokenTransfer, DebugApi};
+use farm::additional_rewards::AdditionalRewardsModule;
+use multiversx_sc::codec::multi_types::OptionalValue;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint,
+    whitebox_legacy::TxTokenTransfer, DebugApi,
+};
 
 pub mod farm_staking_setup;
 use farm_staking::{
     custom_rewards::{BLOCKS_IN_YEAR, MAX_PERCENT},
-    token_attributes::UnbondSftAttributes,
+    token_attributes::{StakingFarmTokenAttributes, UnbondSftAttributes},
+    FarmStaking,
 };
 use farm_staking_setup::*;
 
@@ -231,3 +237,147 @@ fn test_unbond() {
         USER_TOTAL_RIDE_TOKENS + expected_rewards,
     );
 }
+
+#[test]
+fn test_emission_schedule_funding() {
+    DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    // unbounded schedule
+    farm_setup
+        .set_emission_schedule(&[(10, 1_000), (20, 500)])
+        .assert_user_error("Emission schedule must have an end block");
+
+    // 10 * 5_000 + 100 * 10_000_000_000 + 100 * 5_000_000_000 > TOTAL_REWARDS_AMOUNT
+    farm_setup
+        .set_decaying_emission_schedule(10, 10_000_000_000, 100, 5_000, 2)
+        .assert_user_error("Emission schedule is not funded");
+
+    farm_setup
+        .set_decaying_emission_schedule(10, 1_000_000_000, 100, 5_000, 2)
+        .assert_ok();
+    farm_setup.check_emission_schedule_len(3);
+
+    // start blocks must be increasing
+    farm_setup
+        .set_emission_schedule(&[(20, 1_000), (20, 0)])
+        .assert_user_error("Invalid emission schedule start block");
+    farm_setup.check_emission_schedule_len(3);
+
+    // the schedule sets the per block amount
+    farm_setup
+        .set_per_block_rewards(1_000)
+        .assert_user_error("Emission schedule is active");
+
+    // 10 * 5_000 + 100 * 1_000_000_000 + 100 * 500_000_000 must remain
+    farm_setup
+        .withdraw_rewards(850_000_000_000)
+        .assert_user_error("Emission schedule is not funded");
+    farm_setup.withdraw_rewards(849_999_950_000).assert_ok();
+    farm_setup.b_mock.check_esdt_balance(
+        &farm_setup.owner_address,
+        REWARD_TOKEN_ID,
+        &rust_biguint!(849_999_950_000),
+    );
+
+    // an empty schedule removes the current one
+    farm_setup.set_emission_schedule(&[]).assert_ok();
+    farm_setup.check_emission_schedule_len(0);
+    farm_setup.set_per_block_rewards(1_000).assert_ok();
+}
+
+#[test]
+fn test_additional_rewards() {
+    DebugApi::dummy();
+    let mut farm_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    // 100 tokens per block, out of a 5_000 deposit
+    farm_setup.add_additional_reward_token(100, 5_000);
+
+    let farm_in_amount = 100_000_000;
+    farm_setup.stake_farm(farm_in_amount, &[], 1, 0, 0);
+
+    farm_setup.set_block_epoch(5);
+    farm_setup.set_block_nonce(10);
+
+    // values taken from the "test_claim_rewards" test
+    let mut expected_farming_token_balance =
+        rust_biguint!(USER_TOTAL_RIDE_TOKENS - farm_in_amount + 40);
+    farm_setup.claim_rewards(
+        farm_in_amount,
+        1,
+        40,
+        &expected_farming_token_balance,
+        &expected_farming_token_balance,
+        2,
+        400_000,
+    );
+
+    // the additional rewards are sent directly to the user
+    farm_setup.b_mock.check_esdt_balance(
+        &farm_setup.user_address,
+        ADDITIONAL_REWARD_TOKEN_ID,
+        &rust_biguint!(1_000),
+    );
+
+    farm_setup.set_block_nonce(20);
+
+    let user_address = farm_setup.user_address.clone();
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let attributes = StakingFarmTokenAttributes {
+                reward_per_share: managed_biguint!(400_000),
+                compounded_reward: managed_biguint!(0),
+                current_farm_amount: managed_biguint!(farm_in_amount),
+                original_owner: managed_address!(&user_address),
+            };
+            let (_, additional_rewards) = sc
+                .calculate_rewards_for_given_position(
+                    managed_biguint!(farm_in_amount),
+                    attributes,
+                    OptionalValue::Some(2),
+                )
+                .into_tuple();
+            let additional_rewards = additional_rewards.to_vec();
+            assert_eq!(additional_rewards.len(), 1);
+            assert_eq!(additional_rewards.get(0).amount, managed_biguint!(1_000));
+        })
+        .assert_ok();
+
+    expected_farming_token_balance += 40u64;
+    farm_setup.unstake_farm(
+        farm_in_amount,
+        2,
+        40,
+        &expected_farming_token_balance,
+        &expected_farming_token_balance,
+        3,
+        farm_in_amount,
+        &UnbondSftAttributes {
+            unlock_epoch: 5 + MIN_UNBOND_EPOCHS,
+        },
+    );
+    farm_setup.b_mock.check_esdt_balance(
+        &farm_setup.user_address,
+        ADDITIONAL_REWARD_TOKEN_ID,
+        &rust_biguint!(2_000),
+    );
+
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let token_id = managed_token_id!(ADDITIONAL_REWARD_TOKEN_ID);
+            assert_eq!(
+                sc.undistributed_additional_rewards(&token_id).get(),
+                managed_biguint!(3_000)
+            );
+            assert_eq!(
+                sc.additional_reward_reserve(&token_id).get(),
+                managed_biguint!(0)
+            );
+        })
+        .assert_ok();
+}
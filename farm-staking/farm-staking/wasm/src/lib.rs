@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           69
+// Endpoints:                           83
 // Async Callback:                       1
-// Total number of exported functions:  71
+// Total number of exported functions:  85
 
 #![no_std]
 
@@ -26,17 +26,22 @@ multiversx_sc_wasm_adapter::endpoints! {
         mergeFarmTokens => merge_farm_tokens_endpoint
         calculateRewardsForGivenPosition => calculate_rewards_for_given_position
         topUpRewards => top_up_rewards
+        withdrawRewards => withdraw_rewards
         endProduceRewards => end_produce_rewards
         setPerBlockRewardAmount => set_per_block_rewards
         setMaxApr => set_max_apr
         setMinUnbondEpochs => set_min_unbond_epochs_endpoint
         startProduceRewards => start_produce_rewards_endpoint
+        setEmissionSchedule => set_emission_schedule_endpoint
+        setDecayingEmissionSchedule => set_decaying_emission_schedule_endpoint
         getAccumulatedRewards => accumulated_rewards
         getRewardCapacity => reward_capacity
         getAnnualPercentageRewards => max_annual_percentage_rewards
         getMinUnbondEpochs => min_unbond_epochs
         getRewardPerShare => reward_per_share
         getRewardReserve => reward_reserve
+        getEmissionSchedule => emission_schedule
+        hasActiveEmissionSchedule => has_active_emission_schedule
         allowExternalClaimBoostedRewards => allow_external_claim_boosted_rewards
         getFarmingTokenId => farming_token_id
         getRewardTokenId => reward_token_id
@@ -60,6 +65,15 @@ multiversx_sc_wasm_adapter::endpoints! {
         removeAdmin => remove_admin_endpoint
         updateOwnerOrAdmin => update_owner_or_admin_endpoint
         getPermissions => permissions
+        addAdditionalRewardToken => add_additional_reward_token
+        setAdditionalRewardPerBlock => set_additional_reward_per_block
+        depositAdditionalRewards => deposit_additional_rewards
+        getAdditionalRewardTokens => additional_reward_tokens
+        getAdditionalRewardPerBlock => additional_reward_per_block
+        getAdditionalRewardPerShare => additional_reward_per_share
+        getAdditionalRewardReserve => additional_reward_reserve
+        getUndistributedAdditionalRewards => undistributed_additional_rewards
+        getPositionAdditionalRewardPerShare => position_additional_reward_per_share
         setBurnRoleForAddress => set_burn_role_for_address
         stakeFarmThroughProxy => stake_farm_through_proxy
         stakeFarm => stake_farm_endpoint
@@ -3,6 +3,11 @@ multiversx_sc::derive_imports!();
 
 use common_structs::Epoch;
 
+/// Covers the longest governance voting delay and voting period, 3 weeks in total,
+/// until the owner sets another retention period.
+pub const DEFAULT_ENERGY_CHECKPOINT_RETENTION_EPOCHS: Epoch = 30;
+const MAX_CHECKPOINTS_PRUNED_PER_UPDATE: usize = 2;
+
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
 pub struct Energy<M: ManagedTypeApi> {
     amount: BigInt<M>,
@@ -182,10 +187,117 @@ pub trait EnergyModule: crate::events::EventsModule {
 
     fn set_energy_entry(&self, user: &ManagedAddress, new_energy: Energy<Self::Api>) {
         let prev_energy = self.get_updated_energy_entry_for_user(user);
+        self.save_energy_checkpoint(user, &new_energy);
         self.user_energy(user).set(&new_energy);
         self.emit_energy_updated_event(user, prev_energy, new_energy);
     }
 
+    /// Keeps at most one entry per epoch, the last energy value set during that epoch.
+    /// Entries no longer needed for epochs within the retention period
+    /// are pruned along the way.
+    fn save_energy_checkpoint(&self, user: &ManagedAddress, new_energy: &Energy<Self::Api>) {
+        let first_index_mapper = self.user_energy_checkpoints_first_index(user);
+        let last_index_mapper = self.user_energy_checkpoints_last_index(user);
+        let mut last_index = last_index_mapper.get();
+        if last_index == 0 {
+            first_index_mapper.set(1);
+
+            // users that had energy before checkpoints were introduced
+            let energy_mapper = self.user_energy(user);
+            if !energy_mapper.is_empty() {
+                last_index = 1;
+                self.user_energy_checkpoint(user, last_index)
+                    .set(energy_mapper.get());
+            }
+        }
+
+        if last_index > 0 {
+            let last_checkpoint_mapper = self.user_energy_checkpoint(user, last_index);
+            let last_checkpoint = last_checkpoint_mapper.get();
+            if last_checkpoint.get_last_update_epoch() >= new_energy.get_last_update_epoch() {
+                last_checkpoint_mapper.set(new_energy);
+                last_index_mapper.set(last_index);
+                return;
+            }
+        }
+
+        last_index += 1;
+        self.user_energy_checkpoint(user, last_index)
+            .set(new_energy);
+        last_index_mapper.set(last_index);
+
+        self.prune_energy_checkpoints(user, last_index);
+    }
+
+    /// Removes at most `MAX_CHECKPOINTS_PRUNED_PER_UPDATE` of the oldest entries.
+    /// The last entry before the cutoff epoch is kept, as it is the base for queries after it.
+    fn prune_energy_checkpoints(&self, user: &ManagedAddress, last_index: usize) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let retention_epochs = self.get_energy_checkpoint_retention_epochs();
+        if current_epoch <= retention_epochs {
+            return;
+        }
+
+        let cutoff_epoch = current_epoch - retention_epochs;
+        let first_index_mapper = self.user_energy_checkpoints_first_index(user);
+        let mut first_index = first_index_mapper.get();
+        for _ in 0..MAX_CHECKPOINTS_PRUNED_PER_UPDATE {
+            if first_index >= last_index {
+                break;
+            }
+
+            let next_checkpoint = self.user_energy_checkpoint(user, first_index + 1).get();
+            if next_checkpoint.get_last_update_epoch() >= cutoff_epoch {
+                break;
+            }
+
+            self.user_energy_checkpoint(user, first_index).clear();
+            first_index += 1;
+        }
+
+        first_index_mapper.set(first_index);
+    }
+
+    /// Returns the last energy entry set before the given epoch, if any.
+    /// Entries older than the retention period may have been pruned,
+    /// so results for earlier epochs are not reliable.
+    fn get_energy_entry_before_epoch(
+        &self,
+        user: &ManagedAddress,
+        epoch: Epoch,
+    ) -> Option<Energy<Self::Api>> {
+        let last_index = self.user_energy_checkpoints_last_index(user).get();
+        if last_index == 0 {
+            let energy_mapper = self.user_energy(user);
+            if energy_mapper.is_empty() {
+                return None;
+            }
+
+            let energy = energy_mapper.get();
+            if energy.get_last_update_epoch() < epoch {
+                return Some(energy);
+            }
+
+            return None;
+        }
+
+        let mut result = None;
+        let mut low = self.user_energy_checkpoints_first_index(user).get();
+        let mut high = last_index;
+        while low <= high {
+            let mid = (low + high) / 2;
+            let checkpoint = self.user_energy_checkpoint(user, mid).get();
+            if checkpoint.get_last_update_epoch() < epoch {
+                result = Some(checkpoint);
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        result
+    }
+
     #[view(getEnergyEntryForUser)]
     fn get_updated_energy_entry_for_user(&self, user: &ManagedAddress) -> Energy<Self::Api> {
         let current_epoch = self.blockchain().get_block_epoch();
@@ -207,6 +319,62 @@ pub trait EnergyModule: crate::events::EventsModule {
         energy.get_energy_amount()
     }
 
+    /// Returns the user's energy at the start of the given epoch.
+    /// Changes made during or after that epoch are not taken into account.
+    #[view(getEnergyAmountForUserAtEpoch)]
+    fn get_energy_amount_for_user_at_epoch(&self, user: ManagedAddress, epoch: Epoch) -> BigUint {
+        match self.get_energy_entry_before_epoch(&user, epoch) {
+            Some(mut energy) => {
+                energy.deplete(epoch);
+
+                energy.get_energy_amount()
+            }
+            None => BigUint::zero(),
+        }
+    }
+
+    /// The governance contracts query the energy at the start of their proposals,
+    /// so the retention period must cover their longest voting delay and voting period.
+    /// Entries older than the new period are only pruned on the users' next updates.
+    #[only_owner]
+    #[endpoint(setEnergyCheckpointRetentionEpochs)]
+    fn set_energy_checkpoint_retention_epochs(&self, retention_epochs: Epoch) {
+        require!(retention_epochs > 0, "Invalid retention period");
+
+        self.energy_checkpoint_retention_epochs()
+            .set(retention_epochs);
+    }
+
+    #[view(getEnergyCheckpointRetentionEpochs)]
+    fn get_energy_checkpoint_retention_epochs(&self) -> Epoch {
+        let retention_epochs_mapper = self.energy_checkpoint_retention_epochs();
+        if retention_epochs_mapper.is_empty() {
+            return DEFAULT_ENERGY_CHECKPOINT_RETENTION_EPOCHS;
+        }
+
+        retention_epochs_mapper.get()
+    }
+
+    #[storage_mapper("energyCheckpointRetentionEpochs")]
+    fn energy_checkpoint_retention_epochs(&self) -> SingleValueMapper<Epoch>;
+
     #[storage_mapper("userEnergy")]
     fn user_energy(&self, user: &ManagedAddress) -> SingleValueMapper<Energy<Self::Api>>;
+
+    #[storage_mapper("userEnergyCheckpoint")]
+    fn user_energy_checkpoint(
+        &self,
+        user: &ManagedAddress,
+        index: usize,
+    ) -> SingleValueMapper<Energy<Self::Api>>;
+
+    #[storage_mapper("userEnergyCheckpointsFirstIndex")]
+    fn user_energy_checkpoints_first_index(
+        &self,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("userEnergyCheckpointsLastIndex")]
+    fn user_energy_checkpoints_last_index(&self, user: &ManagedAddress)
+        -> SingleValueMapper<usize>;
 }
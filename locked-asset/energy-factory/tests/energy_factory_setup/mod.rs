@@ -263,6 +263,19 @@ where
 
         result
     }
+
+    pub fn get_user_energy_at_epoch(&mut self, user: &Address, epoch: u64) -> num_bigint::BigUint {
+        let mut result = rust_biguint!(0);
+        self.b_mock
+            .execute_query(&self.sc_wrapper, |sc| {
+                let managed_result =
+                    sc.get_energy_amount_for_user_at_epoch(managed_address!(user), epoch);
+                result = to_rust_biguint(managed_result);
+            })
+            .assert_ok();
+
+        result
+    }
 }
 
 pub fn to_rust_biguint(
@@ -2,7 +2,7 @@
 
 mod energy_factory_setup;
 
-use energy_factory::energy::EnergyModule;
+use energy_factory::energy::{EnergyModule, DEFAULT_ENERGY_CHECKPOINT_RETENTION_EPOCHS};
 use energy_factory_setup::*;
 use multiversx_sc::types::BigUint;
 use simple_lock::locked_token::LockedTokenAttributes;
@@ -134,6 +134,184 @@ fn lock_ok() {
     );
 }
 
+#[test]
+fn energy_at_epoch_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    let first_lock_epoch = 1;
+    setup.b_mock.set_block_epoch(first_lock_epoch);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    let first_unlock_epoch = to_start_of_month(first_lock_epoch + LOCK_OPTIONS[0]);
+
+    // changes made during an epoch are not visible at the start of that epoch
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, first_lock_epoch),
+        rust_biguint!(0)
+    );
+
+    let second_lock_epoch = 10;
+    setup.b_mock.set_block_epoch(second_lock_epoch);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[1],
+        )
+        .assert_ok();
+
+    let second_unlock_epoch = to_start_of_month(second_lock_epoch + LOCK_OPTIONS[1]);
+
+    let energy_before_second_lock =
+        rust_biguint!(half_balance) * (first_unlock_epoch - second_lock_epoch);
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, second_lock_epoch),
+        energy_before_second_lock
+    );
+
+    let energy_after_second_lock = rust_biguint!(half_balance)
+        * (first_unlock_epoch - second_lock_epoch - 1)
+        + rust_biguint!(half_balance) * (second_unlock_epoch - second_lock_epoch - 1);
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, second_lock_epoch + 1),
+        energy_after_second_lock
+    );
+
+    // past snapshots are not affected by unlocking early
+    setup.b_mock.set_block_epoch(second_lock_epoch + 1);
+    setup.unlock_early(&first_user, 2, half_balance).assert_ok();
+
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, second_lock_epoch + 1),
+        energy_after_second_lock
+    );
+}
+
+#[test]
+fn energy_checkpoints_pruning_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let lock_amount = USER_BALANCE / 4;
+
+    for epoch in 1..=3 {
+        setup.b_mock.set_block_epoch(epoch);
+        setup
+            .lock(
+                &first_user,
+                BASE_ASSET_TOKEN_ID,
+                lock_amount,
+                LOCK_OPTIONS[0],
+            )
+            .assert_ok();
+    }
+
+    let query_epoch = 10;
+    let energy_at_query_epoch = setup.get_user_energy_at_epoch(&first_user, query_epoch);
+    assert!(energy_at_query_epoch > rust_biguint!(0));
+
+    setup
+        .b_mock
+        .set_block_epoch(query_epoch + DEFAULT_ENERGY_CHECKPOINT_RETENTION_EPOCHS);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            lock_amount,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    // only the last entry before the cutoff epoch is kept
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let user = managed_address!(&first_user);
+            assert_eq!(sc.user_energy_checkpoints_first_index(&user).get(), 3);
+            assert_eq!(sc.user_energy_checkpoints_last_index(&user).get(), 4);
+            assert!(sc.user_energy_checkpoint(&user, 1).is_empty());
+            assert!(sc.user_energy_checkpoint(&user, 2).is_empty());
+        })
+        .assert_ok();
+
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, query_epoch),
+        energy_at_query_epoch
+    );
+}
+
+#[test]
+fn energy_checkpoints_retention_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let owner = setup.owner.clone();
+    let lock_amount = USER_BALANCE / 4;
+
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_energy_checkpoint_retention_epochs(60);
+        })
+        .assert_user_error("Endpoint can only be called by owner");
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_energy_checkpoint_retention_epochs(0);
+        })
+        .assert_user_error("Invalid retention period");
+    setup
+        .b_mock
+        .execute_tx(&owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.set_energy_checkpoint_retention_epochs(60);
+        })
+        .assert_ok();
+
+    for epoch in 1..=3 {
+        setup.b_mock.set_block_epoch(epoch);
+        setup
+            .lock(
+                &first_user,
+                BASE_ASSET_TOKEN_ID,
+                lock_amount,
+                LOCK_OPTIONS[0],
+            )
+            .assert_ok();
+    }
+
+    // the default period has passed, but not the one set by the owner
+    let query_epoch = 10;
+    setup
+        .b_mock
+        .set_block_epoch(query_epoch + DEFAULT_ENERGY_CHECKPOINT_RETENTION_EPOCHS);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            lock_amount,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let user = managed_address!(&first_user);
+            assert_eq!(sc.get_energy_checkpoint_retention_epochs(), 60);
+            assert_eq!(sc.user_energy_checkpoints_first_index(&user).get(), 1);
+            assert_eq!(sc.user_energy_checkpoints_last_index(&user).get(), 4);
+        })
+        .assert_ok();
+}
+
 #[test]
 fn unlock_early_test() {
     let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           34
+// Endpoints:                           37
 // Async Callback:                       1
-// Total number of exported functions:  36
+// Total number of exported functions:  39
 
 #![no_std]
 
@@ -32,6 +32,9 @@ multiversx_sc_wasm_adapter::endpoints! {
         getLegacyLockedTokenId => legacy_locked_token_id
         getEnergyEntryForUser => get_updated_energy_entry_for_user
         getEnergyAmountForUser => get_energy_amount_for_user
+        getEnergyAmountForUserAtEpoch => get_energy_amount_for_user_at_epoch
+        setEnergyCheckpointRetentionEpochs => set_energy_checkpoint_retention_epochs
+        getEnergyCheckpointRetentionEpochs => get_energy_checkpoint_retention_epochs
         addLockOptions => add_lock_options
         getLockOptions => get_lock_options_view
         unlockEarly => unlock_early
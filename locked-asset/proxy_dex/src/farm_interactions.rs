@@ -1,8 +1,8 @@
 multiversx_sc::imports!();
 
 use farm::{
-    base_functions::{ClaimRewardsResultType, ClaimRewardsResultWrapper},
-    EnterFarmResultType, ExitFarmWithPartialPosResultType,
+    base_functions::ClaimRewardsResultWrapper, ClaimRewardsMultiResultType, EnterFarmResultType,
+    ExitFarmMultiResultType,
 };
 use farm_with_locked_rewards::ProxyTrait as _;
 
@@ -45,12 +45,13 @@ pub trait FarmInteractionsModule {
         farm_address: ManagedAddress,
         farm_token: EsdtTokenPayment,
     ) -> ExitFarmResultWrapper<Self::Api> {
-        let raw_result: ExitFarmWithPartialPosResultType<Self::Api> = self
+        let raw_result: ExitFarmMultiResultType<Self::Api> = self
             .farm_contract_proxy(farm_address)
             .exit_farm_endpoint(user)
             .with_esdt_transfer(farm_token)
             .execute_on_dest_context();
-        let (farming_tokens, reward_tokens) = raw_result.into_tuple();
+        // the farm sends the additional rewards directly to the user
+        let (farming_tokens, reward_tokens, _) = raw_result.into_tuple();
 
         ExitFarmResultWrapper {
             farming_tokens,
@@ -64,12 +65,12 @@ pub trait FarmInteractionsModule {
         farm_address: ManagedAddress,
         farm_token: EsdtTokenPayment,
     ) -> ClaimRewardsResultWrapper<Self::Api> {
-        let raw_result: ClaimRewardsResultType<Self::Api> = self
+        let raw_result: ClaimRewardsMultiResultType<Self::Api> = self
             .farm_contract_proxy(farm_address)
             .claim_rewards_endpoint(user)
             .with_esdt_transfer(farm_token)
             .execute_on_dest_context();
-        let (new_farm_token, rewards) = raw_result.into_tuple();
+        let (new_farm_token, rewards, _) = raw_result.into_tuple();
 
         ClaimRewardsResultWrapper {
             new_farm_token,
@@ -5,14 +5,18 @@ multiversx_sc::derive_imports!();
 
 type EnterFarmResultType<BigUint> =
     MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
-type ExitFarmResultType<BigUint> =
-    MultiValue3<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
-type ClaimRewardsResultType<BigUint> =
-    MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
+type ExitFarmResultType<M> = MultiValue3<
+    EsdtTokenPayment<M>,
+    EsdtTokenPayment<M>,
+    MultiValueEncoded<M, EsdtTokenPayment<M>>,
+>;
+type ClaimRewardsResultType<M> = MultiValue3<
+    EsdtTokenPayment<M>,
+    EsdtTokenPayment<M>,
+    MultiValueEncoded<M, EsdtTokenPayment<M>>,
+>;
 
 const ENTER_FARM_RESULTS_LEN: usize = 2;
-const EXIT_FARM_RESULTS_LEN: usize = 2;
-const CLAIM_REWARDS_RESULTS_LEN: usize = 2;
 
 pub struct EnterFarmResultWrapper<M: ManagedTypeApi> {
     pub farm_tokens: EsdtTokenPayment<M>,
@@ -116,8 +120,8 @@ pub trait FarmInteractionsModule {
             ))
             .execute_on_dest_context();
 
+        // the additional rewards, returned last, are sent by the farm directly to the user
         let mut results_wrapper = RawResultWrapper::new(raw_results);
-        results_wrapper.trim_results_front(EXIT_FARM_RESULTS_LEN);
 
         let initial_farming_tokens = results_wrapper.decode_next_result();
         let reward_tokens = results_wrapper.decode_next_result();
@@ -146,8 +150,8 @@ pub trait FarmInteractionsModule {
             ))
             .execute_on_dest_context();
 
+        // the additional rewards, returned last, are sent by the farm directly to the user
         let mut results_wrapper = RawResultWrapper::new(raw_results);
-        results_wrapper.trim_results_front(CLAIM_REWARDS_RESULTS_LEN);
 
         let new_farm_tokens = results_wrapper.decode_next_result();
         let reward_tokens = results_wrapper.decode_next_result();